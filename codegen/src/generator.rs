@@ -40,9 +40,9 @@ fn eval_getter(s: &str, b: usize) -> String {
     } else if s == "cf" {
         format!("cpu.get_cf()")
     } else if s == "d8" || s == "a8" || s == "r8" {
-        format!("mmu.get8(cpu.get_pc().wrapping_add(arg))")
+        format!("mmu.read8(cpu.get_pc().wrapping_add(arg))")
     } else if s == "d16" || s == "a16" {
-        format!("mmu.get16(cpu.get_pc().wrapping_add(arg))")
+        format!("mmu.read16(cpu.get_pc().wrapping_add(arg))")
     } else if s.starts_with("0x") {
         let mut expr = s.split("+");
         let offset = expr.next().expect("No offset");
@@ -51,7 +51,13 @@ fn eval_getter(s: &str, b: usize) -> String {
     } else if is_num(s) {
         format!("{}", s)
     } else if s.starts_with("(") {
-        format!("mmu.get{}({})", b, eval_getter(&s[1..s.len() - 1], b))
+        let addr = eval_getter(&s[1..s.len() - 1], b);
+        if addr.contains("mmu.") {
+            // The address is read from the memory first, as `mmu` can't be borrowed twice.
+            format!("{{ let addr = {}; mmu.read{}(addr) }}", addr, b)
+        } else {
+            format!("mmu.read{}({})", b, addr)
+        }
     } else {
         format!("cpu.get_{}()", s)
     }
@@ -65,7 +71,13 @@ pub fn getter(value: Value, map: HashMap<String, Value>) -> tera::Result<Value>
 
 fn eval_setter(s: &str, b: usize) -> String {
     if s.starts_with("(") {
-        format!("mmu.set{}({}, ", b, eval_getter(&s[1..s.len() - 1], b))
+        let addr = eval_getter(&s[1..s.len() - 1], b);
+        if addr.contains("mmu.") {
+            // The address is read from the memory first, as `mmu` can't be borrowed twice.
+            format!("let addr = {}; mmu.write{}(addr, ", addr, b)
+        } else {
+            format!("mmu.write{}({}, ", b, addr)
+        }
     } else {
         format!("cpu.set_{}(", s)
     }
@@ -98,6 +110,33 @@ pub fn untuple(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
     }
 }
 
+pub fn maxtime(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
+    let tuple = match &value {
+        Value::Number(_) => false,
+        Value::Array(_) => true,
+        _ => false,
+    };
+
+    if tuple {
+        let v = try_get_value!("maxtime", "value", Vec<usize>, value);
+        Ok(to_value(&format!("{}", v[0])).unwrap())
+    } else {
+        let v = try_get_value!("maxtime", "value", usize, value);
+        Ok(to_value(&format!("{}", v)).unwrap())
+    }
+}
+
+fn flageffect(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
+    let v = try_get_value!("flageffect", "value", String, value);
+    let effect = match v.as_str() {
+        "-" => "Unchanged",
+        "0" => "Reset",
+        "1" => "Set",
+        _ => "Computed",
+    };
+    Ok(to_value(effect).unwrap())
+}
+
 pub fn is_cond(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
     let b = match &value {
         Value::Number(_) => false,
@@ -107,7 +146,7 @@ pub fn is_cond(value: Value, _: HashMap<String, Value>) -> tera::Result<Value> {
     Ok(to_value(b).unwrap())
 }
 
-pub fn run(opt: &Generate) -> Result<()> {
+pub fn run(opt: &Generate, root: &str) -> Result<()> {
     let mut tera = compile_templates!(&format!(
         "{}/**/*",
         opt.template.to_str().unwrap_or("templates")
@@ -118,15 +157,30 @@ pub fn run(opt: &Generate) -> Result<()> {
     tera.register_filter("untuple", untuple);
     tera.register_filter("setflag", setflag);
     tera.register_filter("is_cond", is_cond);
+    tera.register_filter("maxtime", maxtime);
+    tera.register_filter("flageffect", flageffect);
 
     let mut context = Context::new();
 
     let file = File::open(&opt.oplist).expect("Op list not found");
     let insts: Vec<Instruction> = serde_yaml::from_reader(file).expect("Unpack error");
 
+    // Place the instructions by opcode, leaving the invalid ones empty.
+    let mut table: Vec<Option<&Instruction>> = vec![None; 256];
+    let mut cb_table: Vec<Option<&Instruction>> = vec![None; 256];
+    for i in &insts {
+        match i.code >> 8 {
+            0x00 => table[(i.code & 0xff) as usize] = Some(i),
+            0xcb => cb_table[(i.code & 0xff) as usize] = Some(i),
+            _ => return Err(Error(format!("Invalid opcode: {:04x}", i.code))),
+        }
+    }
+
     context.insert("insts", &insts);
+    context.insert("table", &table);
+    context.insert("cb_table", &cb_table);
 
-    let output = match tera.render("root.rs", &context) {
+    let output = match tera.render(root, &context) {
         Ok(output) => output,
         Err(e) => {
             println!("Error: {}", e);
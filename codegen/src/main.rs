@@ -48,6 +48,9 @@ pub enum Opt {
     Fetch(Fetch),
     #[structopt(name = "generate")]
     Generate(Generate),
+    /// Generate the tests of the ALU instructions against the reference model.
+    #[structopt(name = "tests")]
+    Tests(Generate),
 }
 
 #[derive(Debug)]
@@ -86,6 +89,7 @@ fn main() -> Result<()> {
 
     match opt {
         Opt::Fetch(opt) => fetcher::run(&opt),
-        Opt::Generate(opt) => generator::run(&opt),
+        Opt::Generate(opt) => generator::run(&opt, "root.rs"),
+        Opt::Tests(opt) => generator::run(&opt, "tests.rs"),
     }
 }
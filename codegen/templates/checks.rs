@@ -0,0 +1,13 @@
+{% macro alu(i, src) %}
+/// {{i.operator}} {{i.operands | join(sep=",")}}
+#[test]
+fn op_{{i.code | hex}}() {
+    check_a(0x{{i.code | hex}}, &FLAGS, r::{{i.operator}}, Some(|cpu, mmu, v| {
+        {%- if src == "d8" -%}
+        mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        {%- else -%}
+        {{ src | setter(bits=8) }}v);
+        {%- endif -%}
+    }));
+}
+{% endmacro %}
@@ -130,7 +130,7 @@
       false
   };
 
-  let v = if cpu.get_nf() { v - adj } else { v + adj };
+  let v = if cpu.get_nf() { v.wrapping_sub(adj) } else { v + adj };
   let v = (v & 0xff) as u8;
   let z = v == 0;
 
@@ -162,7 +162,7 @@
 {% endmacro %}
 
 {% macro ei(i) %}
-  cpu.enable_interrupt();
+  cpu.enable_interrupt_delayed();
 {% endmacro %}
 
 {% macro rlc(i) %}
@@ -3,20 +3,147 @@
 use crate::cpu::Cpu;
 use crate::mmu::Mmu;
 use crate::alu;
-use hashbrown::HashMap;
-use lazy_static::lazy_static;
-use log::*;
-
-lazy_static! {
-    static ref MNEMONICS: HashMap<u16, &'static str> = {
-        let mut m = HashMap::new();
-        {%- for i in insts -%}
-        m.insert(0x{{i.code|hex}}, "{{i.operator}} {{i.operands|join(sep=",")}}");
-        {%- endfor -%}
-        m
+
+/// The metadata and the evaluator of a single instruction.
+#[derive(Clone, Copy)]
+pub struct Inst {
+    /// The opcode, prefixed by `0xcb` for the extended instructions.
+    pub code: u16,
+    /// The mnemonic string.
+    pub mnemonic: &'static str,
+    /// The length of the instruction in bytes.
+    pub size: usize,
+    /// The clock cycles taken by the instruction, without branching for the conditional ones.
+    pub time: usize,
+    exec: fn(u16, &mut Cpu, &mut Mmu) -> (usize, usize),
+}
+
+impl Inst {
+    /// Execute the instruction, returning the clock cycles taken and the length.
+    pub fn exec(&self, arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
+        (self.exec)(arg, cpu, mmu)
+    }
+}
+
+/// The instructions indexed by the opcode.
+pub static INSTS: [Option<Inst>; 256] = [
+    {%- for i in table -%}
+    {%- if i -%}
+    Some(Inst {
+        code: 0x{{i.code|hex}},
+        mnemonic: "{{i.operator}} {{i.operands|join(sep=",")}}",
+        size: {{i.size}},
+        time: {{i.time | untuple}},
+        exec: op_{{i.code|hex}},
+    }),
+    {%- else -%}
+    None,
+    {%- endif -%}
+    {%- endfor -%}
+];
+
+/// The instructions prefixed by `0xcb` indexed by the second byte of the opcode.
+pub static CB_INSTS: [Option<Inst>; 256] = [
+    {%- for i in cb_table -%}
+    {%- if i -%}
+    Some(Inst {
+        code: 0x{{i.code|hex}},
+        mnemonic: "{{i.operator}} {{i.operands|join(sep=",")}}",
+        size: {{i.size}},
+        time: {{i.time | untuple}},
+        exec: op_{{i.code|hex}},
+    }),
+    {%- else -%}
+    None,
+    {%- endif -%}
+    {%- endfor -%}
+];
+
+/// How an instruction affects a flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagEffect {
+    /// The flag is left as is.
+    Unchanged,
+    /// The flag is cleared.
+    Reset,
+    /// The flag is set.
+    Set,
+    /// The flag depends on the result.
+    Computed,
+}
+
+/// The metadata of an instruction, for disassemblers, tracers and tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpMeta {
+    /// The mnemonic string.
+    pub mnemonic: &'static str,
+    /// The length of the instruction in bytes, or 0 for an invalid opcode.
+    pub size: usize,
+    /// The clock cycles taken by the instruction, with the conditional branches not taken.
+    pub min_time: usize,
+    /// The clock cycles taken by the instruction, with the conditional branches taken.
+    pub max_time: usize,
+    /// The effects on the flags, in the order of Z, N, H and C.
+    pub flags: [FlagEffect; 4],
+}
+
+impl OpMeta {
+    /// The metadata of the opcodes which aren't valid instructions.
+    pub const INVALID: OpMeta = OpMeta {
+        mnemonic: "(unknown opcode)",
+        size: 0,
+        min_time: 0,
+        max_time: 0,
+        flags: [FlagEffect::Unchanged; 4],
     };
+
+    /// Return whether the opcode is a valid instruction.
+    pub fn is_valid(&self) -> bool {
+        self.size > 0
+    }
 }
 
+/// The metadata of the instructions indexed by the opcode, followed by the ones prefixed by `0xcb`
+/// indexed by 256 plus the second byte of the opcode.
+pub static OPCODE_META: [OpMeta; 512] = [
+    {%- for i in table -%}
+    {%- if i -%}
+    OpMeta {
+        mnemonic: "{{i.operator}} {{i.operands|join(sep=",")}}",
+        size: {{i.size}},
+        min_time: {{i.time | untuple}},
+        max_time: {{i.time | maxtime}},
+        flags: [
+            FlagEffect::{{i.z | flageffect}},
+            FlagEffect::{{i.n | flageffect}},
+            FlagEffect::{{i.h | flageffect}},
+            FlagEffect::{{i.c | flageffect}},
+        ],
+    },
+    {%- else -%}
+    OpMeta::INVALID,
+    {%- endif -%}
+    {%- endfor -%}
+    {%- for i in cb_table -%}
+    {%- if i -%}
+    OpMeta {
+        mnemonic: "{{i.operator}} {{i.operands|join(sep=",")}}",
+        size: {{i.size}},
+        min_time: {{i.time | untuple}},
+        max_time: {{i.time | maxtime}},
+        flags: [
+            FlagEffect::{{i.z | flageffect}},
+            FlagEffect::{{i.n | flageffect}},
+            FlagEffect::{{i.h | flageffect}},
+            FlagEffect::{{i.c | flageffect}},
+        ],
+    },
+    {%- else -%}
+    OpMeta::INVALID,
+    {%- endif -%}
+    {%- endfor -%}
+];
+
 {% for i in insts %}
 /// {{i.operator}} {{i.operands | join(sep=",")}}
 #[allow(unused_variables)]
@@ -201,19 +328,41 @@ fn op_{{i.code | hex}}(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize)
 }
 {% endfor %}
 
+/// Return the instruction for the given opcode, if it's valid.
+pub fn lookup(code: u16) -> Option<&'static Inst> {
+    let table = match code >> 8 {
+        0x00 => &INSTS,
+        0xcb => &CB_INSTS,
+        _ => return None,
+    };
+
+    table[(code & 0xff) as usize].as_ref()
+}
+
+/// Return the metadata for the given opcode, [`OpMeta::INVALID`][] if it's not valid.
+pub fn meta(code: u16) -> &'static OpMeta {
+    match code >> 8 {
+        0x00 => &OPCODE_META[code as usize],
+        0xcb => &OPCODE_META[0x100 + (code & 0xff) as usize],
+        _ => &OpMeta::INVALID,
+    }
+}
+
 /// Return the mnemonic string for the given opcode.
 pub fn mnem(code: u16) -> &'static str {
-    MNEMONICS.get(&code).unwrap_or(&"(unknown opcode)")
+    meta(code).mnemonic
 }
 
 /// Decodes the opecode and actually executes one instruction.
 pub fn decode(code: u16, arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     trace!("{:04x}: {:04x}: {}", cpu.get_pc(), code, mnem(code));
 
-    match code {
-        {%- for i in insts -%}
-        0x{{i.code | hex}} => op_{{i.code | hex}}(arg, cpu, mmu),
-        {%- endfor -%}
-        _ => panic!("Invalid opcode: {:04x}: {:04x}", cpu.get_pc(), code),
+    match lookup(code) {
+        Some(inst) => inst.exec(arg, cpu, mmu),
+        None => {
+            error!("Invalid opcode: {:04x}: {:04x}", cpu.get_pc(), code);
+            cpu.lock();
+            (4, 0)
+        }
     }
 }
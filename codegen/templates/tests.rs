@@ -0,0 +1,129 @@
+{% import "checks.rs" as checks %}
+
+//! The ALU instructions of the decoder checked against the reference model in `alu.rs`.
+//!
+//! Generated by `codegen tests` from the same instruction list as the decoder.
+
+#![allow(unused_variables)]
+
+use crate::alu::reference as r;
+use crate::cpu::Cpu;
+use crate::inst::lookup;
+use crate::mmu::Mmu;
+
+/// The address `(hl)` points to.
+const HL: u16 = 0xc000;
+
+/// The address of the instruction, followed by its immediate operand.
+const PC: u16 = 0xd000;
+
+/// The flags the instructions start with: only the carry is an input, and the other flags
+/// must not leak into the result.
+const FLAGS: [u8; 2] = [0x00, 0xf0];
+
+/// Every combination of the flags, for DAA which depends on N and H too.
+const ALL_FLAGS: [u8; 16] = [
+    0x00, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80, 0x90, 0xa0, 0xb0, 0xc0, 0xd0, 0xe0,
+    0xf0,
+];
+
+type Reference = fn(u8, u8, u8) -> (u8, u8);
+type Set = fn(&mut Cpu, &mut Mmu, u8);
+type Get = fn(&mut Cpu, &mut Mmu) -> u8;
+
+fn setup(a: u8, f: u8) -> Cpu {
+    let mut cpu = Cpu::new();
+    cpu.set_af(((a as u16) << 8) | f as u16);
+    cpu.set_hl(HL);
+    cpu.set_pc(PC);
+    cpu
+}
+
+/// Check an instruction which stores its result in the accumulator, for every value of the
+/// accumulator and of the operand, if it has one.
+fn check_a(code: u16, flags: &[u8], reference: Reference, set: Option<Set>) {
+    let inst = lookup(code).unwrap();
+    let mut mmu = Mmu::new();
+    let values = if set.is_some() { 0..=255 } else { 0..=0 };
+
+    for a in 0..=255 {
+        for v in values.clone() {
+            for f in flags {
+                let mut cpu = setup(a, *f);
+                if let Some(set) = set {
+                    set(&mut cpu, &mut mmu, v);
+                }
+                // The operand may be the accumulator itself.
+                let a = cpu.get_a();
+
+                inst.exec(1, &mut cpu, &mut mmu);
+
+                let (r, rf) = reference(a, v, *f);
+                assert_eq!(
+                    (cpu.get_a(), cpu.get_af() as u8),
+                    (r, rf),
+                    "{}: a={:02x} v={:02x} f={:02x}",
+                    inst.mnemonic,
+                    a,
+                    v,
+                    f
+                );
+            }
+        }
+    }
+}
+
+/// Check an instruction prefixed by `0xcb`, which stores its result in the operand.
+fn check_cb(code: u16, reference: Reference, set: Set, get: Get) {
+    let inst = lookup(code).unwrap();
+    let mut mmu = Mmu::new();
+
+    for v in 0..=255 {
+        for f in ALL_FLAGS.iter() {
+            let mut cpu = setup(0, *f);
+            set(&mut cpu, &mut mmu, v);
+
+            inst.exec(1, &mut cpu, &mut mmu);
+
+            let (r, rf) = reference(0, v, *f);
+            assert_eq!(
+                (get(&mut cpu, &mut mmu), cpu.get_af() as u8),
+                (r, rf),
+                "{}: v={:02x} f={:02x}",
+                inst.mnemonic,
+                v,
+                f
+            );
+        }
+    }
+}
+
+{% for i in insts -%}
+{%- set src = i.operands | last -%}
+{%- if i.operator == "adc" or i.operator == "sbc" or i.operator == "sub" or i.operator == "and" or i.operator == "or" or i.operator == "xor" or i.operator == "cp" %}
+{{ checks::alu(i=i, src=src) }}
+{%- elif i.operator == "add" and i.bits == 8 %}
+{{ checks::alu(i=i, src=src) }}
+{%- elif i.operator == "daa" %}
+/// {{i.operator}}
+#[test]
+fn op_{{i.code | hex}}() {
+    check_a(0x{{i.code | hex}}, &ALL_FLAGS, r::daa, None);
+}
+{% elif i.operator == "rlc" or i.operator == "rrc" or i.operator == "rl" or i.operator == "rr" or i.operator == "sla" or i.operator == "sra" or i.operator == "srl" or i.operator == "swap" or i.operator == "bit" %}
+/// {{i.operator}} {{i.operands | join(sep=",")}}
+#[test]
+fn op_{{i.code | hex}}() {
+    check_cb(
+        0x{{i.code | hex}},
+        {%- if i.operator == "bit" -%}
+        |_, v, f| r::bit({{i.operands[0]}}, v, f),
+        {%- else -%}
+        r::{{i.operator}},
+        {%- endif -%}
+        |cpu, mmu, v| { {{ src | setter(bits=8) }}v); },
+        |cpu, mmu| {{ src | getter(bits=8) }},
+    );
+}
+{% endif -%}
+{%- endfor %}
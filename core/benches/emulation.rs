@@ -0,0 +1,55 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rgy::bench::{self, Bench};
+
+/// Skip the boot ROM, so the benchmarks run the demo program.
+fn demo() -> Bench {
+    let mut bench = Bench::new(&bench::rom());
+    for _ in 0..400 {
+        bench.frame();
+    }
+    bench
+}
+
+fn frame(c: &mut Criterion) {
+    let mut bench = demo();
+    c.bench_function("frame", |b| b.iter(|| bench.frame()));
+}
+
+fn line(c: &mut Criterion) {
+    let mut bench = demo();
+    c.bench_function("line", |b| b.iter(|| bench.line()));
+}
+
+fn samples(c: &mut Criterion) {
+    let mut bench = demo();
+    let mut out = vec![0; 1024];
+    c.bench_function("samples", |b| b.iter(|| bench.samples(&mut out, 44100)));
+}
+
+/// Run Blargg's cpu_instrs to completion, if the ROM is given by `RGY_CPU_INSTRS`.
+fn cpu_instrs(c: &mut Criterion) {
+    let rom = match std::env::var("RGY_CPU_INSTRS") {
+        Ok(path) => std::fs::read(path).expect("Couldn't read cpu_instrs"),
+        Err(_) => return,
+    };
+
+    let mut group = c.benchmark_group("cpu_instrs");
+    group.sample_size(10);
+    group.bench_function("cpu_instrs", |b| {
+        b.iter(|| {
+            let mut bench = Bench::new(&rom);
+            // The test ROM reports the results over the serial port.
+            for _ in 0..60 * 120 {
+                bench.frame();
+                let out = bench.serial();
+                if out.ends_with(b"Passed all tests\n") || out.ends_with(b"Failed\n") {
+                    break;
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, frame, line, samples, cpu_instrs);
+criterion_main!(benches);
@@ -0,0 +1,210 @@
+//! Running the emulator on a microcontroller such as the RP2040 or the ESP32.
+//!
+//! The emulator only needs `core` and `alloc`: the large buffers are placed in statics with
+//! [`Buffers`][], the display is handed over a line at a time in RGB565 so that each line
+//! can be sent to an SPI LCD by DMA, and the sound is pulled with integer math only.
+//!
+//! The `board` module stands for the HAL of the target. Everything else builds as is for
+//! a bare-metal target, given a global allocator such as `embedded-alloc`. To keep the
+//! example runnable with `cargo run --example embedded`, `main` at the bottom is a host shim
+//! which links `std` for the allocator, the panic handler and the entry point.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::ptr::addr_of_mut;
+use rgy::debug::NullDebugger;
+use rgy::{
+    Buffers, CgbColor, ColorMapper, Config, DmgColor, InstructionStepped, Key, NullObserver,
+    Stream, System, VRAM_WIDTH,
+};
+
+/// The sample rate of the PWM audio output.
+const SAMPLE_RATE: u32 = 22050;
+
+/// The samples played per frame at about 59.7 frames per second.
+const FRAME_SAMPLES: usize = SAMPLE_RATE as usize * 1000 / 59727;
+
+/// The memory map, including OAM and high RAM.
+static mut RAM: [u8; Buffers::RAM_SIZE] = [0; Buffers::RAM_SIZE];
+
+/// The work RAM banks.
+static mut WRAM: [u8; Buffers::WRAM_SIZE] = [0; Buffers::WRAM_SIZE];
+
+/// The video RAM banks.
+static mut VRAM: [u8; Buffers::VRAM_SIZE] = [0; Buffers::VRAM_SIZE];
+
+/// The frame buffer the emulator draws into.
+static mut FRAME: [u32; Buffers::FRAME_SIZE] = [0; Buffers::FRAME_SIZE];
+
+/// The line sent to the LCD, in the RGB565 format of the panel.
+static mut LINE: [u16; VRAM_WIDTH] = [0; VRAM_WIDTH];
+
+/// The samples for the PWM duty cycle, 8 bits each.
+static mut AUDIO: [u8; FRAME_SAMPLES] = [0; FRAME_SAMPLES];
+
+/// The cartridge, in flash on the target. This is a ROM which loops forever.
+static ROM: [u8; 0x8000] = rom();
+
+const fn rom() -> [u8; 0x8000] {
+    let mut rom = [0; 0x8000];
+    // jr -2
+    rom[0x100] = 0x18;
+    rom[0x101] = 0xfe;
+    rom
+}
+
+/// The peripherals of the board, as the HAL of the target would provide them.
+mod board {
+    /// Send a line to the LCD, e.g. by starting an SPI DMA transfer from the buffer.
+    pub fn lcd_line(_y: usize, _line: &'static [u16]) {}
+
+    /// Read the buttons wired to GPIOs. From the lowest bit: right, left, up, down, A, B,
+    /// select and start.
+    pub fn buttons() -> u8 {
+        0
+    }
+
+    /// Queue the PWM duty cycles played at the sample rate, e.g. by a DMA channel.
+    pub fn pwm_play(_samples: &'static [u8]) {}
+
+    /// Wait for the timer marking the next frame.
+    pub fn wait_frame() {}
+
+    /// The microseconds since boot.
+    pub fn micros() -> u64 {
+        0
+    }
+}
+
+/// Draw the pixels in RGB565 directly, so the lines need no conversion.
+struct Rgb565;
+
+impl ColorMapper for Rgb565 {
+    fn dmg(&self, color: DmgColor) -> u32 {
+        color.to_rgb565() as u32
+    }
+
+    fn cgb(&self, r: u8, g: u8, b: u8) -> u32 {
+        CgbColor::new(r, g, b).to_rgb565() as u32
+    }
+}
+
+/// The audio stream, kept by the main loop to pull the samples after each frame.
+type SharedStream = Rc<RefCell<Option<Box<dyn Stream>>>>;
+
+struct Board {
+    stream: SharedStream,
+}
+
+impl rgy::Hardware for Board {
+    fn vram_update(&mut self, line: usize, buffer: &[u32]) {
+        // Safety: the line buffer is only touched here, and the transfer of the previous
+        // line is done by the time the next one is drawn.
+        let out = unsafe { &mut *addr_of_mut!(LINE) };
+        for (out, pixel) in out.iter_mut().zip(buffer) {
+            *out = *pixel as u16;
+        }
+        board::lcd_line(line, out);
+    }
+
+    fn joypad_pressed(&mut self, key: Key) -> bool {
+        let bit = match key {
+            Key::Right => 0,
+            Key::Left => 1,
+            Key::Up => 2,
+            Key::Down => 3,
+            Key::A => 4,
+            Key::B => 5,
+            Key::Select => 6,
+            Key::Start => 7,
+        };
+        board::buttons() & (1 << bit) != 0
+    }
+
+    fn sound_play(&mut self, stream: Box<dyn Stream>) {
+        *self.stream.borrow_mut() = Some(stream);
+    }
+
+    fn clock(&mut self) -> u64 {
+        board::micros()
+    }
+}
+
+impl rgy::SerialPort for Board {}
+
+impl rgy::SchedHook for Board {}
+
+impl rgy::SavePersistence for Board {}
+
+impl rgy::EventHook for Board {}
+
+type Emulator = System<'static, NullDebugger, InstructionStepped, NullObserver, Rgb565>;
+
+/// Create the emulator with its buffers in the statics. Must be called once.
+fn init(rom: &[u8]) -> (Emulator, SharedStream) {
+    // Safety: the statics are handed out once, here.
+    let bufs = unsafe {
+        Buffers::new()
+            .ram(&mut *addr_of_mut!(RAM))
+            .wram(&mut *addr_of_mut!(WRAM))
+            .vram(&mut *addr_of_mut!(VRAM))
+            .frame(&mut *addr_of_mut!(FRAME))
+    };
+
+    // The frames are paced by the frame timer rather than by the emulator.
+    let cfg = Config::new().native_speed(true);
+
+    let stream = Rc::new(RefCell::new(None));
+    let hw = Board {
+        stream: stream.clone(),
+    };
+    let sys = System::with_video(cfg, rom, hw, NullDebugger, bufs, NullObserver, Rgb565);
+
+    (sys, stream)
+}
+
+/// Fill the audio buffer with the PWM duty cycles of the next frame.
+fn pull_audio(stream: &SharedStream) {
+    // Safety: the audio buffer is only touched here, after the previous frame was played.
+    let out = unsafe { &mut *addr_of_mut!(AUDIO) };
+
+    match stream.borrow_mut().as_mut() {
+        Some(stream) => {
+            let max = stream.max() as u32;
+            for sample in out.iter_mut() {
+                *sample = (stream.next(SAMPLE_RATE) as u32 * 255 / max) as u8;
+            }
+        }
+        None => out.iter_mut().for_each(|sample| *sample = 0),
+    }
+
+    board::pwm_play(out);
+}
+
+/// The main loop of the target, running the given number of frames or forever.
+fn run(frames: Option<usize>) {
+    let (mut sys, stream) = init(&ROM);
+
+    let mut drawn = 0;
+    while Some(drawn) != frames {
+        if !sys.run_until_vblank().running {
+            break;
+        }
+        drawn += 1;
+
+        pull_audio(&stream);
+        board::wait_frame();
+    }
+}
+
+extern crate std;
+
+fn main() {
+    run(Some(60));
+    std::println!("Ran 60 frames with static buffers");
+}
@@ -40,7 +40,9 @@ impl rgy::Hardware for Hardware {
             .expect("Couldn't get epoch");
         epoch.as_micros() as u64
     }
+}
 
+impl rgy::SerialPort for Hardware {
     fn send_byte(&mut self, _b: u8) {
         // Send a byte to a serial port.
     }
@@ -49,13 +51,17 @@ impl rgy::Hardware for Hardware {
         // Try to read a byte from a serial port.
         None
     }
+}
 
+impl rgy::SchedHook for Hardware {
     fn sched(&mut self) -> bool {
         // `true` to continue, `false` to stop the emulator.
         println!("It's running!");
         true
     }
+}
 
+impl rgy::SavePersistence for Hardware {
     fn load_ram(&mut self, size: usize) -> Vec<u8> {
         // Return save data.
         vec![0; size]
@@ -66,6 +72,13 @@ impl rgy::Hardware for Hardware {
     }
 }
 
+impl rgy::EventHook for Hardware {
+    fn event(&mut self, event: rgy::Event) {
+        // Trace the changes of the hardware state.
+        println!("Event: {:?}", event);
+    }
+}
+
 fn main() {
     // Create the default config.
     let cfg = Config::new();
@@ -1,7 +1,9 @@
 use rgy::cpu::Cpu;
+use rgy::debug::Breakpoint;
 use rgy::device::IoHandler;
 use rgy::inst::mnem;
 use rgy::mmu::{MemRead, MemWrite, Mmu};
+use rgy::MbcState;
 
 use std::collections::{HashSet, VecDeque};
 use std::fmt;
@@ -52,12 +54,13 @@ impl From<std::num::ParseIntError> for CmdError {
 type CmdResult<T> = std::result::Result<T, CmdError>;
 
 pub struct Debugger {
-    breaks: HashSet<u16>,
+    breaks: HashSet<Breakpoint>,
     rd_watches: HashSet<u16>,
     wr_watches: HashSet<u16>,
     prompt: bool,
     stepping: bool,
     cpu_state: Cpu,
+    mbc_state: MbcState,
     signal: Signal,
     exec_path: VecDeque<u16>,
 }
@@ -71,6 +74,12 @@ impl Debugger {
             prompt: false,
             stepping: false,
             cpu_state: Cpu::new(),
+            mbc_state: MbcState {
+                fixed_bank: 0,
+                rom_bank: 1,
+                ram_bank: 0,
+                ram_enabled: false,
+            },
             signal: Signal::new(),
             exec_path: VecDeque::new(),
         }
@@ -92,7 +101,7 @@ impl Debugger {
         } else if self.stepping {
             true
         } else {
-            self.breaks.contains(&pc)
+            self.breaks.iter().any(|b| b.hits(pc, &self.mbc_state))
         }
     }
 
@@ -176,6 +185,10 @@ impl rgy::debug::Debugger for Debugger {
         self.cpu_state = cpu;
     }
 
+    fn take_mbc_state(&mut self, state: MbcState) {
+        self.mbc_state = state;
+    }
+
     fn on_decode(&mut self, mmu: &Mmu) {
         let pc = self.cpu_state.get_pc();
 
@@ -288,22 +301,26 @@ fn parse_addr(s: &str) -> CmdResult<u16> {
     u16::from_str_radix(s, 16).map_err(|e| CmdError::new(e))
 }
 
+fn parse_break(s: &str) -> CmdResult<Breakpoint> {
+    Breakpoint::parse(s).ok_or_else(|| CmdError::new(format!("Invalid break point: {}", s)))
+}
+
 #[derive(StructOpt, Debug)]
 #[structopt(name = "break", about = "Manage break points.")]
 enum CmdBreak {
     /// Add a break point
     #[structopt(name = "add")]
     Add {
-        /// Address in hex
-        #[structopt(name = "addr", parse(try_from_str = "parse_addr"))]
-        addr: u16,
+        /// Address in hex, optionally prefixed by the ROM bank as `bank:addr`
+        #[structopt(name = "addr", parse(try_from_str = "parse_break"))]
+        addr: Breakpoint,
     },
     /// Remove a break point
     #[structopt(name = "remove")]
     Remove {
-        /// Address in hex
-        #[structopt(name = "addr", parse(try_from_str = "parse_addr"))]
-        addr: u16,
+        /// Address in hex, optionally prefixed by the ROM bank as `bank:addr`
+        #[structopt(name = "addr", parse(try_from_str = "parse_break"))]
+        addr: Breakpoint,
     },
     /// List break points
     #[structopt(name = "list")]
@@ -315,23 +332,23 @@ impl CmdHandler for CmdBreak {
         match self {
             CmdBreak::Add { addr } => {
                 if inner.breaks.insert(*addr) {
-                    println!("Set break point at {:04x}", addr);
+                    println!("Set break point at {}", addr);
                 } else {
-                    println!("Break point already set at {:04x}", addr);
+                    println!("Break point already set at {}", addr);
                 }
             }
             CmdBreak::Remove { addr } => {
                 if inner.breaks.remove(&addr) {
-                    println!("Remove break point at {:04x}", addr);
+                    println!("Remove break point at {}", addr);
                 } else {
-                    println!("Break point isn't set at {:04x}", addr);
+                    println!("Break point isn't set at {}", addr);
                 }
             }
             CmdBreak::List => {
                 println!("Break points: ");
 
                 for addr in inner.breaks.iter() {
-                    println!("* {:04x}", addr);
+                    println!("* {}", addr);
                 }
             }
         }
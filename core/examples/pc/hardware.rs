@@ -1,10 +1,11 @@
-use cpal;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, StreamConfig};
 use log::*;
 use minifb::{Scale, Window, WindowOptions};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
@@ -16,22 +17,25 @@ use rgy::{Key, Stream, VRAM_HEIGHT, VRAM_WIDTH};
 #[derive(Clone)]
 pub struct Hardware {
     rampath: Option<String>,
-    vram: Arc<Mutex<Vec<u32>>>,
+    /// The lines drawn so far, for the frontends driven by `vram_update`.
+    lines: Vec<u32>,
+    frames: SyncSender<Vec<u32>>,
     pcm: SpeakerHandle,
     keystate: Arc<Mutex<HashMap<Key, bool>>>,
     escape: Arc<AtomicBool>,
 }
 
-struct Gui {
+pub struct Gui {
     window: Window,
-    vram: Arc<Mutex<Vec<u32>>>,
+    frames: Receiver<Vec<u32>>,
+    frame: Vec<u32>,
     keystate: Arc<Mutex<HashMap<Key, bool>>>,
     escape: Arc<AtomicBool>,
 }
 
 impl Gui {
     fn new(
-        vram: Arc<Mutex<Vec<u32>>>,
+        frames: Receiver<Vec<u32>>,
         keystate: Arc<Mutex<HashMap<Key, bool>>>,
         escape: Arc<AtomicBool>,
     ) -> Self {
@@ -58,13 +62,15 @@ impl Gui {
 
         Self {
             window,
-            vram,
+            frames,
+            frame: vec![0; VRAM_WIDTH * VRAM_HEIGHT],
             keystate,
             escape,
         }
     }
 
-    fn run(mut self) {
+    /// Run the window until it's closed.
+    pub fn run(mut self) {
         while !self.escape.load(Ordering::Relaxed) {
             std::thread::sleep(Duration::from_millis(10));
             self.vramupdate();
@@ -73,8 +79,11 @@ impl Gui {
     }
 
     fn vramupdate(&mut self) {
-        let vram = self.vram.lock().unwrap().clone();
-        self.window.update_with_buffer(&vram).unwrap();
+        // Show the latest frame, if the emulator drew any since the last update.
+        if let Some(frame) = self.frames.try_iter().last() {
+            self.frame = frame;
+        }
+        self.window.update_with_buffer(&self.frame).unwrap();
     }
 
     fn keyupdate(&mut self) {
@@ -114,8 +123,9 @@ impl Gui {
 }
 
 impl Hardware {
-    pub fn new(rampath: Option<String>) -> Self {
-        let vram = Arc::new(Mutex::new(vec![0; VRAM_WIDTH * VRAM_HEIGHT]));
+    pub fn new(rampath: Option<String>) -> (Self, Gui) {
+        // Frames are dropped rather than queued when the window falls behind.
+        let (frames, frame_rx) = mpsc::sync_channel(2);
 
         let pcm = Pcm::new();
         let handle = pcm.handle();
@@ -134,31 +144,41 @@ impl Hardware {
 
         let escape = Arc::new(AtomicBool::new(false));
 
-        Self {
+        let gui = Gui::new(frame_rx, keystate.clone(), escape.clone());
+
+        let hw = Self {
             rampath,
-            vram,
+            lines: vec![0; VRAM_WIDTH * VRAM_HEIGHT],
+            frames,
             pcm: handle,
             keystate,
             escape,
+        };
+
+        (hw, gui)
+    }
+
+    /// Hand a complete frame over to the window.
+    pub fn present(&self, frame: &[u32]) {
+        match self.frames.try_send(frame.to_vec()) {
+            Ok(()) | Err(TrySendError::Full(_)) => {}
+            Err(TrySendError::Disconnected(_)) => self.escape.store(true, Ordering::Relaxed),
         }
     }
 
-    pub fn run(self) {
-        let bg = Gui::new(
-            self.vram.clone(),
-            self.keystate.clone(),
-            self.escape.clone(),
-        );
-        bg.run();
+    /// Return whether the window was closed.
+    pub fn escaped(&self) -> bool {
+        self.escape.load(Ordering::Relaxed)
     }
 }
 
 impl rgy::Hardware for Hardware {
     fn vram_update(&mut self, line: usize, buf: &[u32]) {
-        let mut vram = self.vram.lock().unwrap();
-        for i in 0..buf.len() {
-            let base = line * VRAM_WIDTH;
-            vram[base + i] = buf[i];
+        let base = line * VRAM_WIDTH;
+        self.lines[base..base + buf.len()].copy_from_slice(buf);
+
+        if line == VRAM_HEIGHT - 1 {
+            self.present(&self.lines);
         }
     }
 
@@ -175,6 +195,15 @@ impl rgy::Hardware for Hardware {
         self.pcm.play(stream)
     }
 
+    fn clock(&mut self) -> u64 {
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Couldn't get epoch");
+        epoch.as_micros() as u64
+    }
+}
+
+impl rgy::SerialPort for Hardware {
     fn send_byte(&mut self, b: u8) {
         info!("Send byte: {:02x}", b);
     }
@@ -182,14 +211,9 @@ impl rgy::Hardware for Hardware {
     fn recv_byte(&mut self) -> Option<u8> {
         None
     }
+}
 
-    fn clock(&mut self) -> u64 {
-        let epoch = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Couldn't get epoch");
-        epoch.as_micros() as u64
-    }
-
+impl rgy::SavePersistence for Hardware {
     fn load_ram(&mut self, size: usize) -> Vec<u8> {
         let mut ram = vec![0; size];
 
@@ -217,12 +241,16 @@ impl rgy::Hardware for Hardware {
             None => {}
         }
     }
+}
 
+impl rgy::SchedHook for Hardware {
     fn sched(&mut self) -> bool {
         !self.escape.load(Ordering::Relaxed)
     }
 }
 
+impl rgy::EventHook for Hardware {}
+
 pub struct Pcm {
     tx: Sender<SpeakerCmd>,
     rx: Receiver<SpeakerCmd>,
@@ -248,54 +276,66 @@ impl Pcm {
     }
 
     pub fn run(self) {
-        let device = cpal::default_output_device().expect("Failed to get default output device");
-        let format = device
-            .default_output_format()
-            .expect("Failed to get default output format");
-        let sample_rate = format.sample_rate.0;
-        let event_loop = cpal::EventLoop::new();
-        let stream_id = event_loop.build_output_stream(&device, &format).unwrap();
-        event_loop.play_stream(stream_id.clone());
-
-        let mut stream = None;
-
-        event_loop.run(move |_, data| {
-            match self.rx.try_recv() {
-                Ok(SpeakerCmd::Play(s)) => {
-                    stream = Some(s);
-                }
-                Ok(SpeakerCmd::Stop) => {
-                    stream = None;
-                }
-                Err(_) => {}
-            }
+        let host = cpal::default_host();
+        let device = host
+            .default_output_device()
+            .expect("Failed to get default output device");
+        let supported = device
+            .default_output_config()
+            .expect("Failed to get default output config");
+        let format = supported.sample_format();
+        let config = supported.config();
+
+        let stream = match format {
+            SampleFormat::F32 => self.build::<f32>(&device, &config),
+            SampleFormat::I16 => self.build::<i16>(&device, &config),
+            SampleFormat::U16 => self.build::<u16>(&device, &config),
+            format => panic!("Unsupported sample format {}", format),
+        };
+        stream.play().expect("Failed to play the audio stream");
+
+        // The stream plays as long as it's alive.
+        loop {
+            std::thread::park();
+        }
+    }
+
+    fn build<T>(self, device: &cpal::Device, config: &StreamConfig) -> cpal::Stream
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let sample_rate = config.sample_rate.0;
+        let channels = config.channels as usize;
+        let rx = self.rx;
+        let mut stream: Option<Box<dyn Stream>> = None;
+
+        device
+            .build_output_stream(
+                config,
+                move |data: &mut [T], _| {
+                    // The emulator hands over the streams without blocking the audio thread.
+                    for cmd in rx.try_iter() {
+                        stream = match cmd {
+                            SpeakerCmd::Play(s) => Some(s),
+                            SpeakerCmd::Stop => None,
+                        };
+                    }
 
-            match data {
-                cpal::StreamData::Output {
-                    buffer: cpal::UnknownTypeOutputBuffer::U16(_buffer),
-                } => unimplemented!(),
-                cpal::StreamData::Output {
-                    buffer: cpal::UnknownTypeOutputBuffer::I16(_buffer),
-                } => unimplemented!(),
-                cpal::StreamData::Output {
-                    buffer: cpal::UnknownTypeOutputBuffer::F32(mut buffer),
-                } => {
-                    for sample in buffer.chunks_mut(format.channels as usize) {
+                    for frame in data.chunks_mut(channels) {
                         let value = match &mut stream {
-                            Some(s) => {
-                                ((s.next(sample_rate) as u64 * 100 / s.max() as u64) as f32 / 100.0)
-                            }
+                            Some(s) => s.next(sample_rate) as f32 / s.max() as f32,
                             None => 0.0,
                         };
 
-                        for out in sample.iter_mut() {
-                            *out = value;
+                        for out in frame.iter_mut() {
+                            *out = T::from_sample(value);
                         }
                     }
-                }
-                _ => (),
-            }
-        });
+                },
+                |err| error!("Audio stream error: {}", err),
+                None,
+            )
+            .expect("Failed to build the audio stream")
     }
 }
 
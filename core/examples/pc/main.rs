@@ -2,14 +2,14 @@ mod debug;
 mod hardware;
 mod loader;
 
-use crate::{
-    debug::Debugger,
-    hardware::Hardware,
-    loader::{load_rom, Loader},
-};
+use crate::{debug::Debugger, hardware::Hardware, loader::load_rom};
 
 use log::*;
+use rgy::debug::NullDebugger;
+use rgy::{Buffers, System};
 use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -37,7 +37,7 @@ pub struct Opt {
     rom: PathBuf,
 }
 
-fn to_cfg(opt: Opt) -> rgy::Config {
+fn to_cfg(opt: &Opt) -> rgy::Config {
     rgy::Config::new()
         .freq(opt.freq)
         .sample(opt.sample)
@@ -58,31 +58,76 @@ fn set_affinity() {
     }
 }
 
+/// Allocate a frame buffer for the emulator to draw into.
+fn frame_buffer() -> &'static mut [u32] {
+    Box::leak(vec![0; Buffers::FRAME_SIZE].into_boxed_slice())
+}
+
+/// Run the emulator a frame at a time, presenting each frame and pacing them to the CPU frequency.
+fn run(opt: &Opt, rom: &[u8], hw: Hardware) {
+    let frame_time = Duration::from_micros(rgy::FRAME_DOTS as u64 * 1_000_000 / opt.freq);
+
+    // The emulator draws into the frame buffers, swapped on VBlank so that a frame is never torn.
+    let bufs = Buffers::new()
+        .frame(frame_buffer())
+        .back_frame(frame_buffer());
+    let cfg = to_cfg(opt).native_speed(true);
+    let mut sys = System::with_buffers(cfg, rom, hw.clone(), NullDebugger, bufs);
+
+    let mut next = Instant::now();
+
+    loop {
+        let data = sys.run_until_vblank();
+        if !data.running || hw.escaped() {
+            break;
+        }
+
+        if data.frame_complete {
+            hw.present(&sys.frame().expect("No frame buffer"));
+        }
+
+        if opt.native_speed {
+            continue;
+        }
+
+        next += frame_time;
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        } else {
+            // Running behind; don't try to catch up with a burst of frames.
+            next = now;
+        }
+    }
+}
+
 fn main() {
     let opt = Opt::from_args();
 
     env_logger::init();
 
-    let hw = Hardware::new(opt.ram.clone());
-    let hw1 = hw.clone();
+    let (hw, gui) = Hardware::new(opt.ram.clone());
 
-    std::thread::spawn(move || {
-        let (rom, hw1) = if opt.rom.is_dir() {
-            let mut ldr = Loader::new(&opt.rom);
+    thread::spawn(move || {
+        let (rom, hw) = if opt.rom.is_dir() {
+            let mut roms = utils::RomDir::scan(&opt.rom).expect("Couldn't read ROM directory");
+            for file in roms.files() {
+                info!("ROM: {} ({})", file.name, file.header.title.trim_end());
+            }
 
-            utils::select(&mut ldr, hw1)
+            utils::select(&mut roms, hw)
         } else {
-            (load_rom(&opt.rom), hw1)
+            (load_rom(&opt.rom), hw)
         };
 
         set_affinity();
 
         if opt.debug {
-            rgy::run_debug(to_cfg(opt), &rom, hw1, Debugger::new());
+            rgy::run_debug(to_cfg(&opt), &rom, hw, Debugger::new());
         } else {
-            rgy::run(to_cfg(opt), &rom, hw1);
+            run(&opt, &rom, hw);
         }
     });
 
-    hw.run();
+    gui.run();
 }
@@ -102,3 +102,141 @@ fn test_signed() {
     assert_eq!(signed(0x0a), 0x000a);
     assert_eq!(signed(0x8a), 0xff8a);
 }
+
+/// A straightforward model of the 8-bit ALU instructions, which the generated tests of the
+/// decoder compare against.
+///
+/// Each function takes the accumulator, the operand and the flag register, and returns the
+/// result and the flag register. The result is the new accumulator, or the new operand for
+/// the instructions prefixed by `0xcb`.
+#[cfg(test)]
+pub mod reference {
+    const Z: u8 = 0x80;
+    const N: u8 = 0x40;
+    const H: u8 = 0x20;
+    const C: u8 = 0x10;
+
+    fn flag(set: bool, mask: u8) -> u8 {
+        if set {
+            mask
+        } else {
+            0
+        }
+    }
+
+    fn zero(v: u8) -> u8 {
+        flag(v == 0, Z)
+    }
+
+    pub fn add(a: u8, v: u8, _f: u8) -> (u8, u8) {
+        adc(a, v, 0)
+    }
+
+    pub fn adc(a: u8, v: u8, f: u8) -> (u8, u8) {
+        let c = (f & C != 0) as u16;
+        let sum = a as u16 + v as u16 + c;
+        let half = (a & 0xf) as u16 + (v & 0xf) as u16 + c;
+        let r = sum as u8;
+        (r, zero(r) | flag(half > 0xf, H) | flag(sum > 0xff, C))
+    }
+
+    pub fn sub(a: u8, v: u8, _f: u8) -> (u8, u8) {
+        sbc(a, v, 0)
+    }
+
+    pub fn sbc(a: u8, v: u8, f: u8) -> (u8, u8) {
+        let c = (f & C != 0) as i16;
+        let diff = a as i16 - v as i16 - c;
+        let half = (a & 0xf) as i16 - (v & 0xf) as i16 - c;
+        let r = diff as u8;
+        (r, zero(r) | N | flag(half < 0, H) | flag(diff < 0, C))
+    }
+
+    pub fn cp(a: u8, v: u8, f: u8) -> (u8, u8) {
+        (a, sub(a, v, f).1)
+    }
+
+    pub fn and(a: u8, v: u8, _f: u8) -> (u8, u8) {
+        (a & v, zero(a & v) | H)
+    }
+
+    pub fn or(a: u8, v: u8, _f: u8) -> (u8, u8) {
+        (a | v, zero(a | v))
+    }
+
+    pub fn xor(a: u8, v: u8, _f: u8) -> (u8, u8) {
+        (a ^ v, zero(a ^ v))
+    }
+
+    /// Adjust the accumulator to BCD after an addition or a subtraction.
+    pub fn daa(a: u8, _v: u8, f: u8) -> (u8, u8) {
+        let mut adjust = 0;
+        let mut carry = f & C != 0;
+
+        if f & N == 0 {
+            if carry || a > 0x99 {
+                adjust |= 0x60;
+                carry = true;
+            }
+            if f & H != 0 || a & 0xf > 0x9 {
+                adjust |= 0x06;
+            }
+            let r = a.wrapping_add(adjust);
+            (r, zero(r) | flag(carry, C))
+        } else {
+            if carry {
+                adjust |= 0x60;
+            }
+            if f & H != 0 {
+                adjust |= 0x06;
+            }
+            let r = a.wrapping_sub(adjust);
+            (r, zero(r) | N | flag(carry, C))
+        }
+    }
+
+    pub fn rlc(_a: u8, v: u8, _f: u8) -> (u8, u8) {
+        let r = v.rotate_left(1);
+        (r, zero(r) | flag(v & 0x80 != 0, C))
+    }
+
+    pub fn rrc(_a: u8, v: u8, _f: u8) -> (u8, u8) {
+        let r = v.rotate_right(1);
+        (r, zero(r) | flag(v & 0x01 != 0, C))
+    }
+
+    pub fn rl(_a: u8, v: u8, f: u8) -> (u8, u8) {
+        let r = (v << 1) | (f & C != 0) as u8;
+        (r, zero(r) | flag(v & 0x80 != 0, C))
+    }
+
+    pub fn rr(_a: u8, v: u8, f: u8) -> (u8, u8) {
+        let r = (v >> 1) | (((f & C != 0) as u8) << 7);
+        (r, zero(r) | flag(v & 0x01 != 0, C))
+    }
+
+    pub fn sla(_a: u8, v: u8, _f: u8) -> (u8, u8) {
+        let r = v << 1;
+        (r, zero(r) | flag(v & 0x80 != 0, C))
+    }
+
+    pub fn sra(_a: u8, v: u8, _f: u8) -> (u8, u8) {
+        let r = (v >> 1) | (v & 0x80);
+        (r, zero(r) | flag(v & 0x01 != 0, C))
+    }
+
+    pub fn srl(_a: u8, v: u8, _f: u8) -> (u8, u8) {
+        let r = v >> 1;
+        (r, zero(r) | flag(v & 0x01 != 0, C))
+    }
+
+    pub fn swap(_a: u8, v: u8, _f: u8) -> (u8, u8) {
+        let r = v.rotate_left(4);
+        (r, zero(r))
+    }
+
+    /// Test the bit of the operand, keeping the carry.
+    pub fn bit(n: u8, v: u8, f: u8) -> (u8, u8) {
+        (v, flag(v & (1 << n) == 0, Z) | H | (f & C))
+    }
+}
@@ -0,0 +1,221 @@
+//! Workloads to measure the performance of the emulator, shared by the benchmarks and embedded targets.
+//!
+//! The workloads don't depend on `std`, so they also run on microcontrollers. There, [`Bench::measure`][]
+//! takes the cycle counter of the target, e.g. the DWT cycle counter of Cortex-M, to report the cost
+//! of emulating a frame without any timer or allocator beyond what the emulator needs.
+
+use crate::debug::NullDebugger;
+use crate::gpu::LINE_DOTS;
+use crate::hardware::{EventHook, Hardware, Key, SavePersistence, SchedHook, SerialPort, Stream};
+use crate::mbc::BOOT_ROM;
+use crate::system::{Config, PollData, System};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::{vec, vec::Vec};
+use core::cell::RefCell;
+
+/// The clock cycles to draw a line.
+const LINE_CYCLES: u64 = LINE_DOTS as u64;
+
+/// The program of the demo ROM at 0x150.
+const PROGRAM: &[u8] = &[
+    0x3e, 0x80, 0xe0, 0x26, // Enable the sound.
+    0x3e, 0x77, 0xe0, 0x24, // Full volume on both outputs.
+    0x3e, 0xff, 0xe0, 0x25, // All the channels on both outputs.
+    0x3e, 0x80, 0xe0, 0x11, 0xe0, 0x16, // 50% duty.
+    0x3e, 0xf0, 0xe0, 0x12, 0xe0, 0x17, // Full envelope.
+    0xaf, 0xe0, 0x13, 0xe0, 0x18, // Frequency, lower bits.
+    0x3e, 0x87, 0xe0, 0x14, // Trigger the tone 1.
+    0x3e, 0x86, 0xe0, 0x19, // Trigger the tone 2.
+    0x21, 0x00, 0x80, // Fill the tiles and the tile maps.
+    0x7d, 0x22, 0x7c, 0xfe, 0xa0, 0x20, 0xf9, //
+    0x21, 0x00, 0xfe, // Fill OAM to scatter sprites.
+    0x7d, 0x22, 0x7d, 0xfe, 0xa0, 0x20, 0xf9, //
+    0x3e, 0x01, 0xe0, 0xff, // Enable VBlank interrupts.
+    0x3e, 0x93, 0xe0, 0x40, // Display the background and the sprites.
+    0xfb, // ei
+    0x76, 0xf0, 0x43, 0x3c, 0xe0, 0x43, 0x18, 0xf8, // Scroll a pixel per frame.
+];
+
+/// Build a ROM which scrolls a screen full of tiles and sprites, playing two tone channels.
+///
+/// The header passes the checks of the boot ROM, so the program starts once the boot ROM finished.
+pub fn rom() -> Vec<u8> {
+    let mut rom = vec![0; 0x8000];
+
+    // nop; jp 0x150
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xc3, 0x50, 0x01]);
+
+    // The boot ROM has a copy of the logo it checks.
+    let logo = BOOT_ROM
+        .windows(4)
+        .position(|w| w == [0xce, 0xed, 0x66, 0x66])
+        .expect("No logo in the boot ROM");
+    rom[0x104..0x134].copy_from_slice(&BOOT_ROM[logo..logo + 0x30]);
+    rom[0x134..0x13c].copy_from_slice(b"RGYBENCH");
+
+    rom[0x14d] = rom[0x134..0x14d]
+        .iter()
+        .fold(0u8, |x, b| x.wrapping_sub(*b).wrapping_sub(1));
+
+    // The VBlank interrupt only wakes up the CPU.
+    rom[0x40] = 0xd9; // reti
+    rom[0x150..0x150 + PROGRAM.len()].copy_from_slice(PROGRAM);
+
+    rom
+}
+
+struct Io {
+    stream: Option<Box<dyn Stream>>,
+    serial: Vec<u8>,
+}
+
+struct Headless(Rc<RefCell<Io>>);
+
+impl Hardware for Headless {
+    fn vram_update(&mut self, _line: usize, _buffer: &[u32]) {}
+
+    fn joypad_pressed(&mut self, _key: Key) -> bool {
+        false
+    }
+
+    fn sound_play(&mut self, stream: Box<dyn Stream>) {
+        self.0.borrow_mut().stream = Some(stream);
+    }
+
+    fn clock(&mut self) -> u64 {
+        0
+    }
+}
+
+impl SavePersistence for Headless {}
+
+impl SerialPort for Headless {
+    fn send_byte(&mut self, b: u8) {
+        self.0.borrow_mut().serial.push(b);
+    }
+}
+
+impl SchedHook for Headless {}
+
+impl EventHook for Headless {}
+
+/// The report of [`Bench::measure`][].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Report {
+    /// The number of frames run.
+    pub frames: u64,
+    /// The clock cycles emulated.
+    pub emulated_cycles: u64,
+    /// The cycles elapsed on the counter of the target.
+    pub host_cycles: u64,
+}
+
+impl Report {
+    /// The cycles of the target spent per frame.
+    pub fn host_cycles_per_frame(&self) -> u64 {
+        self.host_cycles / self.frames.max(1)
+    }
+}
+
+/// The emulator running headless as fast as possible.
+pub struct Bench {
+    sys: System<'static, NullDebugger>,
+    io: Rc<RefCell<Io>>,
+}
+
+impl Bench {
+    /// Create the emulator running the ROM, e.g. [`rom`][].
+    pub fn new(rom: &[u8]) -> Self {
+        let io = Rc::new(RefCell::new(Io {
+            stream: None,
+            serial: Vec::new(),
+        }));
+
+        let cfg = Config::new().native_speed(true);
+        let sys = System::new(cfg, rom, Headless(io.clone()), NullDebugger);
+
+        Self { sys, io }
+    }
+
+    /// Run emulation until a frame is drawn.
+    pub fn frame(&mut self) -> PollData {
+        self.sys.run_until_vblank()
+    }
+
+    /// Run emulation for a line worth of clock cycles.
+    pub fn line(&mut self) -> PollData {
+        self.sys.run_cycles(LINE_CYCLES)
+    }
+
+    /// Fill the buffer with the samples of the sound stream at the given rate.
+    pub fn samples(&mut self, out: &mut [u16], rate: u32) {
+        let mut io = self.io.borrow_mut();
+
+        match io.stream.as_mut() {
+            Some(s) => {
+                for sample in out.iter_mut() {
+                    *sample = s.next(rate);
+                }
+            }
+            None => {
+                for sample in out.iter_mut() {
+                    *sample = 0;
+                }
+            }
+        }
+    }
+
+    /// The bytes sent over the serial port, e.g. the results of test ROMs.
+    pub fn serial(&self) -> Vec<u8> {
+        self.io.borrow().serial.clone()
+    }
+
+    /// Run the frames, reading the cycle counter of the target before and after.
+    pub fn measure<F>(&mut self, frames: u64, mut counter: F) -> Report
+    where
+        F: FnMut() -> u64,
+    {
+        let mut emulated_cycles = 0;
+
+        let start = counter();
+        for _ in 0..frames {
+            emulated_cycles += self.frame().cycles;
+        }
+        let end = counter();
+
+        Report {
+            frames,
+            emulated_cycles,
+            host_cycles: end.wrapping_sub(start),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn demo_rom() {
+        let mut bench = Bench::new(&rom());
+
+        // Wait for the boot ROM to hand over to the program.
+        let mut pc = 0;
+        let report = bench.measure(400, || {
+            pc += 1;
+            pc
+        });
+        assert_eq!(report.host_cycles, 1);
+        assert!(report.emulated_cycles > 399 * crate::FRAME_DOTS as u64);
+
+        // The program scrolls the screen.
+        let scx = bench.sys.debug_read(0xff43);
+        bench.frame();
+        assert_eq!(bench.sys.debug_read(0xff43), scx.wrapping_add(1));
+
+        let mut out = [0; 64];
+        bench.samples(&mut out, 44100);
+        assert!(out.iter().any(|s| *s != 0));
+    }
+}
@@ -0,0 +1,45 @@
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+/// Memory backing a large emulator buffer, either allocated by the emulator
+/// or provided by the user of the library.
+pub enum Buffer<'a> {
+    Owned(Vec<u8>),
+    Borrowed(&'a mut [u8]),
+}
+
+impl<'a> Buffer<'a> {
+    /// Use the given memory if any, otherwise allocate `size` bytes.
+    pub fn new(buf: Option<&'a mut [u8]>, size: usize) -> Self {
+        match buf {
+            Some(buf) => {
+                assert_eq!(buf.len(), size, "Invalid buffer size");
+                for b in buf.iter_mut() {
+                    *b = 0;
+                }
+                Buffer::Borrowed(buf)
+            }
+            None => Buffer::Owned(vec![0; size]),
+        }
+    }
+}
+
+impl Deref for Buffer<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Buffer::Owned(v) => v,
+            Buffer::Borrowed(v) => v,
+        }
+    }
+}
+
+impl DerefMut for Buffer<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            Buffer::Owned(v) => v,
+            Buffer::Borrowed(v) => v,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Capture of the sound and video output, to debug the emulation, record clips
+//! or compare the output against a baseline in regression tests.
+//!
+//! The samples are pulled from the [`Stream`][crate::Stream] passed to [`Hardware::sound_play`][crate::Hardware::sound_play]
+//! into a buffer of the caller. With the `std` feature, [`write_wav`][crate::capture::write_wav] stores them as a WAV file.
+//!
+//! Complete frames are returned by [`System::frame`][crate::System::frame] after
+//! [`System::run_until_vblank`][crate::System::run_until_vblank]. [`pack_rgb`][crate::capture::pack_rgb] turns them into
+//! packed RGB bytes and, with the `std` feature, [`write_ppm`][crate::capture::write_ppm] stores them as a PPM image.
+//! [`hash_rgb`][crate::capture::hash_rgb] reduces them to a hash to compare CGB frames against a baseline.
+//! The frames are expected in the `0xRRGGBB` format of [`Rgb888`][crate::Rgb888].
+
+use crate::hardware::{Stream, VRAM_HEIGHT, VRAM_WIDTH};
+
+/// Samples of a sound stream with the metadata to play them back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pcm<'a> {
+    /// The sample rate in Hz.
+    pub rate: u32,
+    /// The maximum value of the samples, from [`Stream::max`][].
+    pub max: u16,
+    /// The samples.
+    pub samples: &'a [u16],
+}
+
+impl Pcm<'_> {
+    /// The samples as signed 16-bit values centered on zero.
+    pub fn signed(&self) -> impl Iterator<Item = i16> + '_ {
+        let max = self.max.max(1) as i32;
+        self.samples
+            .iter()
+            .map(move |s| ((*s as i32).min(max) * 0xffff / max - 0x8000) as i16)
+    }
+}
+
+/// Fill the buffer with the next samples of the stream at the given rate.
+pub fn capture<'a>(stream: &mut dyn Stream, rate: u32, buf: &'a mut [u16]) -> Pcm<'a> {
+    for sample in buf.iter_mut() {
+        *sample = stream.next(rate);
+    }
+
+    Pcm {
+        rate,
+        max: stream.max(),
+        samples: buf,
+    }
+}
+
+/// Write the samples as a mono 16-bit PCM WAV file.
+#[cfg(feature = "std")]
+pub fn write_wav<W: std::io::Write>(pcm: &Pcm, mut w: W) -> std::io::Result<()> {
+    let data_len = pcm.samples.len() as u32 * 2;
+
+    w.write_all(b"RIFF")?;
+    w.write_all(&(36 + data_len).to_le_bytes())?;
+    w.write_all(b"WAVE")?;
+
+    w.write_all(b"fmt ")?;
+    w.write_all(&16u32.to_le_bytes())?;
+    w.write_all(&1u16.to_le_bytes())?; // PCM
+    w.write_all(&1u16.to_le_bytes())?; // Mono
+    w.write_all(&pcm.rate.to_le_bytes())?;
+    w.write_all(&(pcm.rate * 2).to_le_bytes())?; // Bytes per second
+    w.write_all(&2u16.to_le_bytes())?; // Bytes per frame
+    w.write_all(&16u16.to_le_bytes())?; // Bits per sample
+
+    w.write_all(b"data")?;
+    w.write_all(&data_len.to_le_bytes())?;
+    for s in pcm.signed() {
+        w.write_all(&s.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// The number of bytes of a frame packed by [`pack_rgb`][].
+pub const RGB_FRAME_SIZE: usize = VRAM_WIDTH * VRAM_HEIGHT * 3;
+
+/// Pack the `0xRRGGBB` pixels of the frame as three bytes each, red first.
+///
+/// Returns the number of bytes written, which stops short if the buffer is too small.
+pub fn pack_rgb(frame: &[u32], buf: &mut [u8]) -> usize {
+    let mut len = 0;
+
+    for (px, out) in frame.iter().zip(buf.chunks_exact_mut(3)) {
+        out.copy_from_slice(&px.to_be_bytes()[1..]);
+        len += 3;
+    }
+
+    len
+}
+
+/// Hash the packed RGB bytes of the frame with 64-bit FNV-1a.
+///
+/// The hash is the same on every platform, so it can be stored as the expected output of a test ROM,
+/// including the colors of the CGB. The unused upper byte of the pixels is ignored.
+pub fn hash_rgb(frame: &[u32]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+
+    for px in frame {
+        for b in &px.to_be_bytes()[1..] {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    hash
+}
+
+/// Write the frame as a binary PPM image of the display size.
+#[cfg(feature = "std")]
+pub fn write_ppm<W: std::io::Write>(frame: &[u32], mut w: W) -> std::io::Result<()> {
+    let mut buf = alloc::vec![0; RGB_FRAME_SIZE];
+    let len = pack_rgb(frame, &mut buf);
+
+    write!(w, "P6\n{} {}\n255\n", VRAM_WIDTH, VRAM_HEIGHT)?;
+    w.write_all(&buf[..len])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Ramp(u16);
+
+    impl Stream for Ramp {
+        fn max(&self) -> u16 {
+            4
+        }
+
+        fn next(&mut self, _rate: u32) -> u16 {
+            self.0 = (self.0 + 1) % 5;
+            self.0
+        }
+    }
+
+    #[test]
+    fn capture_samples() {
+        let mut buf = [0; 5];
+        let pcm = capture(&mut Ramp(0), 8000, &mut buf);
+
+        assert_eq!(pcm.rate, 8000);
+        assert_eq!(pcm.max, 4);
+        assert_eq!(pcm.samples, [1, 2, 3, 4, 0]);
+
+        let signed: alloc::vec::Vec<_> = pcm.signed().collect();
+        assert_eq!(signed, [-16385, -1, 16383, 32767, -32768]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn wav() {
+        let mut buf = [0; 4];
+        let pcm = capture(&mut Ramp(0), 8000, &mut buf);
+
+        let mut wav = std::vec::Vec::new();
+        write_wav(&pcm, &mut wav).unwrap();
+
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[4..8], &44u32.to_le_bytes());
+        assert_eq!(&wav[24..28], &8000u32.to_le_bytes());
+        assert_eq!(&wav[40..44], &8u32.to_le_bytes());
+        assert_eq!(&wav[50..52], &32767i16.to_le_bytes());
+    }
+
+    #[test]
+    fn rgb_frame() {
+        let mut buf = [0; 7];
+        assert_eq!(pack_rgb(&[0x123456, 0xabcdef, 0xff], &mut buf), 6);
+        assert_eq!(buf, [0x12, 0x34, 0x56, 0xab, 0xcd, 0xef, 0]);
+    }
+
+    #[test]
+    fn rgb_hash() {
+        assert_eq!(hash_rgb(&[]), 0xcbf29ce484222325);
+        assert_eq!(hash_rgb(&[0x123456]), 0x7486b218c3c86edf);
+        assert_eq!(hash_rgb(&[0xff123456]), hash_rgb(&[0x123456]));
+        assert_ne!(hash_rgb(&[0x123456]), hash_rgb(&[0x563412]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ppm() {
+        let frame = alloc::vec![0xaabbcc; VRAM_WIDTH * VRAM_HEIGHT];
+
+        let mut ppm = std::vec::Vec::new();
+        write_ppm(&frame, &mut ppm).unwrap();
+
+        let header = b"P6\n160 144\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + RGB_FRAME_SIZE);
+        assert_eq!(&ppm[header.len()..header.len() + 3], [0xaa, 0xbb, 0xcc]);
+    }
+}
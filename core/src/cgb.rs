@@ -1,48 +1,135 @@
 use crate::{
+    buffer::Buffer,
     device::IoHandler,
     mmu::{MemRead, MemWrite, Mmu},
+    rng::Rng,
+    system::RamFill,
 };
-use alloc::{vec, vec::Vec};
-use log::*;
+use alloc::vec::Vec;
 
-pub struct Cgb {
+/// The number of 4KB work RAM banks. DMG has only the fixed bank and a single switchable bank.
+const WRAM_BANKS: usize = if cfg!(feature = "color") { 8 } else { 2 };
+
+/// The total size of work RAM allocated.
+pub const WRAM_SIZE: usize = WRAM_BANKS * 0x1000;
+
+/// The CPU clock cycles the CPU is stopped for while the speed switches.
+pub const SPEED_SWITCH_CYCLES: usize = 2050 * 4;
+
+/// Translation of the CPU clock cycles to the clock domains of the peripherals.
+///
+/// In double speed mode, the CPU, the timer, the serial port and OAM DMA run twice as fast,
+/// while the GPU, HDMA and the sound keep running at the normal speed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClockDomain {
+    double_speed: bool,
+}
+
+impl ClockDomain {
+    /// The time in the normal speed domain for the CPU clock cycles.
+    pub fn normal(self, cpu_time: usize) -> usize {
+        if self.double_speed {
+            cpu_time / 2
+        } else {
+            cpu_time
+        }
+    }
+
+    /// The CPU clock cycles for the time in the normal speed domain.
+    pub fn cpu(self, normal_time: usize) -> usize {
+        if self.double_speed {
+            normal_time * 2
+        } else {
+            normal_time
+        }
+    }
+}
+
+pub struct Cgb<'a> {
     double_speed: bool,
     speed_switch: bool,
     wram_select: usize,
-    wram_bank: Vec<Vec<u8>>,
+    wram: Buffer<'a>,
+    key0: u8,
+    boot: bool,
 }
 
 #[allow(unused)]
-impl Cgb {
-    pub fn new() -> Self {
+impl<'a> Cgb<'a> {
+    pub fn new(wram: Buffer<'a>) -> Self {
+        assert_eq!(wram.len(), WRAM_SIZE);
+
         Self {
             double_speed: false,
             speed_switch: false,
             wram_select: 1,
-            wram_bank: (0..8).map(|_| vec![0; 0x1000]).collect(),
+            wram,
+            key0: 0,
+            boot: true,
+        }
+    }
+
+    /// Restore the power-on state, filling the work RAM as on power-on.
+    pub fn reset(&mut self, fill: RamFill, rng: &mut Rng) {
+        let mut wram = core::mem::replace(&mut self.wram, Buffer::Owned(Vec::new()));
+        fill.fill(&mut wram, rng);
+        *self = Cgb::new(wram);
+    }
+
+    /// True once the boot ROM has switched to DMG compatibility mode via KEY0 and unmapped itself.
+    pub fn compat_mode(&self) -> bool {
+        cfg!(feature = "color") && !self.boot && self.key0 & 0x04 != 0
+    }
+
+    /// The CGB-only registers which are locked in DMG compatibility mode.
+    fn cgb_only(addr: u16) -> bool {
+        match addr {
+            0xff4d | 0xff4f | 0xff68..=0xff6c | 0xff70 => true,
+            _ => false,
         }
     }
 
-    pub fn try_switch_speed(&mut self) {
-        if self.speed_switch {
+    /// Switch the speed on `stop` if armed by KEY1, and return whether it's switched.
+    pub fn try_switch_speed(&mut self) -> bool {
+        if cfg!(feature = "color") && self.speed_switch {
             self.double_speed = !self.double_speed;
             self.speed_switch = false;
+            info!("Switch to double speed: {}", self.double_speed);
+            true
+        } else {
+            false
         }
     }
 
     pub fn double_speed(&self) -> bool {
         self.double_speed
     }
+
+    /// The clock domain translation for the current speed.
+    pub fn clock_domain(&self) -> ClockDomain {
+        ClockDomain {
+            double_speed: self.double_speed,
+        }
+    }
 }
 
-impl IoHandler for Cgb {
+impl IoHandler for Cgb<'_> {
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
-        if addr >= 0xc000 && addr <= 0xcfff {
+        if self.compat_mode() && Self::cgb_only(addr) {
+            MemRead::Replace(0xff)
+        } else if addr == 0xff4c {
+            // KEY0 is only visible while the boot ROM is mapped.
+            if cfg!(feature = "color") && self.boot {
+                MemRead::Replace(self.key0)
+            } else {
+                MemRead::Replace(0xff)
+            }
+        } else if addr >= 0xc000 && addr <= 0xcfff {
             let off = addr as usize - 0xc000;
-            MemRead::Replace(self.wram_bank[0][off])
+            MemRead::Replace(self.wram[off])
         } else if addr >= 0xd000 && addr <= 0xdfff {
             let off = addr as usize - 0xd000;
-            MemRead::Replace(self.wram_bank[self.wram_select][off])
+            MemRead::Replace(self.wram[self.wram_select * 0x1000 + off])
         } else if addr == 0xff4d {
             let mut v = 0;
             v |= if self.double_speed { 0x80 } else { 0x00 };
@@ -59,20 +146,82 @@ impl IoHandler for Cgb {
     }
 
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
+        if self.compat_mode() && Self::cgb_only(addr) {
+            debug!(
+                "Blocked CGB register write in compatibility mode: {:04x}",
+                addr
+            );
+            return MemWrite::Block;
+        }
+
+        if addr == 0xff4c {
+            if cfg!(feature = "color") && self.boot {
+                info!("KEY0: {:02x}", value);
+                self.key0 = value;
+            }
+            return MemWrite::Block;
+        } else if addr == 0xff50 {
+            // Unmapping the boot ROM locks KEY0; the MBC handles the rest.
+            self.boot = false;
+            return MemWrite::PassThrough;
+        }
+
         if addr >= 0xc000 && addr <= 0xcfff {
             let off = addr as usize - 0xc000;
-            self.wram_bank[0][off] = value;
+            self.wram[off] = value;
         } else if addr >= 0xd000 && addr <= 0xdfff {
             let off = addr as usize - 0xd000;
-            self.wram_bank[self.wram_select][off] = value;
+            self.wram[self.wram_select * 0x1000 + off] = value;
         } else if addr == 0xff4d {
             self.speed_switch = value & 0x01 != 0;
         } else if addr == 0xff56 {
             warn!("Infrared read");
         } else if addr == 0xff70 {
-            self.wram_select = (value as usize & 0xf).max(1);
+            self.wram_select = (value as usize & 0x7).clamp(1, WRAM_BANKS - 1);
         }
 
         MemWrite::PassThrough
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wram_footprint() {
+        let cgb = Cgb::new(Buffer::new(None, WRAM_SIZE));
+        let size = cgb.wram.len();
+
+        assert_eq!(size, WRAM_SIZE);
+
+        if cfg!(feature = "color") {
+            assert_eq!(size, 0x8000);
+        } else {
+            assert_eq!(size, 0x2000);
+        }
+    }
+
+    #[test]
+    fn key0_compat_mode() {
+        let mmu = Mmu::new();
+        let mut cgb = Cgb::new(Buffer::new(None, WRAM_SIZE));
+
+        cgb.on_write(&mmu, 0xff4c, 0x04);
+        assert!(!cgb.compat_mode());
+
+        cgb.on_write(&mmu, 0xff50, 0x01);
+
+        // KEY0 is locked after the boot ROM is unmapped.
+        cgb.on_write(&mmu, 0xff4c, 0x80);
+        assert!(matches!(cgb.on_read(&mmu, 0xff4c), MemRead::Replace(0xff)));
+
+        if cfg!(feature = "color") {
+            assert!(cgb.compat_mode());
+            assert!(matches!(cgb.on_write(&mmu, 0xff70, 0x03), MemWrite::Block));
+            assert_eq!(cgb.wram_select, 1);
+        } else {
+            assert!(!cgb.compat_mode());
+        }
+    }
+}
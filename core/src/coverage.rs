@@ -0,0 +1,88 @@
+use alloc::{vec, vec::Vec};
+
+/// The bitmap of the ROM bytes executed as an opcode.
+///
+/// Bit `n % 8` of byte `n / 8` is set if the byte at offset `n` in the ROM image was reached.
+pub struct Coverage {
+    bits: Vec<u8>,
+}
+
+impl Coverage {
+    pub(crate) fn new(rom_len: usize) -> Self {
+        Self {
+            bits: vec![0; rom_len.div_ceil(8)],
+        }
+    }
+
+    /// Mark the byte at the offset, ignoring the offsets past the ROM image, e.g. those of
+    /// a custom mapper not masking its banks.
+    pub(crate) fn mark(&mut self, offset: usize) {
+        if let Some(b) = self.bits.get_mut(offset / 8) {
+            *b |= 1 << (offset % 8);
+        }
+    }
+
+    fn offset(bank: usize, addr: u16) -> usize {
+        if addr <= 0x3fff {
+            addr as usize
+        } else {
+            bank * 0x4000 + (addr as usize & 0x3fff)
+        }
+    }
+
+    /// Check if the byte at the offset in the ROM image was executed as an opcode.
+    pub fn covered(&self, offset: usize) -> bool {
+        self.bits
+            .get(offset / 8)
+            .is_some_and(|b| b & (1 << (offset % 8)) != 0)
+    }
+
+    /// Check if the byte at the address of the ROM bank was executed as an opcode.
+    ///
+    /// The bank is ignored for the addresses in the fixed bank area `0x0000-0x3fff`.
+    pub fn covered_at(&self, bank: usize, addr: u16) -> bool {
+        addr <= 0x7fff && self.covered(Self::offset(bank, addr))
+    }
+
+    /// The number of ROM bytes executed as an opcode.
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// The raw bitmap, one bit per byte of the ROM image.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Clear the bitmap.
+    pub fn clear(&mut self) {
+        for b in self.bits.iter_mut() {
+            *b = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bank_offset() {
+        let mut c = Coverage::new(0x10000);
+
+        c.mark(0x0150);
+        c.mark(0x8123);
+        // Past the ROM image, e.g. from a custom mapper.
+        c.mark(0x10000);
+
+        assert!(c.covered_at(5, 0x0150));
+        assert!(c.covered_at(2, 0x4123));
+        assert!(!c.covered_at(1, 0x4123));
+        assert!(!c.covered_at(2, 0xc123));
+        assert_eq!(c.count(), 2);
+        assert_eq!(c.as_bytes()[0x8123 / 8], 0x08);
+
+        c.clear();
+        assert_eq!(c.count(), 0);
+    }
+}
@@ -2,12 +2,13 @@ use crate::device::Device;
 use crate::ic::Ic;
 use crate::inst::decode;
 use crate::mmu::Mmu;
-use log::*;
+use crate::system::Model;
 
 use alloc::fmt;
 
 /// Represents CPU state.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cpu {
     a: u8,
     b: u8,
@@ -20,7 +21,19 @@ pub struct Cpu {
     pc: u16,
     sp: u16,
     ime: bool,
+    ei_delay: bool,
     halt: bool,
+    halt_ime: bool,
+    halt_entered: bool,
+    halt_bug: bool,
+    locked: bool,
+    stop: bool,
+}
+
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl fmt::Display for Cpu {
@@ -68,14 +81,62 @@ impl Cpu {
             pc: 0,
             sp: 0,
             ime: true,
+            ei_delay: false,
             halt: false,
+            halt_ime: false,
+            halt_entered: false,
+            halt_bug: false,
+            locked: false,
+            stop: false,
         }
     }
 
+    /// Create the CPU state left by the boot ROM of the model, about to run the cartridge at 0x100.
+    ///
+    /// The half-carry and carry flags of the DMG, the MGB and the DMG0 depend on the header checksum;
+    /// these are the values for a non-zero checksum, which most cartridges have.
+    pub fn after_boot(model: Model) -> Cpu {
+        let (af, bc, de, hl) = match model {
+            Model::Dmg0 => (0x0100, 0xff13, 0x00c1, 0x8403),
+            Model::Dmg => (0x01b0, 0x0013, 0x00d8, 0x014d),
+            Model::Mgb => (0xffb0, 0x0013, 0x00d8, 0x014d),
+            Model::Sgb => (0x0100, 0x0014, 0x0000, 0xc060),
+            Model::Sgb2 => (0xff00, 0x0014, 0x0000, 0xc060),
+            Model::Cgb => (0x1180, 0x0000, 0xff56, 0x000d),
+            Model::Agb => (0x1100, 0x0100, 0xff56, 0x000d),
+        };
+
+        let mut cpu = Cpu::new();
+        cpu.set_af(af);
+        cpu.set_bc(bc);
+        cpu.set_de(de);
+        cpu.set_hl(hl);
+        cpu.set_sp(0xfffe);
+        cpu.set_pc(0x0100);
+        cpu
+    }
+
     /// Switch the CPU state to halting.
     pub fn halt(&mut self) {
         debug!("Halted");
-        // TODO: self.halt = true;
+        self.halt = true;
+        self.halt_ime = self.ime;
+        self.halt_entered = true;
+    }
+
+    /// Lock up the CPU, as an invalid opcode does. Only a reset recovers from it.
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    /// Check if the CPU is locked up.
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Check if the CPU is halting.
+    pub fn halted(&self) -> bool {
+        self.halt
     }
 
     /// Execute a single instruction.
@@ -83,22 +144,60 @@ impl Cpu {
     /// The function fetches an instruction code from the memory,
     /// decodes it, and updates the CPU/memory state accordingly.
     /// The return value is the number of clock cycles consumed by the instruction.
-    /// If the CPU is in the halt state, the function does nothing but returns a fixed clock cycle.
+    /// If the CPU is in the halt state or locked up, the function does nothing but returns a fixed clock cycle.
     pub fn execute(&mut self, mmu: &mut Mmu) -> usize {
-        if self.halt {
-            4
+        self.execute_op(mmu).1
+    }
+
+    /// Execute a single instruction like [`Cpu::execute`][], also returning its opcode,
+    /// or `None` if the CPU is in the halt state or locked up.
+    pub(crate) fn execute_op(&mut self, mmu: &mut Mmu) -> (Option<u16>, usize) {
+        if self.halt || self.locked {
+            (None, 4)
         } else {
-            let (code, arg) = self.fetch(mmu);
-            let (time, size) = decode(code, arg, self, mmu);
-            self.set_pc(self.get_pc().wrapping_add(size as u16));
-            time
+            let (code, time) = self.execute_inst(mmu);
+            (Some(code), time)
         }
     }
 
+    /// Execute a single instruction like [`Cpu::execute`][], returning the opcode along with
+    /// the clock cycles consumed, for the tests to check the cycles of the generated decoder.
+    #[cfg(test)]
+    pub fn step_inst(&mut self, mmu: &mut Mmu) -> (u16, usize) {
+        self.execute_inst(mmu)
+    }
+
+    fn execute_inst(&mut self, mmu: &mut Mmu) -> (u16, usize) {
+        let ei_delay = self.ei_delay;
+
+        let (code, arg) = self.fetch(mmu);
+        for _ in 0..arg {
+            mmu.tick();
+        }
+        let (time, size) = decode(code, arg, self, mmu);
+
+        // With the halt bug, the byte after HALT is read twice.
+        let size = if core::mem::replace(&mut self.halt_bug, false) {
+            size - 1
+        } else {
+            size
+        };
+        self.set_pc(self.get_pc().wrapping_add(size as u16));
+
+        // EI takes effect after the instruction following it, unless it's DI.
+        if ei_delay && self.ei_delay {
+            self.ei_delay = false;
+            self.ime = true;
+        }
+
+        (code, time)
+    }
+
     /// Disable interrupts to this CPU.
     pub fn disable_interrupt(&mut self) {
         debug!("Disable interrupt");
         self.ime = false;
+        self.ei_delay = false;
     }
 
     /// Enable interrupts to this CPU.
@@ -107,9 +206,41 @@ impl Cpu {
         self.ime = true;
     }
 
+    /// Enable interrupts to this CPU after the next instruction, as `ei` does.
+    pub fn enable_interrupt_delayed(&mut self) {
+        debug!("Enable interrupt after the next instruction");
+        self.ei_delay = true;
+    }
+
+    /// Gets the value of the interrupt master enable flag.
+    pub fn get_ime(&self) -> bool {
+        self.ime
+    }
+
     /// Check if pending interrupts in the interrupt controller,
     /// and process them if any.
     pub fn check_interrupt(&mut self, mmu: &mut Mmu, ic: &Device<Ic>) -> usize {
+        if self.locked {
+            return 0;
+        }
+
+        if core::mem::replace(&mut self.halt_entered, false)
+            && !self.halt_ime
+            && ic.borrow().peek().is_some()
+        {
+            if self.ime {
+                // EI right before HALT: the interrupt returns to HALT, which is executed again.
+                debug!("Interrupted on ei + halt");
+                self.set_pc(self.get_pc().wrapping_sub(1));
+            } else {
+                // HALT bug: the CPU doesn't halt and fails to increment PC.
+                debug!("Halt bug");
+                self.halt = false;
+                self.halt_bug = true;
+                return 0;
+            }
+        }
+
         if !self.ime {
             if self.halt {
                 // If HALT is executed while interrupt is disabled,
@@ -145,8 +276,16 @@ impl Cpu {
     }
 
     /// Stop the CPU.
-    pub fn stop(&self) {
-        // TODO: Stop.
+    ///
+    /// Only the CGB speed switch is emulated, which the system performs on [`Cpu::take_stop`][].
+    pub fn stop(&mut self) {
+        // TODO: Stop until a button is pressed.
+        self.stop = true;
+    }
+
+    /// Return whether a `stop` instruction was executed since the last call, and reset it.
+    pub fn take_stop(&mut self) -> bool {
+        core::mem::replace(&mut self.stop, false)
     }
 
     /// Gets the value of `z` flag in the flag register.
@@ -343,14 +482,14 @@ impl Cpu {
     pub fn push(&mut self, mmu: &mut Mmu, v: u16) {
         let p = self.get_sp().wrapping_sub(2);
         self.set_sp(self.get_sp().wrapping_sub(2));
-        mmu.set16(p, v)
+        mmu.write16(p, v)
     }
 
     /// Pops a 16-bit value from the stack, updating the stack pointer register.
     pub fn pop(&mut self, mmu: &mut Mmu) -> u16 {
         let p = self.get_sp();
         self.set_sp(self.get_sp().wrapping_add(2));
-        mmu.get16(p)
+        mmu.read16(p)
     }
 
     /// Fetches an opcode from the memory and returns it with its length.
@@ -360,7 +499,7 @@ impl Cpu {
         let fb = mmu.get8(pc);
 
         if fb == 0xcb {
-            let sb = mmu.get8(pc + 1);
+            let sb = mmu.get8(pc.wrapping_add(1));
             (0xcb00 | sb as u16, 2)
         } else {
             (fb as u16, 1)
@@ -371,8 +510,10 @@ impl Cpu {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::inst::decode;
-    use alloc::{vec, vec::Vec};
+    use crate::inst::{decode, lookup, meta, FlagEffect, CB_INSTS, INSTS};
+    use crate::mmu::Clock;
+    use alloc::{rc::Rc, vec, vec::Vec};
+    use core::cell::Cell;
 
     fn write(mmu: &mut Mmu, m: Vec<u8>) {
         for i in 0..m.len() {
@@ -380,6 +521,16 @@ mod test {
         }
     }
 
+    fn setup_ic(mmu: &mut Mmu) -> Device<Ic> {
+        let ic = Device::new(Ic::new());
+        mmu.add_handler((0xff0f, 0xff0f), ic.handler());
+        mmu.add_handler((0xffff, 0xffff), ic.handler());
+        // Request and enable VBlank interrupt
+        mmu.set8(0xffff, 0x01);
+        mmu.set8(0xff0f, 0x01);
+        ic
+    }
+
     fn exec(cpu: &mut Cpu, mmu: &mut Mmu) {
         let (code, arg) = cpu.fetch(&mmu);
 
@@ -430,4 +581,262 @@ mod test {
         exec(&mut cpu, &mut mmu); // cp e
         assert_eq!(cpu.get_zf(), true);
     }
+
+    #[test]
+    fn ei_delay() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+        let ic = setup_ic(&mut mmu);
+
+        cpu.disable_interrupt();
+        cpu.set_sp(0xd000);
+        write(&mut mmu, vec![0xfb, 0x00, 0x00]);
+
+        cpu.execute(&mut mmu); // ei
+        assert_eq!(cpu.check_interrupt(&mut mmu, &ic), 0);
+        cpu.execute(&mut mmu); // nop
+        assert_eq!(cpu.check_interrupt(&mut mmu, &ic), 16);
+        assert_eq!(cpu.get_pc(), 0x40);
+        assert_eq!(mmu.get16(0xcffe), 0x0002);
+    }
+
+    #[test]
+    fn ei_di() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+        let ic = setup_ic(&mut mmu);
+
+        cpu.disable_interrupt();
+        write(&mut mmu, vec![0xfb, 0xf3, 0x00]);
+
+        cpu.execute(&mut mmu); // ei
+        cpu.execute(&mut mmu); // di
+        cpu.execute(&mut mmu); // nop
+        assert_eq!(cpu.check_interrupt(&mut mmu, &ic), 0);
+        assert_eq!(cpu.get_pc(), 0x03);
+    }
+
+    #[test]
+    fn ei_halt() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+        let ic = setup_ic(&mut mmu);
+
+        cpu.disable_interrupt();
+        cpu.set_sp(0xd000);
+        write(&mut mmu, vec![0xfb, 0x76, 0x00]);
+
+        cpu.execute(&mut mmu); // ei
+        assert_eq!(cpu.check_interrupt(&mut mmu, &ic), 0);
+        cpu.execute(&mut mmu); // halt
+        assert_eq!(cpu.check_interrupt(&mut mmu, &ic), 16);
+        assert_eq!(cpu.get_pc(), 0x40);
+        // The interrupt returns to the HALT instruction.
+        assert_eq!(mmu.get16(0xcffe), 0x0001);
+    }
+
+    #[test]
+    fn halt_bug() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+        let ic = setup_ic(&mut mmu);
+
+        cpu.disable_interrupt();
+        write(&mut mmu, vec![0x76, 0x3c, 0x00]);
+
+        cpu.execute(&mut mmu); // halt
+        assert_eq!(cpu.check_interrupt(&mut mmu, &ic), 0);
+        cpu.execute(&mut mmu); // inc a
+        assert_eq!(cpu.get_pc(), 0x01);
+        cpu.execute(&mut mmu); // inc a
+        assert_eq!(cpu.get_pc(), 0x02);
+        assert_eq!(cpu.get_a(), 2);
+    }
+
+    struct Counter(Rc<Cell<usize>>);
+
+    impl Clock for Counter {
+        fn tick(&self, _mmu: &mut Mmu) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn cycle_ticks() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+        let ticks = Rc::new(Cell::new(0));
+
+        mmu.set_clock(Counter(ticks.clone()));
+        cpu.set_sp(0xd000);
+        cpu.set_hl(0xc000);
+        write(&mut mmu, vec![0x77, 0xc5, 0xfa, 0x00, 0xc0]);
+
+        cpu.execute(&mut mmu); // ld (hl),a
+        assert_eq!(mmu.take_cycles(), 2);
+        cpu.execute(&mut mmu); // push bc
+        assert_eq!(mmu.take_cycles(), 3);
+        cpu.execute(&mut mmu); // ld a,(a16)
+        assert_eq!(mmu.take_cycles(), 4);
+        assert_eq!(ticks.get(), 9);
+    }
+
+    #[test]
+    fn inst_table() {
+        let inst = lookup(0xcb37).unwrap();
+        assert_eq!(inst.mnemonic, "swap a");
+        assert_eq!((inst.size, inst.time), (2, 8));
+
+        let inst = lookup(0x0020).unwrap();
+        assert_eq!(inst.mnemonic, "jr nz,r8");
+        assert_eq!((inst.size, inst.time), (2, 8));
+
+        assert!(lookup(0x00d3).is_none());
+        assert!(lookup(0x1200).is_none());
+    }
+
+    /// Run the instruction at 0xc000 with the flags, returning the opcode and the cycles.
+    fn step_code(code: u16, f: u8) -> (u16, usize) {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+
+        cpu.set_pc(0xc000);
+        cpu.set_sp(0xd000);
+        cpu.set_af(f as u16);
+        if code > 0xff {
+            write_at(&mut mmu, 0xc000, &[0xcb, code as u8]);
+        } else {
+            write_at(&mut mmu, 0xc000, &[code as u8]);
+        }
+
+        cpu.step_inst(&mut mmu)
+    }
+
+    fn write_at(mmu: &mut Mmu, addr: u16, m: &[u8]) {
+        for (i, b) in m.iter().enumerate() {
+            mmu.set8(addr + i as u16, *b);
+        }
+    }
+
+    #[test]
+    fn cycle_table() {
+        // The decoder takes the cycles of the metadata table, with the branches taken or not.
+        for inst in INSTS.iter().chain(CB_INSTS.iter()).flatten() {
+            // The prefix is decoded along with the extended instruction.
+            if inst.code == 0xcb {
+                continue;
+            }
+
+            let m = meta(inst.code);
+            assert_eq!((m.mnemonic, m.size), (inst.mnemonic, inst.size));
+
+            let cond = m.mnemonic.split([' ', ',']).nth(1);
+            let (not_taken, taken) = match cond {
+                Some("nz") | Some("nc") => (0x90, 0x00),
+                Some("z") | Some("c") | Some("cf") => (0x00, 0x90),
+                _ => (0x00, 0x00),
+            };
+            assert_eq!(
+                step_code(inst.code, not_taken),
+                (inst.code, m.min_time),
+                "{}",
+                m.mnemonic
+            );
+            assert_eq!(
+                step_code(inst.code, taken),
+                (inst.code, m.max_time),
+                "{}",
+                m.mnemonic
+            );
+        }
+
+        assert!(!meta(0x00d3).is_valid());
+        assert!(!meta(0x1200).is_valid());
+        assert_eq!(meta(0xcb37).flags[3], FlagEffect::Reset);
+    }
+
+    /// Run the instruction at 0xc000 with the immediate operand.
+    fn step_imm(cpu: &mut Cpu, mmu: &mut Mmu, code: &[u8]) {
+        cpu.set_pc(0xc000);
+        write_at(mmu, 0xc000, code);
+        cpu.step_inst(mmu);
+    }
+
+    fn bcd(v: u8) -> u8 {
+        (v / 10) << 4 | (v % 10)
+    }
+
+    #[test]
+    fn daa_bcd() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+
+        // Every BCD addition and subtraction, with and without carry, is adjusted to BCD.
+        for p in 0..100 {
+            for q in 0..100 {
+                for c in [false, true] {
+                    // adc a,d8; daa
+                    cpu.set_a(bcd(p));
+                    cpu.set_cf(c);
+                    step_imm(&mut cpu, &mut mmu, &[0xce, bcd(q), 0x27]);
+                    cpu.step_inst(&mut mmu);
+                    let sum = p + q + c as u8;
+                    assert_eq!(cpu.get_a(), bcd(sum % 100), "{} + {} + {}", p, q, c);
+                    assert_eq!(cpu.get_cf(), sum >= 100);
+                    assert_eq!(cpu.get_zf(), sum == 0 || sum == 100);
+                    assert!(!cpu.get_hf());
+
+                    // sbc a,d8; daa
+                    cpu.set_a(bcd(p));
+                    cpu.set_cf(c);
+                    step_imm(&mut cpu, &mut mmu, &[0xde, bcd(q), 0x27]);
+                    cpu.step_inst(&mut mmu);
+                    let diff = p as i16 - q as i16 - c as i16;
+                    assert_eq!(
+                        cpu.get_a(),
+                        bcd(diff.rem_euclid(100) as u8),
+                        "{} - {} - {}",
+                        p,
+                        q,
+                        c
+                    );
+                    assert_eq!(cpu.get_cf(), diff < 0);
+                    assert!(cpu.get_nf());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sp_offset_flags() {
+        let mut mmu = Mmu::new();
+        let mut cpu = Cpu::new();
+
+        // The flags come from the unsigned addition of the offset to the lower byte of SP,
+        // whatever the sign of the offset.
+        for high in [0x00u16, 0x7f, 0xc0, 0xff] {
+            for low in 0..=0xff {
+                for r in 0..=0xffu16 {
+                    let sp = (high << 8) | low;
+                    let v = sp.wrapping_add(r as i8 as u16);
+                    let h = (sp & 0xf) + (r & 0xf) > 0xf;
+                    let c = (sp & 0xff) + r > 0xff;
+
+                    // add sp,r8
+                    cpu.set_sp(sp);
+                    cpu.set_af(0x00f0);
+                    step_imm(&mut cpu, &mut mmu, &[0xe8, r as u8]);
+                    assert_eq!(cpu.get_sp(), v);
+                    assert_eq!(cpu.get_af() as u8, (h as u8) << 5 | (c as u8) << 4);
+
+                    // ld hl,sp+r8
+                    cpu.set_sp(sp);
+                    cpu.set_af(0x00f0);
+                    step_imm(&mut cpu, &mut mmu, &[0xf8, r as u8]);
+                    assert_eq!((cpu.get_sp(), cpu.get_hl()), (sp, v));
+                    assert_eq!(cpu.get_af() as u8, (h as u8) << 5 | (c as u8) << 4);
+                }
+            }
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use crate::cpu::Cpu;
 use crate::device::IoHandler;
+use crate::mbc::MbcState;
 use crate::mmu::{MemRead, MemWrite, Mmu};
+use core::fmt;
 
 /// Debugger interface.
 ///
@@ -12,6 +14,9 @@ pub trait Debugger: IoHandler {
     /// The function is called right before the emulator starts executing an instruction. Deprecated.
     fn take_cpu_snapshot(&mut self, cpu: Cpu);
 
+    /// The function is called right before [`Debugger::on_decode`][] with the banks mapped by the cartridge.
+    fn take_mbc_state(&mut self, _state: MbcState) {}
+
     /// Decode an instruction.
     fn on_decode(&mut self, mmu: &Mmu);
 
@@ -26,6 +31,72 @@ impl dyn Debugger {
     }
 }
 
+/// A break point on an address, optionally restricted to a ROM bank.
+///
+/// As the same address of the switchable bank area `0x4000-0x7fff` maps different code
+/// depending on the selected bank, the bank tells which of them to break on. The fixed bank
+/// area `0x0000-0x3fff` is bank zero, unless the controller switches 32KB banks.
+/// Addresses outside of the ROM are always in bank zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Breakpoint {
+    /// The ROM bank, or `None` to break in any bank.
+    pub bank: Option<usize>,
+    /// The address.
+    pub addr: u16,
+}
+
+impl Breakpoint {
+    /// Create a break point on the address in any bank.
+    pub fn new(addr: u16) -> Self {
+        Self { bank: None, addr }
+    }
+
+    /// Create a break point on the address in the ROM bank.
+    pub fn banked(bank: usize, addr: u16) -> Self {
+        let bank = if addr <= 0x7fff { bank } else { 0 };
+
+        Self {
+            bank: Some(bank),
+            addr,
+        }
+    }
+
+    /// Parse a break point in hex, either `addr` or `bank:addr`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.split_once(':') {
+            Some((bank, addr)) => Some(Self::banked(
+                usize::from_str_radix(bank.trim_start_matches("0x"), 16).ok()?,
+                u16::from_str_radix(addr.trim_start_matches("0x"), 16).ok()?,
+            )),
+            None => Some(Self::new(
+                u16::from_str_radix(s.trim_start_matches("0x"), 16).ok()?,
+            )),
+        }
+    }
+
+    /// Check if the break point hits at the program counter with the banks mapped by the cartridge.
+    pub fn hits(&self, pc: u16, state: &MbcState) -> bool {
+        if pc != self.addr {
+            return false;
+        }
+
+        match self.bank {
+            Some(bank) if pc <= 0x3fff => bank == state.fixed_bank,
+            Some(bank) if pc <= 0x7fff => bank == state.rom_bank,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.bank {
+            Some(bank) => write!(f, "{:02x}:{:04x}", bank, self.addr),
+            None => write!(f, "{:04x}", self.addr),
+        }
+    }
+}
+
 /// Empty debugger which does nothing.
 pub struct NullDebugger;
 
@@ -48,3 +119,51 @@ impl IoHandler for NullDebugger {
         MemWrite::PassThrough
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn state(rom_bank: usize) -> MbcState {
+        MbcState {
+            fixed_bank: 0,
+            rom_bank,
+            ram_bank: 0,
+            ram_enabled: false,
+        }
+    }
+
+    #[test]
+    fn breakpoint() {
+        let bp = Breakpoint::parse("3:4100").unwrap();
+        assert_eq!(bp, Breakpoint::banked(3, 0x4100));
+        assert!(bp.hits(0x4100, &state(3)));
+        assert!(!bp.hits(0x4100, &state(2)));
+        assert!(!bp.hits(0x4101, &state(3)));
+
+        let bp = Breakpoint::parse("0x150").unwrap();
+        assert_eq!(bp, Breakpoint::new(0x150));
+        assert!(bp.hits(0x150, &state(5)));
+
+        // The fixed bank area is bank zero, unless a 32KB bank is mapped there.
+        let bp = Breakpoint::parse("0:0150").unwrap();
+        assert!(bp.hits(0x150, &state(1)));
+        let bp = Breakpoint::parse("4:0150").unwrap();
+        assert!(!bp.hits(0x150, &state(5)));
+        let wisdom_tree = MbcState {
+            fixed_bank: 4,
+            ..state(5)
+        };
+        assert!(bp.hits(0x150, &wisdom_tree));
+
+        let bp = Breakpoint::parse("5:c000").unwrap();
+        assert_eq!(bp.bank, Some(0));
+
+        assert_eq!(Breakpoint::parse("zz"), None);
+        assert_eq!(Breakpoint::parse("1:"), None);
+        assert_eq!(
+            alloc::format!("{}", Breakpoint::banked(1, 0x4000)),
+            "01:4000"
+        );
+    }
+}
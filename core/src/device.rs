@@ -32,6 +32,12 @@ impl<T> Device<T> {
     }
 }
 
+impl<T> Clone for Device<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1)
+    }
+}
+
 impl<T: IoHandler> Device<T> {
     /// Return the memory-mapped I/O handler of the device.
     pub fn handler(&self) -> IoMemHandler<T> {
@@ -1,28 +1,98 @@
 use crate::device::IoHandler;
-use crate::mmu::{MemRead, MemWrite, Mmu};
-use log::*;
+use crate::mmu::{Handle, MemRead, MemWrite, Mmu};
 
 pub struct Dma {
+    /// The handler of the debugger, which doesn't see the accesses of the DMA as the CPU doesn't make them.
+    dbg: Handle,
     on: bool,
     src: u8,
+    pos: u16,
+    /// The last value written to FF46.
+    reg: u8,
+    /// A transfer was requested, and starts after a machine cycle.
+    start: bool,
+    /// The transfers started since the last call to `take_transfers`.
+    transfers: u64,
 }
 
 impl Dma {
-    pub fn new() -> Self {
-        Self { on: false, src: 0 }
+    pub fn new(dbg: Handle) -> Self {
+        Self {
+            dbg,
+            on: false,
+            src: 0,
+            pos: 0,
+            reg: 0,
+            start: false,
+            transfers: 0,
+        }
+    }
+
+    /// Restore the power-on state.
+    pub fn reset(&mut self) {
+        *self = Dma::new(self.dbg.clone());
+    }
+
+    /// Return the transfers started since the last call, and reset the count.
+    pub fn take_transfers(&mut self) -> u64 {
+        core::mem::replace(&mut self.transfers, 0)
+    }
+
+    /// The source address, where E000-FFFF reads from the work RAM below.
+    fn src_addr(&self) -> u16 {
+        let src = (self.src as u16) << 8;
+        if src >= 0xe000 {
+            src - 0x2000
+        } else {
+            src
+        }
+    }
+
+    /// Copy the byte at the position of the transfer to OAM.
+    ///
+    /// The source is always below E000, so the cartridge, the video RAM and the work RAM are read
+    /// through their handlers but the I/O registers can't be reached.
+    fn copy(&self, mmu: &mut Mmu, pos: u16) {
+        let value = mmu.get8_skip(self.src_addr() + pos, Some(&self.dbg));
+        mmu.set8_skip(0xfe00 + pos, value, Some(&self.dbg));
     }
 
     pub fn step(&mut self, mmu: &mut Mmu) {
-        if self.on {
-            assert!(self.src <= 0x80 || self.src >= 0x9f);
+        if self.start {
+            self.start = false;
+            self.src = self.reg;
+            self.transfers += 1;
+
             debug!("Perform DMA transfer: {:02x}", self.src);
 
-            let src = (self.src as u16) << 8;
             for i in 0..0xa0 {
-                mmu.set8(0xfe00 + i, mmu.get8(src + i));
+                self.copy(mmu, i);
             }
+        }
+    }
+
+    /// Transfer a single byte, as the DMA does on each machine cycle.
+    pub fn tick(&mut self, mmu: &mut Mmu) {
+        // A transfer requested in the previous cycle takes over after this one,
+        // so a restart keeps the running transfer going for one more byte.
+        let start = self.start;
+
+        if self.on {
+            self.copy(mmu, self.pos);
 
-            self.on = false;
+            self.pos += 1;
+            if self.pos == 0xa0 {
+                debug!("DMA transfer completed: {:02x}", self.src);
+                self.on = false;
+            }
+        }
+
+        if start {
+            self.start = false;
+            self.on = true;
+            self.src = self.reg;
+            self.pos = 0;
+            self.transfers += 1;
         }
     }
 }
@@ -30,13 +100,59 @@ impl Dma {
 impl IoHandler for Dma {
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
         assert_eq!(addr, 0xff46);
-        debug!("Start DMA transfer: {:02x}", self.src);
-        self.on = true;
-        self.src = value;
+        debug!("Start DMA transfer: {:02x}", value);
+        self.reg = value;
+        self.start = true;
         MemWrite::Block
     }
 
     fn on_read(&mut self, _mmu: &Mmu, _addr: u16) -> MemRead {
-        MemRead::Replace(0)
+        MemRead::Replace(self.reg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debug::NullDebugger;
+    use crate::device::Device;
+
+    #[test]
+    fn restart() {
+        let mut mmu = Mmu::new();
+        let dbg = mmu.add_handler((0x0000, 0xffff), Device::new(NullDebugger).handler());
+        for i in 0..0xa0 {
+            mmu.set8(0xc000 + i, 1);
+            mmu.set8(0xc100 + i, 2);
+        }
+
+        let mut dma = Dma::new(dbg);
+        dma.on_write(&mmu, 0xff46, 0xc0);
+        assert!(matches!(dma.on_read(&mmu, 0xff46), MemRead::Replace(0xc0)));
+
+        // Nothing is copied in the start-up cycle.
+        dma.tick(&mut mmu);
+        assert_eq!(mmu.get8(0xfe00), 0);
+
+        for _ in 0..4 {
+            dma.tick(&mut mmu);
+        }
+        assert_eq!(mmu.get8(0xfe03), 1);
+
+        // The running transfer copies one more byte before the restart takes over.
+        dma.on_write(&mmu, 0xff46, 0xc1);
+        dma.tick(&mut mmu);
+        assert_eq!(mmu.get8(0xfe04), 1);
+        assert_eq!(mmu.get8(0xfe00), 1);
+
+        dma.tick(&mut mmu);
+        assert_eq!(mmu.get8(0xfe00), 2);
+
+        for _ in 0..0x9f {
+            dma.tick(&mut mmu);
+        }
+        assert_eq!(mmu.get8(0xfe9f), 2);
+        assert!(!dma.on);
+        assert!(matches!(dma.on_read(&mmu, 0xff46), MemRead::Replace(0xc1)));
     }
 }
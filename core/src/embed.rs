@@ -0,0 +1,174 @@
+//! The emulator driven a frame at a time, shared by the bindings for other languages.
+
+use crate::debug::NullDebugger;
+#[cfg(any(feature = "ffi", feature = "libretro"))]
+use crate::error::Error;
+use crate::hardware::{EventHook, Hardware, Key, SavePersistence, SchedHook, SerialPort, Stream};
+use crate::system::{Config, System, FRAME_CYCLES};
+use crate::{VRAM_HEIGHT, VRAM_WIDTH};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::{vec, vec::Vec};
+use core::cell::RefCell;
+
+/// The state shared between the bindings and the emulator.
+struct Io {
+    frame: Vec<u32>,
+    buttons: u8,
+    stream: Option<Box<dyn Stream>>,
+    clock: u64,
+}
+
+struct SharedHardware(Rc<RefCell<Io>>);
+
+impl Hardware for SharedHardware {
+    fn vram_update(&mut self, line: usize, buffer: &[u32]) {
+        let off = line * VRAM_WIDTH;
+        self.0.borrow_mut().frame[off..off + VRAM_WIDTH].copy_from_slice(buffer);
+    }
+
+    fn joypad_pressed(&mut self, key: Key) -> bool {
+        let bit = match key {
+            Key::Right => 0,
+            Key::Left => 1,
+            Key::Up => 2,
+            Key::Down => 3,
+            Key::A => 4,
+            Key::B => 5,
+            Key::Select => 6,
+            Key::Start => 7,
+        };
+        self.0.borrow().buttons & (1 << bit) != 0
+    }
+
+    fn sound_play(&mut self, stream: Box<dyn Stream>) {
+        self.0.borrow_mut().stream = Some(stream);
+    }
+
+    fn clock(&mut self) -> u64 {
+        self.0.borrow().clock
+    }
+}
+
+impl SavePersistence for SharedHardware {}
+
+impl SerialPort for SharedHardware {}
+
+impl SchedHook for SharedHardware {}
+
+impl EventHook for SharedHardware {}
+
+/// The emulator whose frames are paced by the caller.
+pub(crate) struct Embedded {
+    sys: System<'static, NullDebugger>,
+    io: Rc<RefCell<Io>>,
+    frame_time: u64,
+}
+
+impl Embedded {
+    /// Create the emulator running the ROM, accepting malformed ROMs like [`System::new`][].
+    #[cfg(feature = "wasm")]
+    pub fn new(rom: &[u8]) -> Self {
+        let (cfg, io, frame_time) = Self::parts();
+        let sys = System::new(cfg, rom, SharedHardware(io.clone()), NullDebugger);
+
+        Self {
+            sys,
+            io,
+            frame_time,
+        }
+    }
+
+    /// Create the emulator running the ROM, failing like [`System::try_new`][] if it's malformed.
+    #[cfg(any(feature = "ffi", feature = "libretro"))]
+    pub fn try_new(rom: &[u8]) -> Result<Self, Error> {
+        let (cfg, io, frame_time) = Self::parts();
+        let sys = System::try_new(cfg, rom, SharedHardware(io.clone()), NullDebugger)?;
+
+        Ok(Self {
+            sys,
+            io,
+            frame_time,
+        })
+    }
+
+    /// Replace the running ROM, keeping the frame buffer and the inputs in place.
+    #[cfg(feature = "ffi")]
+    pub fn load_rom(&mut self, rom: &[u8]) -> Result<(), Error> {
+        let hw = SharedHardware(self.io.clone());
+        self.sys = System::try_new(Self::config(), rom, hw, NullDebugger)?;
+        Ok(())
+    }
+
+    /// The configuration of the emulator.
+    fn config() -> Config {
+        // The caller paces the frames, so the CPU isn't paced.
+        Config::new().native_speed(true)
+    }
+
+    /// The configuration, the state shared with the hardware and the time of a frame.
+    fn parts() -> (Config, Rc<RefCell<Io>>, u64) {
+        let cfg = Self::config();
+        let frame_time = FRAME_CYCLES * 1_000_000 / cfg.freq;
+
+        let io = Rc::new(RefCell::new(Io {
+            frame: vec![0; VRAM_WIDTH * VRAM_HEIGHT],
+            buttons: 0,
+            stream: None,
+            clock: 0,
+        }));
+
+        (cfg, io, frame_time)
+    }
+
+    #[cfg(any(feature = "ffi", feature = "libretro"))]
+    pub fn system(&mut self) -> &mut System<'static, NullDebugger> {
+        &mut self.sys
+    }
+
+    /// The frames per second of the emulated display.
+    #[cfg(feature = "libretro")]
+    pub fn fps(&self) -> f64 {
+        1_000_000.0 / self.frame_time as f64
+    }
+
+    /// Run emulation until a frame is drawn. Returns `false` once the emulator stopped.
+    pub fn step_frame(&mut self) -> bool {
+        // The clock only advances with the emulated time, in microseconds.
+        self.io.borrow_mut().clock += self.frame_time;
+        self.sys.run_until_vblank().running
+    }
+
+    /// The frame buffer, `VRAM_WIDTH * VRAM_HEIGHT` pixels in rows. It never moves.
+    pub fn frame(&self) -> (*const u32, usize) {
+        let io = self.io.borrow();
+        (io.frame.as_ptr(), io.frame.len())
+    }
+
+    /// Set the pressed buttons. From the lowest bit: right, left, up, down, A, B, select and start.
+    pub fn set_buttons(&mut self, bits: u8) {
+        let old = core::mem::replace(&mut self.io.borrow_mut().buttons, bits);
+        if old != bits {
+            self.sys.poll_joypad();
+        }
+    }
+
+    /// Fill the buffer with mono samples in `[0, 1]` at the given sample rate.
+    pub fn audio_pull(&mut self, out: &mut [f32], rate: u32) {
+        let mut io = self.io.borrow_mut();
+
+        match io.stream.as_mut() {
+            Some(s) => {
+                let max = s.max() as f32;
+                for sample in out.iter_mut() {
+                    *sample = s.next(rate) as f32 / max;
+                }
+            }
+            None => {
+                for sample in out.iter_mut() {
+                    *sample = 0.0;
+                }
+            }
+        }
+    }
+}
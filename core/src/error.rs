@@ -0,0 +1,36 @@
+use core::fmt;
+
+/// The error returned when the emulator can't be set up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The ROM ends before the end of the cartridge header.
+    RomTooShort {
+        /// The length of the given ROM.
+        actual: usize,
+    },
+    /// The length of the ROM doesn't match the ROM size declared in the cartridge header.
+    RomSizeMismatch {
+        /// The ROM size declared in the cartridge header.
+        expected: usize,
+        /// The length of the given ROM.
+        actual: usize,
+    },
+    /// The game requires a CGB, which isn't emulated, and [`Config::reject_cgb_only`][crate::Config::reject_cgb_only] is set.
+    RequiresCgb,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::RomTooShort { actual } => {
+                write!(f, "ROM too short for the cartridge header: {}", actual)
+            }
+            Error::RomSizeMismatch { expected, actual } => write!(
+                f,
+                "ROM size mismatch: header: {}, actual: {}",
+                expected, actual
+            ),
+            Error::RequiresCgb => write!(f, "CGB-only game without CGB emulation"),
+        }
+    }
+}
@@ -1,6 +1,5 @@
 use crate::hardware::HardwareHandle;
 use crate::system::Config;
-use log::*;
 
 pub struct FreqControl {
     hw: HardwareHandle,
@@ -21,12 +20,22 @@ impl FreqControl {
             delay: 0,
             sample: cfg.sample,
             delay_unit: cfg.delay_unit,
-            target_freq: cfg.freq,
+            target_freq: Self::target(cfg),
         }
     }
 
+    fn target(cfg: &Config) -> u64 {
+        (cfg.clock() as f64 * cfg.speed as f64) as u64
+    }
+
+    /// Follow a change of the speed multiplier.
+    pub fn set_target(&mut self, cfg: &Config) {
+        self.target_freq = Self::target(cfg);
+    }
+
     pub fn reset(&mut self) {
         self.last = self.hw.get().borrow_mut().clock();
+        self.cycles = 0;
     }
 
     pub fn adjust(&mut self, time: usize) {
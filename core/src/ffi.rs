@@ -0,0 +1,177 @@
+//! C ABI to embed the emulator in frontends written in other languages.
+//!
+//! Build the library with `cargo rustc --release --features ffi --crate-type cdylib`
+//! (or `staticlib`). All the functions take the handle returned by [`rgy_create`][],
+//! which must be released by [`rgy_destroy`][].
+//!
+//! The state which can be serialized is the cartridge battery-backed RAM, in the `.sav`
+//! format of [`System::export_save`][crate::System::export_save].
+
+use crate::embed::Embedded;
+use alloc::boxed::Box;
+use core::{ptr, slice};
+
+/// The emulator handle.
+pub struct RgyEmulator(Embedded);
+
+/// Create the emulator running the ROM of `len` bytes at `rom`.
+///
+/// Returns null if the ROM is malformed, e.g. shorter than its header tells.
+///
+/// # Safety
+///
+/// `rom` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_create(rom: *const u8, len: usize) -> *mut RgyEmulator {
+    if rom.is_null() {
+        return ptr::null_mut();
+    }
+    let rom = slice::from_raw_parts(rom, len);
+
+    match Embedded::try_new(rom) {
+        Ok(emu) => Box::into_raw(Box::new(RgyEmulator(emu))),
+        Err(e) => {
+            error!("Invalid ROM: {}", e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Destroy the emulator.
+///
+/// # Safety
+///
+/// `emu` must be null or a handle from [`rgy_create`][] which isn't used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_destroy(emu: *mut RgyEmulator) {
+    if !emu.is_null() {
+        drop(Box::from_raw(emu));
+    }
+}
+
+/// Replace the running ROM, resetting the emulator.
+///
+/// Returns `false`, keeping the running ROM, if the ROM is malformed.
+///
+/// # Safety
+///
+/// `emu` must be a valid handle, and `rom` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_load_rom(emu: *mut RgyEmulator, rom: *const u8, len: usize) -> bool {
+    if emu.is_null() || rom.is_null() {
+        return false;
+    }
+    let rom = slice::from_raw_parts(rom, len);
+
+    match (*emu).0.load_rom(rom) {
+        Ok(()) => true,
+        Err(e) => {
+            error!("Invalid ROM: {}", e);
+            false
+        }
+    }
+}
+
+/// Run emulation until a frame is drawn. Returns `false` once the emulator stopped.
+///
+/// # Safety
+///
+/// `emu` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_step_frame(emu: *mut RgyEmulator) -> bool {
+    match emu.as_mut() {
+        Some(emu) => emu.0.step_frame(),
+        None => false,
+    }
+}
+
+/// Return the frame buffer, storing the number of pixels in `len`.
+///
+/// The pixels are `0x00RRGGBB` in rows of 160. The pointer stays valid until the handle is destroyed,
+/// including across [`rgy_load_rom`][].
+///
+/// # Safety
+///
+/// `emu` must be a valid handle, and `len` must be null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_framebuffer(emu: *const RgyEmulator, len: *mut usize) -> *const u32 {
+    let (buf, n) = match emu.as_ref() {
+        Some(emu) => emu.0.frame(),
+        None => (ptr::null(), 0),
+    };
+    if !len.is_null() {
+        *len = n;
+    }
+    buf
+}
+
+/// Set the pressed buttons. From the lowest bit: right, left, up, down, A, B, select and start.
+///
+/// # Safety
+///
+/// `emu` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_set_buttons(emu: *mut RgyEmulator, bits: u8) {
+    if let Some(emu) = emu.as_mut() {
+        emu.0.set_buttons(bits);
+    }
+}
+
+/// Fill `len` mono samples in `[0, 1]` at the given sample rate.
+///
+/// # Safety
+///
+/// `emu` must be a valid handle, and `out` must point to `len` writable floats.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_audio_pull(
+    emu: *mut RgyEmulator,
+    out: *mut f32,
+    len: usize,
+    rate: u32,
+) {
+    if let (Some(emu), false) = (emu.as_mut(), out.is_null()) {
+        emu.0.audio_pull(slice::from_raw_parts_mut(out, len), rate);
+    }
+}
+
+/// Copy the save data into `out` of `len` bytes, returning its full size.
+///
+/// Nothing is copied if `out` is null or too small, so the size can be queried first.
+///
+/// # Safety
+///
+/// `emu` must be a valid handle, and `out` must be null or point to `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_export_save(emu: *mut RgyEmulator, out: *mut u8, len: usize) -> usize {
+    let emu = match emu.as_mut() {
+        Some(emu) => emu,
+        None => return 0,
+    };
+    let save = emu.0.system().export_save();
+
+    if !out.is_null() && len >= save.len() {
+        slice::from_raw_parts_mut(out, save.len()).copy_from_slice(&save);
+    }
+    save.len()
+}
+
+/// Restore the save data of `len` bytes. Returns `false` if the size doesn't match the cartridge.
+///
+/// # Safety
+///
+/// `emu` must be a valid handle, and `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rgy_import_save(
+    emu: *mut RgyEmulator,
+    data: *const u8,
+    len: usize,
+) -> bool {
+    match (emu.as_mut(), data.is_null()) {
+        (Some(emu), false) => emu
+            .0
+            .system()
+            .import_save(slice::from_raw_parts(data, len))
+            .is_ok(),
+        _ => false,
+    }
+}
@@ -0,0 +1,62 @@
+//! Entry points for the fuzz targets, which feed them arbitrary bytes.
+//!
+//! Malformed ROMs must not make the emulator panic, whatever the header says
+//! and whatever the code does. The targets live in the `fuzz` directory and
+//! are run by `cargo fuzz run <target>`.
+
+use crate::cpu::Cpu;
+use crate::device::IoHandler;
+use crate::hardware::{HardwareHandle, NullHardware};
+use crate::mbc::Mbc;
+use crate::mmu::Mmu;
+use alloc::vec;
+
+/// The header of the ROM, taken from the front of the fuzz input.
+const HEADER: core::ops::Range<usize> = 0x100..0x150;
+
+/// Execute the bytes as instructions on the bare memory map, without any peripheral.
+pub fn cpu(data: &[u8]) {
+    let mut mmu = Mmu::new();
+    for (i, b) in data.iter().take(0x10000).enumerate() {
+        mmu.set8(i as u16, *b);
+    }
+
+    let mut cpu = Cpu::new();
+    for _ in 0..data.len() * 4 {
+        cpu.execute(&mut mmu);
+    }
+}
+
+/// Create the cartridge from the header in the front of the bytes, then apply the rest as
+/// memory accesses to the controller.
+///
+/// Each access takes three bytes: the address, of which the upper bits select the ROM area,
+/// the RAM area or the boot ROM register, and the value to write, or a read if the address is odd.
+pub fn mbc(data: &[u8]) {
+    let len = data.len().min(HEADER.len());
+    let (header, accesses) = data.split_at(len);
+
+    let mut rom = vec![0; 0x8000];
+    rom[HEADER.start..HEADER.start + len].copy_from_slice(header);
+
+    let mmu = Mmu::new();
+    let mut mbc = Mbc::new(HardwareHandle::new(NullHardware), rom);
+
+    for access in accesses.chunks_exact(3) {
+        let addr = u16::from_le_bytes([access[0], access[1]]);
+        let addr = match addr >> 14 {
+            0 | 1 => addr & 0x7fff,
+            2 => 0xa000 | (addr & 0x1fff),
+            _ => 0xff50,
+        };
+
+        if addr & 1 == 1 {
+            mbc.on_read(&mmu, addr);
+        } else {
+            mbc.on_write(&mmu, addr, access[2]);
+        }
+    }
+
+    mbc.state();
+    mbc.export_save();
+}
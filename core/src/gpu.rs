@@ -1,9 +1,209 @@
+use crate::buffer::Buffer;
 use crate::device::IoHandler;
-use crate::hardware::{HardwareHandle, VRAM_HEIGHT, VRAM_WIDTH};
+use crate::hardware::{Event, HardwareHandle, VRAM_HEIGHT, VRAM_WIDTH};
 use crate::ic::Irq;
 use crate::mmu::{MemRead, MemWrite, Mmu};
+use crate::rng::Rng;
+use crate::stats::FrameStats;
+use crate::system::Config;
 use alloc::{vec, vec::Vec};
-use log::*;
+
+/// The number of 8KB VRAM banks. The second bank only exists on CGB.
+const VRAM_BANKS: usize = if cfg!(feature = "color") { 2 } else { 1 };
+
+/// The total size of video RAM allocated.
+pub const VRAM_SIZE: usize = VRAM_BANKS * 0x2000;
+
+/// The number of pixels in a frame.
+pub const FRAME_SIZE: usize = VRAM_WIDTH * VRAM_HEIGHT;
+
+/// The dots of the OAM scan (mode 2) at the start of each visible line.
+///
+/// A dot is a clock cycle of the display, which is a CPU clock cycle at normal speed.
+pub const OAM_SCAN_DOTS: usize = 80;
+
+/// The dots of drawing a line (mode 3). The hardware takes longer with sprites and scrolling.
+pub const DRAW_MIN_DOTS: usize = 172;
+
+/// The dots of HBlank (mode 0), which shrinks as drawing takes longer on the hardware.
+pub const HBLANK_MAX_DOTS: usize = LINE_DOTS - OAM_SCAN_DOTS - DRAW_MIN_DOTS;
+
+/// The dots of a line, visible or in VBlank.
+pub const LINE_DOTS: usize = 456;
+
+/// The lines of a frame, the visible ones followed by the 10 lines of VBlank.
+pub const FRAME_LINES: usize = 154;
+
+/// The dots of a frame.
+pub const FRAME_DOTS: usize = LINE_DOTS * FRAME_LINES;
+
+/// The observer of the writes to the video memory, e.g. to mirror the tile data into
+/// the renderer of a tile-based display.
+///
+/// The observer is a type parameter of [`System`][crate::System], so the default
+/// [`NullObserver`][] costs nothing.
+pub trait VideoObserver: 'static {
+    /// Whether the writes are observed. When `false`, OAM writes skip the observer altogether.
+    const ENABLED: bool = true;
+
+    /// Called on a write to VRAM, OAM or a palette register, including the writes by DMA.
+    ///
+    /// `bank` is the VRAM bank written to, which is zero outside VRAM.
+    fn on_write(&mut self, addr: u16, bank: usize, value: u8);
+}
+
+/// The observer which ignores the writes.
+pub struct NullObserver;
+
+impl VideoObserver for NullObserver {
+    const ENABLED: bool = false;
+
+    fn on_write(&mut self, _addr: u16, _bank: usize, _value: u8) {}
+}
+
+/// The shades of the DMG display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DmgColor {
+    /// Color index 0 of the default palette.
+    White,
+    /// Color index 1 of the default palette.
+    LightGray,
+    /// Color index 2 of the default palette.
+    DarkGray,
+    /// Color index 3 of the default palette.
+    Black,
+}
+
+impl DmgColor {
+    /// The shade of a 2-bit color index. The upper bits are ignored.
+    pub fn from_index(index: u8) -> Self {
+        match index & 0x3 {
+            0 => DmgColor::White,
+            1 => DmgColor::LightGray,
+            2 => DmgColor::DarkGray,
+            _ => DmgColor::Black,
+        }
+    }
+
+    /// The 2-bit color index of the shade.
+    pub fn index(self) -> u8 {
+        match self {
+            DmgColor::White => 0,
+            DmgColor::LightGray => 1,
+            DmgColor::DarkGray => 2,
+            DmgColor::Black => 3,
+        }
+    }
+
+    /// Decode a palette byte as written to BGP, OBP0 or OBP1 into the shades of color indices 0 to 3.
+    pub fn decode_palette(value: u8) -> [DmgColor; 4] {
+        [0, 2, 4, 6].map(|shift| DmgColor::from_index(value >> shift))
+    }
+
+    /// Encode the shades of color indices 0 to 3 into a palette byte, the inverse of
+    /// [`DmgColor::decode_palette`][].
+    pub fn encode_palette(palette: [DmgColor; 4]) -> u8 {
+        palette
+            .iter()
+            .enumerate()
+            .fold(0, |p, (i, c)| p | c.index() << (i * 2))
+    }
+
+    /// The `0xRRGGBB` pixel of the shade, as drawn by [`Rgb888`][].
+    pub fn to_rgb888(self) -> u32 {
+        match self {
+            DmgColor::White => 0xdddddd,
+            DmgColor::LightGray => 0xaaaaaa,
+            DmgColor::DarkGray => 0x888888,
+            DmgColor::Black => 0x555555,
+        }
+    }
+
+    /// The RGB565 pixel of the shade.
+    pub fn to_rgb565(self) -> u16 {
+        let v = self.to_rgb888() & 0xff;
+        (((v >> 3) << 11) | ((v >> 2) << 5) | (v >> 3)) as u16
+    }
+}
+
+/// A CGB color, whose components range from 0 to 31.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CgbColor {
+    /// The red component.
+    pub r: u8,
+    /// The green component.
+    pub g: u8,
+    /// The blue component.
+    pub b: u8,
+}
+
+impl CgbColor {
+    /// Create the color from its components. The upper bits of each are ignored.
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: r & 0x1f,
+            g: g & 0x1f,
+            b: b & 0x1f,
+        }
+    }
+
+    /// Decode the 15-bit color stored in the palette memory, red in the low bits.
+    pub fn from_raw(raw: u16) -> Self {
+        Self::new(raw as u8, (raw >> 5) as u8, (raw >> 10) as u8)
+    }
+
+    /// Encode the color as stored in the palette memory.
+    pub fn to_raw(self) -> u16 {
+        (self.r as u16 & 0x1f) | (self.g as u16 & 0x1f) << 5 | (self.b as u16 & 0x1f) << 10
+    }
+
+    /// The `0xRRGGBB` pixel of the color, as drawn by [`Rgb888`][].
+    pub fn to_rgb888(self) -> u32 {
+        color_adjust(self.r) << 16 | color_adjust(self.g) << 8 | color_adjust(self.b)
+    }
+
+    /// The RGB565 pixel of the color, expanding green to 6 bits.
+    pub fn to_rgb565(self) -> u16 {
+        let (r, g, b) = (
+            self.r as u16 & 0x1f,
+            self.g as u16 & 0x1f,
+            self.b as u16 & 0x1f,
+        );
+        r << 11 | (g << 1 | g >> 4) << 5 | b
+    }
+}
+
+/// The mapping from the colors of the display to the pixels of the frontend.
+///
+/// The pixels are written to the frame buffer and passed to [`Hardware::vram_update`][crate::Hardware::vram_update]
+/// as is, so a frontend can produce its native pixel format, e.g. RGB565, or apply its own palette,
+/// without converting each frame.
+pub trait ColorMapper: 'static {
+    /// The pixel for a DMG shade.
+    fn dmg(&self, color: DmgColor) -> u32;
+
+    /// The pixel for a CGB color, whose components range from 0 to 31.
+    fn cgb(&self, r: u8, g: u8, b: u8) -> u32;
+}
+
+/// The default mapping to `0xRRGGBB` pixels.
+pub struct Rgb888;
+
+impl ColorMapper for Rgb888 {
+    fn dmg(&self, color: DmgColor) -> u32 {
+        color.to_rgb888()
+    }
+
+    fn cgb(&self, r: u8, g: u8, b: u8) -> u32 {
+        CgbColor::new(r, g, b).to_rgb888()
+    }
+}
+
+/// Flag of the background color indices whose tile has the BG-to-OAM priority in CGB mode.
+const BG_PRIORITY: usize = 0x4;
+
+/// The OAM range, observed by the GPU if the observer is enabled.
+pub(crate) const OAM: (u16, u16) = (0xfe00, 0xfe9f);
 
 #[derive(Debug, Clone)]
 enum Mode {
@@ -38,7 +238,7 @@ impl From<u8> for Mode {
     }
 }
 
-pub struct Gpu {
+pub struct Gpu<'a, O = NullObserver, C = Rgb888> {
     irq: Irq,
 
     clocks: usize,
@@ -51,11 +251,17 @@ pub struct Gpu {
 
     ly: u8,
     lyc: u8,
+    /// The result of the last LY=LYC comparison, to request the interrupt on its rising edge.
+    lyc_match: bool,
     scy: u8,
     scx: u8,
 
     wx: u8,
     wy: u8,
+    /// LY matched WY in this frame, which lets the window show from then on.
+    win_active: bool,
+    /// The internal line counter of the window, only advanced on the lines showing it.
+    win_line: u8,
 
     enable: bool,
     winmap: u16,
@@ -72,19 +278,138 @@ pub struct Gpu {
     obj_palette1: Vec<Color>,
     bg_color_palette: ColorPalette,
     obj_color_palette: ColorPalette,
-    vram: Vec<Vec<u8>>,
+    vram: Buffer<'a>,
     vram_select: usize,
 
     hdma: Hdma,
+    hdma_stall: usize,
+
+    lines: u64,
+    vblank: bool,
+    /// The work counted for the statistics, and whether VBlank was entered since taken.
+    counts: FrameStats,
+    counted_frame: bool,
+    frame: Option<&'a mut [u32]>,
+    /// The last complete frame, swapped with `frame` on VBlank.
+    front: Option<&'a mut [u32]>,
+
+    map_gen: [u32; 64],
+    tile_gen: [u32; 3],
+    palette_gen: u32,
+    line_cache: Option<LineCache>,
+
+    line: LineRegs,
+    palette_splits: Vec<PaletteSplit>,
+    /// The sprites of the current line, found by the OAM scan.
+    sprites: Vec<Sprite>,
+
+    /// OPRI: sprite priority by X coordinate (DMG) rather than by OAM index (CGB).
+    x_priority: bool,
+
+    /// The emulated model is a CGB, even if the build doesn't support its colors.
+    cgb_model: bool,
+    /// The CGB runs a DMG game in compatibility mode.
+    compat: bool,
+
+    observer: O,
+    colors: C,
+}
+
+/// Registers latched when the PPU starts drawing a line (mode 3).
+struct LineRegs {
+    scx: u8,
+    scy: u8,
+    bg_palette: Vec<Color>,
+    obj_palette0: Vec<Color>,
+    obj_palette1: Vec<Color>,
+}
+
+/// Where a pixel of the current line took its color from.
+#[derive(Clone, Copy, PartialEq)]
+enum PixelSource {
+    None,
+    Bg,
+    Obj0,
+    Obj1,
+}
+
+/// A DMG palette write which landed in the middle of mode 3, and applies
+/// from pixel `x` to the end of the line.
+struct PaletteSplit {
+    x: usize,
+    source: PixelSource,
+    palette: Vec<Color>,
+}
+
+/// The number of sprites the OAM scan picks up for a line.
+const LINE_SPRITES: usize = 10;
+
+/// A sprite picked up by the OAM scan (mode 2) for the current line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Sprite {
+    index: u16,
+    ypos: u8,
+    xpos: u8,
+    tile: u8,
+    attr: u8,
+}
+
+/// Collect the sprites covering the line in OAM order, stopping at the
+/// number of sprites the hardware can draw on a line.
+fn scan_oam(oam: &[u8], ly: u8, spsize: u16, sprites: &mut Vec<Sprite>) {
+    sprites.clear();
+
+    for (i, entry) in oam.chunks_exact(4).enumerate() {
+        if sprites.len() == LINE_SPRITES {
+            break;
+        }
+
+        // The sprite covers the lines from ypos - 16.
+        let tyoff = (ly as u16 + 16).wrapping_sub(entry[0] as u16);
+        if tyoff >= spsize {
+            continue;
+        }
+
+        sprites.push(Sprite {
+            index: i as u16,
+            ypos: entry[0],
+            xpos: entry[1],
+            tile: entry[2],
+            attr: entry[3],
+        });
+    }
+}
+
+/// Sort the sprites from the highest priority to the lowest.
+fn sort_sprites(sprites: &mut [Sprite], x_priority: bool) {
+    if x_priority {
+        // Smaller X wins; sprites at the same X fall back to the OAM order.
+        sprites.sort_by_key(|s| (s.xpos, s.index));
+    } else {
+        sprites.sort_by_key(|s| s.index);
+    }
+}
+
+/// Recolor the pixels from `split.x` onwards which were drawn with the
+/// palette the split replaces.
+fn apply_split<C: ColorMapper>(
+    buf: &mut [u32],
+    src: &[(PixelSource, usize)],
+    split: &PaletteSplit,
+    colors: &C,
+) {
+    for (x, (source, coli)) in src.iter().enumerate().skip(split.x) {
+        if *source == split.source {
+            buf[x] = split.palette[*coli].map(colors);
+        }
+    }
 }
 
 fn to_palette(p: u8) -> Vec<Color> {
-    vec![
-        ((p >> 0) & 0x3).into(),
-        ((p >> 2) & 0x3).into(),
-        ((p >> 4) & 0x3).into(),
-        ((p >> 6) & 0x3).into(),
-    ]
+    DmgColor::decode_palette(p)
+        .iter()
+        .map(|c| c.index().into())
+        .collect()
 }
 
 fn from_palette(p: Vec<Color>) -> u8 {
@@ -103,6 +428,7 @@ struct SpriteAttribute<'a> {
 
 struct MapAttribute<'a> {
     palette: &'a [Color],
+    source: PixelSource,
     vram_bank: usize,
     xflip: bool,
     yflip: bool,
@@ -150,6 +476,11 @@ impl ColorPalette {
             self.cols[idx][off / 2].set_high(value)
         }
 
+        self.skip();
+    }
+
+    /// Advance the index after a write to the data register, even if the write is ignored.
+    fn skip(&mut self) {
         if self.auto_inc {
             self.index = (self.index + 1) % 0x40;
         }
@@ -217,20 +548,14 @@ fn color_adjust(v: u8) -> u32 {
     }
 }
 
-impl From<Color> for u32 {
-    fn from(c: Color) -> u32 {
-        match c {
-            Color::White => 0xdddddd,
-            Color::LightGray => 0xaaaaaa,
-            Color::DarkGray => 0x888888,
-            Color::Black => 0x555555,
-            Color::Rgb(r, g, b) => {
-                let mut c = 0;
-                c |= color_adjust(r) << 16;
-                c |= color_adjust(g) << 8;
-                c |= color_adjust(b);
-                c
-            }
+impl Color {
+    fn map<C: ColorMapper>(self, colors: &C) -> u32 {
+        match self {
+            Color::White => colors.dmg(DmgColor::White),
+            Color::LightGray => colors.dmg(DmgColor::LightGray),
+            Color::DarkGray => colors.dmg(DmgColor::DarkGray),
+            Color::Black => colors.dmg(DmgColor::Black),
+            Color::Rgb(r, g, b) => colors.cgb(r, g, b),
         }
     }
 }
@@ -259,6 +584,9 @@ impl From<u8> for Color {
     }
 }
 
+/// The CPU cycles one 0x10 byte block of HDMA takes in normal speed.
+pub(crate) const HDMA_BLOCK_CYCLES: usize = 32;
+
 struct Hdma {
     on: bool,
     src_low: u8,
@@ -281,11 +609,19 @@ impl Hdma {
             dst_high: 0,
             src_wip: 0,
             dst_wip: 0,
-            len: 0,
+            len: 0xff,
             hblank: false,
         }
     }
 
+    /// The value of HDMA5: the remaining blocks minus one, with bit 7 set unless a transfer is active.
+    fn status(&self) -> u8 {
+        let mut v = 0;
+        v |= self.len & 0x7f;
+        v |= if self.on { 0x00 } else { 0x80 };
+        v
+    }
+
     fn start(&mut self, value: u8) {
         if self.on && self.hblank && value & 0x80 == 0 {
             self.on = false;
@@ -321,12 +657,15 @@ impl Hdma {
 
             let range = (self.dst_wip, self.src_wip, size);
 
-            self.src_wip += size;
-            self.dst_wip += size;
+            self.src_wip = self.src_wip.wrapping_add(size);
+            self.dst_wip = self.dst_wip.wrapping_add(size);
             let (rem, of) = self.len.overflowing_sub(1);
 
-            self.len = if self.hblank { rem } else { 0xff };
-            self.on = if self.hblank { !of } else { false };
+            // The transfer also ends once the destination runs off the end of VRAM.
+            let end = of || self.dst_wip < 0x8000 || self.dst_wip > 0x9fff;
+
+            self.len = if self.hblank && !end { rem } else { 0xff };
+            self.on = self.hblank && !end;
 
             Some(range)
         } else {
@@ -335,8 +674,92 @@ impl Hdma {
     }
 }
 
-impl Gpu {
-    pub fn new(hw: HardwareHandle, irq: Irq) -> Self {
+/// Hit/miss counters of the background line cache.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineCacheStats {
+    /// The number of lines whose background was reused from the cache.
+    pub hits: u64,
+    /// The number of lines whose background was rendered.
+    pub misses: u64,
+}
+
+/// The inputs the background and window of a line depend on.
+#[derive(Clone, PartialEq, Eq)]
+struct LineKey {
+    ctrl: u8,
+    scx: u8,
+    scy: u8,
+    wx: u8,
+    win_line: Option<u8>,
+    bgp: u8,
+    palette_gen: u32,
+    bg_row_gen: u32,
+    win_row_gen: u32,
+    tile_gen: [u32; 3],
+}
+
+struct CachedLine {
+    key: LineKey,
+    buf: Vec<u32>,
+    bgbuf: Vec<usize>,
+}
+
+struct LineCache {
+    lines: Vec<Option<CachedLine>>,
+    stats: LineCacheStats,
+}
+
+impl LineCache {
+    fn new() -> Self {
+        Self {
+            lines: (0..VRAM_HEIGHT).map(|_| None).collect(),
+            stats: LineCacheStats::default(),
+        }
+    }
+
+    fn get(&mut self, ly: usize, key: &LineKey) -> Option<(Vec<u32>, Vec<usize>)> {
+        match &self.lines[ly] {
+            Some(line) if line.key == *key => {
+                self.stats.hits += 1;
+                Some((line.buf.clone(), line.bgbuf.clone()))
+            }
+            _ => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, ly: usize, key: LineKey, buf: &[u32], bgbuf: &[usize]) {
+        self.lines[ly] = Some(CachedLine {
+            key,
+            buf: buf.to_vec(),
+            bgbuf: bgbuf.to_vec(),
+        });
+    }
+}
+
+impl<'a, O: VideoObserver, C: ColorMapper> Gpu<'a, O, C> {
+    pub fn new(
+        hw: HardwareHandle,
+        irq: Irq,
+        cfg: &Config,
+        vram: Buffer<'a>,
+        frame: Option<&'a mut [u32]>,
+        front: Option<&'a mut [u32]>,
+        observer: O,
+        colors: C,
+    ) -> Self {
+        assert_eq!(vram.len(), VRAM_SIZE);
+        for frame in frame.iter().chain(front.iter()) {
+            assert_eq!(frame.len(), FRAME_SIZE, "Invalid frame buffer size");
+        }
+        assert!(
+            front.is_none() || frame.is_some(),
+            "Back frame buffer without frame buffer"
+        );
+
         Self {
             irq: irq,
             clocks: 0,
@@ -347,10 +770,13 @@ impl Gpu {
             mode: Mode::None,
             ly: 0,
             lyc: 0,
+            lyc_match: false,
             scy: 0,
             scx: 0,
             wx: 0,
             wy: 0,
+            win_active: false,
+            win_line: 0,
             enable: false,
             winmap: 0x9800,
             winenable: false,
@@ -380,37 +806,285 @@ impl Gpu {
             ],
             bg_color_palette: ColorPalette::new(),
             obj_color_palette: ColorPalette::new(),
-            vram: vec![vec![0; 0x2000]; 2],
+            vram,
             vram_select: 0,
             hdma: Hdma::new(),
+            hdma_stall: 0,
+            lines: 0,
+            vblank: false,
+            counts: FrameStats::default(),
+            counted_frame: false,
+            frame,
+            front,
+            map_gen: [0; 64],
+            tile_gen: [0; 3],
+            palette_gen: 0,
+            line_cache: if cfg.line_cache {
+                Some(LineCache::new())
+            } else {
+                None
+            },
+            line: LineRegs {
+                scx: 0,
+                scy: 0,
+                bg_palette: to_palette(0xe4),
+                obj_palette0: to_palette(0xe4),
+                obj_palette1: to_palette(0xe4),
+            },
+            palette_splits: Vec::new(),
+            sprites: Vec::with_capacity(LINE_SPRITES),
+            x_priority: !cfg.model.cgb_mode(),
+            cgb_model: cfg.model.cgb(),
+            compat: false,
+            observer,
+            colors,
         }
     }
 
+    pub fn line_cache_stats(&self) -> LineCacheStats {
+        self.line_cache
+            .as_ref()
+            .map(|c| c.stats)
+            .unwrap_or_default()
+    }
+
     fn hdma_run(&mut self, mmu: &Mmu) {
         match self.hdma.run() {
             Some((dst, src, size)) => {
                 for i in 0..size {
-                    self.write_vram(dst + i, mmu.get8(src + i), self.vram_select);
+                    // The transfer stops at the end of VRAM.
+                    let dst = dst.wrapping_add(i);
+                    if !(0x8000..=0x9fff).contains(&dst) {
+                        break;
+                    }
+
+                    // VRAM can't be the source, and E000-FFFF reads from the external RAM.
+                    let value = match src.wrapping_add(i) {
+                        0x8000..=0x9fff => 0xff,
+                        a @ 0xe000..=0xffff => mmu.get8(a - 0x4000),
+                        a => mmu.get8(a),
+                    };
+                    self.write_vram(dst, value, self.vram_select);
                 }
+                self.hdma_stall += size as usize / 0x10 * HDMA_BLOCK_CYCLES;
+                self.counts.hdma_blocks += size as u64 / 0x10;
             }
             _ => {}
         }
     }
 
+    /// Restore the power-on state, keeping the buffers, the observer and the color mapper.
+    ///
+    /// The video RAM is filled as on power-on.
+    pub fn reset(&mut self, cfg: &Config, rng: &mut Rng) {
+        let mut vram = core::mem::replace(&mut self.vram, Buffer::Owned(Vec::new()));
+        cfg.ram_fill.fill(&mut vram, rng);
+
+        let fresh = Gpu::new(
+            self.hw.clone(),
+            self.irq.clone(),
+            cfg,
+            vram,
+            self.frame.take(),
+            self.front.take(),
+            NullObserver,
+            Rgb888,
+        );
+
+        // Every field but the observer and the color mapper comes from the new state.
+        let Gpu {
+            irq,
+            clocks,
+            lyc_interrupt,
+            oam_interrupt,
+            vblank_interrupt,
+            hblank_interrupt,
+            mode,
+            ly,
+            lyc,
+            lyc_match,
+            scy,
+            scx,
+            wx,
+            wy,
+            win_active,
+            win_line,
+            enable,
+            winmap,
+            winenable,
+            tiles,
+            bgmap,
+            spsize,
+            spenable,
+            bgenable,
+            hw,
+            bg_palette,
+            obj_palette0,
+            obj_palette1,
+            bg_color_palette,
+            obj_color_palette,
+            vram,
+            vram_select,
+            hdma,
+            hdma_stall,
+            lines,
+            vblank,
+            counts,
+            counted_frame,
+            frame,
+            front,
+            map_gen,
+            tile_gen,
+            palette_gen,
+            line_cache,
+            line,
+            palette_splits,
+            sprites,
+            x_priority,
+            cgb_model,
+            compat,
+            observer: _,
+            colors: _,
+        } = fresh;
+
+        self.irq = irq;
+        self.clocks = clocks;
+        self.lyc_interrupt = lyc_interrupt;
+        self.oam_interrupt = oam_interrupt;
+        self.vblank_interrupt = vblank_interrupt;
+        self.hblank_interrupt = hblank_interrupt;
+        self.mode = mode;
+        self.ly = ly;
+        self.lyc = lyc;
+        self.lyc_match = lyc_match;
+        self.scy = scy;
+        self.scx = scx;
+        self.wx = wx;
+        self.wy = wy;
+        self.win_active = win_active;
+        self.win_line = win_line;
+        self.enable = enable;
+        self.winmap = winmap;
+        self.winenable = winenable;
+        self.tiles = tiles;
+        self.bgmap = bgmap;
+        self.spsize = spsize;
+        self.spenable = spenable;
+        self.bgenable = bgenable;
+        self.hw = hw;
+        self.bg_palette = bg_palette;
+        self.obj_palette0 = obj_palette0;
+        self.obj_palette1 = obj_palette1;
+        self.bg_color_palette = bg_color_palette;
+        self.obj_color_palette = obj_color_palette;
+        self.vram = vram;
+        self.vram_select = vram_select;
+        self.hdma = hdma;
+        self.hdma_stall = hdma_stall;
+        self.lines = lines;
+        self.vblank = vblank;
+        self.counts = counts;
+        self.counted_frame = counted_frame;
+        self.frame = frame;
+        self.front = front;
+        self.map_gen = map_gen;
+        self.tile_gen = tile_gen;
+        self.palette_gen = palette_gen;
+        self.line_cache = line_cache;
+        self.line = line;
+        self.palette_splits = palette_splits;
+        self.sprites = sprites;
+        self.x_priority = x_priority;
+        self.cgb_model = cgb_model;
+        self.compat = compat;
+    }
+
+    /// Follow the DMG compatibility mode of the CGB, which behaves as a DMG with colors.
+    pub fn set_compat_mode(&mut self, compat: bool) {
+        self.compat = compat;
+    }
+
+    /// Whether the CGB features are enabled: a CGB is emulated, outside of compatibility mode.
+    fn cgb_mode(&self) -> bool {
+        cfg!(feature = "color") && self.cgb_model && !self.compat
+    }
+
+    /// Return the number of lines drawn since the last call, and reset it.
+    pub fn take_lines(&mut self) -> u64 {
+        core::mem::replace(&mut self.lines, 0)
+    }
+
+    /// The last complete frame if the frame buffer is double-buffered,
+    /// otherwise the frame buffer being drawn.
+    pub fn frame(&self) -> Option<&[u32]> {
+        self.front.as_deref().or(self.frame.as_deref())
+    }
+
+    /// The dot within the current line, from 0 to `LINE_DOTS - 1`, or 0 while the display is off.
+    pub fn line_dot(&self) -> usize {
+        match self.mode {
+            Mode::OAM | Mode::VBlank => self.clocks,
+            Mode::VRAM => OAM_SCAN_DOTS + self.clocks,
+            Mode::HBlank => OAM_SCAN_DOTS + DRAW_MIN_DOTS + self.clocks,
+            Mode::None => 0,
+        }
+    }
+
+    /// The dots until the display enters VBlank, or `None` while the display is off.
+    pub fn vblank_in(&self) -> Option<usize> {
+        if !self.enabled() {
+            return None;
+        }
+
+        let vblank = VRAM_HEIGHT * LINE_DOTS;
+        let dot = self.ly as usize * LINE_DOTS + self.line_dot();
+        Some(if dot < vblank {
+            vblank - dot
+        } else {
+            FRAME_DOTS - dot + vblank
+        })
+    }
+
+    /// Return whether the display is on.
+    pub fn enabled(&self) -> bool {
+        !matches!(self.mode, Mode::None)
+    }
+
+    /// Return whether the PPU entered VBlank since the last call, and reset it.
+    pub fn take_vblank(&mut self) -> bool {
+        core::mem::replace(&mut self.vblank, false)
+    }
+
+    /// Return the work counted for the statistics and whether VBlank was entered since the last call,
+    /// and reset them.
+    pub fn take_counts(&mut self) -> (FrameStats, bool) {
+        (
+            core::mem::take(&mut self.counts),
+            core::mem::replace(&mut self.counted_frame, false),
+        )
+    }
+
+    /// Return the cycles the CPU has to stay halted for the HDMA transfers done so far, and reset it.
+    pub fn take_hdma_stall(&mut self) -> usize {
+        core::mem::replace(&mut self.hdma_stall, 0)
+    }
+
     pub fn step(&mut self, time: usize, mmu: &mut Mmu) {
         let clocks = self.clocks + time;
 
         let (clocks, mode) = match &self.mode {
             Mode::OAM => {
-                if clocks >= 80 {
+                if clocks >= OAM_SCAN_DOTS {
+                    self.scan_sprites(mmu);
+                    self.latch_line();
                     (0, Mode::VRAM)
                 } else {
                     (clocks, Mode::OAM)
                 }
             }
             Mode::VRAM => {
-                if clocks >= 172 {
-                    self.draw(mmu);
+                if clocks >= DRAW_MIN_DOTS {
+                    self.draw();
                     self.hdma_run(mmu);
 
                     if self.hblank_interrupt {
@@ -423,12 +1097,18 @@ impl Gpu {
                 }
             }
             Mode::HBlank => {
-                if clocks >= 204 {
+                if clocks >= HBLANK_MAX_DOTS {
                     self.ly += 1;
 
                     // ly becomes 144 before vblank interrupt
                     if self.ly > 143 {
                         self.irq.vblank(true);
+                        self.vblank = true;
+                        self.counted_frame = true;
+
+                        if self.front.is_some() {
+                            core::mem::swap(&mut self.frame, &mut self.front);
+                        }
 
                         if self.vblank_interrupt {
                             self.irq.lcd(true);
@@ -447,11 +1127,12 @@ impl Gpu {
                 }
             }
             Mode::VBlank => {
-                if clocks >= 456 {
+                if clocks >= LINE_DOTS {
                     self.ly += 1;
 
-                    if self.ly > 153 {
+                    if self.ly as usize >= FRAME_LINES {
                         self.ly = 0;
+                        self.reset_window();
 
                         if self.oam_interrupt {
                             self.irq.lcd(true);
@@ -468,101 +1149,235 @@ impl Gpu {
             Mode::None => (0, Mode::None),
         };
 
-        if self.lyc_interrupt && self.lyc == self.ly {
+        self.clocks = clocks;
+        self.mode = mode;
+
+        self.compare_lyc();
+    }
+
+    /// Compare LY with LYC, requesting the interrupt when they start matching.
+    ///
+    /// This runs whenever either changes, so a LYC write matching the current line takes effect
+    /// right away rather than on the next line.
+    fn compare_lyc(&mut self) {
+        let matched = self.enabled() && self.ly == self.lyc;
+        if matched && !self.lyc_match && self.lyc_interrupt {
             self.irq.lcd(true);
         }
+        self.lyc_match = matched;
+    }
+
+    fn scan_sprites(&mut self, mmu: &Mmu) {
+        scan_oam(mmu.oam(), self.ly, self.spsize, &mut self.sprites);
+    }
+
+    fn latch_line(&mut self) {
+        // WY is only compared at the start of the lines, and the match holds until the next frame.
+        if self.ly == self.wy {
+            self.win_active = true;
+        }
 
-        self.clocks = clocks;
-        self.mode = mode;
+        self.line = LineRegs {
+            scx: self.scx,
+            scy: self.scy,
+            bg_palette: self.bg_palette.clone(),
+            obj_palette0: self.obj_palette0.clone(),
+            obj_palette1: self.obj_palette1.clone(),
+        };
+        self.palette_splits.clear();
     }
 
-    fn draw(&mut self, mmu: &Mmu) {
-        let height = VRAM_HEIGHT;
-        let width = VRAM_WIDTH;
+    /// Whether the window shows on the current line. WX=166 and above put it off the screen.
+    fn window_visible(&self) -> bool {
+        self.winenable && self.win_active && self.wx < 166
+    }
+
+    fn reset_window(&mut self) {
+        self.win_active = false;
+        self.win_line = 0;
+    }
 
-        if self.ly >= height as u8 {
+    /// Record a palette write during mode 3 so that it only affects the
+    /// pixels which haven't been pushed to the screen yet.
+    fn split_palette(&mut self, source: PixelSource, value: u8) {
+        if cfg!(feature = "color") || !matches!(self.mode, Mode::VRAM) {
             return;
         }
 
+        // The first 12 dots of mode 3 fetch tiles without outputting pixels.
+        let x = self.clocks.saturating_sub(12).min(VRAM_WIDTH);
+
+        self.palette_splits.push(PaletteSplit {
+            x,
+            source,
+            palette: to_palette(value),
+        });
+    }
+
+    fn line_key(&self) -> LineKey {
+        let map_row = |mapbase: u16, row: u16| {
+            let map = if mapbase == 0x9c00 { 32 } else { 0 };
+            self.map_gen[map + row as usize % 32]
+        };
+
+        let bg_row = (self.ly as u16 + self.line.scy as u16) % 256 / 8;
+        let win_row = self.win_line as u16 / 8;
+
+        LineKey {
+            ctrl: self.on_read_ctrl(),
+            scx: self.line.scx,
+            scy: self.line.scy,
+            wx: self.wx,
+            win_line: if self.window_visible() {
+                Some(self.win_line)
+            } else {
+                None
+            },
+            bgp: from_palette(self.line.bg_palette.clone()),
+            palette_gen: self.palette_gen,
+            bg_row_gen: map_row(self.bgmap, bg_row),
+            win_row_gen: map_row(self.winmap, win_row),
+            tile_gen: self.tile_gen,
+        }
+    }
+
+    fn draw_bg_cached(&mut self) -> (Vec<u32>, Vec<usize>) {
+        if self.line_cache.is_none() {
+            return self.draw_bg();
+        }
+
+        let ly = self.ly as usize;
+        let key = self.line_key();
+
+        if let Some(line) = self.line_cache.as_mut().and_then(|c| c.get(ly, &key)) {
+            return line;
+        }
+
+        let (buf, bgbuf) = self.draw_bg();
+
+        if let Some(cache) = self.line_cache.as_mut() {
+            cache.insert(ly, key, &buf, &bgbuf);
+        }
+
+        (buf, bgbuf)
+    }
+
+    fn draw_bg(&self) -> (Vec<u32>, Vec<usize>) {
+        let width = VRAM_WIDTH;
+
         let mut buf = vec![0; width];
         let mut bgbuf = vec![0; width];
 
-        if self.bgenable {
+        // In CGB mode, LCDC bit 0 is the master priority rather than the background enable.
+        if self.bgenable || self.cgb_mode() {
             let mapbase = self.bgmap;
 
-            let yy = (self.ly as u16 + self.scy as u16) % 256;
+            let yy = (self.ly as u16 + self.line.scy as u16) % 256;
             let ty = yy / 8;
             let tyoff = yy % 8;
 
-            for x in 0..width as u16 {
-                let xx = (x + self.scx as u16) % 256;
-                let tx = xx / 8;
-                let txoff = xx % 8;
+            // Decode the background tile by tile; 21 tiles cover the line
+            // when scx isn't a multiple of 8.
+            let xoff = self.line.scx as usize % 8;
+
+            for i in 0..(width / 8 + 1) {
+                let tx = (self.line.scx as u16 / 8 + i as u16) % 32;
 
                 let tbase = self.get_tile_base(mapbase, tx, ty);
                 let tattr = self.get_tile_attr(mapbase, tx, ty);
 
                 let tyoff = if tattr.yflip { 7 - tyoff } else { tyoff };
-                let txoff = if tattr.xflip { 7 - txoff } else { txoff };
 
-                #[cfg(feature = "color")]
-                {
-                    assert_eq!(tattr.priority, false);
-                }
+                let priority = if tattr.priority { BG_PRIORITY } else { 0 };
+                let row = self.get_tile_row(tbase, tyoff, tattr.vram_bank, tattr.xflip);
 
-                let coli = self.get_tile_byte(tbase, txoff, tyoff, tattr.vram_bank);
-                let col = tattr.palette[coli].into();
+                for (px, coli) in row.iter().enumerate() {
+                    let x = match (i * 8 + px).checked_sub(xoff) {
+                        Some(x) if x < width => x,
+                        _ => continue,
+                    };
 
-                buf[x as usize] = col;
-                bgbuf[x as usize] = coli;
+                    buf[x] = tattr.palette[*coli].map(&self.colors);
+                    bgbuf[x] = *coli | priority;
+                }
             }
         }
 
-        if self.winenable {
+        if self.window_visible() {
             let mapbase = self.winmap;
 
-            if self.ly >= self.wy {
-                let yy = (self.ly - self.wy) as u16;
-                let ty = yy / 8;
-                let tyoff = yy % 8;
+            let yy = self.win_line as u16;
+            let ty = yy / 8;
+            let tyoff = yy % 8;
 
-                for x in 0..width as u16 {
-                    if x + 7 < self.wx as u16 {
-                        continue;
-                    }
-                    let xx = (x + 7 - self.wx as u16) as u16; // x - (wx - 7)
-                    let tx = xx / 8;
-                    let txoff = xx % 8;
+            // The window starts at `wx - 7` on the screen, clipped on the left below 7.
+            let wx = self.wx as usize;
+
+            for tx in 0..(width as u16 / 8 + 1) {
+                let base = tx as usize * 8 + wx;
+                if base >= width + 7 {
+                    break;
+                }
 
-                    let tbase = self.get_tile_base(mapbase, tx, ty);
-                    let tattr = self.get_tile_attr(mapbase, tx, ty);
+                let tbase = self.get_tile_base(mapbase, tx, ty);
+                let tattr = self.get_tile_attr(mapbase, tx, ty);
 
-                    let coli = self.get_tile_byte(tbase, txoff, tyoff, tattr.vram_bank);
-                    let col = tattr.palette[coli].into();
+                let priority = if tattr.priority { BG_PRIORITY } else { 0 };
+                let row = self.get_tile_row(tbase, tyoff, tattr.vram_bank, false);
 
-                    buf[x as usize] = col;
+                for (px, coli) in row.iter().enumerate() {
+                    let x = match (base + px).checked_sub(7) {
+                        Some(x) if x < width => x,
+                        _ => continue,
+                    };
+
+                    buf[x] = tattr.palette[*coli].map(&self.colors);
+                    bgbuf[x] = *coli | priority;
                 }
             }
         }
 
-        if self.spenable {
-            for i in 0..40 {
-                let oam = 0xfe00 + i * 4;
-                let ypos = mmu.get8(oam + 0) as u16;
-                let xpos = mmu.get8(oam + 1) as u16;
-                let ti = mmu.get8(oam + 2);
-                let attr = self.get_sp_attr(mmu.get8(oam + 3));
-
-                let ly = self.ly as u16;
-                if ly + 16 < ypos {
-                    // This sprite doesn't hit the current ly
-                    continue;
-                }
-                let tyoff = ly as u16 + 16 - ypos; // ly - (ypos - 16)
-                if tyoff >= self.spsize {
-                    // This sprite doesn't hit the current ly
-                    continue;
+        (buf, bgbuf)
+    }
+
+    fn draw(&mut self) {
+        let width = VRAM_WIDTH;
+
+        if self.ly >= VRAM_HEIGHT as u8 {
+            return;
+        }
+
+        let (mut buf, bgbuf) = self.draw_bg_cached();
+        if self.window_visible() {
+            self.win_line = self.win_line.wrapping_add(1);
+        }
+
+        let mut src: Vec<_> = bgbuf
+            .iter()
+            .map(|coli| {
+                if self.bgenable || self.winenable || self.cgb_mode() {
+                    (PixelSource::Bg, *coli & 0x3)
+                } else {
+                    (PixelSource::None, 0)
                 }
+            })
+            .collect();
+
+        // In CGB mode, the sprites are above the background whatever the priorities with LCDC bit 0 clear.
+        let bg_over_obj = self.bgenable || !self.cgb_mode();
+
+        if self.spenable {
+            let mut order = self.sprites.clone();
+            sort_sprites(&mut order, self.x_priority);
+
+            // Draw the lowest priority sprite first so higher ones end up on top.
+            for sprite in order.iter().rev() {
+                let xpos = sprite.xpos as u16;
+                let ti = sprite.tile;
+                let attr = self.get_sp_attr(sprite.attr);
+
+                // The OAM scan only keeps the sprites hitting the current ly.
+                let tyoff = self.ly as u16 + 16 - sprite.ypos as u16; // ly - (ypos - 16)
                 let tyoff = if attr.yflip {
                     self.spsize - 1 - tyoff
                 } else {
@@ -580,44 +1395,55 @@ impl Gpu {
                 let tyoff = tyoff % 8;
 
                 let tiles = 0x8000;
+                let tbase = tiles + ti as u16 * 16;
 
-                for x in 0..width as u16 {
-                    if x + 8 < xpos {
-                        continue;
-                    }
-                    let txoff = x + 8 - xpos; // x - (xpos - 8)
-                    if txoff >= 8 {
-                        continue;
-                    }
-                    let txoff = if attr.xflip { 7 - txoff } else { txoff };
+                let row = self.get_tile_row(tbase, tyoff, attr.vram_bank, attr.xflip);
 
-                    let tbase = tiles + ti as u16 * 16;
+                for (txoff, coli) in row.iter().enumerate() {
+                    // x - (xpos - 8)
+                    let x = match (txoff + xpos as usize).checked_sub(8) {
+                        Some(x) if x < width => x,
+                        _ => continue,
+                    };
 
-                    let coli = self.get_tile_byte(tbase, txoff, tyoff, attr.vram_bank);
-
-                    if coli == 0 {
+                    if *coli == 0 {
                         // Color index 0 means transparent
                         continue;
                     }
 
-                    let col = attr.palette[coli];
+                    let col = attr.palette[*coli];
 
-                    let bgcoli = bgbuf[x as usize];
+                    let bgcoli = bgbuf[x] & 0x3;
+                    let bg_priority = attr.priority || bgbuf[x] & BG_PRIORITY != 0;
 
-                    if attr.priority && bgcoli != 0 {
+                    if bg_over_obj && bg_priority && bgcoli != 0 {
                         // If priority is lower than bg color 1-3, don't draw
                         continue;
                     }
 
-                    buf[x as usize] = col.into();
+                    buf[x] = col.map(&self.colors);
+                    src[x] = (attr.source, *coli);
                 }
             }
         }
 
-        self.hw
-            .get()
-            .borrow_mut()
-            .vram_update(self.ly as usize, &buf);
+        for split in &self.palette_splits {
+            apply_split(&mut buf, &src, split, &self.colors);
+        }
+
+        match self.frame.as_mut() {
+            Some(frame) => {
+                let off = self.ly as usize * width;
+                frame[off..off + width].copy_from_slice(&buf);
+            }
+            None => self
+                .hw
+                .get()
+                .borrow_mut()
+                .vram_update(self.ly as usize, &buf),
+        }
+        self.lines += 1;
+        self.counts.lines += 1;
     }
 
     fn on_write_ctrl(&mut self, value: u8) {
@@ -634,11 +1460,14 @@ impl Gpu {
 
         if !old_enable && self.enable {
             info!("LCD enabled");
+            self.event(Event::Lcd(true));
             self.clocks = 0;
             self.mode = Mode::HBlank;
             self.irq.vblank(false);
+            self.reset_window();
         } else if old_enable && !self.enable {
             info!("LCD disabled");
+            self.event(Event::Lcd(false));
             self.mode = Mode::None;
             self.irq.vblank(false);
         }
@@ -653,8 +1482,26 @@ impl Gpu {
         debug!("Background enable: {}", self.bgenable);
     }
 
+    fn event(&self, event: Event) {
+        self.hw.get().borrow_mut().event(event);
+    }
+
     fn on_write_status(&mut self, value: u8) {
-        self.lyc_interrupt = value & 0x40 != 0;
+        // On DMG, the write enables every source for a cycle, which requests the interrupt
+        // in HBlank, in VBlank and on LY=LYC. Road Rash and Zerd no Densetsu depend on it.
+        let hit = matches!(self.mode, Mode::HBlank | Mode::VBlank) || self.lyc == self.ly;
+        if !self.cgb_model && self.enabled() && hit {
+            debug!("STAT write requests the interrupt");
+            self.irq.lcd(true);
+        }
+
+        // Enabling the source while LY matches LYC requests the interrupt as well.
+        let lyc_interrupt = value & 0x40 != 0;
+        if lyc_interrupt && !self.lyc_interrupt && self.lyc_match {
+            self.irq.lcd(true);
+        }
+
+        self.lyc_interrupt = lyc_interrupt;
         self.oam_interrupt = value & 0x20 != 0;
         self.vblank_interrupt = value & 0x10 != 0;
         self.hblank_interrupt = value & 0x08 != 0;
@@ -665,7 +1512,7 @@ impl Gpu {
         debug!("HBlank interrupt: {}", self.hblank_interrupt);
     }
 
-    fn on_read_ctrl(&mut self) -> u8 {
+    fn on_read_ctrl(&self) -> u8 {
         let mut v = 0;
         v |= if self.enable { 0x80 } else { 0x00 };
         v |= if self.winmap == 0x9c00 { 0x40 } else { 0x00 };
@@ -693,14 +1540,39 @@ impl Gpu {
         v
     }
 
+    /// Whether the palette RAM is inaccessible to the CPU, i.e. while the GPU draws a line.
+    fn palette_locked(&self) -> bool {
+        matches!(self.mode, Mode::VRAM)
+    }
+
     fn read_vram(&self, addr: u16, bank: usize) -> u8 {
         let off = addr as usize - 0x8000;
-        self.vram[bank][off]
+        self.vram[bank * 0x2000 + off]
+    }
+
+    /// The observer of the writes to the video memory.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// The observer of the writes to the video memory.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
     }
 
     fn write_vram(&mut self, addr: u16, value: u8, bank: usize) {
+        self.observer.on_write(addr, bank, value);
+
         let off = addr as usize - 0x8000;
-        self.vram[bank][off] = value;
+        self.vram[bank * 0x2000 + off] = value;
+
+        if off < 0x1800 {
+            let block = off / 0x800;
+            self.tile_gen[block] = self.tile_gen[block].wrapping_add(1);
+        } else {
+            let row = (off - 0x1800) / 32;
+            self.map_gen[row] = self.map_gen[row].wrapping_add(1);
+        }
     }
 
     fn get_tile_base(&self, mapbase: u16, tx: u16, ty: u16) -> u16 {
@@ -721,6 +1593,7 @@ impl Gpu {
 
             MapAttribute {
                 palette: &self.bg_color_palette.cols[attr & 0x7][..],
+                source: PixelSource::Bg,
                 vram_bank: (attr >> 3) & 1,
                 xflip: attr & 0x20 != 0,
                 yflip: attr & 0x40 != 0,
@@ -728,7 +1601,8 @@ impl Gpu {
             }
         } else {
             MapAttribute {
-                palette: &self.bg_palette,
+                palette: &self.line.bg_palette,
+                source: PixelSource::Bg,
                 vram_bank: 0,
                 xflip: false,
                 yflip: false,
@@ -743,20 +1617,22 @@ impl Gpu {
 
             MapAttribute {
                 palette: &self.obj_color_palette.cols[attr & 0x7][..],
+                source: PixelSource::Obj0,
                 vram_bank: (attr >> 3) & 1,
                 xflip: attr & 0x20 != 0,
                 yflip: attr & 0x40 != 0,
                 priority: attr & 0x80 != 0,
             }
         } else {
-            let palette = if attr & 0x10 != 0 {
-                &self.obj_palette1
+            let (palette, source) = if attr & 0x10 != 0 {
+                (&self.line.obj_palette1, PixelSource::Obj1)
             } else {
-                &self.obj_palette0
+                (&self.line.obj_palette0, PixelSource::Obj0)
             };
 
             MapAttribute {
                 palette,
+                source,
                 vram_bank: 0,
                 xflip: attr & 0x20 != 0,
                 yflip: attr & 0x40 != 0,
@@ -765,21 +1641,31 @@ impl Gpu {
         }
     }
 
-    fn get_tile_byte(&self, tilebase: u16, txoff: u16, tyoff: u16, bank: usize) -> usize {
-        let l = self.read_vram(tilebase + tyoff * 2, bank);
-        let h = self.read_vram(tilebase + tyoff * 2 + 1, bank);
+    /// Decode one 8-pixel row of a tile into color indices at once.
+    fn get_tile_row(&self, tilebase: u16, tyoff: u16, bank: usize, xflip: bool) -> [usize; 8] {
+        let off = bank * 0x2000 + (tilebase + tyoff * 2) as usize - 0x8000;
+        let (l, h) = match self.vram.get(off..off + 2) {
+            Some(&[l, h]) => (l, h),
+            _ => (0, 0),
+        };
+
+        let mut row = [0; 8];
 
-        let l = (l >> (7 - txoff)) & 1;
-        let h = ((h >> (7 - txoff)) & 1) << 1;
+        for (i, p) in row.iter_mut().enumerate() {
+            let bit = if xflip { i } else { 7 - i };
+            *p = (((h >> bit) & 1) << 1 | ((l >> bit) & 1)) as usize;
+        }
 
-        (h | l) as usize
+        row
     }
 }
 
-impl IoHandler for Gpu {
+impl<O: VideoObserver, C: ColorMapper> IoHandler for Gpu<'_, O, C> {
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
         if addr >= 0x8000 && addr <= 0x9fff {
             MemRead::Replace(self.read_vram(addr, self.vram_select))
+        } else if addr >= OAM.0 && addr <= OAM.1 {
+            MemRead::PassThrough
         } else if addr == 0xff40 {
             MemRead::Replace(self.on_read_ctrl())
         } else if addr == 0xff41 {
@@ -818,28 +1704,52 @@ impl IoHandler for Gpu {
         } else if addr == 0xff54 {
             MemRead::Replace(self.hdma.dst_low)
         } else if addr == 0xff55 {
-            let mut v = 0;
-            v |= self.hdma.len & 0x7f;
-            v |= if self.hdma.on { 0x00 } else { 0x80 };
-            MemRead::Replace(v)
+            MemRead::Replace(self.hdma.status())
         } else if addr == 0xff68 {
             MemRead::PassThrough
+        } else if matches!(addr, 0xff69 | 0xff6b) && self.palette_locked() {
+            MemRead::Replace(0xff)
         } else if addr == 0xff69 {
             MemRead::Replace(self.bg_color_palette.read())
         } else if addr == 0xff6a {
             MemRead::PassThrough
         } else if addr == 0xff6b {
             MemRead::Replace(self.obj_color_palette.read())
+        } else if addr == 0xff6c {
+            if cfg!(feature = "color") {
+                MemRead::Replace(0xfe | self.x_priority as u8)
+            } else {
+                MemRead::Replace(0xff)
+            }
         } else {
             warn!("Unsupported GPU register read: {:04x}", addr);
             MemRead::Replace(0)
         }
     }
 
-    fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
+    fn on_write(&mut self, mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
         trace!("Write GPU register: {:04x} {:02x}", addr, value);
+        if matches!(addr, 0xff69 | 0xff6b) && self.palette_locked() {
+            // The write is ignored, but the index is still incremented.
+            debug!("Palette write while drawing: {:04x} {:02x}", addr, value);
+            if addr == 0xff69 {
+                self.bg_color_palette.skip();
+            } else {
+                self.obj_color_palette.skip();
+            }
+            return MemWrite::PassThrough;
+        }
+        if matches!(addr, 0xff47..=0xff49 | 0xff69 | 0xff6b) {
+            self.event(Event::Palette { addr, value });
+        }
+        if matches!(addr, 0xff47..=0xff49 | 0xff68..=0xff6b) {
+            self.observer.on_write(addr, 0, value);
+        }
+
         if addr >= 0x8000 && addr <= 0x9fff {
             self.write_vram(addr, value, self.vram_select);
+        } else if addr >= OAM.0 && addr <= OAM.1 {
+            self.observer.on_write(addr, 0, value);
         } else if addr == 0xff40 {
             self.on_write_ctrl(value);
         } else if addr == 0xff41 {
@@ -850,19 +1760,25 @@ impl IoHandler for Gpu {
             debug!("Write SCX: {}", value);
             self.scx = value;
         } else if addr == 0xff44 {
-            self.ly = 0;
+            // LY is read-only; old documents claiming writes reset it are wrong.
+            debug!("Ignored write to LY: {:02x}", value);
+            return MemWrite::Block;
         } else if addr == 0xff45 {
             self.lyc = value;
+            self.compare_lyc();
         } else if addr == 0xff46 {
             unreachable!("Request DMA: {:02x}", value);
         } else if addr == 0xff47 {
             self.bg_palette = to_palette(value);
+            self.split_palette(PixelSource::Bg, value);
             debug!("Bg palette updated: {:?}", self.bg_palette);
         } else if addr == 0xff48 {
             self.obj_palette0 = to_palette(value);
+            self.split_palette(PixelSource::Obj0, value);
             debug!("Object palette 0 updated: {:?}", self.obj_palette0);
         } else if addr == 0xff49 {
             self.obj_palette1 = to_palette(value);
+            self.split_palette(PixelSource::Obj1, value);
             debug!("Object palette 1 updated: {:?}", self.obj_palette1);
         } else if addr == 0xff4a {
             debug!("Window Y: {}", value);
@@ -871,7 +1787,7 @@ impl IoHandler for Gpu {
             debug!("Window X: {}", value);
             self.wx = value;
         } else if addr == 0xff4f {
-            self.vram_select = value as usize & 1;
+            self.vram_select = value as usize & (VRAM_BANKS - 1);
         } else if addr == 0xff51 {
             self.hdma.src_high = value;
         } else if addr == 0xff52 {
@@ -882,14 +1798,26 @@ impl IoHandler for Gpu {
             self.hdma.dst_low = value;
         } else if addr == 0xff55 {
             self.hdma.start(value);
+
+            // General purpose transfers complete at once while the CPU is halted.
+            // An HBlank transfer started during HBlank copies its first block right away.
+            if self.hdma.on && (!self.hdma.hblank || matches!(self.mode, Mode::HBlank)) {
+                self.hdma_run(mmu);
+            }
         } else if addr == 0xff68 {
             self.bg_color_palette.select(value);
         } else if addr == 0xff69 {
             self.bg_color_palette.write(value);
+            self.palette_gen = self.palette_gen.wrapping_add(1);
         } else if addr == 0xff6a {
             self.obj_color_palette.select(value);
         } else if addr == 0xff6b {
             self.obj_color_palette.write(value);
+        } else if addr == 0xff6c {
+            if cfg!(feature = "color") {
+                self.x_priority = value & 0x01 != 0;
+                debug!("Object priority by X: {}", self.x_priority);
+            }
         } else {
             warn!(
                 "Unsupported GPU register is written: {:04x} {:02x}",
@@ -900,3 +1828,420 @@ impl IoHandler for Gpu {
         MemWrite::PassThrough
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::NullHardware;
+    use crate::ic::Ic;
+
+    fn gpu(ic: &Ic) -> Gpu<'static> {
+        gpu_with(ic, &Config::new(), None, NullObserver)
+    }
+
+    fn gpu_with<'a, O: VideoObserver>(
+        ic: &Ic,
+        cfg: &Config,
+        frame: Option<&'a mut [u32]>,
+        observer: O,
+    ) -> Gpu<'a, O> {
+        Gpu::new(
+            HardwareHandle::new(NullHardware),
+            ic.irq(),
+            cfg,
+            Buffer::new(None, VRAM_SIZE),
+            frame,
+            None,
+            observer,
+            Rgb888,
+        )
+    }
+
+    #[test]
+    fn hdma_status() {
+        let mut hdma = Hdma::new();
+        assert_eq!(hdma.status(), 0xff);
+
+        // HBlank transfer of 3 blocks.
+        hdma.dst_high = 0x80;
+        hdma.start(0x82);
+        assert_eq!(hdma.status(), 0x02);
+
+        assert_eq!(hdma.run(), Some((0x8000, 0x0000, 0x10)));
+        assert_eq!(hdma.status(), 0x01);
+
+        // Terminating keeps the remaining length with bit 7 set.
+        hdma.start(0x00);
+        assert_eq!(hdma.status(), 0x81);
+        assert_eq!(hdma.run(), None);
+
+        // Restart and run to completion.
+        hdma.start(0x81);
+        assert!(hdma.run().is_some());
+        assert!(hdma.run().is_some());
+        assert_eq!(hdma.run(), None);
+        assert_eq!(hdma.status(), 0xff);
+    }
+
+    fn order(sprites: &[Sprite]) -> Vec<(u16, u8)> {
+        sprites.iter().map(|s| (s.index, s.xpos)).collect()
+    }
+
+    #[test]
+    fn sprite_priority() {
+        let mut sprites: Vec<_> = [(0, 40), (1, 20), (2, 40), (3, 20)]
+            .iter()
+            .map(|&(index, xpos)| Sprite {
+                index,
+                ypos: 16,
+                xpos,
+                tile: 0,
+                attr: 0,
+            })
+            .collect();
+
+        sort_sprites(&mut sprites, true);
+        assert_eq!(order(&sprites), [(1, 20), (3, 20), (0, 40), (2, 40)]);
+
+        sort_sprites(&mut sprites, false);
+        assert_eq!(order(&sprites), [(0, 40), (1, 20), (2, 40), (3, 20)]);
+    }
+
+    #[test]
+    fn oam_scan() {
+        // Sprites 1-12 cover the lines 8-15, sprite 0 the lines 0-7.
+        let mut oam = [0; 0xa0];
+        oam[0] = 16;
+        for i in 1..13 {
+            oam[i * 4] = 24;
+            oam[i * 4 + 1] = i as u8;
+        }
+
+        let mut sprites = Vec::new();
+        scan_oam(&oam, 7, 8, &mut sprites);
+        assert_eq!(order(&sprites), [(0, 0)]);
+
+        // Only the first 10 sprites in OAM order make it.
+        scan_oam(&oam, 8, 8, &mut sprites);
+        assert_eq!(sprites.len(), LINE_SPRITES);
+        assert_eq!(sprites[0].index, 1);
+        assert_eq!(sprites[9].index, 10);
+
+        scan_oam(&oam, 16, 8, &mut sprites);
+        assert!(sprites.is_empty());
+
+        // Tall sprites cover 16 lines.
+        scan_oam(&oam, 15, 16, &mut sprites);
+        assert_eq!(sprites[0].index, 0);
+        assert_eq!(sprites.len(), LINE_SPRITES);
+    }
+
+    #[test]
+    fn video_observer() {
+        struct Writes(Vec<(u16, usize, u8)>);
+
+        impl VideoObserver for Writes {
+            fn on_write(&mut self, addr: u16, bank: usize, value: u8) {
+                self.0.push((addr, bank, value));
+            }
+        }
+
+        let ic = Ic::new();
+        let mut gpu = gpu_with(&ic, &Config::new(), None, Writes(Vec::new()));
+        let mmu = Mmu::new();
+
+        gpu.on_write(&mmu, 0x8010, 0x12);
+        gpu.on_write(&mmu, 0xfe00, 0x34);
+        gpu.on_write(&mmu, 0xff47, 0xe4);
+        // Other registers aren't video memory.
+        gpu.on_write(&mmu, 0xff42, 0x01);
+
+        assert_eq!(
+            gpu.observer().0,
+            [(0x8010, 0, 0x12), (0xfe00, 0, 0x34), (0xff47, 0, 0xe4)]
+        );
+    }
+
+    #[test]
+    fn palette_lock() {
+        let ic = Ic::new();
+        let mut gpu = gpu(&ic);
+        let mmu = Mmu::new();
+
+        gpu.on_write(&mmu, 0xff68, 0x80);
+        gpu.on_write(&mmu, 0xff69, 0x12);
+
+        // Drawing blocks the data register, but the index is still incremented.
+        gpu.mode = Mode::VRAM;
+        gpu.on_write(&mmu, 0xff69, 0x34);
+        assert!(matches!(gpu.on_read(&mmu, 0xff69), MemRead::Replace(0xff)));
+
+        gpu.mode = Mode::HBlank;
+        gpu.on_write(&mmu, 0xff69, 0x56);
+        gpu.on_write(&mmu, 0xff68, 0x00);
+        assert!(matches!(gpu.on_read(&mmu, 0xff69), MemRead::Replace(0x12)));
+        gpu.on_write(&mmu, 0xff68, 0x02);
+        assert!(matches!(gpu.on_read(&mmu, 0xff69), MemRead::Replace(0x56)));
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn master_priority() {
+        let ic = Ic::new();
+        let mut frame = vec![0; VRAM_WIDTH * VRAM_HEIGHT];
+        let mut gpu = gpu_with(&ic, &Config::new(), Some(&mut frame), NullObserver);
+
+        // Tile 0 is color 1 all over, for both the background and the sprite.
+        for addr in (0x8000..0x8010).step_by(2) {
+            gpu.write_vram(addr, 0xff, 0);
+        }
+        gpu.tiles = 0x8000;
+        gpu.spenable = true;
+        gpu.bg_color_palette.cols[0][1] = Color::Rgb(0x1f, 0, 0);
+        gpu.obj_color_palette.cols[0][1] = Color::Rgb(0, 0, 0x1f);
+        gpu.sprites = vec![Sprite {
+            index: 0,
+            ypos: 16,
+            xpos: 8,
+            tile: 0,
+            attr: 0,
+        }];
+        gpu.latch_line();
+
+        let bg = Color::Rgb(0x1f, 0, 0).map(&Rgb888);
+        let obj = Color::Rgb(0, 0, 0x1f).map(&Rgb888);
+        let mut draw = |bgenable, tile_priority, compat| {
+            gpu.set_compat_mode(compat);
+            gpu.bgenable = bgenable;
+            gpu.write_vram(0x9800, if tile_priority { 0x80 } else { 0x00 }, 1);
+            gpu.draw();
+            let frame = gpu.frame().unwrap();
+            (frame[0], frame[8])
+        };
+
+        assert_eq!(draw(true, false, false), (obj, bg));
+        // The tile's BG-to-OAM priority puts the background over the sprite.
+        assert_eq!(draw(true, true, false), (bg, bg));
+        // With LCDC bit 0 clear, the background is still drawn but below the sprites.
+        assert_eq!(draw(false, true, false), (obj, bg));
+
+        // In DMG compatibility mode, LCDC bit 0 disables the background as on DMG.
+        let (_, pixel) = draw(false, false, true);
+        assert_ne!(pixel, bg);
+    }
+
+    #[test]
+    fn window_edges() {
+        let ic = Ic::new();
+        let mut gpu = gpu(&ic);
+
+        // The background is color 1, the window is color 3 on the left half of each tile and color 0
+        // on the right one.
+        for i in 0..8 {
+            gpu.write_vram(0x8000 + i * 2, 0xff, 0);
+            gpu.write_vram(0x8010 + i * 2, 0xf0, 0);
+            gpu.write_vram(0x8011 + i * 2, 0xf0, 0);
+        }
+        for i in 0..0x400 {
+            gpu.write_vram(0x9c00 + i, 0x01, 0);
+        }
+        gpu.tiles = 0x8000;
+        gpu.bgenable = true;
+        gpu.winenable = true;
+        gpu.winmap = 0x9c00;
+
+        let line = |gpu: &mut Gpu, ly| {
+            gpu.ly = ly;
+            gpu.latch_line();
+            let (_, bgbuf) = gpu.draw_bg();
+            gpu.draw();
+            bgbuf[..8].to_vec()
+        };
+
+        // WX below 7 clips the left of the window.
+        gpu.wy = 0;
+        gpu.wx = 3;
+        assert_eq!(line(&mut gpu, 0), [0, 0, 0, 0, 3, 3, 3, 3]);
+        assert_eq!(gpu.win_line, 1);
+
+        // The window keeps showing after WY moves below LY.
+        gpu.wy = 100;
+        gpu.wx = 7;
+        assert_eq!(line(&mut gpu, 1), [3, 3, 3, 3, 0, 0, 0, 0]);
+        assert_eq!(gpu.win_line, 2);
+
+        // WX=166 hides the window without advancing its line counter.
+        gpu.wx = 166;
+        assert_eq!(line(&mut gpu, 2), [1; 8]);
+        assert_eq!(gpu.win_line, 2);
+
+        // A new frame waits for LY to match WY again.
+        gpu.reset_window();
+        gpu.wx = 7;
+        assert_eq!(line(&mut gpu, 0), [1; 8]);
+        assert_eq!(line(&mut gpu, 100)[0], 3);
+    }
+
+    #[test]
+    fn ly_read_only() {
+        let ic = Ic::new();
+        let mut gpu = gpu(&ic);
+        let mmu = Mmu::new();
+
+        gpu.ly = 42;
+        assert!(matches!(gpu.on_write(&mmu, 0xff44, 0x00), MemWrite::Block));
+        assert!(matches!(gpu.on_read(&mmu, 0xff44), MemRead::Replace(42)));
+    }
+
+    #[test]
+    fn stat_write_quirk() {
+        use crate::system::Model;
+
+        // The quirk depends on the emulated model rather than the build.
+        for model in [Model::Dmg, Model::Cgb] {
+            let ic = Ic::new();
+            let mut gpu = gpu_with(&ic, &Config::new().model(model), None, NullObserver);
+            let mmu = Mmu::new();
+            let lcd = |ic: &Ic| ic.state(false).request & 0x02 != 0;
+
+            gpu.mode = Mode::OAM;
+            gpu.ly = 1;
+            gpu.on_write(&mmu, 0xff41, 0x00);
+            assert!(!lcd(&ic));
+
+            // The sources are enabled for a cycle on DMG only.
+            gpu.mode = Mode::HBlank;
+            gpu.on_write(&mmu, 0xff41, 0x00);
+            assert_eq!(lcd(&ic), model == Model::Dmg, "{:?}", model);
+        }
+    }
+
+    #[test]
+    fn lyc_edge() {
+        let ic = Ic::new();
+        let irq = ic.irq();
+        let mut gpu = gpu(&ic);
+        let mut mmu = Mmu::new();
+        let lcd = |ic: &Ic| ic.state(false).request & 0x02 != 0;
+
+        gpu.mode = Mode::OAM;
+        gpu.ly = 5;
+        gpu.on_write(&mmu, 0xff41, 0x40);
+        gpu.step(4, &mut mmu);
+        assert!(!lcd(&ic));
+
+        // A LYC write matching the current line requests the interrupt right away.
+        gpu.on_write(&mmu, 0xff45, 5);
+        assert!(lcd(&ic));
+
+        // Only once while LY keeps matching.
+        irq.lcd(false);
+        gpu.step(4, &mut mmu);
+        assert!(!lcd(&ic));
+
+        gpu.on_write(&mmu, 0xff45, 6);
+        gpu.ly = 6;
+        gpu.step(4, &mut mmu);
+        assert!(lcd(&ic));
+    }
+
+    #[test]
+    fn line_dots() {
+        assert_eq!(HBLANK_MAX_DOTS, 204);
+        assert_eq!(FRAME_DOTS, 70224);
+
+        let ic = Ic::new();
+        let mut gpu = gpu(&ic);
+        let mut mmu = Mmu::new();
+        assert_eq!(gpu.line_dot(), 0);
+
+        gpu.mode = Mode::OAM;
+        let mut dots = Vec::new();
+        for time in [40, 40, 100, 72, 204] {
+            gpu.step(time, &mut mmu);
+            dots.push((gpu.ly, gpu.line_dot()));
+        }
+        assert_eq!(dots, [(0, 40), (0, 80), (0, 180), (0, 252), (1, 0)]);
+    }
+
+    #[test]
+    fn color_mapper() {
+        struct Rgb565;
+
+        impl ColorMapper for Rgb565 {
+            fn dmg(&self, color: DmgColor) -> u32 {
+                match color {
+                    DmgColor::White => 0xffff,
+                    _ => 0x0000,
+                }
+            }
+
+            fn cgb(&self, r: u8, g: u8, b: u8) -> u32 {
+                (r as u32) << 11 | (g as u32) << 6 | b as u32
+            }
+        }
+
+        assert_eq!(Color::Rgb(0x1f, 0x00, 0x10).map(&Rgb888), 0xff00f0);
+        assert_eq!(Color::Rgb(0x1f, 0x00, 0x10).map(&Rgb565), 0xf810);
+        assert_eq!(Color::White.map(&Rgb565), 0xffff);
+        assert_eq!(Color::DarkGray.map(&Rgb888), 0x888888);
+    }
+
+    #[test]
+    fn color_conversions() {
+        let palette = DmgColor::decode_palette(0xe4);
+        assert_eq!(
+            palette,
+            [
+                DmgColor::White,
+                DmgColor::LightGray,
+                DmgColor::DarkGray,
+                DmgColor::Black
+            ]
+        );
+        assert_eq!(DmgColor::encode_palette(palette), 0xe4);
+        assert_eq!(
+            DmgColor::encode_palette(DmgColor::decode_palette(0x1b)),
+            0x1b
+        );
+        assert_eq!(DmgColor::LightGray.to_rgb888(), 0xaaaaaa);
+        assert_eq!(DmgColor::White.to_rgb565(), 0xdefb);
+
+        let color = CgbColor::from_raw(0x7c1f);
+        assert_eq!(color, CgbColor::new(0x1f, 0x00, 0x1f));
+        assert_eq!(color.to_raw(), 0x7c1f);
+        assert_eq!(color.to_rgb888(), 0xff00ff);
+        assert_eq!(color.to_rgb565(), 0xf81f);
+        assert_eq!(CgbColor::new(0, 0x1f, 0).to_rgb565(), 0x07e0);
+
+        // The palette memory holds the same encoding.
+        let mut c = Color::rgb();
+        c.set_low(0x1f);
+        c.set_high(0x7c);
+        assert_eq!(c.map(&Rgb888), color.to_rgb888());
+    }
+
+    #[test]
+    fn palette_split() {
+        let white = Color::White.map(&Rgb888);
+        let black = Color::Black.map(&Rgb888);
+
+        let mut buf = vec![white; 8];
+        let mut src = vec![(PixelSource::Bg, 0); 8];
+        src[5] = (PixelSource::Obj0, 0);
+        src[6] = (PixelSource::None, 0);
+
+        // BGP inverted from pixel 4 onwards only touches later bg pixels.
+        let split = PaletteSplit {
+            x: 4,
+            source: PixelSource::Bg,
+            palette: to_palette(0x1b),
+        };
+        apply_split(&mut buf, &src, &split, &Rgb888);
+
+        assert_eq!(
+            buf,
+            [white, white, white, white, black, white, white, black]
+        );
+    }
+}
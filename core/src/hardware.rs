@@ -1,6 +1,6 @@
 use alloc::boxed::Box;
 use alloc::rc::Rc;
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::cell::RefCell;
 
 /// The width of the VRAM.
@@ -11,6 +11,7 @@ pub const VRAM_HEIGHT: usize = 144;
 
 /// Represents a key of the joypad.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Key {
     /// Cursor right key.
     Right,
@@ -30,6 +31,17 @@ pub enum Key {
     Start,
 }
 
+/// The output of a sound channel for one sample.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChannelSample {
+    /// The amplitude of the channel, from 0 to 15.
+    pub amp: u16,
+    /// The volume of the left output (SO2), from 0 to 7, or 0 if the channel isn't routed to it.
+    pub left: u16,
+    /// The volume of the right output (SO1), from 0 to 7, or 0 if the channel isn't routed to it.
+    pub right: u16,
+}
+
 /// Sound wave stream which generates the wave to be played by the sound device.
 pub trait Stream: Send + 'static {
     /// The maximum value of the amplitude returned by this stream.
@@ -38,6 +50,21 @@ pub trait Stream: Send + 'static {
     /// The argument takes the sample rate, and the return value indicates the amplitude,
     /// whose max value is determined by [`Stream::max`][].
     fn next(&mut self, rate: u32) -> u16;
+
+    /// The outputs of the four channels, i.e. tone 1, tone 2, wave and noise, for the next sample,
+    /// to mix them in the frontend instead of calling [`Stream::next`][].
+    ///
+    /// The default implementation returns the output of [`Stream::next`][] as the amplitude of
+    /// the first channel, at full volume on both outputs.
+    fn next_channels(&mut self, rate: u32) -> [ChannelSample; 4] {
+        let mut channels = [ChannelSample::default(); 4];
+        channels[0] = ChannelSample {
+            amp: self.next(rate),
+            left: 7,
+            right: 7,
+        };
+        channels
+    }
 }
 
 #[derive(Clone)]
@@ -57,7 +84,11 @@ impl HardwareHandle {
 ///
 /// The users of this emulator library need to implement this trait,
 /// providing OS-specific functions.
-pub trait Hardware {
+///
+/// The optional capabilities are split into [`SavePersistence`][], [`SerialPort`][], [`SchedHook`][]
+/// and [`EventHook`][], whose functions all have default implementations. A target which doesn't
+/// use them only needs empty `impl` blocks for those traits.
+pub trait Hardware: SavePersistence + SerialPort + SchedHook + EventHook {
     /// Called when one horizontal line in the display is updated.
     fn vram_update(&mut self, line: usize, buffer: &[u32]);
 
@@ -71,22 +102,109 @@ pub trait Hardware {
     /// Clock source used by the emulator.
     /// The return value needs to be epoch time in microseconds.
     fn clock(&mut self) -> u64;
+}
+
+/// The hardware without any input or output, to run the emulator in tests.
+#[cfg(any(test, feature = "fuzz"))]
+pub(crate) struct NullHardware;
+
+#[cfg(any(test, feature = "fuzz"))]
+impl Hardware for NullHardware {
+    fn vram_update(&mut self, _line: usize, _buffer: &[u32]) {}
+
+    fn joypad_pressed(&mut self, _key: Key) -> bool {
+        false
+    }
+
+    fn sound_play(&mut self, _stream: Box<dyn Stream>) {}
+
+    fn clock(&mut self) -> u64 {
+        0
+    }
+}
+
+#[cfg(any(test, feature = "fuzz"))]
+impl SavePersistence for NullHardware {}
 
+#[cfg(any(test, feature = "fuzz"))]
+impl SerialPort for NullHardware {}
+
+#[cfg(any(test, feature = "fuzz"))]
+impl SchedHook for NullHardware {}
+
+#[cfg(any(test, feature = "fuzz"))]
+impl EventHook for NullHardware {}
+
+/// Persistence of the cartridge battery-backed RAM.
+///
+/// The default implementation starts with zeroed RAM and drops the writes. Targets which
+/// persist the RAM themselves, through [`System::cartridge_ram`][crate::System::cartridge_ram]
+/// or [`System::export_save`][crate::System::export_save], can keep it. Note that
+/// [`SavePersistence::save_ram`][] is called on every write to the RAM.
+pub trait SavePersistence {
+    /// Called when the emulator loads the save data into the cartridge battery-backed RAM.
+    ///
+    /// The size is the RAM size declared in the cartridge header, which is also the size of
+    /// the RAM passed to [`SavePersistence::save_ram`][].
+    fn load_ram(&mut self, size: usize) -> Vec<u8> {
+        vec![0; size]
+    }
+
+    /// Called when the CPU writes save data to the cartridge battery-backed RAM.
+    fn save_ram(&mut self, _ram: &[u8]) {}
+}
+
+/// The link cable connected to the serial port.
+///
+/// The default implementation behaves as if no cable is connected.
+pub trait SerialPort {
     /// Send one byte to the serial port.
-    fn send_byte(&mut self, b: u8);
+    fn send_byte(&mut self, _b: u8) {}
 
     /// Try receiving one byte from the serial port.
-    fn recv_byte(&mut self) -> Option<u8>;
+    fn recv_byte(&mut self) -> Option<u8> {
+        None
+    }
+}
 
+/// The hook called while the emulator runs.
+pub trait SchedHook {
     /// Called every time the CPU executes one instruction.
     /// Returning `false` stops the emulator.
     fn sched(&mut self) -> bool {
         true
     }
+}
 
-    /// Called when the CPU attempts to write save data to the cartridge battery-backed RAM.
-    fn load_ram(&mut self, size: usize) -> Vec<u8>;
+/// A notable change of the emulated hardware state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    /// The LCD was turned on (`true`) or off (`false`).
+    Lcd(bool),
+    /// A palette register, i.e. BGP, OBP0, OBP1, BCPD or OCPD, was written.
+    Palette {
+        /// The address of the register.
+        addr: u16,
+        /// The value written.
+        value: u8,
+    },
+    /// The cartridge mapped another ROM bank at 0x4000-0x7fff.
+    RomBank(usize),
+    /// The cartridge mapped another RAM bank at 0xa000-0xbfff.
+    RamBank(usize),
+    /// The cartridge RAM was enabled (`true`) or disabled (`false`).
+    CartRam(bool),
+    /// The boot ROM was unmapped, handing over to the cartridge.
+    BootRomDisabled,
+}
 
-    /// Called when the CPU attempts to read save data from the cartridge battery-backed RAM.
-    fn save_ram(&mut self, ram: &[u8]);
+/// The hook receiving structured events, for frontends which trace the emulation
+/// without parsing log messages.
+///
+/// Unlike the log messages, the events are raised without the `log` feature.
+/// The default implementation ignores them.
+pub trait EventHook {
+    /// Called when the emulated hardware changes its state.
+    fn event(&mut self, _event: Event) {}
 }
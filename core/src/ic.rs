@@ -2,7 +2,6 @@ use crate::device::IoHandler;
 use crate::mmu::{MemRead, MemWrite, Mmu};
 use alloc::rc::Rc;
 use core::cell::RefCell;
-use log::*;
 
 #[derive(Clone)]
 pub struct Irq {
@@ -64,6 +63,27 @@ impl Ints {
     }
 }
 
+/// Snapshot of the interrupt controller state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterruptState {
+    /// The interrupt master enable flag of the CPU.
+    pub ime: bool,
+    /// The value of the interrupt enable register (IE, 0xffff).
+    pub enable: u8,
+    /// The value of the interrupt request register (IF, 0xff0f).
+    pub request: u8,
+}
+
+impl InterruptState {
+    /// The interrupts which are both enabled and requested.
+    ///
+    /// The CPU services them if `ime` is set, otherwise they only wake up the CPU from halt.
+    pub fn pending(&self) -> u8 {
+        self.enable & self.request
+    }
+}
+
 pub struct Ic {
     enable: Rc<RefCell<Ints>>,
     request: Rc<RefCell<Ints>>,
@@ -77,10 +97,24 @@ impl Ic {
         }
     }
 
+    /// Clear the enabled and requested interrupts, keeping the interrupt lines of the devices.
+    pub fn reset(&mut self) {
+        *self.enable.borrow_mut() = Ints::default();
+        *self.request.borrow_mut() = Ints::default();
+    }
+
     pub fn irq(&self) -> Irq {
         Irq::new(self.request.clone())
     }
 
+    pub fn state(&self, ime: bool) -> InterruptState {
+        InterruptState {
+            ime,
+            enable: self.enable.borrow().get(),
+            request: self.request.borrow().get(),
+        }
+    }
+
     pub fn peek(&self) -> Option<u8> {
         self.check(false)
     }
@@ -1,518 +1,9626 @@
 use crate::alu;
 use crate::cpu::Cpu;
 use crate::mmu::Mmu;
-use hashbrown::HashMap;
-use lazy_static::lazy_static;
-use log::*;
-
-lazy_static! {
-    static ref MNEMONICS: HashMap<u16, &'static str> = {
-        let mut m = HashMap::new();
-        m.insert(0x0000, "nop ");
-        m.insert(0x0001, "ld bc,d16");
-        m.insert(0x0002, "ld (bc),a");
-        m.insert(0x0003, "inc bc");
-        m.insert(0x0004, "inc b");
-        m.insert(0x0005, "dec b");
-        m.insert(0x0006, "ld b,d8");
-        m.insert(0x0007, "rlca ");
-        m.insert(0x0008, "ld (a16),sp");
-        m.insert(0x0009, "add hl,bc");
-        m.insert(0x000a, "ld a,(bc)");
-        m.insert(0x000b, "dec bc");
-        m.insert(0x000c, "inc c");
-        m.insert(0x000d, "dec c");
-        m.insert(0x000e, "ld c,d8");
-        m.insert(0x000f, "rrca ");
-        m.insert(0x0010, "stop 0");
-        m.insert(0x0011, "ld de,d16");
-        m.insert(0x0012, "ld (de),a");
-        m.insert(0x0013, "inc de");
-        m.insert(0x0014, "inc d");
-        m.insert(0x0015, "dec d");
-        m.insert(0x0016, "ld d,d8");
-        m.insert(0x0017, "rla ");
-        m.insert(0x0018, "jr r8");
-        m.insert(0x0019, "add hl,de");
-        m.insert(0x001a, "ld a,(de)");
-        m.insert(0x001b, "dec de");
-        m.insert(0x001c, "inc e");
-        m.insert(0x001d, "dec e");
-        m.insert(0x001e, "ld e,d8");
-        m.insert(0x001f, "rra ");
-        m.insert(0x0020, "jr nz,r8");
-        m.insert(0x0021, "ld hl,d16");
-        m.insert(0x0022, "ldi (hl),a");
-        m.insert(0x0023, "inc hl");
-        m.insert(0x0024, "inc h");
-        m.insert(0x0025, "dec h");
-        m.insert(0x0026, "ld h,d8");
-        m.insert(0x0027, "daa ");
-        m.insert(0x0028, "jr z,r8");
-        m.insert(0x0029, "add hl,hl");
-        m.insert(0x002a, "ldi a,(hl)");
-        m.insert(0x002b, "dec hl");
-        m.insert(0x002c, "inc l");
-        m.insert(0x002d, "dec l");
-        m.insert(0x002e, "ld l,d8");
-        m.insert(0x002f, "cpl ");
-        m.insert(0x0030, "jr nc,r8");
-        m.insert(0x0031, "ld sp,d16");
-        m.insert(0x0032, "ldd (hl),a");
-        m.insert(0x0033, "inc sp");
-        m.insert(0x0034, "inc (hl)");
-        m.insert(0x0035, "dec (hl)");
-        m.insert(0x0036, "ld (hl),d8");
-        m.insert(0x0037, "scf ");
-        m.insert(0x0038, "jr cf,r8");
-        m.insert(0x0039, "add hl,sp");
-        m.insert(0x003a, "ldd a,(hl)");
-        m.insert(0x003b, "dec sp");
-        m.insert(0x003c, "inc a");
-        m.insert(0x003d, "dec a");
-        m.insert(0x003e, "ld a,d8");
-        m.insert(0x003f, "ccf ");
-        m.insert(0x0040, "ld b,b");
-        m.insert(0x0041, "ld b,c");
-        m.insert(0x0042, "ld b,d");
-        m.insert(0x0043, "ld b,e");
-        m.insert(0x0044, "ld b,h");
-        m.insert(0x0045, "ld b,l");
-        m.insert(0x0046, "ld b,(hl)");
-        m.insert(0x0047, "ld b,a");
-        m.insert(0x0048, "ld c,b");
-        m.insert(0x0049, "ld c,c");
-        m.insert(0x004a, "ld c,d");
-        m.insert(0x004b, "ld c,e");
-        m.insert(0x004c, "ld c,h");
-        m.insert(0x004d, "ld c,l");
-        m.insert(0x004e, "ld c,(hl)");
-        m.insert(0x004f, "ld c,a");
-        m.insert(0x0050, "ld d,b");
-        m.insert(0x0051, "ld d,c");
-        m.insert(0x0052, "ld d,d");
-        m.insert(0x0053, "ld d,e");
-        m.insert(0x0054, "ld d,h");
-        m.insert(0x0055, "ld d,l");
-        m.insert(0x0056, "ld d,(hl)");
-        m.insert(0x0057, "ld d,a");
-        m.insert(0x0058, "ld e,b");
-        m.insert(0x0059, "ld e,c");
-        m.insert(0x005a, "ld e,d");
-        m.insert(0x005b, "ld e,e");
-        m.insert(0x005c, "ld e,h");
-        m.insert(0x005d, "ld e,l");
-        m.insert(0x005e, "ld e,(hl)");
-        m.insert(0x005f, "ld e,a");
-        m.insert(0x0060, "ld h,b");
-        m.insert(0x0061, "ld h,c");
-        m.insert(0x0062, "ld h,d");
-        m.insert(0x0063, "ld h,e");
-        m.insert(0x0064, "ld h,h");
-        m.insert(0x0065, "ld h,l");
-        m.insert(0x0066, "ld h,(hl)");
-        m.insert(0x0067, "ld h,a");
-        m.insert(0x0068, "ld l,b");
-        m.insert(0x0069, "ld l,c");
-        m.insert(0x006a, "ld l,d");
-        m.insert(0x006b, "ld l,e");
-        m.insert(0x006c, "ld l,h");
-        m.insert(0x006d, "ld l,l");
-        m.insert(0x006e, "ld l,(hl)");
-        m.insert(0x006f, "ld l,a");
-        m.insert(0x0070, "ld (hl),b");
-        m.insert(0x0071, "ld (hl),c");
-        m.insert(0x0072, "ld (hl),d");
-        m.insert(0x0073, "ld (hl),e");
-        m.insert(0x0074, "ld (hl),h");
-        m.insert(0x0075, "ld (hl),l");
-        m.insert(0x0076, "halt ");
-        m.insert(0x0077, "ld (hl),a");
-        m.insert(0x0078, "ld a,b");
-        m.insert(0x0079, "ld a,c");
-        m.insert(0x007a, "ld a,d");
-        m.insert(0x007b, "ld a,e");
-        m.insert(0x007c, "ld a,h");
-        m.insert(0x007d, "ld a,l");
-        m.insert(0x007e, "ld a,(hl)");
-        m.insert(0x007f, "ld a,a");
-        m.insert(0x0080, "add a,b");
-        m.insert(0x0081, "add a,c");
-        m.insert(0x0082, "add a,d");
-        m.insert(0x0083, "add a,e");
-        m.insert(0x0084, "add a,h");
-        m.insert(0x0085, "add a,l");
-        m.insert(0x0086, "add a,(hl)");
-        m.insert(0x0087, "add a,a");
-        m.insert(0x0088, "adc a,b");
-        m.insert(0x0089, "adc a,c");
-        m.insert(0x008a, "adc a,d");
-        m.insert(0x008b, "adc a,e");
-        m.insert(0x008c, "adc a,h");
-        m.insert(0x008d, "adc a,l");
-        m.insert(0x008e, "adc a,(hl)");
-        m.insert(0x008f, "adc a,a");
-        m.insert(0x0090, "sub b");
-        m.insert(0x0091, "sub c");
-        m.insert(0x0092, "sub d");
-        m.insert(0x0093, "sub e");
-        m.insert(0x0094, "sub h");
-        m.insert(0x0095, "sub l");
-        m.insert(0x0096, "sub (hl)");
-        m.insert(0x0097, "sub a");
-        m.insert(0x0098, "sbc a,b");
-        m.insert(0x0099, "sbc a,c");
-        m.insert(0x009a, "sbc a,d");
-        m.insert(0x009b, "sbc a,e");
-        m.insert(0x009c, "sbc a,h");
-        m.insert(0x009d, "sbc a,l");
-        m.insert(0x009e, "sbc a,(hl)");
-        m.insert(0x009f, "sbc a,a");
-        m.insert(0x00a0, "and b");
-        m.insert(0x00a1, "and c");
-        m.insert(0x00a2, "and d");
-        m.insert(0x00a3, "and e");
-        m.insert(0x00a4, "and h");
-        m.insert(0x00a5, "and l");
-        m.insert(0x00a6, "and (hl)");
-        m.insert(0x00a7, "and a");
-        m.insert(0x00a8, "xor b");
-        m.insert(0x00a9, "xor c");
-        m.insert(0x00aa, "xor d");
-        m.insert(0x00ab, "xor e");
-        m.insert(0x00ac, "xor h");
-        m.insert(0x00ad, "xor l");
-        m.insert(0x00ae, "xor (hl)");
-        m.insert(0x00af, "xor a");
-        m.insert(0x00b0, "or b");
-        m.insert(0x00b1, "or c");
-        m.insert(0x00b2, "or d");
-        m.insert(0x00b3, "or e");
-        m.insert(0x00b4, "or h");
-        m.insert(0x00b5, "or l");
-        m.insert(0x00b6, "or (hl)");
-        m.insert(0x00b7, "or a");
-        m.insert(0x00b8, "cp b");
-        m.insert(0x00b9, "cp c");
-        m.insert(0x00ba, "cp d");
-        m.insert(0x00bb, "cp e");
-        m.insert(0x00bc, "cp h");
-        m.insert(0x00bd, "cp l");
-        m.insert(0x00be, "cp (hl)");
-        m.insert(0x00bf, "cp a");
-        m.insert(0x00c0, "ret nz");
-        m.insert(0x00c1, "pop bc");
-        m.insert(0x00c2, "jp nz,a16");
-        m.insert(0x00c3, "jp a16");
-        m.insert(0x00c4, "call nz,a16");
-        m.insert(0x00c5, "push bc");
-        m.insert(0x00c6, "add a,d8");
-        m.insert(0x00c7, "rst 0x00");
-        m.insert(0x00c8, "ret z");
-        m.insert(0x00c9, "ret ");
-        m.insert(0x00ca, "jp z,a16");
-        m.insert(0x00cb, "prefix cb");
-        m.insert(0x00cc, "call z,a16");
-        m.insert(0x00cd, "call a16");
-        m.insert(0x00ce, "adc a,d8");
-        m.insert(0x00cf, "rst 0x08");
-        m.insert(0x00d0, "ret nc");
-        m.insert(0x00d1, "pop de");
-        m.insert(0x00d2, "jp nc,a16");
-        m.insert(0x00d4, "call nc,a16");
-        m.insert(0x00d5, "push de");
-        m.insert(0x00d6, "sub d8");
-        m.insert(0x00d7, "rst 0x10");
-        m.insert(0x00d8, "ret cf");
-        m.insert(0x00d9, "reti ");
-        m.insert(0x00da, "jp cf,a16");
-        m.insert(0x00dc, "call cf,a16");
-        m.insert(0x00de, "sbc a,d8");
-        m.insert(0x00df, "rst 0x18");
-        m.insert(0x00e0, "ld (0xff00+a8),a");
-        m.insert(0x00e1, "pop hl");
-        m.insert(0x00e2, "ld (0xff00+c),a");
-        m.insert(0x00e5, "push hl");
-        m.insert(0x00e6, "and d8");
-        m.insert(0x00e7, "rst 0x20");
-        m.insert(0x00e8, "add sp,r8");
-        m.insert(0x00e9, "jp hl");
-        m.insert(0x00ea, "ld (a16),a");
-        m.insert(0x00ee, "xor d8");
-        m.insert(0x00ef, "rst 0x28");
-        m.insert(0x00f0, "ld a,(0xff00+a8)");
-        m.insert(0x00f1, "pop af");
-        m.insert(0x00f2, "ld a,(0xff00+c)");
-        m.insert(0x00f3, "di ");
-        m.insert(0x00f5, "push af");
-        m.insert(0x00f6, "or d8");
-        m.insert(0x00f7, "rst 0x30");
-        m.insert(0x00f8, "ldhl sp,r8");
-        m.insert(0x00f9, "ld sp,hl");
-        m.insert(0x00fa, "ld a,(a16)");
-        m.insert(0x00fb, "ei ");
-        m.insert(0x00fe, "cp d8");
-        m.insert(0x00ff, "rst 0x38");
-        m.insert(0xcb00, "rlc b");
-        m.insert(0xcb01, "rlc c");
-        m.insert(0xcb02, "rlc d");
-        m.insert(0xcb03, "rlc e");
-        m.insert(0xcb04, "rlc h");
-        m.insert(0xcb05, "rlc l");
-        m.insert(0xcb06, "rlc (hl)");
-        m.insert(0xcb07, "rlc a");
-        m.insert(0xcb08, "rrc b");
-        m.insert(0xcb09, "rrc c");
-        m.insert(0xcb0a, "rrc d");
-        m.insert(0xcb0b, "rrc e");
-        m.insert(0xcb0c, "rrc h");
-        m.insert(0xcb0d, "rrc l");
-        m.insert(0xcb0e, "rrc (hl)");
-        m.insert(0xcb0f, "rrc a");
-        m.insert(0xcb10, "rl b");
-        m.insert(0xcb11, "rl c");
-        m.insert(0xcb12, "rl d");
-        m.insert(0xcb13, "rl e");
-        m.insert(0xcb14, "rl h");
-        m.insert(0xcb15, "rl l");
-        m.insert(0xcb16, "rl (hl)");
-        m.insert(0xcb17, "rl a");
-        m.insert(0xcb18, "rr b");
-        m.insert(0xcb19, "rr c");
-        m.insert(0xcb1a, "rr d");
-        m.insert(0xcb1b, "rr e");
-        m.insert(0xcb1c, "rr h");
-        m.insert(0xcb1d, "rr l");
-        m.insert(0xcb1e, "rr (hl)");
-        m.insert(0xcb1f, "rr a");
-        m.insert(0xcb20, "sla b");
-        m.insert(0xcb21, "sla c");
-        m.insert(0xcb22, "sla d");
-        m.insert(0xcb23, "sla e");
-        m.insert(0xcb24, "sla h");
-        m.insert(0xcb25, "sla l");
-        m.insert(0xcb26, "sla (hl)");
-        m.insert(0xcb27, "sla a");
-        m.insert(0xcb28, "sra b");
-        m.insert(0xcb29, "sra c");
-        m.insert(0xcb2a, "sra d");
-        m.insert(0xcb2b, "sra e");
-        m.insert(0xcb2c, "sra h");
-        m.insert(0xcb2d, "sra l");
-        m.insert(0xcb2e, "sra (hl)");
-        m.insert(0xcb2f, "sra a");
-        m.insert(0xcb30, "swap b");
-        m.insert(0xcb31, "swap c");
-        m.insert(0xcb32, "swap d");
-        m.insert(0xcb33, "swap e");
-        m.insert(0xcb34, "swap h");
-        m.insert(0xcb35, "swap l");
-        m.insert(0xcb36, "swap (hl)");
-        m.insert(0xcb37, "swap a");
-        m.insert(0xcb38, "srl b");
-        m.insert(0xcb39, "srl c");
-        m.insert(0xcb3a, "srl d");
-        m.insert(0xcb3b, "srl e");
-        m.insert(0xcb3c, "srl h");
-        m.insert(0xcb3d, "srl l");
-        m.insert(0xcb3e, "srl (hl)");
-        m.insert(0xcb3f, "srl a");
-        m.insert(0xcb40, "bit 0,b");
-        m.insert(0xcb41, "bit 0,c");
-        m.insert(0xcb42, "bit 0,d");
-        m.insert(0xcb43, "bit 0,e");
-        m.insert(0xcb44, "bit 0,h");
-        m.insert(0xcb45, "bit 0,l");
-        m.insert(0xcb46, "bit 0,(hl)");
-        m.insert(0xcb47, "bit 0,a");
-        m.insert(0xcb48, "bit 1,b");
-        m.insert(0xcb49, "bit 1,c");
-        m.insert(0xcb4a, "bit 1,d");
-        m.insert(0xcb4b, "bit 1,e");
-        m.insert(0xcb4c, "bit 1,h");
-        m.insert(0xcb4d, "bit 1,l");
-        m.insert(0xcb4e, "bit 1,(hl)");
-        m.insert(0xcb4f, "bit 1,a");
-        m.insert(0xcb50, "bit 2,b");
-        m.insert(0xcb51, "bit 2,c");
-        m.insert(0xcb52, "bit 2,d");
-        m.insert(0xcb53, "bit 2,e");
-        m.insert(0xcb54, "bit 2,h");
-        m.insert(0xcb55, "bit 2,l");
-        m.insert(0xcb56, "bit 2,(hl)");
-        m.insert(0xcb57, "bit 2,a");
-        m.insert(0xcb58, "bit 3,b");
-        m.insert(0xcb59, "bit 3,c");
-        m.insert(0xcb5a, "bit 3,d");
-        m.insert(0xcb5b, "bit 3,e");
-        m.insert(0xcb5c, "bit 3,h");
-        m.insert(0xcb5d, "bit 3,l");
-        m.insert(0xcb5e, "bit 3,(hl)");
-        m.insert(0xcb5f, "bit 3,a");
-        m.insert(0xcb60, "bit 4,b");
-        m.insert(0xcb61, "bit 4,c");
-        m.insert(0xcb62, "bit 4,d");
-        m.insert(0xcb63, "bit 4,e");
-        m.insert(0xcb64, "bit 4,h");
-        m.insert(0xcb65, "bit 4,l");
-        m.insert(0xcb66, "bit 4,(hl)");
-        m.insert(0xcb67, "bit 4,a");
-        m.insert(0xcb68, "bit 5,b");
-        m.insert(0xcb69, "bit 5,c");
-        m.insert(0xcb6a, "bit 5,d");
-        m.insert(0xcb6b, "bit 5,e");
-        m.insert(0xcb6c, "bit 5,h");
-        m.insert(0xcb6d, "bit 5,l");
-        m.insert(0xcb6e, "bit 5,(hl)");
-        m.insert(0xcb6f, "bit 5,a");
-        m.insert(0xcb70, "bit 6,b");
-        m.insert(0xcb71, "bit 6,c");
-        m.insert(0xcb72, "bit 6,d");
-        m.insert(0xcb73, "bit 6,e");
-        m.insert(0xcb74, "bit 6,h");
-        m.insert(0xcb75, "bit 6,l");
-        m.insert(0xcb76, "bit 6,(hl)");
-        m.insert(0xcb77, "bit 6,a");
-        m.insert(0xcb78, "bit 7,b");
-        m.insert(0xcb79, "bit 7,c");
-        m.insert(0xcb7a, "bit 7,d");
-        m.insert(0xcb7b, "bit 7,e");
-        m.insert(0xcb7c, "bit 7,h");
-        m.insert(0xcb7d, "bit 7,l");
-        m.insert(0xcb7e, "bit 7,(hl)");
-        m.insert(0xcb7f, "bit 7,a");
-        m.insert(0xcb80, "res 0,b");
-        m.insert(0xcb81, "res 0,c");
-        m.insert(0xcb82, "res 0,d");
-        m.insert(0xcb83, "res 0,e");
-        m.insert(0xcb84, "res 0,h");
-        m.insert(0xcb85, "res 0,l");
-        m.insert(0xcb86, "res 0,(hl)");
-        m.insert(0xcb87, "res 0,a");
-        m.insert(0xcb88, "res 1,b");
-        m.insert(0xcb89, "res 1,c");
-        m.insert(0xcb8a, "res 1,d");
-        m.insert(0xcb8b, "res 1,e");
-        m.insert(0xcb8c, "res 1,h");
-        m.insert(0xcb8d, "res 1,l");
-        m.insert(0xcb8e, "res 1,(hl)");
-        m.insert(0xcb8f, "res 1,a");
-        m.insert(0xcb90, "res 2,b");
-        m.insert(0xcb91, "res 2,c");
-        m.insert(0xcb92, "res 2,d");
-        m.insert(0xcb93, "res 2,e");
-        m.insert(0xcb94, "res 2,h");
-        m.insert(0xcb95, "res 2,l");
-        m.insert(0xcb96, "res 2,(hl)");
-        m.insert(0xcb97, "res 2,a");
-        m.insert(0xcb98, "res 3,b");
-        m.insert(0xcb99, "res 3,c");
-        m.insert(0xcb9a, "res 3,d");
-        m.insert(0xcb9b, "res 3,e");
-        m.insert(0xcb9c, "res 3,h");
-        m.insert(0xcb9d, "res 3,l");
-        m.insert(0xcb9e, "res 3,(hl)");
-        m.insert(0xcb9f, "res 3,a");
-        m.insert(0xcba0, "res 4,b");
-        m.insert(0xcba1, "res 4,c");
-        m.insert(0xcba2, "res 4,d");
-        m.insert(0xcba3, "res 4,e");
-        m.insert(0xcba4, "res 4,h");
-        m.insert(0xcba5, "res 4,l");
-        m.insert(0xcba6, "res 4,(hl)");
-        m.insert(0xcba7, "res 4,a");
-        m.insert(0xcba8, "res 5,b");
-        m.insert(0xcba9, "res 5,c");
-        m.insert(0xcbaa, "res 5,d");
-        m.insert(0xcbab, "res 5,e");
-        m.insert(0xcbac, "res 5,h");
-        m.insert(0xcbad, "res 5,l");
-        m.insert(0xcbae, "res 5,(hl)");
-        m.insert(0xcbaf, "res 5,a");
-        m.insert(0xcbb0, "res 6,b");
-        m.insert(0xcbb1, "res 6,c");
-        m.insert(0xcbb2, "res 6,d");
-        m.insert(0xcbb3, "res 6,e");
-        m.insert(0xcbb4, "res 6,h");
-        m.insert(0xcbb5, "res 6,l");
-        m.insert(0xcbb6, "res 6,(hl)");
-        m.insert(0xcbb7, "res 6,a");
-        m.insert(0xcbb8, "res 7,b");
-        m.insert(0xcbb9, "res 7,c");
-        m.insert(0xcbba, "res 7,d");
-        m.insert(0xcbbb, "res 7,e");
-        m.insert(0xcbbc, "res 7,h");
-        m.insert(0xcbbd, "res 7,l");
-        m.insert(0xcbbe, "res 7,(hl)");
-        m.insert(0xcbbf, "res 7,a");
-        m.insert(0xcbc0, "set 0,b");
-        m.insert(0xcbc1, "set 0,c");
-        m.insert(0xcbc2, "set 0,d");
-        m.insert(0xcbc3, "set 0,e");
-        m.insert(0xcbc4, "set 0,h");
-        m.insert(0xcbc5, "set 0,l");
-        m.insert(0xcbc6, "set 0,(hl)");
-        m.insert(0xcbc7, "set 0,a");
-        m.insert(0xcbc8, "set 1,b");
-        m.insert(0xcbc9, "set 1,c");
-        m.insert(0xcbca, "set 1,d");
-        m.insert(0xcbcb, "set 1,e");
-        m.insert(0xcbcc, "set 1,h");
-        m.insert(0xcbcd, "set 1,l");
-        m.insert(0xcbce, "set 1,(hl)");
-        m.insert(0xcbcf, "set 1,a");
-        m.insert(0xcbd0, "set 2,b");
-        m.insert(0xcbd1, "set 2,c");
-        m.insert(0xcbd2, "set 2,d");
-        m.insert(0xcbd3, "set 2,e");
-        m.insert(0xcbd4, "set 2,h");
-        m.insert(0xcbd5, "set 2,l");
-        m.insert(0xcbd6, "set 2,(hl)");
-        m.insert(0xcbd7, "set 2,a");
-        m.insert(0xcbd8, "set 3,b");
-        m.insert(0xcbd9, "set 3,c");
-        m.insert(0xcbda, "set 3,d");
-        m.insert(0xcbdb, "set 3,e");
-        m.insert(0xcbdc, "set 3,h");
-        m.insert(0xcbdd, "set 3,l");
-        m.insert(0xcbde, "set 3,(hl)");
-        m.insert(0xcbdf, "set 3,a");
-        m.insert(0xcbe0, "set 4,b");
-        m.insert(0xcbe1, "set 4,c");
-        m.insert(0xcbe2, "set 4,d");
-        m.insert(0xcbe3, "set 4,e");
-        m.insert(0xcbe4, "set 4,h");
-        m.insert(0xcbe5, "set 4,l");
-        m.insert(0xcbe6, "set 4,(hl)");
-        m.insert(0xcbe7, "set 4,a");
-        m.insert(0xcbe8, "set 5,b");
-        m.insert(0xcbe9, "set 5,c");
-        m.insert(0xcbea, "set 5,d");
-        m.insert(0xcbeb, "set 5,e");
-        m.insert(0xcbec, "set 5,h");
-        m.insert(0xcbed, "set 5,l");
-        m.insert(0xcbee, "set 5,(hl)");
-        m.insert(0xcbef, "set 5,a");
-        m.insert(0xcbf0, "set 6,b");
-        m.insert(0xcbf1, "set 6,c");
-        m.insert(0xcbf2, "set 6,d");
-        m.insert(0xcbf3, "set 6,e");
-        m.insert(0xcbf4, "set 6,h");
-        m.insert(0xcbf5, "set 6,l");
-        m.insert(0xcbf6, "set 6,(hl)");
-        m.insert(0xcbf7, "set 6,a");
-        m.insert(0xcbf8, "set 7,b");
-        m.insert(0xcbf9, "set 7,c");
-        m.insert(0xcbfa, "set 7,d");
-        m.insert(0xcbfb, "set 7,e");
-        m.insert(0xcbfc, "set 7,h");
-        m.insert(0xcbfd, "set 7,l");
-        m.insert(0xcbfe, "set 7,(hl)");
-        m.insert(0xcbff, "set 7,a");
-        m
+
+/// The metadata and the evaluator of a single instruction.
+#[derive(Clone, Copy)]
+pub struct Inst {
+    /// The opcode, prefixed by `0xcb` for the extended instructions.
+    pub code: u16,
+    /// The mnemonic string.
+    pub mnemonic: &'static str,
+    /// The length of the instruction in bytes.
+    pub size: usize,
+    /// The clock cycles taken by the instruction, without branching for the conditional ones.
+    pub time: usize,
+    exec: fn(u16, &mut Cpu, &mut Mmu) -> (usize, usize),
+}
+
+impl Inst {
+    /// Execute the instruction, returning the clock cycles taken and the length.
+    pub fn exec(&self, arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
+        (self.exec)(arg, cpu, mmu)
+    }
+}
+
+/// The instructions indexed by the opcode.
+pub static INSTS: [Option<Inst>; 256] = [
+    Some(Inst {
+        code: 0x0000,
+        mnemonic: "nop ",
+        size: 1,
+        time: 4,
+        exec: op_0000,
+    }),
+    Some(Inst {
+        code: 0x0001,
+        mnemonic: "ld bc,d16",
+        size: 3,
+        time: 12,
+        exec: op_0001,
+    }),
+    Some(Inst {
+        code: 0x0002,
+        mnemonic: "ld (bc),a",
+        size: 1,
+        time: 8,
+        exec: op_0002,
+    }),
+    Some(Inst {
+        code: 0x0003,
+        mnemonic: "inc bc",
+        size: 1,
+        time: 8,
+        exec: op_0003,
+    }),
+    Some(Inst {
+        code: 0x0004,
+        mnemonic: "inc b",
+        size: 1,
+        time: 4,
+        exec: op_0004,
+    }),
+    Some(Inst {
+        code: 0x0005,
+        mnemonic: "dec b",
+        size: 1,
+        time: 4,
+        exec: op_0005,
+    }),
+    Some(Inst {
+        code: 0x0006,
+        mnemonic: "ld b,d8",
+        size: 2,
+        time: 8,
+        exec: op_0006,
+    }),
+    Some(Inst {
+        code: 0x0007,
+        mnemonic: "rlca ",
+        size: 1,
+        time: 4,
+        exec: op_0007,
+    }),
+    Some(Inst {
+        code: 0x0008,
+        mnemonic: "ld (a16),sp",
+        size: 3,
+        time: 20,
+        exec: op_0008,
+    }),
+    Some(Inst {
+        code: 0x0009,
+        mnemonic: "add hl,bc",
+        size: 1,
+        time: 8,
+        exec: op_0009,
+    }),
+    Some(Inst {
+        code: 0x000a,
+        mnemonic: "ld a,(bc)",
+        size: 1,
+        time: 8,
+        exec: op_000a,
+    }),
+    Some(Inst {
+        code: 0x000b,
+        mnemonic: "dec bc",
+        size: 1,
+        time: 8,
+        exec: op_000b,
+    }),
+    Some(Inst {
+        code: 0x000c,
+        mnemonic: "inc c",
+        size: 1,
+        time: 4,
+        exec: op_000c,
+    }),
+    Some(Inst {
+        code: 0x000d,
+        mnemonic: "dec c",
+        size: 1,
+        time: 4,
+        exec: op_000d,
+    }),
+    Some(Inst {
+        code: 0x000e,
+        mnemonic: "ld c,d8",
+        size: 2,
+        time: 8,
+        exec: op_000e,
+    }),
+    Some(Inst {
+        code: 0x000f,
+        mnemonic: "rrca ",
+        size: 1,
+        time: 4,
+        exec: op_000f,
+    }),
+    Some(Inst {
+        code: 0x0010,
+        mnemonic: "stop 0",
+        size: 2,
+        time: 4,
+        exec: op_0010,
+    }),
+    Some(Inst {
+        code: 0x0011,
+        mnemonic: "ld de,d16",
+        size: 3,
+        time: 12,
+        exec: op_0011,
+    }),
+    Some(Inst {
+        code: 0x0012,
+        mnemonic: "ld (de),a",
+        size: 1,
+        time: 8,
+        exec: op_0012,
+    }),
+    Some(Inst {
+        code: 0x0013,
+        mnemonic: "inc de",
+        size: 1,
+        time: 8,
+        exec: op_0013,
+    }),
+    Some(Inst {
+        code: 0x0014,
+        mnemonic: "inc d",
+        size: 1,
+        time: 4,
+        exec: op_0014,
+    }),
+    Some(Inst {
+        code: 0x0015,
+        mnemonic: "dec d",
+        size: 1,
+        time: 4,
+        exec: op_0015,
+    }),
+    Some(Inst {
+        code: 0x0016,
+        mnemonic: "ld d,d8",
+        size: 2,
+        time: 8,
+        exec: op_0016,
+    }),
+    Some(Inst {
+        code: 0x0017,
+        mnemonic: "rla ",
+        size: 1,
+        time: 4,
+        exec: op_0017,
+    }),
+    Some(Inst {
+        code: 0x0018,
+        mnemonic: "jr r8",
+        size: 2,
+        time: 12,
+        exec: op_0018,
+    }),
+    Some(Inst {
+        code: 0x0019,
+        mnemonic: "add hl,de",
+        size: 1,
+        time: 8,
+        exec: op_0019,
+    }),
+    Some(Inst {
+        code: 0x001a,
+        mnemonic: "ld a,(de)",
+        size: 1,
+        time: 8,
+        exec: op_001a,
+    }),
+    Some(Inst {
+        code: 0x001b,
+        mnemonic: "dec de",
+        size: 1,
+        time: 8,
+        exec: op_001b,
+    }),
+    Some(Inst {
+        code: 0x001c,
+        mnemonic: "inc e",
+        size: 1,
+        time: 4,
+        exec: op_001c,
+    }),
+    Some(Inst {
+        code: 0x001d,
+        mnemonic: "dec e",
+        size: 1,
+        time: 4,
+        exec: op_001d,
+    }),
+    Some(Inst {
+        code: 0x001e,
+        mnemonic: "ld e,d8",
+        size: 2,
+        time: 8,
+        exec: op_001e,
+    }),
+    Some(Inst {
+        code: 0x001f,
+        mnemonic: "rra ",
+        size: 1,
+        time: 4,
+        exec: op_001f,
+    }),
+    Some(Inst {
+        code: 0x0020,
+        mnemonic: "jr nz,r8",
+        size: 2,
+        time: 8,
+        exec: op_0020,
+    }),
+    Some(Inst {
+        code: 0x0021,
+        mnemonic: "ld hl,d16",
+        size: 3,
+        time: 12,
+        exec: op_0021,
+    }),
+    Some(Inst {
+        code: 0x0022,
+        mnemonic: "ldi (hl),a",
+        size: 1,
+        time: 8,
+        exec: op_0022,
+    }),
+    Some(Inst {
+        code: 0x0023,
+        mnemonic: "inc hl",
+        size: 1,
+        time: 8,
+        exec: op_0023,
+    }),
+    Some(Inst {
+        code: 0x0024,
+        mnemonic: "inc h",
+        size: 1,
+        time: 4,
+        exec: op_0024,
+    }),
+    Some(Inst {
+        code: 0x0025,
+        mnemonic: "dec h",
+        size: 1,
+        time: 4,
+        exec: op_0025,
+    }),
+    Some(Inst {
+        code: 0x0026,
+        mnemonic: "ld h,d8",
+        size: 2,
+        time: 8,
+        exec: op_0026,
+    }),
+    Some(Inst {
+        code: 0x0027,
+        mnemonic: "daa ",
+        size: 1,
+        time: 4,
+        exec: op_0027,
+    }),
+    Some(Inst {
+        code: 0x0028,
+        mnemonic: "jr z,r8",
+        size: 2,
+        time: 8,
+        exec: op_0028,
+    }),
+    Some(Inst {
+        code: 0x0029,
+        mnemonic: "add hl,hl",
+        size: 1,
+        time: 8,
+        exec: op_0029,
+    }),
+    Some(Inst {
+        code: 0x002a,
+        mnemonic: "ldi a,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_002a,
+    }),
+    Some(Inst {
+        code: 0x002b,
+        mnemonic: "dec hl",
+        size: 1,
+        time: 8,
+        exec: op_002b,
+    }),
+    Some(Inst {
+        code: 0x002c,
+        mnemonic: "inc l",
+        size: 1,
+        time: 4,
+        exec: op_002c,
+    }),
+    Some(Inst {
+        code: 0x002d,
+        mnemonic: "dec l",
+        size: 1,
+        time: 4,
+        exec: op_002d,
+    }),
+    Some(Inst {
+        code: 0x002e,
+        mnemonic: "ld l,d8",
+        size: 2,
+        time: 8,
+        exec: op_002e,
+    }),
+    Some(Inst {
+        code: 0x002f,
+        mnemonic: "cpl ",
+        size: 1,
+        time: 4,
+        exec: op_002f,
+    }),
+    Some(Inst {
+        code: 0x0030,
+        mnemonic: "jr nc,r8",
+        size: 2,
+        time: 8,
+        exec: op_0030,
+    }),
+    Some(Inst {
+        code: 0x0031,
+        mnemonic: "ld sp,d16",
+        size: 3,
+        time: 12,
+        exec: op_0031,
+    }),
+    Some(Inst {
+        code: 0x0032,
+        mnemonic: "ldd (hl),a",
+        size: 1,
+        time: 8,
+        exec: op_0032,
+    }),
+    Some(Inst {
+        code: 0x0033,
+        mnemonic: "inc sp",
+        size: 1,
+        time: 8,
+        exec: op_0033,
+    }),
+    Some(Inst {
+        code: 0x0034,
+        mnemonic: "inc (hl)",
+        size: 1,
+        time: 12,
+        exec: op_0034,
+    }),
+    Some(Inst {
+        code: 0x0035,
+        mnemonic: "dec (hl)",
+        size: 1,
+        time: 12,
+        exec: op_0035,
+    }),
+    Some(Inst {
+        code: 0x0036,
+        mnemonic: "ld (hl),d8",
+        size: 2,
+        time: 12,
+        exec: op_0036,
+    }),
+    Some(Inst {
+        code: 0x0037,
+        mnemonic: "scf ",
+        size: 1,
+        time: 4,
+        exec: op_0037,
+    }),
+    Some(Inst {
+        code: 0x0038,
+        mnemonic: "jr cf,r8",
+        size: 2,
+        time: 8,
+        exec: op_0038,
+    }),
+    Some(Inst {
+        code: 0x0039,
+        mnemonic: "add hl,sp",
+        size: 1,
+        time: 8,
+        exec: op_0039,
+    }),
+    Some(Inst {
+        code: 0x003a,
+        mnemonic: "ldd a,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_003a,
+    }),
+    Some(Inst {
+        code: 0x003b,
+        mnemonic: "dec sp",
+        size: 1,
+        time: 8,
+        exec: op_003b,
+    }),
+    Some(Inst {
+        code: 0x003c,
+        mnemonic: "inc a",
+        size: 1,
+        time: 4,
+        exec: op_003c,
+    }),
+    Some(Inst {
+        code: 0x003d,
+        mnemonic: "dec a",
+        size: 1,
+        time: 4,
+        exec: op_003d,
+    }),
+    Some(Inst {
+        code: 0x003e,
+        mnemonic: "ld a,d8",
+        size: 2,
+        time: 8,
+        exec: op_003e,
+    }),
+    Some(Inst {
+        code: 0x003f,
+        mnemonic: "ccf ",
+        size: 1,
+        time: 4,
+        exec: op_003f,
+    }),
+    Some(Inst {
+        code: 0x0040,
+        mnemonic: "ld b,b",
+        size: 1,
+        time: 4,
+        exec: op_0040,
+    }),
+    Some(Inst {
+        code: 0x0041,
+        mnemonic: "ld b,c",
+        size: 1,
+        time: 4,
+        exec: op_0041,
+    }),
+    Some(Inst {
+        code: 0x0042,
+        mnemonic: "ld b,d",
+        size: 1,
+        time: 4,
+        exec: op_0042,
+    }),
+    Some(Inst {
+        code: 0x0043,
+        mnemonic: "ld b,e",
+        size: 1,
+        time: 4,
+        exec: op_0043,
+    }),
+    Some(Inst {
+        code: 0x0044,
+        mnemonic: "ld b,h",
+        size: 1,
+        time: 4,
+        exec: op_0044,
+    }),
+    Some(Inst {
+        code: 0x0045,
+        mnemonic: "ld b,l",
+        size: 1,
+        time: 4,
+        exec: op_0045,
+    }),
+    Some(Inst {
+        code: 0x0046,
+        mnemonic: "ld b,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_0046,
+    }),
+    Some(Inst {
+        code: 0x0047,
+        mnemonic: "ld b,a",
+        size: 1,
+        time: 4,
+        exec: op_0047,
+    }),
+    Some(Inst {
+        code: 0x0048,
+        mnemonic: "ld c,b",
+        size: 1,
+        time: 4,
+        exec: op_0048,
+    }),
+    Some(Inst {
+        code: 0x0049,
+        mnemonic: "ld c,c",
+        size: 1,
+        time: 4,
+        exec: op_0049,
+    }),
+    Some(Inst {
+        code: 0x004a,
+        mnemonic: "ld c,d",
+        size: 1,
+        time: 4,
+        exec: op_004a,
+    }),
+    Some(Inst {
+        code: 0x004b,
+        mnemonic: "ld c,e",
+        size: 1,
+        time: 4,
+        exec: op_004b,
+    }),
+    Some(Inst {
+        code: 0x004c,
+        mnemonic: "ld c,h",
+        size: 1,
+        time: 4,
+        exec: op_004c,
+    }),
+    Some(Inst {
+        code: 0x004d,
+        mnemonic: "ld c,l",
+        size: 1,
+        time: 4,
+        exec: op_004d,
+    }),
+    Some(Inst {
+        code: 0x004e,
+        mnemonic: "ld c,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_004e,
+    }),
+    Some(Inst {
+        code: 0x004f,
+        mnemonic: "ld c,a",
+        size: 1,
+        time: 4,
+        exec: op_004f,
+    }),
+    Some(Inst {
+        code: 0x0050,
+        mnemonic: "ld d,b",
+        size: 1,
+        time: 4,
+        exec: op_0050,
+    }),
+    Some(Inst {
+        code: 0x0051,
+        mnemonic: "ld d,c",
+        size: 1,
+        time: 4,
+        exec: op_0051,
+    }),
+    Some(Inst {
+        code: 0x0052,
+        mnemonic: "ld d,d",
+        size: 1,
+        time: 4,
+        exec: op_0052,
+    }),
+    Some(Inst {
+        code: 0x0053,
+        mnemonic: "ld d,e",
+        size: 1,
+        time: 4,
+        exec: op_0053,
+    }),
+    Some(Inst {
+        code: 0x0054,
+        mnemonic: "ld d,h",
+        size: 1,
+        time: 4,
+        exec: op_0054,
+    }),
+    Some(Inst {
+        code: 0x0055,
+        mnemonic: "ld d,l",
+        size: 1,
+        time: 4,
+        exec: op_0055,
+    }),
+    Some(Inst {
+        code: 0x0056,
+        mnemonic: "ld d,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_0056,
+    }),
+    Some(Inst {
+        code: 0x0057,
+        mnemonic: "ld d,a",
+        size: 1,
+        time: 4,
+        exec: op_0057,
+    }),
+    Some(Inst {
+        code: 0x0058,
+        mnemonic: "ld e,b",
+        size: 1,
+        time: 4,
+        exec: op_0058,
+    }),
+    Some(Inst {
+        code: 0x0059,
+        mnemonic: "ld e,c",
+        size: 1,
+        time: 4,
+        exec: op_0059,
+    }),
+    Some(Inst {
+        code: 0x005a,
+        mnemonic: "ld e,d",
+        size: 1,
+        time: 4,
+        exec: op_005a,
+    }),
+    Some(Inst {
+        code: 0x005b,
+        mnemonic: "ld e,e",
+        size: 1,
+        time: 4,
+        exec: op_005b,
+    }),
+    Some(Inst {
+        code: 0x005c,
+        mnemonic: "ld e,h",
+        size: 1,
+        time: 4,
+        exec: op_005c,
+    }),
+    Some(Inst {
+        code: 0x005d,
+        mnemonic: "ld e,l",
+        size: 1,
+        time: 4,
+        exec: op_005d,
+    }),
+    Some(Inst {
+        code: 0x005e,
+        mnemonic: "ld e,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_005e,
+    }),
+    Some(Inst {
+        code: 0x005f,
+        mnemonic: "ld e,a",
+        size: 1,
+        time: 4,
+        exec: op_005f,
+    }),
+    Some(Inst {
+        code: 0x0060,
+        mnemonic: "ld h,b",
+        size: 1,
+        time: 4,
+        exec: op_0060,
+    }),
+    Some(Inst {
+        code: 0x0061,
+        mnemonic: "ld h,c",
+        size: 1,
+        time: 4,
+        exec: op_0061,
+    }),
+    Some(Inst {
+        code: 0x0062,
+        mnemonic: "ld h,d",
+        size: 1,
+        time: 4,
+        exec: op_0062,
+    }),
+    Some(Inst {
+        code: 0x0063,
+        mnemonic: "ld h,e",
+        size: 1,
+        time: 4,
+        exec: op_0063,
+    }),
+    Some(Inst {
+        code: 0x0064,
+        mnemonic: "ld h,h",
+        size: 1,
+        time: 4,
+        exec: op_0064,
+    }),
+    Some(Inst {
+        code: 0x0065,
+        mnemonic: "ld h,l",
+        size: 1,
+        time: 4,
+        exec: op_0065,
+    }),
+    Some(Inst {
+        code: 0x0066,
+        mnemonic: "ld h,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_0066,
+    }),
+    Some(Inst {
+        code: 0x0067,
+        mnemonic: "ld h,a",
+        size: 1,
+        time: 4,
+        exec: op_0067,
+    }),
+    Some(Inst {
+        code: 0x0068,
+        mnemonic: "ld l,b",
+        size: 1,
+        time: 4,
+        exec: op_0068,
+    }),
+    Some(Inst {
+        code: 0x0069,
+        mnemonic: "ld l,c",
+        size: 1,
+        time: 4,
+        exec: op_0069,
+    }),
+    Some(Inst {
+        code: 0x006a,
+        mnemonic: "ld l,d",
+        size: 1,
+        time: 4,
+        exec: op_006a,
+    }),
+    Some(Inst {
+        code: 0x006b,
+        mnemonic: "ld l,e",
+        size: 1,
+        time: 4,
+        exec: op_006b,
+    }),
+    Some(Inst {
+        code: 0x006c,
+        mnemonic: "ld l,h",
+        size: 1,
+        time: 4,
+        exec: op_006c,
+    }),
+    Some(Inst {
+        code: 0x006d,
+        mnemonic: "ld l,l",
+        size: 1,
+        time: 4,
+        exec: op_006d,
+    }),
+    Some(Inst {
+        code: 0x006e,
+        mnemonic: "ld l,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_006e,
+    }),
+    Some(Inst {
+        code: 0x006f,
+        mnemonic: "ld l,a",
+        size: 1,
+        time: 4,
+        exec: op_006f,
+    }),
+    Some(Inst {
+        code: 0x0070,
+        mnemonic: "ld (hl),b",
+        size: 1,
+        time: 8,
+        exec: op_0070,
+    }),
+    Some(Inst {
+        code: 0x0071,
+        mnemonic: "ld (hl),c",
+        size: 1,
+        time: 8,
+        exec: op_0071,
+    }),
+    Some(Inst {
+        code: 0x0072,
+        mnemonic: "ld (hl),d",
+        size: 1,
+        time: 8,
+        exec: op_0072,
+    }),
+    Some(Inst {
+        code: 0x0073,
+        mnemonic: "ld (hl),e",
+        size: 1,
+        time: 8,
+        exec: op_0073,
+    }),
+    Some(Inst {
+        code: 0x0074,
+        mnemonic: "ld (hl),h",
+        size: 1,
+        time: 8,
+        exec: op_0074,
+    }),
+    Some(Inst {
+        code: 0x0075,
+        mnemonic: "ld (hl),l",
+        size: 1,
+        time: 8,
+        exec: op_0075,
+    }),
+    Some(Inst {
+        code: 0x0076,
+        mnemonic: "halt ",
+        size: 1,
+        time: 4,
+        exec: op_0076,
+    }),
+    Some(Inst {
+        code: 0x0077,
+        mnemonic: "ld (hl),a",
+        size: 1,
+        time: 8,
+        exec: op_0077,
+    }),
+    Some(Inst {
+        code: 0x0078,
+        mnemonic: "ld a,b",
+        size: 1,
+        time: 4,
+        exec: op_0078,
+    }),
+    Some(Inst {
+        code: 0x0079,
+        mnemonic: "ld a,c",
+        size: 1,
+        time: 4,
+        exec: op_0079,
+    }),
+    Some(Inst {
+        code: 0x007a,
+        mnemonic: "ld a,d",
+        size: 1,
+        time: 4,
+        exec: op_007a,
+    }),
+    Some(Inst {
+        code: 0x007b,
+        mnemonic: "ld a,e",
+        size: 1,
+        time: 4,
+        exec: op_007b,
+    }),
+    Some(Inst {
+        code: 0x007c,
+        mnemonic: "ld a,h",
+        size: 1,
+        time: 4,
+        exec: op_007c,
+    }),
+    Some(Inst {
+        code: 0x007d,
+        mnemonic: "ld a,l",
+        size: 1,
+        time: 4,
+        exec: op_007d,
+    }),
+    Some(Inst {
+        code: 0x007e,
+        mnemonic: "ld a,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_007e,
+    }),
+    Some(Inst {
+        code: 0x007f,
+        mnemonic: "ld a,a",
+        size: 1,
+        time: 4,
+        exec: op_007f,
+    }),
+    Some(Inst {
+        code: 0x0080,
+        mnemonic: "add a,b",
+        size: 1,
+        time: 4,
+        exec: op_0080,
+    }),
+    Some(Inst {
+        code: 0x0081,
+        mnemonic: "add a,c",
+        size: 1,
+        time: 4,
+        exec: op_0081,
+    }),
+    Some(Inst {
+        code: 0x0082,
+        mnemonic: "add a,d",
+        size: 1,
+        time: 4,
+        exec: op_0082,
+    }),
+    Some(Inst {
+        code: 0x0083,
+        mnemonic: "add a,e",
+        size: 1,
+        time: 4,
+        exec: op_0083,
+    }),
+    Some(Inst {
+        code: 0x0084,
+        mnemonic: "add a,h",
+        size: 1,
+        time: 4,
+        exec: op_0084,
+    }),
+    Some(Inst {
+        code: 0x0085,
+        mnemonic: "add a,l",
+        size: 1,
+        time: 4,
+        exec: op_0085,
+    }),
+    Some(Inst {
+        code: 0x0086,
+        mnemonic: "add a,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_0086,
+    }),
+    Some(Inst {
+        code: 0x0087,
+        mnemonic: "add a,a",
+        size: 1,
+        time: 4,
+        exec: op_0087,
+    }),
+    Some(Inst {
+        code: 0x0088,
+        mnemonic: "adc a,b",
+        size: 1,
+        time: 4,
+        exec: op_0088,
+    }),
+    Some(Inst {
+        code: 0x0089,
+        mnemonic: "adc a,c",
+        size: 1,
+        time: 4,
+        exec: op_0089,
+    }),
+    Some(Inst {
+        code: 0x008a,
+        mnemonic: "adc a,d",
+        size: 1,
+        time: 4,
+        exec: op_008a,
+    }),
+    Some(Inst {
+        code: 0x008b,
+        mnemonic: "adc a,e",
+        size: 1,
+        time: 4,
+        exec: op_008b,
+    }),
+    Some(Inst {
+        code: 0x008c,
+        mnemonic: "adc a,h",
+        size: 1,
+        time: 4,
+        exec: op_008c,
+    }),
+    Some(Inst {
+        code: 0x008d,
+        mnemonic: "adc a,l",
+        size: 1,
+        time: 4,
+        exec: op_008d,
+    }),
+    Some(Inst {
+        code: 0x008e,
+        mnemonic: "adc a,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_008e,
+    }),
+    Some(Inst {
+        code: 0x008f,
+        mnemonic: "adc a,a",
+        size: 1,
+        time: 4,
+        exec: op_008f,
+    }),
+    Some(Inst {
+        code: 0x0090,
+        mnemonic: "sub b",
+        size: 1,
+        time: 4,
+        exec: op_0090,
+    }),
+    Some(Inst {
+        code: 0x0091,
+        mnemonic: "sub c",
+        size: 1,
+        time: 4,
+        exec: op_0091,
+    }),
+    Some(Inst {
+        code: 0x0092,
+        mnemonic: "sub d",
+        size: 1,
+        time: 4,
+        exec: op_0092,
+    }),
+    Some(Inst {
+        code: 0x0093,
+        mnemonic: "sub e",
+        size: 1,
+        time: 4,
+        exec: op_0093,
+    }),
+    Some(Inst {
+        code: 0x0094,
+        mnemonic: "sub h",
+        size: 1,
+        time: 4,
+        exec: op_0094,
+    }),
+    Some(Inst {
+        code: 0x0095,
+        mnemonic: "sub l",
+        size: 1,
+        time: 4,
+        exec: op_0095,
+    }),
+    Some(Inst {
+        code: 0x0096,
+        mnemonic: "sub (hl)",
+        size: 1,
+        time: 8,
+        exec: op_0096,
+    }),
+    Some(Inst {
+        code: 0x0097,
+        mnemonic: "sub a",
+        size: 1,
+        time: 4,
+        exec: op_0097,
+    }),
+    Some(Inst {
+        code: 0x0098,
+        mnemonic: "sbc a,b",
+        size: 1,
+        time: 4,
+        exec: op_0098,
+    }),
+    Some(Inst {
+        code: 0x0099,
+        mnemonic: "sbc a,c",
+        size: 1,
+        time: 4,
+        exec: op_0099,
+    }),
+    Some(Inst {
+        code: 0x009a,
+        mnemonic: "sbc a,d",
+        size: 1,
+        time: 4,
+        exec: op_009a,
+    }),
+    Some(Inst {
+        code: 0x009b,
+        mnemonic: "sbc a,e",
+        size: 1,
+        time: 4,
+        exec: op_009b,
+    }),
+    Some(Inst {
+        code: 0x009c,
+        mnemonic: "sbc a,h",
+        size: 1,
+        time: 4,
+        exec: op_009c,
+    }),
+    Some(Inst {
+        code: 0x009d,
+        mnemonic: "sbc a,l",
+        size: 1,
+        time: 4,
+        exec: op_009d,
+    }),
+    Some(Inst {
+        code: 0x009e,
+        mnemonic: "sbc a,(hl)",
+        size: 1,
+        time: 8,
+        exec: op_009e,
+    }),
+    Some(Inst {
+        code: 0x009f,
+        mnemonic: "sbc a,a",
+        size: 1,
+        time: 4,
+        exec: op_009f,
+    }),
+    Some(Inst {
+        code: 0x00a0,
+        mnemonic: "and b",
+        size: 1,
+        time: 4,
+        exec: op_00a0,
+    }),
+    Some(Inst {
+        code: 0x00a1,
+        mnemonic: "and c",
+        size: 1,
+        time: 4,
+        exec: op_00a1,
+    }),
+    Some(Inst {
+        code: 0x00a2,
+        mnemonic: "and d",
+        size: 1,
+        time: 4,
+        exec: op_00a2,
+    }),
+    Some(Inst {
+        code: 0x00a3,
+        mnemonic: "and e",
+        size: 1,
+        time: 4,
+        exec: op_00a3,
+    }),
+    Some(Inst {
+        code: 0x00a4,
+        mnemonic: "and h",
+        size: 1,
+        time: 4,
+        exec: op_00a4,
+    }),
+    Some(Inst {
+        code: 0x00a5,
+        mnemonic: "and l",
+        size: 1,
+        time: 4,
+        exec: op_00a5,
+    }),
+    Some(Inst {
+        code: 0x00a6,
+        mnemonic: "and (hl)",
+        size: 1,
+        time: 8,
+        exec: op_00a6,
+    }),
+    Some(Inst {
+        code: 0x00a7,
+        mnemonic: "and a",
+        size: 1,
+        time: 4,
+        exec: op_00a7,
+    }),
+    Some(Inst {
+        code: 0x00a8,
+        mnemonic: "xor b",
+        size: 1,
+        time: 4,
+        exec: op_00a8,
+    }),
+    Some(Inst {
+        code: 0x00a9,
+        mnemonic: "xor c",
+        size: 1,
+        time: 4,
+        exec: op_00a9,
+    }),
+    Some(Inst {
+        code: 0x00aa,
+        mnemonic: "xor d",
+        size: 1,
+        time: 4,
+        exec: op_00aa,
+    }),
+    Some(Inst {
+        code: 0x00ab,
+        mnemonic: "xor e",
+        size: 1,
+        time: 4,
+        exec: op_00ab,
+    }),
+    Some(Inst {
+        code: 0x00ac,
+        mnemonic: "xor h",
+        size: 1,
+        time: 4,
+        exec: op_00ac,
+    }),
+    Some(Inst {
+        code: 0x00ad,
+        mnemonic: "xor l",
+        size: 1,
+        time: 4,
+        exec: op_00ad,
+    }),
+    Some(Inst {
+        code: 0x00ae,
+        mnemonic: "xor (hl)",
+        size: 1,
+        time: 8,
+        exec: op_00ae,
+    }),
+    Some(Inst {
+        code: 0x00af,
+        mnemonic: "xor a",
+        size: 1,
+        time: 4,
+        exec: op_00af,
+    }),
+    Some(Inst {
+        code: 0x00b0,
+        mnemonic: "or b",
+        size: 1,
+        time: 4,
+        exec: op_00b0,
+    }),
+    Some(Inst {
+        code: 0x00b1,
+        mnemonic: "or c",
+        size: 1,
+        time: 4,
+        exec: op_00b1,
+    }),
+    Some(Inst {
+        code: 0x00b2,
+        mnemonic: "or d",
+        size: 1,
+        time: 4,
+        exec: op_00b2,
+    }),
+    Some(Inst {
+        code: 0x00b3,
+        mnemonic: "or e",
+        size: 1,
+        time: 4,
+        exec: op_00b3,
+    }),
+    Some(Inst {
+        code: 0x00b4,
+        mnemonic: "or h",
+        size: 1,
+        time: 4,
+        exec: op_00b4,
+    }),
+    Some(Inst {
+        code: 0x00b5,
+        mnemonic: "or l",
+        size: 1,
+        time: 4,
+        exec: op_00b5,
+    }),
+    Some(Inst {
+        code: 0x00b6,
+        mnemonic: "or (hl)",
+        size: 1,
+        time: 8,
+        exec: op_00b6,
+    }),
+    Some(Inst {
+        code: 0x00b7,
+        mnemonic: "or a",
+        size: 1,
+        time: 4,
+        exec: op_00b7,
+    }),
+    Some(Inst {
+        code: 0x00b8,
+        mnemonic: "cp b",
+        size: 1,
+        time: 4,
+        exec: op_00b8,
+    }),
+    Some(Inst {
+        code: 0x00b9,
+        mnemonic: "cp c",
+        size: 1,
+        time: 4,
+        exec: op_00b9,
+    }),
+    Some(Inst {
+        code: 0x00ba,
+        mnemonic: "cp d",
+        size: 1,
+        time: 4,
+        exec: op_00ba,
+    }),
+    Some(Inst {
+        code: 0x00bb,
+        mnemonic: "cp e",
+        size: 1,
+        time: 4,
+        exec: op_00bb,
+    }),
+    Some(Inst {
+        code: 0x00bc,
+        mnemonic: "cp h",
+        size: 1,
+        time: 4,
+        exec: op_00bc,
+    }),
+    Some(Inst {
+        code: 0x00bd,
+        mnemonic: "cp l",
+        size: 1,
+        time: 4,
+        exec: op_00bd,
+    }),
+    Some(Inst {
+        code: 0x00be,
+        mnemonic: "cp (hl)",
+        size: 1,
+        time: 8,
+        exec: op_00be,
+    }),
+    Some(Inst {
+        code: 0x00bf,
+        mnemonic: "cp a",
+        size: 1,
+        time: 4,
+        exec: op_00bf,
+    }),
+    Some(Inst {
+        code: 0x00c0,
+        mnemonic: "ret nz",
+        size: 1,
+        time: 8,
+        exec: op_00c0,
+    }),
+    Some(Inst {
+        code: 0x00c1,
+        mnemonic: "pop bc",
+        size: 1,
+        time: 12,
+        exec: op_00c1,
+    }),
+    Some(Inst {
+        code: 0x00c2,
+        mnemonic: "jp nz,a16",
+        size: 3,
+        time: 12,
+        exec: op_00c2,
+    }),
+    Some(Inst {
+        code: 0x00c3,
+        mnemonic: "jp a16",
+        size: 3,
+        time: 16,
+        exec: op_00c3,
+    }),
+    Some(Inst {
+        code: 0x00c4,
+        mnemonic: "call nz,a16",
+        size: 3,
+        time: 12,
+        exec: op_00c4,
+    }),
+    Some(Inst {
+        code: 0x00c5,
+        mnemonic: "push bc",
+        size: 1,
+        time: 16,
+        exec: op_00c5,
+    }),
+    Some(Inst {
+        code: 0x00c6,
+        mnemonic: "add a,d8",
+        size: 2,
+        time: 8,
+        exec: op_00c6,
+    }),
+    Some(Inst {
+        code: 0x00c7,
+        mnemonic: "rst 0x00",
+        size: 1,
+        time: 16,
+        exec: op_00c7,
+    }),
+    Some(Inst {
+        code: 0x00c8,
+        mnemonic: "ret z",
+        size: 1,
+        time: 8,
+        exec: op_00c8,
+    }),
+    Some(Inst {
+        code: 0x00c9,
+        mnemonic: "ret ",
+        size: 1,
+        time: 16,
+        exec: op_00c9,
+    }),
+    Some(Inst {
+        code: 0x00ca,
+        mnemonic: "jp z,a16",
+        size: 3,
+        time: 12,
+        exec: op_00ca,
+    }),
+    Some(Inst {
+        code: 0x00cb,
+        mnemonic: "prefix cb",
+        size: 1,
+        time: 4,
+        exec: op_00cb,
+    }),
+    Some(Inst {
+        code: 0x00cc,
+        mnemonic: "call z,a16",
+        size: 3,
+        time: 12,
+        exec: op_00cc,
+    }),
+    Some(Inst {
+        code: 0x00cd,
+        mnemonic: "call a16",
+        size: 3,
+        time: 24,
+        exec: op_00cd,
+    }),
+    Some(Inst {
+        code: 0x00ce,
+        mnemonic: "adc a,d8",
+        size: 2,
+        time: 8,
+        exec: op_00ce,
+    }),
+    Some(Inst {
+        code: 0x00cf,
+        mnemonic: "rst 0x08",
+        size: 1,
+        time: 16,
+        exec: op_00cf,
+    }),
+    Some(Inst {
+        code: 0x00d0,
+        mnemonic: "ret nc",
+        size: 1,
+        time: 8,
+        exec: op_00d0,
+    }),
+    Some(Inst {
+        code: 0x00d1,
+        mnemonic: "pop de",
+        size: 1,
+        time: 12,
+        exec: op_00d1,
+    }),
+    Some(Inst {
+        code: 0x00d2,
+        mnemonic: "jp nc,a16",
+        size: 3,
+        time: 12,
+        exec: op_00d2,
+    }),
+    None,
+    Some(Inst {
+        code: 0x00d4,
+        mnemonic: "call nc,a16",
+        size: 3,
+        time: 12,
+        exec: op_00d4,
+    }),
+    Some(Inst {
+        code: 0x00d5,
+        mnemonic: "push de",
+        size: 1,
+        time: 16,
+        exec: op_00d5,
+    }),
+    Some(Inst {
+        code: 0x00d6,
+        mnemonic: "sub d8",
+        size: 2,
+        time: 8,
+        exec: op_00d6,
+    }),
+    Some(Inst {
+        code: 0x00d7,
+        mnemonic: "rst 0x10",
+        size: 1,
+        time: 16,
+        exec: op_00d7,
+    }),
+    Some(Inst {
+        code: 0x00d8,
+        mnemonic: "ret cf",
+        size: 1,
+        time: 8,
+        exec: op_00d8,
+    }),
+    Some(Inst {
+        code: 0x00d9,
+        mnemonic: "reti ",
+        size: 1,
+        time: 16,
+        exec: op_00d9,
+    }),
+    Some(Inst {
+        code: 0x00da,
+        mnemonic: "jp cf,a16",
+        size: 3,
+        time: 12,
+        exec: op_00da,
+    }),
+    None,
+    Some(Inst {
+        code: 0x00dc,
+        mnemonic: "call cf,a16",
+        size: 3,
+        time: 12,
+        exec: op_00dc,
+    }),
+    None,
+    Some(Inst {
+        code: 0x00de,
+        mnemonic: "sbc a,d8",
+        size: 2,
+        time: 8,
+        exec: op_00de,
+    }),
+    Some(Inst {
+        code: 0x00df,
+        mnemonic: "rst 0x18",
+        size: 1,
+        time: 16,
+        exec: op_00df,
+    }),
+    Some(Inst {
+        code: 0x00e0,
+        mnemonic: "ld (0xff00+a8),a",
+        size: 2,
+        time: 12,
+        exec: op_00e0,
+    }),
+    Some(Inst {
+        code: 0x00e1,
+        mnemonic: "pop hl",
+        size: 1,
+        time: 12,
+        exec: op_00e1,
+    }),
+    Some(Inst {
+        code: 0x00e2,
+        mnemonic: "ld (0xff00+c),a",
+        size: 1,
+        time: 8,
+        exec: op_00e2,
+    }),
+    None,
+    None,
+    Some(Inst {
+        code: 0x00e5,
+        mnemonic: "push hl",
+        size: 1,
+        time: 16,
+        exec: op_00e5,
+    }),
+    Some(Inst {
+        code: 0x00e6,
+        mnemonic: "and d8",
+        size: 2,
+        time: 8,
+        exec: op_00e6,
+    }),
+    Some(Inst {
+        code: 0x00e7,
+        mnemonic: "rst 0x20",
+        size: 1,
+        time: 16,
+        exec: op_00e7,
+    }),
+    Some(Inst {
+        code: 0x00e8,
+        mnemonic: "add sp,r8",
+        size: 2,
+        time: 16,
+        exec: op_00e8,
+    }),
+    Some(Inst {
+        code: 0x00e9,
+        mnemonic: "jp hl",
+        size: 1,
+        time: 4,
+        exec: op_00e9,
+    }),
+    Some(Inst {
+        code: 0x00ea,
+        mnemonic: "ld (a16),a",
+        size: 3,
+        time: 16,
+        exec: op_00ea,
+    }),
+    None,
+    None,
+    None,
+    Some(Inst {
+        code: 0x00ee,
+        mnemonic: "xor d8",
+        size: 2,
+        time: 8,
+        exec: op_00ee,
+    }),
+    Some(Inst {
+        code: 0x00ef,
+        mnemonic: "rst 0x28",
+        size: 1,
+        time: 16,
+        exec: op_00ef,
+    }),
+    Some(Inst {
+        code: 0x00f0,
+        mnemonic: "ld a,(0xff00+a8)",
+        size: 2,
+        time: 12,
+        exec: op_00f0,
+    }),
+    Some(Inst {
+        code: 0x00f1,
+        mnemonic: "pop af",
+        size: 1,
+        time: 12,
+        exec: op_00f1,
+    }),
+    Some(Inst {
+        code: 0x00f2,
+        mnemonic: "ld a,(0xff00+c)",
+        size: 1,
+        time: 8,
+        exec: op_00f2,
+    }),
+    Some(Inst {
+        code: 0x00f3,
+        mnemonic: "di ",
+        size: 1,
+        time: 4,
+        exec: op_00f3,
+    }),
+    None,
+    Some(Inst {
+        code: 0x00f5,
+        mnemonic: "push af",
+        size: 1,
+        time: 16,
+        exec: op_00f5,
+    }),
+    Some(Inst {
+        code: 0x00f6,
+        mnemonic: "or d8",
+        size: 2,
+        time: 8,
+        exec: op_00f6,
+    }),
+    Some(Inst {
+        code: 0x00f7,
+        mnemonic: "rst 0x30",
+        size: 1,
+        time: 16,
+        exec: op_00f7,
+    }),
+    Some(Inst {
+        code: 0x00f8,
+        mnemonic: "ldhl sp,r8",
+        size: 2,
+        time: 12,
+        exec: op_00f8,
+    }),
+    Some(Inst {
+        code: 0x00f9,
+        mnemonic: "ld sp,hl",
+        size: 1,
+        time: 8,
+        exec: op_00f9,
+    }),
+    Some(Inst {
+        code: 0x00fa,
+        mnemonic: "ld a,(a16)",
+        size: 3,
+        time: 16,
+        exec: op_00fa,
+    }),
+    Some(Inst {
+        code: 0x00fb,
+        mnemonic: "ei ",
+        size: 1,
+        time: 4,
+        exec: op_00fb,
+    }),
+    None,
+    None,
+    Some(Inst {
+        code: 0x00fe,
+        mnemonic: "cp d8",
+        size: 2,
+        time: 8,
+        exec: op_00fe,
+    }),
+    Some(Inst {
+        code: 0x00ff,
+        mnemonic: "rst 0x38",
+        size: 1,
+        time: 16,
+        exec: op_00ff,
+    }),
+];
+
+/// The instructions prefixed by `0xcb` indexed by the second byte of the opcode.
+pub static CB_INSTS: [Option<Inst>; 256] = [
+    Some(Inst {
+        code: 0xcb00,
+        mnemonic: "rlc b",
+        size: 2,
+        time: 8,
+        exec: op_cb00,
+    }),
+    Some(Inst {
+        code: 0xcb01,
+        mnemonic: "rlc c",
+        size: 2,
+        time: 8,
+        exec: op_cb01,
+    }),
+    Some(Inst {
+        code: 0xcb02,
+        mnemonic: "rlc d",
+        size: 2,
+        time: 8,
+        exec: op_cb02,
+    }),
+    Some(Inst {
+        code: 0xcb03,
+        mnemonic: "rlc e",
+        size: 2,
+        time: 8,
+        exec: op_cb03,
+    }),
+    Some(Inst {
+        code: 0xcb04,
+        mnemonic: "rlc h",
+        size: 2,
+        time: 8,
+        exec: op_cb04,
+    }),
+    Some(Inst {
+        code: 0xcb05,
+        mnemonic: "rlc l",
+        size: 2,
+        time: 8,
+        exec: op_cb05,
+    }),
+    Some(Inst {
+        code: 0xcb06,
+        mnemonic: "rlc (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb06,
+    }),
+    Some(Inst {
+        code: 0xcb07,
+        mnemonic: "rlc a",
+        size: 2,
+        time: 8,
+        exec: op_cb07,
+    }),
+    Some(Inst {
+        code: 0xcb08,
+        mnemonic: "rrc b",
+        size: 2,
+        time: 8,
+        exec: op_cb08,
+    }),
+    Some(Inst {
+        code: 0xcb09,
+        mnemonic: "rrc c",
+        size: 2,
+        time: 8,
+        exec: op_cb09,
+    }),
+    Some(Inst {
+        code: 0xcb0a,
+        mnemonic: "rrc d",
+        size: 2,
+        time: 8,
+        exec: op_cb0a,
+    }),
+    Some(Inst {
+        code: 0xcb0b,
+        mnemonic: "rrc e",
+        size: 2,
+        time: 8,
+        exec: op_cb0b,
+    }),
+    Some(Inst {
+        code: 0xcb0c,
+        mnemonic: "rrc h",
+        size: 2,
+        time: 8,
+        exec: op_cb0c,
+    }),
+    Some(Inst {
+        code: 0xcb0d,
+        mnemonic: "rrc l",
+        size: 2,
+        time: 8,
+        exec: op_cb0d,
+    }),
+    Some(Inst {
+        code: 0xcb0e,
+        mnemonic: "rrc (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb0e,
+    }),
+    Some(Inst {
+        code: 0xcb0f,
+        mnemonic: "rrc a",
+        size: 2,
+        time: 8,
+        exec: op_cb0f,
+    }),
+    Some(Inst {
+        code: 0xcb10,
+        mnemonic: "rl b",
+        size: 2,
+        time: 8,
+        exec: op_cb10,
+    }),
+    Some(Inst {
+        code: 0xcb11,
+        mnemonic: "rl c",
+        size: 2,
+        time: 8,
+        exec: op_cb11,
+    }),
+    Some(Inst {
+        code: 0xcb12,
+        mnemonic: "rl d",
+        size: 2,
+        time: 8,
+        exec: op_cb12,
+    }),
+    Some(Inst {
+        code: 0xcb13,
+        mnemonic: "rl e",
+        size: 2,
+        time: 8,
+        exec: op_cb13,
+    }),
+    Some(Inst {
+        code: 0xcb14,
+        mnemonic: "rl h",
+        size: 2,
+        time: 8,
+        exec: op_cb14,
+    }),
+    Some(Inst {
+        code: 0xcb15,
+        mnemonic: "rl l",
+        size: 2,
+        time: 8,
+        exec: op_cb15,
+    }),
+    Some(Inst {
+        code: 0xcb16,
+        mnemonic: "rl (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb16,
+    }),
+    Some(Inst {
+        code: 0xcb17,
+        mnemonic: "rl a",
+        size: 2,
+        time: 8,
+        exec: op_cb17,
+    }),
+    Some(Inst {
+        code: 0xcb18,
+        mnemonic: "rr b",
+        size: 2,
+        time: 8,
+        exec: op_cb18,
+    }),
+    Some(Inst {
+        code: 0xcb19,
+        mnemonic: "rr c",
+        size: 2,
+        time: 8,
+        exec: op_cb19,
+    }),
+    Some(Inst {
+        code: 0xcb1a,
+        mnemonic: "rr d",
+        size: 2,
+        time: 8,
+        exec: op_cb1a,
+    }),
+    Some(Inst {
+        code: 0xcb1b,
+        mnemonic: "rr e",
+        size: 2,
+        time: 8,
+        exec: op_cb1b,
+    }),
+    Some(Inst {
+        code: 0xcb1c,
+        mnemonic: "rr h",
+        size: 2,
+        time: 8,
+        exec: op_cb1c,
+    }),
+    Some(Inst {
+        code: 0xcb1d,
+        mnemonic: "rr l",
+        size: 2,
+        time: 8,
+        exec: op_cb1d,
+    }),
+    Some(Inst {
+        code: 0xcb1e,
+        mnemonic: "rr (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb1e,
+    }),
+    Some(Inst {
+        code: 0xcb1f,
+        mnemonic: "rr a",
+        size: 2,
+        time: 8,
+        exec: op_cb1f,
+    }),
+    Some(Inst {
+        code: 0xcb20,
+        mnemonic: "sla b",
+        size: 2,
+        time: 8,
+        exec: op_cb20,
+    }),
+    Some(Inst {
+        code: 0xcb21,
+        mnemonic: "sla c",
+        size: 2,
+        time: 8,
+        exec: op_cb21,
+    }),
+    Some(Inst {
+        code: 0xcb22,
+        mnemonic: "sla d",
+        size: 2,
+        time: 8,
+        exec: op_cb22,
+    }),
+    Some(Inst {
+        code: 0xcb23,
+        mnemonic: "sla e",
+        size: 2,
+        time: 8,
+        exec: op_cb23,
+    }),
+    Some(Inst {
+        code: 0xcb24,
+        mnemonic: "sla h",
+        size: 2,
+        time: 8,
+        exec: op_cb24,
+    }),
+    Some(Inst {
+        code: 0xcb25,
+        mnemonic: "sla l",
+        size: 2,
+        time: 8,
+        exec: op_cb25,
+    }),
+    Some(Inst {
+        code: 0xcb26,
+        mnemonic: "sla (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb26,
+    }),
+    Some(Inst {
+        code: 0xcb27,
+        mnemonic: "sla a",
+        size: 2,
+        time: 8,
+        exec: op_cb27,
+    }),
+    Some(Inst {
+        code: 0xcb28,
+        mnemonic: "sra b",
+        size: 2,
+        time: 8,
+        exec: op_cb28,
+    }),
+    Some(Inst {
+        code: 0xcb29,
+        mnemonic: "sra c",
+        size: 2,
+        time: 8,
+        exec: op_cb29,
+    }),
+    Some(Inst {
+        code: 0xcb2a,
+        mnemonic: "sra d",
+        size: 2,
+        time: 8,
+        exec: op_cb2a,
+    }),
+    Some(Inst {
+        code: 0xcb2b,
+        mnemonic: "sra e",
+        size: 2,
+        time: 8,
+        exec: op_cb2b,
+    }),
+    Some(Inst {
+        code: 0xcb2c,
+        mnemonic: "sra h",
+        size: 2,
+        time: 8,
+        exec: op_cb2c,
+    }),
+    Some(Inst {
+        code: 0xcb2d,
+        mnemonic: "sra l",
+        size: 2,
+        time: 8,
+        exec: op_cb2d,
+    }),
+    Some(Inst {
+        code: 0xcb2e,
+        mnemonic: "sra (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb2e,
+    }),
+    Some(Inst {
+        code: 0xcb2f,
+        mnemonic: "sra a",
+        size: 2,
+        time: 8,
+        exec: op_cb2f,
+    }),
+    Some(Inst {
+        code: 0xcb30,
+        mnemonic: "swap b",
+        size: 2,
+        time: 8,
+        exec: op_cb30,
+    }),
+    Some(Inst {
+        code: 0xcb31,
+        mnemonic: "swap c",
+        size: 2,
+        time: 8,
+        exec: op_cb31,
+    }),
+    Some(Inst {
+        code: 0xcb32,
+        mnemonic: "swap d",
+        size: 2,
+        time: 8,
+        exec: op_cb32,
+    }),
+    Some(Inst {
+        code: 0xcb33,
+        mnemonic: "swap e",
+        size: 2,
+        time: 8,
+        exec: op_cb33,
+    }),
+    Some(Inst {
+        code: 0xcb34,
+        mnemonic: "swap h",
+        size: 2,
+        time: 8,
+        exec: op_cb34,
+    }),
+    Some(Inst {
+        code: 0xcb35,
+        mnemonic: "swap l",
+        size: 2,
+        time: 8,
+        exec: op_cb35,
+    }),
+    Some(Inst {
+        code: 0xcb36,
+        mnemonic: "swap (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb36,
+    }),
+    Some(Inst {
+        code: 0xcb37,
+        mnemonic: "swap a",
+        size: 2,
+        time: 8,
+        exec: op_cb37,
+    }),
+    Some(Inst {
+        code: 0xcb38,
+        mnemonic: "srl b",
+        size: 2,
+        time: 8,
+        exec: op_cb38,
+    }),
+    Some(Inst {
+        code: 0xcb39,
+        mnemonic: "srl c",
+        size: 2,
+        time: 8,
+        exec: op_cb39,
+    }),
+    Some(Inst {
+        code: 0xcb3a,
+        mnemonic: "srl d",
+        size: 2,
+        time: 8,
+        exec: op_cb3a,
+    }),
+    Some(Inst {
+        code: 0xcb3b,
+        mnemonic: "srl e",
+        size: 2,
+        time: 8,
+        exec: op_cb3b,
+    }),
+    Some(Inst {
+        code: 0xcb3c,
+        mnemonic: "srl h",
+        size: 2,
+        time: 8,
+        exec: op_cb3c,
+    }),
+    Some(Inst {
+        code: 0xcb3d,
+        mnemonic: "srl l",
+        size: 2,
+        time: 8,
+        exec: op_cb3d,
+    }),
+    Some(Inst {
+        code: 0xcb3e,
+        mnemonic: "srl (hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb3e,
+    }),
+    Some(Inst {
+        code: 0xcb3f,
+        mnemonic: "srl a",
+        size: 2,
+        time: 8,
+        exec: op_cb3f,
+    }),
+    Some(Inst {
+        code: 0xcb40,
+        mnemonic: "bit 0,b",
+        size: 2,
+        time: 8,
+        exec: op_cb40,
+    }),
+    Some(Inst {
+        code: 0xcb41,
+        mnemonic: "bit 0,c",
+        size: 2,
+        time: 8,
+        exec: op_cb41,
+    }),
+    Some(Inst {
+        code: 0xcb42,
+        mnemonic: "bit 0,d",
+        size: 2,
+        time: 8,
+        exec: op_cb42,
+    }),
+    Some(Inst {
+        code: 0xcb43,
+        mnemonic: "bit 0,e",
+        size: 2,
+        time: 8,
+        exec: op_cb43,
+    }),
+    Some(Inst {
+        code: 0xcb44,
+        mnemonic: "bit 0,h",
+        size: 2,
+        time: 8,
+        exec: op_cb44,
+    }),
+    Some(Inst {
+        code: 0xcb45,
+        mnemonic: "bit 0,l",
+        size: 2,
+        time: 8,
+        exec: op_cb45,
+    }),
+    Some(Inst {
+        code: 0xcb46,
+        mnemonic: "bit 0,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb46,
+    }),
+    Some(Inst {
+        code: 0xcb47,
+        mnemonic: "bit 0,a",
+        size: 2,
+        time: 8,
+        exec: op_cb47,
+    }),
+    Some(Inst {
+        code: 0xcb48,
+        mnemonic: "bit 1,b",
+        size: 2,
+        time: 8,
+        exec: op_cb48,
+    }),
+    Some(Inst {
+        code: 0xcb49,
+        mnemonic: "bit 1,c",
+        size: 2,
+        time: 8,
+        exec: op_cb49,
+    }),
+    Some(Inst {
+        code: 0xcb4a,
+        mnemonic: "bit 1,d",
+        size: 2,
+        time: 8,
+        exec: op_cb4a,
+    }),
+    Some(Inst {
+        code: 0xcb4b,
+        mnemonic: "bit 1,e",
+        size: 2,
+        time: 8,
+        exec: op_cb4b,
+    }),
+    Some(Inst {
+        code: 0xcb4c,
+        mnemonic: "bit 1,h",
+        size: 2,
+        time: 8,
+        exec: op_cb4c,
+    }),
+    Some(Inst {
+        code: 0xcb4d,
+        mnemonic: "bit 1,l",
+        size: 2,
+        time: 8,
+        exec: op_cb4d,
+    }),
+    Some(Inst {
+        code: 0xcb4e,
+        mnemonic: "bit 1,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb4e,
+    }),
+    Some(Inst {
+        code: 0xcb4f,
+        mnemonic: "bit 1,a",
+        size: 2,
+        time: 8,
+        exec: op_cb4f,
+    }),
+    Some(Inst {
+        code: 0xcb50,
+        mnemonic: "bit 2,b",
+        size: 2,
+        time: 8,
+        exec: op_cb50,
+    }),
+    Some(Inst {
+        code: 0xcb51,
+        mnemonic: "bit 2,c",
+        size: 2,
+        time: 8,
+        exec: op_cb51,
+    }),
+    Some(Inst {
+        code: 0xcb52,
+        mnemonic: "bit 2,d",
+        size: 2,
+        time: 8,
+        exec: op_cb52,
+    }),
+    Some(Inst {
+        code: 0xcb53,
+        mnemonic: "bit 2,e",
+        size: 2,
+        time: 8,
+        exec: op_cb53,
+    }),
+    Some(Inst {
+        code: 0xcb54,
+        mnemonic: "bit 2,h",
+        size: 2,
+        time: 8,
+        exec: op_cb54,
+    }),
+    Some(Inst {
+        code: 0xcb55,
+        mnemonic: "bit 2,l",
+        size: 2,
+        time: 8,
+        exec: op_cb55,
+    }),
+    Some(Inst {
+        code: 0xcb56,
+        mnemonic: "bit 2,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb56,
+    }),
+    Some(Inst {
+        code: 0xcb57,
+        mnemonic: "bit 2,a",
+        size: 2,
+        time: 8,
+        exec: op_cb57,
+    }),
+    Some(Inst {
+        code: 0xcb58,
+        mnemonic: "bit 3,b",
+        size: 2,
+        time: 8,
+        exec: op_cb58,
+    }),
+    Some(Inst {
+        code: 0xcb59,
+        mnemonic: "bit 3,c",
+        size: 2,
+        time: 8,
+        exec: op_cb59,
+    }),
+    Some(Inst {
+        code: 0xcb5a,
+        mnemonic: "bit 3,d",
+        size: 2,
+        time: 8,
+        exec: op_cb5a,
+    }),
+    Some(Inst {
+        code: 0xcb5b,
+        mnemonic: "bit 3,e",
+        size: 2,
+        time: 8,
+        exec: op_cb5b,
+    }),
+    Some(Inst {
+        code: 0xcb5c,
+        mnemonic: "bit 3,h",
+        size: 2,
+        time: 8,
+        exec: op_cb5c,
+    }),
+    Some(Inst {
+        code: 0xcb5d,
+        mnemonic: "bit 3,l",
+        size: 2,
+        time: 8,
+        exec: op_cb5d,
+    }),
+    Some(Inst {
+        code: 0xcb5e,
+        mnemonic: "bit 3,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb5e,
+    }),
+    Some(Inst {
+        code: 0xcb5f,
+        mnemonic: "bit 3,a",
+        size: 2,
+        time: 8,
+        exec: op_cb5f,
+    }),
+    Some(Inst {
+        code: 0xcb60,
+        mnemonic: "bit 4,b",
+        size: 2,
+        time: 8,
+        exec: op_cb60,
+    }),
+    Some(Inst {
+        code: 0xcb61,
+        mnemonic: "bit 4,c",
+        size: 2,
+        time: 8,
+        exec: op_cb61,
+    }),
+    Some(Inst {
+        code: 0xcb62,
+        mnemonic: "bit 4,d",
+        size: 2,
+        time: 8,
+        exec: op_cb62,
+    }),
+    Some(Inst {
+        code: 0xcb63,
+        mnemonic: "bit 4,e",
+        size: 2,
+        time: 8,
+        exec: op_cb63,
+    }),
+    Some(Inst {
+        code: 0xcb64,
+        mnemonic: "bit 4,h",
+        size: 2,
+        time: 8,
+        exec: op_cb64,
+    }),
+    Some(Inst {
+        code: 0xcb65,
+        mnemonic: "bit 4,l",
+        size: 2,
+        time: 8,
+        exec: op_cb65,
+    }),
+    Some(Inst {
+        code: 0xcb66,
+        mnemonic: "bit 4,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb66,
+    }),
+    Some(Inst {
+        code: 0xcb67,
+        mnemonic: "bit 4,a",
+        size: 2,
+        time: 8,
+        exec: op_cb67,
+    }),
+    Some(Inst {
+        code: 0xcb68,
+        mnemonic: "bit 5,b",
+        size: 2,
+        time: 8,
+        exec: op_cb68,
+    }),
+    Some(Inst {
+        code: 0xcb69,
+        mnemonic: "bit 5,c",
+        size: 2,
+        time: 8,
+        exec: op_cb69,
+    }),
+    Some(Inst {
+        code: 0xcb6a,
+        mnemonic: "bit 5,d",
+        size: 2,
+        time: 8,
+        exec: op_cb6a,
+    }),
+    Some(Inst {
+        code: 0xcb6b,
+        mnemonic: "bit 5,e",
+        size: 2,
+        time: 8,
+        exec: op_cb6b,
+    }),
+    Some(Inst {
+        code: 0xcb6c,
+        mnemonic: "bit 5,h",
+        size: 2,
+        time: 8,
+        exec: op_cb6c,
+    }),
+    Some(Inst {
+        code: 0xcb6d,
+        mnemonic: "bit 5,l",
+        size: 2,
+        time: 8,
+        exec: op_cb6d,
+    }),
+    Some(Inst {
+        code: 0xcb6e,
+        mnemonic: "bit 5,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb6e,
+    }),
+    Some(Inst {
+        code: 0xcb6f,
+        mnemonic: "bit 5,a",
+        size: 2,
+        time: 8,
+        exec: op_cb6f,
+    }),
+    Some(Inst {
+        code: 0xcb70,
+        mnemonic: "bit 6,b",
+        size: 2,
+        time: 8,
+        exec: op_cb70,
+    }),
+    Some(Inst {
+        code: 0xcb71,
+        mnemonic: "bit 6,c",
+        size: 2,
+        time: 8,
+        exec: op_cb71,
+    }),
+    Some(Inst {
+        code: 0xcb72,
+        mnemonic: "bit 6,d",
+        size: 2,
+        time: 8,
+        exec: op_cb72,
+    }),
+    Some(Inst {
+        code: 0xcb73,
+        mnemonic: "bit 6,e",
+        size: 2,
+        time: 8,
+        exec: op_cb73,
+    }),
+    Some(Inst {
+        code: 0xcb74,
+        mnemonic: "bit 6,h",
+        size: 2,
+        time: 8,
+        exec: op_cb74,
+    }),
+    Some(Inst {
+        code: 0xcb75,
+        mnemonic: "bit 6,l",
+        size: 2,
+        time: 8,
+        exec: op_cb75,
+    }),
+    Some(Inst {
+        code: 0xcb76,
+        mnemonic: "bit 6,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb76,
+    }),
+    Some(Inst {
+        code: 0xcb77,
+        mnemonic: "bit 6,a",
+        size: 2,
+        time: 8,
+        exec: op_cb77,
+    }),
+    Some(Inst {
+        code: 0xcb78,
+        mnemonic: "bit 7,b",
+        size: 2,
+        time: 8,
+        exec: op_cb78,
+    }),
+    Some(Inst {
+        code: 0xcb79,
+        mnemonic: "bit 7,c",
+        size: 2,
+        time: 8,
+        exec: op_cb79,
+    }),
+    Some(Inst {
+        code: 0xcb7a,
+        mnemonic: "bit 7,d",
+        size: 2,
+        time: 8,
+        exec: op_cb7a,
+    }),
+    Some(Inst {
+        code: 0xcb7b,
+        mnemonic: "bit 7,e",
+        size: 2,
+        time: 8,
+        exec: op_cb7b,
+    }),
+    Some(Inst {
+        code: 0xcb7c,
+        mnemonic: "bit 7,h",
+        size: 2,
+        time: 8,
+        exec: op_cb7c,
+    }),
+    Some(Inst {
+        code: 0xcb7d,
+        mnemonic: "bit 7,l",
+        size: 2,
+        time: 8,
+        exec: op_cb7d,
+    }),
+    Some(Inst {
+        code: 0xcb7e,
+        mnemonic: "bit 7,(hl)",
+        size: 2,
+        time: 12,
+        exec: op_cb7e,
+    }),
+    Some(Inst {
+        code: 0xcb7f,
+        mnemonic: "bit 7,a",
+        size: 2,
+        time: 8,
+        exec: op_cb7f,
+    }),
+    Some(Inst {
+        code: 0xcb80,
+        mnemonic: "res 0,b",
+        size: 2,
+        time: 8,
+        exec: op_cb80,
+    }),
+    Some(Inst {
+        code: 0xcb81,
+        mnemonic: "res 0,c",
+        size: 2,
+        time: 8,
+        exec: op_cb81,
+    }),
+    Some(Inst {
+        code: 0xcb82,
+        mnemonic: "res 0,d",
+        size: 2,
+        time: 8,
+        exec: op_cb82,
+    }),
+    Some(Inst {
+        code: 0xcb83,
+        mnemonic: "res 0,e",
+        size: 2,
+        time: 8,
+        exec: op_cb83,
+    }),
+    Some(Inst {
+        code: 0xcb84,
+        mnemonic: "res 0,h",
+        size: 2,
+        time: 8,
+        exec: op_cb84,
+    }),
+    Some(Inst {
+        code: 0xcb85,
+        mnemonic: "res 0,l",
+        size: 2,
+        time: 8,
+        exec: op_cb85,
+    }),
+    Some(Inst {
+        code: 0xcb86,
+        mnemonic: "res 0,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb86,
+    }),
+    Some(Inst {
+        code: 0xcb87,
+        mnemonic: "res 0,a",
+        size: 2,
+        time: 8,
+        exec: op_cb87,
+    }),
+    Some(Inst {
+        code: 0xcb88,
+        mnemonic: "res 1,b",
+        size: 2,
+        time: 8,
+        exec: op_cb88,
+    }),
+    Some(Inst {
+        code: 0xcb89,
+        mnemonic: "res 1,c",
+        size: 2,
+        time: 8,
+        exec: op_cb89,
+    }),
+    Some(Inst {
+        code: 0xcb8a,
+        mnemonic: "res 1,d",
+        size: 2,
+        time: 8,
+        exec: op_cb8a,
+    }),
+    Some(Inst {
+        code: 0xcb8b,
+        mnemonic: "res 1,e",
+        size: 2,
+        time: 8,
+        exec: op_cb8b,
+    }),
+    Some(Inst {
+        code: 0xcb8c,
+        mnemonic: "res 1,h",
+        size: 2,
+        time: 8,
+        exec: op_cb8c,
+    }),
+    Some(Inst {
+        code: 0xcb8d,
+        mnemonic: "res 1,l",
+        size: 2,
+        time: 8,
+        exec: op_cb8d,
+    }),
+    Some(Inst {
+        code: 0xcb8e,
+        mnemonic: "res 1,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb8e,
+    }),
+    Some(Inst {
+        code: 0xcb8f,
+        mnemonic: "res 1,a",
+        size: 2,
+        time: 8,
+        exec: op_cb8f,
+    }),
+    Some(Inst {
+        code: 0xcb90,
+        mnemonic: "res 2,b",
+        size: 2,
+        time: 8,
+        exec: op_cb90,
+    }),
+    Some(Inst {
+        code: 0xcb91,
+        mnemonic: "res 2,c",
+        size: 2,
+        time: 8,
+        exec: op_cb91,
+    }),
+    Some(Inst {
+        code: 0xcb92,
+        mnemonic: "res 2,d",
+        size: 2,
+        time: 8,
+        exec: op_cb92,
+    }),
+    Some(Inst {
+        code: 0xcb93,
+        mnemonic: "res 2,e",
+        size: 2,
+        time: 8,
+        exec: op_cb93,
+    }),
+    Some(Inst {
+        code: 0xcb94,
+        mnemonic: "res 2,h",
+        size: 2,
+        time: 8,
+        exec: op_cb94,
+    }),
+    Some(Inst {
+        code: 0xcb95,
+        mnemonic: "res 2,l",
+        size: 2,
+        time: 8,
+        exec: op_cb95,
+    }),
+    Some(Inst {
+        code: 0xcb96,
+        mnemonic: "res 2,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb96,
+    }),
+    Some(Inst {
+        code: 0xcb97,
+        mnemonic: "res 2,a",
+        size: 2,
+        time: 8,
+        exec: op_cb97,
+    }),
+    Some(Inst {
+        code: 0xcb98,
+        mnemonic: "res 3,b",
+        size: 2,
+        time: 8,
+        exec: op_cb98,
+    }),
+    Some(Inst {
+        code: 0xcb99,
+        mnemonic: "res 3,c",
+        size: 2,
+        time: 8,
+        exec: op_cb99,
+    }),
+    Some(Inst {
+        code: 0xcb9a,
+        mnemonic: "res 3,d",
+        size: 2,
+        time: 8,
+        exec: op_cb9a,
+    }),
+    Some(Inst {
+        code: 0xcb9b,
+        mnemonic: "res 3,e",
+        size: 2,
+        time: 8,
+        exec: op_cb9b,
+    }),
+    Some(Inst {
+        code: 0xcb9c,
+        mnemonic: "res 3,h",
+        size: 2,
+        time: 8,
+        exec: op_cb9c,
+    }),
+    Some(Inst {
+        code: 0xcb9d,
+        mnemonic: "res 3,l",
+        size: 2,
+        time: 8,
+        exec: op_cb9d,
+    }),
+    Some(Inst {
+        code: 0xcb9e,
+        mnemonic: "res 3,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cb9e,
+    }),
+    Some(Inst {
+        code: 0xcb9f,
+        mnemonic: "res 3,a",
+        size: 2,
+        time: 8,
+        exec: op_cb9f,
+    }),
+    Some(Inst {
+        code: 0xcba0,
+        mnemonic: "res 4,b",
+        size: 2,
+        time: 8,
+        exec: op_cba0,
+    }),
+    Some(Inst {
+        code: 0xcba1,
+        mnemonic: "res 4,c",
+        size: 2,
+        time: 8,
+        exec: op_cba1,
+    }),
+    Some(Inst {
+        code: 0xcba2,
+        mnemonic: "res 4,d",
+        size: 2,
+        time: 8,
+        exec: op_cba2,
+    }),
+    Some(Inst {
+        code: 0xcba3,
+        mnemonic: "res 4,e",
+        size: 2,
+        time: 8,
+        exec: op_cba3,
+    }),
+    Some(Inst {
+        code: 0xcba4,
+        mnemonic: "res 4,h",
+        size: 2,
+        time: 8,
+        exec: op_cba4,
+    }),
+    Some(Inst {
+        code: 0xcba5,
+        mnemonic: "res 4,l",
+        size: 2,
+        time: 8,
+        exec: op_cba5,
+    }),
+    Some(Inst {
+        code: 0xcba6,
+        mnemonic: "res 4,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cba6,
+    }),
+    Some(Inst {
+        code: 0xcba7,
+        mnemonic: "res 4,a",
+        size: 2,
+        time: 8,
+        exec: op_cba7,
+    }),
+    Some(Inst {
+        code: 0xcba8,
+        mnemonic: "res 5,b",
+        size: 2,
+        time: 8,
+        exec: op_cba8,
+    }),
+    Some(Inst {
+        code: 0xcba9,
+        mnemonic: "res 5,c",
+        size: 2,
+        time: 8,
+        exec: op_cba9,
+    }),
+    Some(Inst {
+        code: 0xcbaa,
+        mnemonic: "res 5,d",
+        size: 2,
+        time: 8,
+        exec: op_cbaa,
+    }),
+    Some(Inst {
+        code: 0xcbab,
+        mnemonic: "res 5,e",
+        size: 2,
+        time: 8,
+        exec: op_cbab,
+    }),
+    Some(Inst {
+        code: 0xcbac,
+        mnemonic: "res 5,h",
+        size: 2,
+        time: 8,
+        exec: op_cbac,
+    }),
+    Some(Inst {
+        code: 0xcbad,
+        mnemonic: "res 5,l",
+        size: 2,
+        time: 8,
+        exec: op_cbad,
+    }),
+    Some(Inst {
+        code: 0xcbae,
+        mnemonic: "res 5,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbae,
+    }),
+    Some(Inst {
+        code: 0xcbaf,
+        mnemonic: "res 5,a",
+        size: 2,
+        time: 8,
+        exec: op_cbaf,
+    }),
+    Some(Inst {
+        code: 0xcbb0,
+        mnemonic: "res 6,b",
+        size: 2,
+        time: 8,
+        exec: op_cbb0,
+    }),
+    Some(Inst {
+        code: 0xcbb1,
+        mnemonic: "res 6,c",
+        size: 2,
+        time: 8,
+        exec: op_cbb1,
+    }),
+    Some(Inst {
+        code: 0xcbb2,
+        mnemonic: "res 6,d",
+        size: 2,
+        time: 8,
+        exec: op_cbb2,
+    }),
+    Some(Inst {
+        code: 0xcbb3,
+        mnemonic: "res 6,e",
+        size: 2,
+        time: 8,
+        exec: op_cbb3,
+    }),
+    Some(Inst {
+        code: 0xcbb4,
+        mnemonic: "res 6,h",
+        size: 2,
+        time: 8,
+        exec: op_cbb4,
+    }),
+    Some(Inst {
+        code: 0xcbb5,
+        mnemonic: "res 6,l",
+        size: 2,
+        time: 8,
+        exec: op_cbb5,
+    }),
+    Some(Inst {
+        code: 0xcbb6,
+        mnemonic: "res 6,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbb6,
+    }),
+    Some(Inst {
+        code: 0xcbb7,
+        mnemonic: "res 6,a",
+        size: 2,
+        time: 8,
+        exec: op_cbb7,
+    }),
+    Some(Inst {
+        code: 0xcbb8,
+        mnemonic: "res 7,b",
+        size: 2,
+        time: 8,
+        exec: op_cbb8,
+    }),
+    Some(Inst {
+        code: 0xcbb9,
+        mnemonic: "res 7,c",
+        size: 2,
+        time: 8,
+        exec: op_cbb9,
+    }),
+    Some(Inst {
+        code: 0xcbba,
+        mnemonic: "res 7,d",
+        size: 2,
+        time: 8,
+        exec: op_cbba,
+    }),
+    Some(Inst {
+        code: 0xcbbb,
+        mnemonic: "res 7,e",
+        size: 2,
+        time: 8,
+        exec: op_cbbb,
+    }),
+    Some(Inst {
+        code: 0xcbbc,
+        mnemonic: "res 7,h",
+        size: 2,
+        time: 8,
+        exec: op_cbbc,
+    }),
+    Some(Inst {
+        code: 0xcbbd,
+        mnemonic: "res 7,l",
+        size: 2,
+        time: 8,
+        exec: op_cbbd,
+    }),
+    Some(Inst {
+        code: 0xcbbe,
+        mnemonic: "res 7,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbbe,
+    }),
+    Some(Inst {
+        code: 0xcbbf,
+        mnemonic: "res 7,a",
+        size: 2,
+        time: 8,
+        exec: op_cbbf,
+    }),
+    Some(Inst {
+        code: 0xcbc0,
+        mnemonic: "set 0,b",
+        size: 2,
+        time: 8,
+        exec: op_cbc0,
+    }),
+    Some(Inst {
+        code: 0xcbc1,
+        mnemonic: "set 0,c",
+        size: 2,
+        time: 8,
+        exec: op_cbc1,
+    }),
+    Some(Inst {
+        code: 0xcbc2,
+        mnemonic: "set 0,d",
+        size: 2,
+        time: 8,
+        exec: op_cbc2,
+    }),
+    Some(Inst {
+        code: 0xcbc3,
+        mnemonic: "set 0,e",
+        size: 2,
+        time: 8,
+        exec: op_cbc3,
+    }),
+    Some(Inst {
+        code: 0xcbc4,
+        mnemonic: "set 0,h",
+        size: 2,
+        time: 8,
+        exec: op_cbc4,
+    }),
+    Some(Inst {
+        code: 0xcbc5,
+        mnemonic: "set 0,l",
+        size: 2,
+        time: 8,
+        exec: op_cbc5,
+    }),
+    Some(Inst {
+        code: 0xcbc6,
+        mnemonic: "set 0,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbc6,
+    }),
+    Some(Inst {
+        code: 0xcbc7,
+        mnemonic: "set 0,a",
+        size: 2,
+        time: 8,
+        exec: op_cbc7,
+    }),
+    Some(Inst {
+        code: 0xcbc8,
+        mnemonic: "set 1,b",
+        size: 2,
+        time: 8,
+        exec: op_cbc8,
+    }),
+    Some(Inst {
+        code: 0xcbc9,
+        mnemonic: "set 1,c",
+        size: 2,
+        time: 8,
+        exec: op_cbc9,
+    }),
+    Some(Inst {
+        code: 0xcbca,
+        mnemonic: "set 1,d",
+        size: 2,
+        time: 8,
+        exec: op_cbca,
+    }),
+    Some(Inst {
+        code: 0xcbcb,
+        mnemonic: "set 1,e",
+        size: 2,
+        time: 8,
+        exec: op_cbcb,
+    }),
+    Some(Inst {
+        code: 0xcbcc,
+        mnemonic: "set 1,h",
+        size: 2,
+        time: 8,
+        exec: op_cbcc,
+    }),
+    Some(Inst {
+        code: 0xcbcd,
+        mnemonic: "set 1,l",
+        size: 2,
+        time: 8,
+        exec: op_cbcd,
+    }),
+    Some(Inst {
+        code: 0xcbce,
+        mnemonic: "set 1,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbce,
+    }),
+    Some(Inst {
+        code: 0xcbcf,
+        mnemonic: "set 1,a",
+        size: 2,
+        time: 8,
+        exec: op_cbcf,
+    }),
+    Some(Inst {
+        code: 0xcbd0,
+        mnemonic: "set 2,b",
+        size: 2,
+        time: 8,
+        exec: op_cbd0,
+    }),
+    Some(Inst {
+        code: 0xcbd1,
+        mnemonic: "set 2,c",
+        size: 2,
+        time: 8,
+        exec: op_cbd1,
+    }),
+    Some(Inst {
+        code: 0xcbd2,
+        mnemonic: "set 2,d",
+        size: 2,
+        time: 8,
+        exec: op_cbd2,
+    }),
+    Some(Inst {
+        code: 0xcbd3,
+        mnemonic: "set 2,e",
+        size: 2,
+        time: 8,
+        exec: op_cbd3,
+    }),
+    Some(Inst {
+        code: 0xcbd4,
+        mnemonic: "set 2,h",
+        size: 2,
+        time: 8,
+        exec: op_cbd4,
+    }),
+    Some(Inst {
+        code: 0xcbd5,
+        mnemonic: "set 2,l",
+        size: 2,
+        time: 8,
+        exec: op_cbd5,
+    }),
+    Some(Inst {
+        code: 0xcbd6,
+        mnemonic: "set 2,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbd6,
+    }),
+    Some(Inst {
+        code: 0xcbd7,
+        mnemonic: "set 2,a",
+        size: 2,
+        time: 8,
+        exec: op_cbd7,
+    }),
+    Some(Inst {
+        code: 0xcbd8,
+        mnemonic: "set 3,b",
+        size: 2,
+        time: 8,
+        exec: op_cbd8,
+    }),
+    Some(Inst {
+        code: 0xcbd9,
+        mnemonic: "set 3,c",
+        size: 2,
+        time: 8,
+        exec: op_cbd9,
+    }),
+    Some(Inst {
+        code: 0xcbda,
+        mnemonic: "set 3,d",
+        size: 2,
+        time: 8,
+        exec: op_cbda,
+    }),
+    Some(Inst {
+        code: 0xcbdb,
+        mnemonic: "set 3,e",
+        size: 2,
+        time: 8,
+        exec: op_cbdb,
+    }),
+    Some(Inst {
+        code: 0xcbdc,
+        mnemonic: "set 3,h",
+        size: 2,
+        time: 8,
+        exec: op_cbdc,
+    }),
+    Some(Inst {
+        code: 0xcbdd,
+        mnemonic: "set 3,l",
+        size: 2,
+        time: 8,
+        exec: op_cbdd,
+    }),
+    Some(Inst {
+        code: 0xcbde,
+        mnemonic: "set 3,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbde,
+    }),
+    Some(Inst {
+        code: 0xcbdf,
+        mnemonic: "set 3,a",
+        size: 2,
+        time: 8,
+        exec: op_cbdf,
+    }),
+    Some(Inst {
+        code: 0xcbe0,
+        mnemonic: "set 4,b",
+        size: 2,
+        time: 8,
+        exec: op_cbe0,
+    }),
+    Some(Inst {
+        code: 0xcbe1,
+        mnemonic: "set 4,c",
+        size: 2,
+        time: 8,
+        exec: op_cbe1,
+    }),
+    Some(Inst {
+        code: 0xcbe2,
+        mnemonic: "set 4,d",
+        size: 2,
+        time: 8,
+        exec: op_cbe2,
+    }),
+    Some(Inst {
+        code: 0xcbe3,
+        mnemonic: "set 4,e",
+        size: 2,
+        time: 8,
+        exec: op_cbe3,
+    }),
+    Some(Inst {
+        code: 0xcbe4,
+        mnemonic: "set 4,h",
+        size: 2,
+        time: 8,
+        exec: op_cbe4,
+    }),
+    Some(Inst {
+        code: 0xcbe5,
+        mnemonic: "set 4,l",
+        size: 2,
+        time: 8,
+        exec: op_cbe5,
+    }),
+    Some(Inst {
+        code: 0xcbe6,
+        mnemonic: "set 4,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbe6,
+    }),
+    Some(Inst {
+        code: 0xcbe7,
+        mnemonic: "set 4,a",
+        size: 2,
+        time: 8,
+        exec: op_cbe7,
+    }),
+    Some(Inst {
+        code: 0xcbe8,
+        mnemonic: "set 5,b",
+        size: 2,
+        time: 8,
+        exec: op_cbe8,
+    }),
+    Some(Inst {
+        code: 0xcbe9,
+        mnemonic: "set 5,c",
+        size: 2,
+        time: 8,
+        exec: op_cbe9,
+    }),
+    Some(Inst {
+        code: 0xcbea,
+        mnemonic: "set 5,d",
+        size: 2,
+        time: 8,
+        exec: op_cbea,
+    }),
+    Some(Inst {
+        code: 0xcbeb,
+        mnemonic: "set 5,e",
+        size: 2,
+        time: 8,
+        exec: op_cbeb,
+    }),
+    Some(Inst {
+        code: 0xcbec,
+        mnemonic: "set 5,h",
+        size: 2,
+        time: 8,
+        exec: op_cbec,
+    }),
+    Some(Inst {
+        code: 0xcbed,
+        mnemonic: "set 5,l",
+        size: 2,
+        time: 8,
+        exec: op_cbed,
+    }),
+    Some(Inst {
+        code: 0xcbee,
+        mnemonic: "set 5,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbee,
+    }),
+    Some(Inst {
+        code: 0xcbef,
+        mnemonic: "set 5,a",
+        size: 2,
+        time: 8,
+        exec: op_cbef,
+    }),
+    Some(Inst {
+        code: 0xcbf0,
+        mnemonic: "set 6,b",
+        size: 2,
+        time: 8,
+        exec: op_cbf0,
+    }),
+    Some(Inst {
+        code: 0xcbf1,
+        mnemonic: "set 6,c",
+        size: 2,
+        time: 8,
+        exec: op_cbf1,
+    }),
+    Some(Inst {
+        code: 0xcbf2,
+        mnemonic: "set 6,d",
+        size: 2,
+        time: 8,
+        exec: op_cbf2,
+    }),
+    Some(Inst {
+        code: 0xcbf3,
+        mnemonic: "set 6,e",
+        size: 2,
+        time: 8,
+        exec: op_cbf3,
+    }),
+    Some(Inst {
+        code: 0xcbf4,
+        mnemonic: "set 6,h",
+        size: 2,
+        time: 8,
+        exec: op_cbf4,
+    }),
+    Some(Inst {
+        code: 0xcbf5,
+        mnemonic: "set 6,l",
+        size: 2,
+        time: 8,
+        exec: op_cbf5,
+    }),
+    Some(Inst {
+        code: 0xcbf6,
+        mnemonic: "set 6,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbf6,
+    }),
+    Some(Inst {
+        code: 0xcbf7,
+        mnemonic: "set 6,a",
+        size: 2,
+        time: 8,
+        exec: op_cbf7,
+    }),
+    Some(Inst {
+        code: 0xcbf8,
+        mnemonic: "set 7,b",
+        size: 2,
+        time: 8,
+        exec: op_cbf8,
+    }),
+    Some(Inst {
+        code: 0xcbf9,
+        mnemonic: "set 7,c",
+        size: 2,
+        time: 8,
+        exec: op_cbf9,
+    }),
+    Some(Inst {
+        code: 0xcbfa,
+        mnemonic: "set 7,d",
+        size: 2,
+        time: 8,
+        exec: op_cbfa,
+    }),
+    Some(Inst {
+        code: 0xcbfb,
+        mnemonic: "set 7,e",
+        size: 2,
+        time: 8,
+        exec: op_cbfb,
+    }),
+    Some(Inst {
+        code: 0xcbfc,
+        mnemonic: "set 7,h",
+        size: 2,
+        time: 8,
+        exec: op_cbfc,
+    }),
+    Some(Inst {
+        code: 0xcbfd,
+        mnemonic: "set 7,l",
+        size: 2,
+        time: 8,
+        exec: op_cbfd,
+    }),
+    Some(Inst {
+        code: 0xcbfe,
+        mnemonic: "set 7,(hl)",
+        size: 2,
+        time: 16,
+        exec: op_cbfe,
+    }),
+    Some(Inst {
+        code: 0xcbff,
+        mnemonic: "set 7,a",
+        size: 2,
+        time: 8,
+        exec: op_cbff,
+    }),
+];
+
+/// How an instruction affects a flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlagEffect {
+    /// The flag is left as is.
+    Unchanged,
+    /// The flag is cleared.
+    Reset,
+    /// The flag is set.
+    Set,
+    /// The flag depends on the result.
+    Computed,
+}
+
+/// The metadata of an instruction, for disassemblers, tracers and tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpMeta {
+    /// The mnemonic string.
+    pub mnemonic: &'static str,
+    /// The length of the instruction in bytes, or 0 for an invalid opcode.
+    pub size: usize,
+    /// The clock cycles taken by the instruction, with the conditional branches not taken.
+    pub min_time: usize,
+    /// The clock cycles taken by the instruction, with the conditional branches taken.
+    pub max_time: usize,
+    /// The effects on the flags, in the order of Z, N, H and C.
+    pub flags: [FlagEffect; 4],
+}
+
+impl OpMeta {
+    /// The metadata of the opcodes which aren't valid instructions.
+    pub const INVALID: OpMeta = OpMeta {
+        mnemonic: "(unknown opcode)",
+        size: 0,
+        min_time: 0,
+        max_time: 0,
+        flags: [FlagEffect::Unchanged; 4],
     };
+
+    /// Return whether the opcode is a valid instruction.
+    pub fn is_valid(&self) -> bool {
+        self.size > 0
+    }
 }
 
+/// The metadata of the instructions indexed by the opcode, followed by the ones prefixed by `0xcb`
+/// indexed by 256 plus the second byte of the opcode.
+pub static OPCODE_META: [OpMeta; 512] = [
+    OpMeta {
+        mnemonic: "nop ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld bc,d16",
+        size: 3,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (bc),a",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc bc",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlca ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (a16),sp",
+        size: 3,
+        min_time: 20,
+        max_time: 20,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add hl,bc",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,(bc)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec bc",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrca ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "stop 0",
+        size: 2,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld de,d16",
+        size: 3,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (de),a",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc de",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rla ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jr r8",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add hl,de",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,(de)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec de",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rra ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jr nz,r8",
+        size: 2,
+        min_time: 8,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld hl,d16",
+        size: 3,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ldi (hl),a",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc hl",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "daa ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jr z,r8",
+        size: 2,
+        min_time: 8,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add hl,hl",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ldi a,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec hl",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cpl ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Set,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jr nc,r8",
+        size: 2,
+        min_time: 8,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld sp,d16",
+        size: 3,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ldd (hl),a",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc sp",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc (hl)",
+        size: 1,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec (hl)",
+        size: 1,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),d8",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "scf ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jr cf,r8",
+        size: 2,
+        min_time: 8,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add hl,sp",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ldd a,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec sp",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "inc a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "dec a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ccf ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld b,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld c,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld d,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld e,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld h,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld l,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),b",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),c",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),d",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),e",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),h",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),l",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "halt ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (hl),a",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub (hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,(hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sbc a,a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and (hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor (hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "xor a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or (hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp b",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp c",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp d",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp e",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp h",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp l",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp (hl)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "cp a",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ret nz",
+        size: 1,
+        min_time: 8,
+        max_time: 20,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "pop bc",
+        size: 1,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jp nz,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jp a16",
+        size: 3,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "call nz,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 24,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "push bc",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add a,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x00",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ret z",
+        size: 1,
+        min_time: 8,
+        max_time: 20,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ret ",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jp z,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "prefix cb",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "call z,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 24,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "call a16",
+        size: 3,
+        min_time: 24,
+        max_time: 24,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "adc a,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x08",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ret nc",
+        size: 1,
+        min_time: 8,
+        max_time: 20,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "pop de",
+        size: 1,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jp nc,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "call nc,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 24,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "push de",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sub d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x10",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ret cf",
+        size: 1,
+        min_time: 8,
+        max_time: 20,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "reti ",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jp cf,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "call cf,a16",
+        size: 3,
+        min_time: 12,
+        max_time: 24,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "sbc a,d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x18",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (0xff00+a8),a",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "pop hl",
+        size: 1,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (0xff00+c),a",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "push hl",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "and d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x20",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "add sp,r8",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "jp hl",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld (a16),a",
+        size: 3,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta::INVALID,
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "xor d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x28",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,(0xff00+a8)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "pop af",
+        size: 1,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,(0xff00+c)",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "di ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "push af",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "or d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x30",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ldhl sp,r8",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld sp,hl",
+        size: 1,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ld a,(a16)",
+        size: 3,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "ei ",
+        size: 1,
+        min_time: 4,
+        max_time: 4,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta::INVALID,
+    OpMeta::INVALID,
+    OpMeta {
+        mnemonic: "cp d8",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Set,
+            FlagEffect::Computed,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rst 0x38",
+        size: 1,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rlc a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rrc a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rl a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "rr a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sla a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "sra a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "swap a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl (hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "srl a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Reset,
+            FlagEffect::Computed,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 0,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 1,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 2,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 3,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 4,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 5,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 6,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,(hl)",
+        size: 2,
+        min_time: 12,
+        max_time: 12,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "bit 7,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Computed,
+            FlagEffect::Reset,
+            FlagEffect::Set,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 0,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 1,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 2,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 3,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 4,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 5,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 6,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "res 7,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 0,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 1,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 2,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 3,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 4,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 5,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 6,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,b",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,c",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,d",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,e",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,h",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,l",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,(hl)",
+        size: 2,
+        min_time: 16,
+        max_time: 16,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+    OpMeta {
+        mnemonic: "set 7,a",
+        size: 2,
+        min_time: 8,
+        max_time: 8,
+        flags: [
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+            FlagEffect::Unchanged,
+        ],
+    },
+];
+
 /// nop
 #[allow(unused_variables)]
 fn op_0000(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
@@ -522,7 +9630,7 @@ fn op_0000(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld bc,d16
 #[allow(unused_variables)]
 fn op_0001(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get16(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read16(cpu.get_pc().wrapping_add(arg));
     cpu.set_bc(v);
 
     (12, 3)
@@ -532,7 +9640,7 @@ fn op_0001(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0002(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(cpu.get_bc(), v);
+    mmu.write8(cpu.get_bc(), v);
 
     (8, 1)
 }
@@ -575,7 +9683,7 @@ fn op_0005(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld b,d8
 #[allow(unused_variables)]
 fn op_0006(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_b(v);
 
     (8, 2)
@@ -601,7 +9709,8 @@ fn op_0007(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0008(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_sp();
-    mmu.set16(mmu.get16(cpu.get_pc().wrapping_add(arg)), v);
+    let addr = mmu.read16(cpu.get_pc().wrapping_add(arg));
+    mmu.write16(addr, v);
 
     (20, 3)
 }
@@ -624,7 +9733,7 @@ fn op_0009(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,(bc)
 #[allow(unused_variables)]
 fn op_000a(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_bc());
+    let v = mmu.read8(cpu.get_bc());
     cpu.set_a(v);
 
     (8, 1)
@@ -668,7 +9777,7 @@ fn op_000d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld c,d8
 #[allow(unused_variables)]
 fn op_000e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_c(v);
 
     (8, 2)
@@ -701,7 +9810,7 @@ fn op_0010(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld de,d16
 #[allow(unused_variables)]
 fn op_0011(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get16(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read16(cpu.get_pc().wrapping_add(arg));
     cpu.set_de(v);
 
     (12, 3)
@@ -711,7 +9820,7 @@ fn op_0011(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0012(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(cpu.get_de(), v);
+    mmu.write8(cpu.get_de(), v);
 
     (8, 1)
 }
@@ -754,7 +9863,7 @@ fn op_0015(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld d,d8
 #[allow(unused_variables)]
 fn op_0016(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_d(v);
 
     (8, 2)
@@ -779,7 +9888,7 @@ fn op_0017(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// jr r8
 #[allow(unused_variables)]
 fn op_0018(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let p = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let p = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let pc = cpu.get_pc().wrapping_add(alu::signed(p));
     cpu.set_pc(pc);
 
@@ -804,7 +9913,7 @@ fn op_0019(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,(de)
 #[allow(unused_variables)]
 fn op_001a(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_de());
+    let v = mmu.read8(cpu.get_de());
     cpu.set_a(v);
 
     (8, 1)
@@ -848,7 +9957,7 @@ fn op_001d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld e,d8
 #[allow(unused_variables)]
 fn op_001e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_e(v);
 
     (8, 2)
@@ -875,7 +9984,7 @@ fn op_001f(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_0020(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = !cpu.get_zf();
     if flg {
-        let p = mmu.get8(cpu.get_pc().wrapping_add(arg));
+        let p = mmu.read8(cpu.get_pc().wrapping_add(arg));
         let pc = cpu.get_pc().wrapping_add(alu::signed(p));
         cpu.set_pc(pc);
         return (12, 2);
@@ -887,7 +9996,7 @@ fn op_0020(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld hl,d16
 #[allow(unused_variables)]
 fn op_0021(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get16(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read16(cpu.get_pc().wrapping_add(arg));
     cpu.set_hl(v);
 
     (12, 3)
@@ -897,7 +10006,7 @@ fn op_0021(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0022(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     cpu.set_hl(cpu.get_hl().wrapping_add(1));
 
@@ -942,7 +10051,7 @@ fn op_0025(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld h,d8
 #[allow(unused_variables)]
 fn op_0026(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_h(v);
 
     (8, 2)
@@ -966,7 +10075,11 @@ fn op_0027(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
         false
     };
 
-    let v = if cpu.get_nf() { v - adj } else { v + adj };
+    let v = if cpu.get_nf() {
+        v.wrapping_sub(adj)
+    } else {
+        v + adj
+    };
     let v = (v & 0xff) as u8;
     let z = v == 0;
 
@@ -984,7 +10097,7 @@ fn op_0027(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_0028(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = cpu.get_zf();
     if flg {
-        let p = mmu.get8(cpu.get_pc().wrapping_add(arg));
+        let p = mmu.read8(cpu.get_pc().wrapping_add(arg));
         let pc = cpu.get_pc().wrapping_add(alu::signed(p));
         cpu.set_pc(pc);
         return (12, 2);
@@ -1011,7 +10124,7 @@ fn op_0029(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ldi a,(hl)
 #[allow(unused_variables)]
 fn op_002a(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_a(v);
 
     cpu.set_hl(cpu.get_hl().wrapping_add(1));
@@ -1057,7 +10170,7 @@ fn op_002d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld l,d8
 #[allow(unused_variables)]
 fn op_002e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_l(v);
 
     (8, 2)
@@ -1079,7 +10192,7 @@ fn op_002f(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_0030(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = !cpu.get_cf();
     if flg {
-        let p = mmu.get8(cpu.get_pc().wrapping_add(arg));
+        let p = mmu.read8(cpu.get_pc().wrapping_add(arg));
         let pc = cpu.get_pc().wrapping_add(alu::signed(p));
         cpu.set_pc(pc);
         return (12, 2);
@@ -1091,7 +10204,7 @@ fn op_0030(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld sp,d16
 #[allow(unused_variables)]
 fn op_0031(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get16(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read16(cpu.get_pc().wrapping_add(arg));
     cpu.set_sp(v);
 
     (12, 3)
@@ -1101,7 +10214,7 @@ fn op_0031(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0032(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     cpu.set_hl(cpu.get_hl().wrapping_sub(1));
 
@@ -1120,9 +10233,9 @@ fn op_0033(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// inc (hl)
 #[allow(unused_variables)]
 fn op_0034(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let (v, h, c, z) = alu::add8(v, 1, false);
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(h);
@@ -1133,9 +10246,9 @@ fn op_0034(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// dec (hl)
 #[allow(unused_variables)]
 fn op_0035(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let (v, h, c, z) = alu::sub8(v, 1, false);
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(true);
     cpu.set_hf(h);
@@ -1146,8 +10259,8 @@ fn op_0035(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld (hl),d8
 #[allow(unused_variables)]
 fn op_0036(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
-    mmu.set8(cpu.get_hl(), v);
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
+    mmu.write8(cpu.get_hl(), v);
 
     (12, 2)
 }
@@ -1169,7 +10282,7 @@ fn op_0037(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_0038(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = cpu.get_cf();
     if flg {
-        let p = mmu.get8(cpu.get_pc().wrapping_add(arg));
+        let p = mmu.read8(cpu.get_pc().wrapping_add(arg));
         let pc = cpu.get_pc().wrapping_add(alu::signed(p));
         cpu.set_pc(pc);
         return (12, 2);
@@ -1196,7 +10309,7 @@ fn op_0039(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ldd a,(hl)
 #[allow(unused_variables)]
 fn op_003a(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_a(v);
 
     cpu.set_hl(cpu.get_hl().wrapping_sub(1));
@@ -1242,7 +10355,7 @@ fn op_003d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,d8
 #[allow(unused_variables)]
 fn op_003e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let v = mmu.read8(cpu.get_pc().wrapping_add(arg));
     cpu.set_a(v);
 
     (8, 2)
@@ -1317,7 +10430,7 @@ fn op_0045(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld b,(hl)
 #[allow(unused_variables)]
 fn op_0046(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_b(v);
 
     (8, 1)
@@ -1389,7 +10502,7 @@ fn op_004d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld c,(hl)
 #[allow(unused_variables)]
 fn op_004e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_c(v);
 
     (8, 1)
@@ -1461,7 +10574,7 @@ fn op_0055(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld d,(hl)
 #[allow(unused_variables)]
 fn op_0056(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_d(v);
 
     (8, 1)
@@ -1533,7 +10646,7 @@ fn op_005d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld e,(hl)
 #[allow(unused_variables)]
 fn op_005e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_e(v);
 
     (8, 1)
@@ -1605,7 +10718,7 @@ fn op_0065(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld h,(hl)
 #[allow(unused_variables)]
 fn op_0066(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_h(v);
 
     (8, 1)
@@ -1677,7 +10790,7 @@ fn op_006d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld l,(hl)
 #[allow(unused_variables)]
 fn op_006e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_l(v);
 
     (8, 1)
@@ -1696,7 +10809,7 @@ fn op_006f(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0070(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_b();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1705,7 +10818,7 @@ fn op_0070(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0071(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_c();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1714,7 +10827,7 @@ fn op_0071(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0072(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_d();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1723,7 +10836,7 @@ fn op_0072(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0073(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_e();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1732,7 +10845,7 @@ fn op_0073(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0074(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_h();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1741,7 +10854,7 @@ fn op_0074(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0075(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_l();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1758,7 +10871,7 @@ fn op_0076(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0077(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
 
     (8, 1)
 }
@@ -1820,7 +10933,7 @@ fn op_007d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,(hl)
 #[allow(unused_variables)]
 fn op_007e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     cpu.set_a(v);
 
     (8, 1)
@@ -1929,7 +11042,7 @@ fn op_0085(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0086(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let (v, h, c, z) = alu::add8(p, q, false);
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -2049,7 +11162,7 @@ fn op_008d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_008e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let (v, h, c, z) = alu::add8(p, q, cpu.get_cf());
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -2169,7 +11282,7 @@ fn op_0095(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_0096(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let (v, h, c, z) = alu::sub8(p, q, false);
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -2289,7 +11402,7 @@ fn op_009d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_009e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let (v, h, c, z) = alu::sub8(p, q, cpu.get_cf());
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -2396,7 +11509,7 @@ fn op_00a5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// and (hl)
 #[allow(unused_variables)]
 fn op_00a6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.set_a(cpu.get_a() & mmu.get8(cpu.get_hl()));
+    cpu.set_a(cpu.get_a() & mmu.read8(cpu.get_hl()));
     let z = cpu.get_a() == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -2500,7 +11613,7 @@ fn op_00ad(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// xor (hl)
 #[allow(unused_variables)]
 fn op_00ae(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.set_a(cpu.get_a() ^ mmu.get8(cpu.get_hl()));
+    cpu.set_a(cpu.get_a() ^ mmu.read8(cpu.get_hl()));
     let z = cpu.get_a() == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -2604,7 +11717,7 @@ fn op_00b5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// or (hl)
 #[allow(unused_variables)]
 fn op_00b6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.set_a(cpu.get_a() | mmu.get8(cpu.get_hl()));
+    cpu.set_a(cpu.get_a() | mmu.read8(cpu.get_hl()));
     let z = cpu.get_a() == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -2715,7 +11828,7 @@ fn op_00bd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00be(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let (_, h, c, z) = alu::sub8(p, q, false);
     cpu.set_zf(z);
     cpu.set_nf(true);
@@ -2766,7 +11879,7 @@ fn op_00c1(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_00c2(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = !cpu.get_zf();
     if flg {
-        let pc = mmu.get16(cpu.get_pc().wrapping_add(arg));
+        let pc = mmu.read16(cpu.get_pc().wrapping_add(arg));
         cpu.set_pc(pc);
         return (16, 0);
     }
@@ -2777,7 +11890,7 @@ fn op_00c2(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// jp a16
 #[allow(unused_variables)]
 fn op_00c3(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let pc = mmu.get16(cpu.get_pc().wrapping_add(arg));
+    let pc = mmu.read16(cpu.get_pc().wrapping_add(arg));
     cpu.set_pc(pc.wrapping_sub(3));
 
     (16, 3)
@@ -2789,7 +11902,7 @@ fn op_00c4(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = !cpu.get_zf();
     if flg {
         cpu.push(mmu, cpu.get_pc().wrapping_add(3));
-        cpu.set_pc(mmu.get16(cpu.get_pc().wrapping_add(arg)));
+        cpu.set_pc(mmu.read16(cpu.get_pc().wrapping_add(arg)));
         return (24, 0);
     }
 
@@ -2808,7 +11921,7 @@ fn op_00c5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00c6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (v, h, c, z) = alu::add8(p, q, false);
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -2855,7 +11968,7 @@ fn op_00c9(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_00ca(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = cpu.get_zf();
     if flg {
-        let pc = mmu.get16(cpu.get_pc().wrapping_add(arg));
+        let pc = mmu.read16(cpu.get_pc().wrapping_add(arg));
         cpu.set_pc(pc);
         return (16, 0);
     }
@@ -2875,7 +11988,7 @@ fn op_00cc(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = cpu.get_zf();
     if flg {
         cpu.push(mmu, cpu.get_pc().wrapping_add(3));
-        cpu.set_pc(mmu.get16(cpu.get_pc().wrapping_add(arg)));
+        cpu.set_pc(mmu.read16(cpu.get_pc().wrapping_add(arg)));
         return (24, 0);
     }
 
@@ -2886,7 +11999,7 @@ fn op_00cc(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00cd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     cpu.push(mmu, cpu.get_pc().wrapping_add(3));
-    cpu.set_pc(mmu.get16(cpu.get_pc().wrapping_add(arg)).wrapping_sub(3));
+    cpu.set_pc(mmu.read16(cpu.get_pc().wrapping_add(arg)).wrapping_sub(3));
 
     (24, 3)
 }
@@ -2895,7 +12008,7 @@ fn op_00cd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00ce(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (v, h, c, z) = alu::add8(p, q, cpu.get_cf());
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -2942,7 +12055,7 @@ fn op_00d1(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_00d2(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = !cpu.get_cf();
     if flg {
-        let pc = mmu.get16(cpu.get_pc().wrapping_add(arg));
+        let pc = mmu.read16(cpu.get_pc().wrapping_add(arg));
         cpu.set_pc(pc);
         return (16, 0);
     }
@@ -2956,7 +12069,7 @@ fn op_00d4(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = !cpu.get_cf();
     if flg {
         cpu.push(mmu, cpu.get_pc().wrapping_add(3));
-        cpu.set_pc(mmu.get16(cpu.get_pc().wrapping_add(arg)));
+        cpu.set_pc(mmu.read16(cpu.get_pc().wrapping_add(arg)));
         return (24, 0);
     }
 
@@ -2975,7 +12088,7 @@ fn op_00d5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00d6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (v, h, c, z) = alu::sub8(p, q, false);
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -3023,7 +12136,7 @@ fn op_00d9(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 fn op_00da(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = cpu.get_cf();
     if flg {
-        let pc = mmu.get16(cpu.get_pc().wrapping_add(arg));
+        let pc = mmu.read16(cpu.get_pc().wrapping_add(arg));
         cpu.set_pc(pc);
         return (16, 0);
     }
@@ -3037,7 +12150,7 @@ fn op_00dc(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let flg = cpu.get_cf();
     if flg {
         cpu.push(mmu, cpu.get_pc().wrapping_add(3));
-        cpu.set_pc(mmu.get16(cpu.get_pc().wrapping_add(arg)));
+        cpu.set_pc(mmu.read16(cpu.get_pc().wrapping_add(arg)));
         return (24, 0);
     }
 
@@ -3048,7 +12161,7 @@ fn op_00dc(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00de(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (v, h, c, z) = alu::sub8(p, q, cpu.get_cf());
     cpu.set_a(v);
     cpu.set_zf(z);
@@ -3072,7 +12185,8 @@ fn op_00df(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00e0(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(0xff00 + mmu.get8(cpu.get_pc().wrapping_add(arg)) as u16, v);
+    let addr = 0xff00 + mmu.read8(cpu.get_pc().wrapping_add(arg)) as u16;
+    mmu.write8(addr, v);
 
     (12, 2)
 }
@@ -3090,7 +12204,7 @@ fn op_00e1(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00e2(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(0xff00 + cpu.get_c() as u16, v);
+    mmu.write8(0xff00 + cpu.get_c() as u16, v);
 
     (8, 1)
 }
@@ -3106,7 +12220,7 @@ fn op_00e5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// and d8
 #[allow(unused_variables)]
 fn op_00e6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.set_a(cpu.get_a() & mmu.get8(cpu.get_pc().wrapping_add(arg)));
+    cpu.set_a(cpu.get_a() & mmu.read8(cpu.get_pc().wrapping_add(arg)));
     let z = cpu.get_a() == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -3129,7 +12243,7 @@ fn op_00e7(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00e8(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_sp();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (v, h, c, z) = alu::add16e(p, q, false);
     cpu.set_sp(v);
     cpu.set_zf(false);
@@ -3153,7 +12267,8 @@ fn op_00e9(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00ea(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let v = cpu.get_a();
-    mmu.set8(mmu.get16(cpu.get_pc().wrapping_add(arg)), v);
+    let addr = mmu.read16(cpu.get_pc().wrapping_add(arg));
+    mmu.write8(addr, v);
 
     (16, 3)
 }
@@ -3161,7 +12276,7 @@ fn op_00ea(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// xor d8
 #[allow(unused_variables)]
 fn op_00ee(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.set_a(cpu.get_a() ^ mmu.get8(cpu.get_pc().wrapping_add(arg)));
+    cpu.set_a(cpu.get_a() ^ mmu.read8(cpu.get_pc().wrapping_add(arg)));
     let z = cpu.get_a() == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -3183,7 +12298,10 @@ fn op_00ef(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,(0xff00+a8)
 #[allow(unused_variables)]
 fn op_00f0(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(0xff00 + mmu.get8(cpu.get_pc().wrapping_add(arg)) as u16);
+    let v = {
+        let addr = 0xff00 + mmu.read8(cpu.get_pc().wrapping_add(arg)) as u16;
+        mmu.read8(addr)
+    };
     cpu.set_a(v);
 
     (12, 2)
@@ -3201,7 +12319,7 @@ fn op_00f1(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,(0xff00+c)
 #[allow(unused_variables)]
 fn op_00f2(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(0xff00 + cpu.get_c() as u16);
+    let v = mmu.read8(0xff00 + cpu.get_c() as u16);
     cpu.set_a(v);
 
     (8, 1)
@@ -3226,7 +12344,7 @@ fn op_00f5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// or d8
 #[allow(unused_variables)]
 fn op_00f6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.set_a(cpu.get_a() | mmu.get8(cpu.get_pc().wrapping_add(arg)));
+    cpu.set_a(cpu.get_a() | mmu.read8(cpu.get_pc().wrapping_add(arg)));
     let z = cpu.get_a() == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -3249,7 +12367,7 @@ fn op_00f7(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00f8(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_sp();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (v, h, c, z) = alu::add16e(p, q, false);
     cpu.set_hl(v);
     cpu.set_zf(false);
@@ -3272,7 +12390,10 @@ fn op_00f9(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ld a,(a16)
 #[allow(unused_variables)]
 fn op_00fa(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(mmu.get16(cpu.get_pc().wrapping_add(arg)));
+    let v = {
+        let addr = mmu.read16(cpu.get_pc().wrapping_add(arg));
+        mmu.read8(addr)
+    };
     cpu.set_a(v);
 
     (16, 3)
@@ -3281,7 +12402,7 @@ fn op_00fa(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// ei
 #[allow(unused_variables)]
 fn op_00fb(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    cpu.enable_interrupt();
+    cpu.enable_interrupt_delayed();
 
     (4, 1)
 }
@@ -3290,7 +12411,7 @@ fn op_00fb(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_00fe(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = cpu.get_a();
-    let q = mmu.get8(cpu.get_pc().wrapping_add(arg));
+    let q = mmu.read8(cpu.get_pc().wrapping_add(arg));
     let (_, h, c, z) = alu::sub8(p, q, false);
     cpu.set_zf(z);
     cpu.set_nf(true);
@@ -3408,11 +12529,11 @@ fn op_cb05(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// rlc (hl)
 #[allow(unused_variables)]
 fn op_cb06(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 0x80 != 0;
     let v = v.rotate_left(1);
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -3536,11 +12657,11 @@ fn op_cb0d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// rrc (hl)
 #[allow(unused_variables)]
 fn op_cb0e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 1 != 0;
     let v = v.rotate_right(1);
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -3670,12 +12791,12 @@ fn op_cb15(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// rl (hl)
 #[allow(unused_variables)]
 fn op_cb16(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 0x80 != 0;
     let v = v.wrapping_shl(1);
     let v = v | if cpu.get_cf() { 1 } else { 0 };
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -3806,12 +12927,12 @@ fn op_cb1d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// rr (hl)
 #[allow(unused_variables)]
 fn op_cb1e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 1 != 0;
     let v = v.wrapping_shr(1);
     let v = v | if cpu.get_cf() { 0x80 } else { 0 };
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -3936,11 +13057,11 @@ fn op_cb25(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// sla (hl)
 #[allow(unused_variables)]
 fn op_cb26(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 0x80 != 0;
     let v = v.wrapping_shl(1);
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -4076,13 +13197,13 @@ fn op_cb2d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// sra (hl)
 #[allow(unused_variables)]
 fn op_cb2e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 1 != 0;
     let msb = v & 0x80;
     let v = v.wrapping_shr(1);
     let v = v | msb;
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -4202,9 +13323,9 @@ fn op_cb35(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// swap (hl)
 #[allow(unused_variables)]
 fn op_cb36(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let v = v.rotate_left(4);
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     let z = v == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -4328,11 +13449,11 @@ fn op_cb3d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 /// srl (hl)
 #[allow(unused_variables)]
 fn op_cb3e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
-    let v = mmu.get8(cpu.get_hl());
+    let v = mmu.read8(cpu.get_hl());
     let c = v & 1 != 0;
     let v = v.wrapping_shr(1);
     let z = v == 0;
-    mmu.set8(cpu.get_hl(), v);
+    mmu.write8(cpu.get_hl(), v);
     cpu.set_zf(z);
     cpu.set_nf(false);
     cpu.set_hf(false);
@@ -4439,7 +13560,7 @@ fn op_cb45(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb46(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 0;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -4543,7 +13664,7 @@ fn op_cb4d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb4e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 1;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -4647,7 +13768,7 @@ fn op_cb55(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb56(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 2;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -4751,7 +13872,7 @@ fn op_cb5d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb5e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 3;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -4855,7 +13976,7 @@ fn op_cb65(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb66(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 4;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -4959,7 +14080,7 @@ fn op_cb6d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb6e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 5;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -5063,7 +14184,7 @@ fn op_cb75(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb76(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 6;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -5167,7 +14288,7 @@ fn op_cb7d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb7e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 7;
-    let q = mmu.get8(cpu.get_hl());
+    let q = mmu.read8(cpu.get_hl());
     let z = q & (1 << p) == 0;
     cpu.set_zf(z);
     cpu.set_nf(false);
@@ -5253,8 +14374,8 @@ fn op_cb85(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb86(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 0;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5333,8 +14454,8 @@ fn op_cb8d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb8e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 1;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5413,8 +14534,8 @@ fn op_cb95(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb96(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 2;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5493,8 +14614,8 @@ fn op_cb9d(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cb9e(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 3;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5573,8 +14694,8 @@ fn op_cba5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cba6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 4;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5653,8 +14774,8 @@ fn op_cbad(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbae(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 5;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5733,8 +14854,8 @@ fn op_cbb5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbb6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 6;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5813,8 +14934,8 @@ fn op_cbbd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbbe(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 7;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q & !(1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q & !(1 << p));
 
     (16, 2)
 }
@@ -5893,8 +15014,8 @@ fn op_cbc5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbc6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 0;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -5973,8 +15094,8 @@ fn op_cbcd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbce(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 1;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6053,8 +15174,8 @@ fn op_cbd5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbd6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 2;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6133,8 +15254,8 @@ fn op_cbdd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbde(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 3;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6213,8 +15334,8 @@ fn op_cbe5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbe6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 4;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6293,8 +15414,8 @@ fn op_cbed(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbee(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 5;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6373,8 +15494,8 @@ fn op_cbf5(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbf6(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 6;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6453,8 +15574,8 @@ fn op_cbfd(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
 #[allow(unused_variables)]
 fn op_cbfe(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     let p = 7;
-    let q = mmu.get8(cpu.get_hl());
-    mmu.set8(cpu.get_hl(), q | (1 << p));
+    let q = mmu.read8(cpu.get_hl());
+    mmu.write8(cpu.get_hl(), q | (1 << p));
 
     (16, 2)
 }
@@ -6469,517 +15590,41 @@ fn op_cbff(arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     (8, 2)
 }
 
+/// Return the instruction for the given opcode, if it's valid.
+pub fn lookup(code: u16) -> Option<&'static Inst> {
+    let table = match code >> 8 {
+        0x00 => &INSTS,
+        0xcb => &CB_INSTS,
+        _ => return None,
+    };
+
+    table[(code & 0xff) as usize].as_ref()
+}
+
+/// Return the metadata for the given opcode, [`OpMeta::INVALID`][] if it's not valid.
+pub fn meta(code: u16) -> &'static OpMeta {
+    match code >> 8 {
+        0x00 => &OPCODE_META[code as usize],
+        0xcb => &OPCODE_META[0x100 + (code & 0xff) as usize],
+        _ => &OpMeta::INVALID,
+    }
+}
+
 /// Return the mnemonic string for the given opcode.
 pub fn mnem(code: u16) -> &'static str {
-    MNEMONICS.get(&code).unwrap_or(&"(unknown opcode)")
+    meta(code).mnemonic
 }
 
 /// Decodes the opecode and actually executes one instruction.
 pub fn decode(code: u16, arg: u16, cpu: &mut Cpu, mmu: &mut Mmu) -> (usize, usize) {
     trace!("{:04x}: {:04x}: {}", cpu.get_pc(), code, mnem(code));
 
-    match code {
-        0x0000 => op_0000(arg, cpu, mmu),
-        0x0001 => op_0001(arg, cpu, mmu),
-        0x0002 => op_0002(arg, cpu, mmu),
-        0x0003 => op_0003(arg, cpu, mmu),
-        0x0004 => op_0004(arg, cpu, mmu),
-        0x0005 => op_0005(arg, cpu, mmu),
-        0x0006 => op_0006(arg, cpu, mmu),
-        0x0007 => op_0007(arg, cpu, mmu),
-        0x0008 => op_0008(arg, cpu, mmu),
-        0x0009 => op_0009(arg, cpu, mmu),
-        0x000a => op_000a(arg, cpu, mmu),
-        0x000b => op_000b(arg, cpu, mmu),
-        0x000c => op_000c(arg, cpu, mmu),
-        0x000d => op_000d(arg, cpu, mmu),
-        0x000e => op_000e(arg, cpu, mmu),
-        0x000f => op_000f(arg, cpu, mmu),
-        0x0010 => op_0010(arg, cpu, mmu),
-        0x0011 => op_0011(arg, cpu, mmu),
-        0x0012 => op_0012(arg, cpu, mmu),
-        0x0013 => op_0013(arg, cpu, mmu),
-        0x0014 => op_0014(arg, cpu, mmu),
-        0x0015 => op_0015(arg, cpu, mmu),
-        0x0016 => op_0016(arg, cpu, mmu),
-        0x0017 => op_0017(arg, cpu, mmu),
-        0x0018 => op_0018(arg, cpu, mmu),
-        0x0019 => op_0019(arg, cpu, mmu),
-        0x001a => op_001a(arg, cpu, mmu),
-        0x001b => op_001b(arg, cpu, mmu),
-        0x001c => op_001c(arg, cpu, mmu),
-        0x001d => op_001d(arg, cpu, mmu),
-        0x001e => op_001e(arg, cpu, mmu),
-        0x001f => op_001f(arg, cpu, mmu),
-        0x0020 => op_0020(arg, cpu, mmu),
-        0x0021 => op_0021(arg, cpu, mmu),
-        0x0022 => op_0022(arg, cpu, mmu),
-        0x0023 => op_0023(arg, cpu, mmu),
-        0x0024 => op_0024(arg, cpu, mmu),
-        0x0025 => op_0025(arg, cpu, mmu),
-        0x0026 => op_0026(arg, cpu, mmu),
-        0x0027 => op_0027(arg, cpu, mmu),
-        0x0028 => op_0028(arg, cpu, mmu),
-        0x0029 => op_0029(arg, cpu, mmu),
-        0x002a => op_002a(arg, cpu, mmu),
-        0x002b => op_002b(arg, cpu, mmu),
-        0x002c => op_002c(arg, cpu, mmu),
-        0x002d => op_002d(arg, cpu, mmu),
-        0x002e => op_002e(arg, cpu, mmu),
-        0x002f => op_002f(arg, cpu, mmu),
-        0x0030 => op_0030(arg, cpu, mmu),
-        0x0031 => op_0031(arg, cpu, mmu),
-        0x0032 => op_0032(arg, cpu, mmu),
-        0x0033 => op_0033(arg, cpu, mmu),
-        0x0034 => op_0034(arg, cpu, mmu),
-        0x0035 => op_0035(arg, cpu, mmu),
-        0x0036 => op_0036(arg, cpu, mmu),
-        0x0037 => op_0037(arg, cpu, mmu),
-        0x0038 => op_0038(arg, cpu, mmu),
-        0x0039 => op_0039(arg, cpu, mmu),
-        0x003a => op_003a(arg, cpu, mmu),
-        0x003b => op_003b(arg, cpu, mmu),
-        0x003c => op_003c(arg, cpu, mmu),
-        0x003d => op_003d(arg, cpu, mmu),
-        0x003e => op_003e(arg, cpu, mmu),
-        0x003f => op_003f(arg, cpu, mmu),
-        0x0040 => op_0040(arg, cpu, mmu),
-        0x0041 => op_0041(arg, cpu, mmu),
-        0x0042 => op_0042(arg, cpu, mmu),
-        0x0043 => op_0043(arg, cpu, mmu),
-        0x0044 => op_0044(arg, cpu, mmu),
-        0x0045 => op_0045(arg, cpu, mmu),
-        0x0046 => op_0046(arg, cpu, mmu),
-        0x0047 => op_0047(arg, cpu, mmu),
-        0x0048 => op_0048(arg, cpu, mmu),
-        0x0049 => op_0049(arg, cpu, mmu),
-        0x004a => op_004a(arg, cpu, mmu),
-        0x004b => op_004b(arg, cpu, mmu),
-        0x004c => op_004c(arg, cpu, mmu),
-        0x004d => op_004d(arg, cpu, mmu),
-        0x004e => op_004e(arg, cpu, mmu),
-        0x004f => op_004f(arg, cpu, mmu),
-        0x0050 => op_0050(arg, cpu, mmu),
-        0x0051 => op_0051(arg, cpu, mmu),
-        0x0052 => op_0052(arg, cpu, mmu),
-        0x0053 => op_0053(arg, cpu, mmu),
-        0x0054 => op_0054(arg, cpu, mmu),
-        0x0055 => op_0055(arg, cpu, mmu),
-        0x0056 => op_0056(arg, cpu, mmu),
-        0x0057 => op_0057(arg, cpu, mmu),
-        0x0058 => op_0058(arg, cpu, mmu),
-        0x0059 => op_0059(arg, cpu, mmu),
-        0x005a => op_005a(arg, cpu, mmu),
-        0x005b => op_005b(arg, cpu, mmu),
-        0x005c => op_005c(arg, cpu, mmu),
-        0x005d => op_005d(arg, cpu, mmu),
-        0x005e => op_005e(arg, cpu, mmu),
-        0x005f => op_005f(arg, cpu, mmu),
-        0x0060 => op_0060(arg, cpu, mmu),
-        0x0061 => op_0061(arg, cpu, mmu),
-        0x0062 => op_0062(arg, cpu, mmu),
-        0x0063 => op_0063(arg, cpu, mmu),
-        0x0064 => op_0064(arg, cpu, mmu),
-        0x0065 => op_0065(arg, cpu, mmu),
-        0x0066 => op_0066(arg, cpu, mmu),
-        0x0067 => op_0067(arg, cpu, mmu),
-        0x0068 => op_0068(arg, cpu, mmu),
-        0x0069 => op_0069(arg, cpu, mmu),
-        0x006a => op_006a(arg, cpu, mmu),
-        0x006b => op_006b(arg, cpu, mmu),
-        0x006c => op_006c(arg, cpu, mmu),
-        0x006d => op_006d(arg, cpu, mmu),
-        0x006e => op_006e(arg, cpu, mmu),
-        0x006f => op_006f(arg, cpu, mmu),
-        0x0070 => op_0070(arg, cpu, mmu),
-        0x0071 => op_0071(arg, cpu, mmu),
-        0x0072 => op_0072(arg, cpu, mmu),
-        0x0073 => op_0073(arg, cpu, mmu),
-        0x0074 => op_0074(arg, cpu, mmu),
-        0x0075 => op_0075(arg, cpu, mmu),
-        0x0076 => op_0076(arg, cpu, mmu),
-        0x0077 => op_0077(arg, cpu, mmu),
-        0x0078 => op_0078(arg, cpu, mmu),
-        0x0079 => op_0079(arg, cpu, mmu),
-        0x007a => op_007a(arg, cpu, mmu),
-        0x007b => op_007b(arg, cpu, mmu),
-        0x007c => op_007c(arg, cpu, mmu),
-        0x007d => op_007d(arg, cpu, mmu),
-        0x007e => op_007e(arg, cpu, mmu),
-        0x007f => op_007f(arg, cpu, mmu),
-        0x0080 => op_0080(arg, cpu, mmu),
-        0x0081 => op_0081(arg, cpu, mmu),
-        0x0082 => op_0082(arg, cpu, mmu),
-        0x0083 => op_0083(arg, cpu, mmu),
-        0x0084 => op_0084(arg, cpu, mmu),
-        0x0085 => op_0085(arg, cpu, mmu),
-        0x0086 => op_0086(arg, cpu, mmu),
-        0x0087 => op_0087(arg, cpu, mmu),
-        0x0088 => op_0088(arg, cpu, mmu),
-        0x0089 => op_0089(arg, cpu, mmu),
-        0x008a => op_008a(arg, cpu, mmu),
-        0x008b => op_008b(arg, cpu, mmu),
-        0x008c => op_008c(arg, cpu, mmu),
-        0x008d => op_008d(arg, cpu, mmu),
-        0x008e => op_008e(arg, cpu, mmu),
-        0x008f => op_008f(arg, cpu, mmu),
-        0x0090 => op_0090(arg, cpu, mmu),
-        0x0091 => op_0091(arg, cpu, mmu),
-        0x0092 => op_0092(arg, cpu, mmu),
-        0x0093 => op_0093(arg, cpu, mmu),
-        0x0094 => op_0094(arg, cpu, mmu),
-        0x0095 => op_0095(arg, cpu, mmu),
-        0x0096 => op_0096(arg, cpu, mmu),
-        0x0097 => op_0097(arg, cpu, mmu),
-        0x0098 => op_0098(arg, cpu, mmu),
-        0x0099 => op_0099(arg, cpu, mmu),
-        0x009a => op_009a(arg, cpu, mmu),
-        0x009b => op_009b(arg, cpu, mmu),
-        0x009c => op_009c(arg, cpu, mmu),
-        0x009d => op_009d(arg, cpu, mmu),
-        0x009e => op_009e(arg, cpu, mmu),
-        0x009f => op_009f(arg, cpu, mmu),
-        0x00a0 => op_00a0(arg, cpu, mmu),
-        0x00a1 => op_00a1(arg, cpu, mmu),
-        0x00a2 => op_00a2(arg, cpu, mmu),
-        0x00a3 => op_00a3(arg, cpu, mmu),
-        0x00a4 => op_00a4(arg, cpu, mmu),
-        0x00a5 => op_00a5(arg, cpu, mmu),
-        0x00a6 => op_00a6(arg, cpu, mmu),
-        0x00a7 => op_00a7(arg, cpu, mmu),
-        0x00a8 => op_00a8(arg, cpu, mmu),
-        0x00a9 => op_00a9(arg, cpu, mmu),
-        0x00aa => op_00aa(arg, cpu, mmu),
-        0x00ab => op_00ab(arg, cpu, mmu),
-        0x00ac => op_00ac(arg, cpu, mmu),
-        0x00ad => op_00ad(arg, cpu, mmu),
-        0x00ae => op_00ae(arg, cpu, mmu),
-        0x00af => op_00af(arg, cpu, mmu),
-        0x00b0 => op_00b0(arg, cpu, mmu),
-        0x00b1 => op_00b1(arg, cpu, mmu),
-        0x00b2 => op_00b2(arg, cpu, mmu),
-        0x00b3 => op_00b3(arg, cpu, mmu),
-        0x00b4 => op_00b4(arg, cpu, mmu),
-        0x00b5 => op_00b5(arg, cpu, mmu),
-        0x00b6 => op_00b6(arg, cpu, mmu),
-        0x00b7 => op_00b7(arg, cpu, mmu),
-        0x00b8 => op_00b8(arg, cpu, mmu),
-        0x00b9 => op_00b9(arg, cpu, mmu),
-        0x00ba => op_00ba(arg, cpu, mmu),
-        0x00bb => op_00bb(arg, cpu, mmu),
-        0x00bc => op_00bc(arg, cpu, mmu),
-        0x00bd => op_00bd(arg, cpu, mmu),
-        0x00be => op_00be(arg, cpu, mmu),
-        0x00bf => op_00bf(arg, cpu, mmu),
-        0x00c0 => op_00c0(arg, cpu, mmu),
-        0x00c1 => op_00c1(arg, cpu, mmu),
-        0x00c2 => op_00c2(arg, cpu, mmu),
-        0x00c3 => op_00c3(arg, cpu, mmu),
-        0x00c4 => op_00c4(arg, cpu, mmu),
-        0x00c5 => op_00c5(arg, cpu, mmu),
-        0x00c6 => op_00c6(arg, cpu, mmu),
-        0x00c7 => op_00c7(arg, cpu, mmu),
-        0x00c8 => op_00c8(arg, cpu, mmu),
-        0x00c9 => op_00c9(arg, cpu, mmu),
-        0x00ca => op_00ca(arg, cpu, mmu),
-        0x00cb => op_00cb(arg, cpu, mmu),
-        0x00cc => op_00cc(arg, cpu, mmu),
-        0x00cd => op_00cd(arg, cpu, mmu),
-        0x00ce => op_00ce(arg, cpu, mmu),
-        0x00cf => op_00cf(arg, cpu, mmu),
-        0x00d0 => op_00d0(arg, cpu, mmu),
-        0x00d1 => op_00d1(arg, cpu, mmu),
-        0x00d2 => op_00d2(arg, cpu, mmu),
-        0x00d4 => op_00d4(arg, cpu, mmu),
-        0x00d5 => op_00d5(arg, cpu, mmu),
-        0x00d6 => op_00d6(arg, cpu, mmu),
-        0x00d7 => op_00d7(arg, cpu, mmu),
-        0x00d8 => op_00d8(arg, cpu, mmu),
-        0x00d9 => op_00d9(arg, cpu, mmu),
-        0x00da => op_00da(arg, cpu, mmu),
-        0x00dc => op_00dc(arg, cpu, mmu),
-        0x00de => op_00de(arg, cpu, mmu),
-        0x00df => op_00df(arg, cpu, mmu),
-        0x00e0 => op_00e0(arg, cpu, mmu),
-        0x00e1 => op_00e1(arg, cpu, mmu),
-        0x00e2 => op_00e2(arg, cpu, mmu),
-        0x00e5 => op_00e5(arg, cpu, mmu),
-        0x00e6 => op_00e6(arg, cpu, mmu),
-        0x00e7 => op_00e7(arg, cpu, mmu),
-        0x00e8 => op_00e8(arg, cpu, mmu),
-        0x00e9 => op_00e9(arg, cpu, mmu),
-        0x00ea => op_00ea(arg, cpu, mmu),
-        0x00ee => op_00ee(arg, cpu, mmu),
-        0x00ef => op_00ef(arg, cpu, mmu),
-        0x00f0 => op_00f0(arg, cpu, mmu),
-        0x00f1 => op_00f1(arg, cpu, mmu),
-        0x00f2 => op_00f2(arg, cpu, mmu),
-        0x00f3 => op_00f3(arg, cpu, mmu),
-        0x00f5 => op_00f5(arg, cpu, mmu),
-        0x00f6 => op_00f6(arg, cpu, mmu),
-        0x00f7 => op_00f7(arg, cpu, mmu),
-        0x00f8 => op_00f8(arg, cpu, mmu),
-        0x00f9 => op_00f9(arg, cpu, mmu),
-        0x00fa => op_00fa(arg, cpu, mmu),
-        0x00fb => op_00fb(arg, cpu, mmu),
-        0x00fe => op_00fe(arg, cpu, mmu),
-        0x00ff => op_00ff(arg, cpu, mmu),
-        0xcb00 => op_cb00(arg, cpu, mmu),
-        0xcb01 => op_cb01(arg, cpu, mmu),
-        0xcb02 => op_cb02(arg, cpu, mmu),
-        0xcb03 => op_cb03(arg, cpu, mmu),
-        0xcb04 => op_cb04(arg, cpu, mmu),
-        0xcb05 => op_cb05(arg, cpu, mmu),
-        0xcb06 => op_cb06(arg, cpu, mmu),
-        0xcb07 => op_cb07(arg, cpu, mmu),
-        0xcb08 => op_cb08(arg, cpu, mmu),
-        0xcb09 => op_cb09(arg, cpu, mmu),
-        0xcb0a => op_cb0a(arg, cpu, mmu),
-        0xcb0b => op_cb0b(arg, cpu, mmu),
-        0xcb0c => op_cb0c(arg, cpu, mmu),
-        0xcb0d => op_cb0d(arg, cpu, mmu),
-        0xcb0e => op_cb0e(arg, cpu, mmu),
-        0xcb0f => op_cb0f(arg, cpu, mmu),
-        0xcb10 => op_cb10(arg, cpu, mmu),
-        0xcb11 => op_cb11(arg, cpu, mmu),
-        0xcb12 => op_cb12(arg, cpu, mmu),
-        0xcb13 => op_cb13(arg, cpu, mmu),
-        0xcb14 => op_cb14(arg, cpu, mmu),
-        0xcb15 => op_cb15(arg, cpu, mmu),
-        0xcb16 => op_cb16(arg, cpu, mmu),
-        0xcb17 => op_cb17(arg, cpu, mmu),
-        0xcb18 => op_cb18(arg, cpu, mmu),
-        0xcb19 => op_cb19(arg, cpu, mmu),
-        0xcb1a => op_cb1a(arg, cpu, mmu),
-        0xcb1b => op_cb1b(arg, cpu, mmu),
-        0xcb1c => op_cb1c(arg, cpu, mmu),
-        0xcb1d => op_cb1d(arg, cpu, mmu),
-        0xcb1e => op_cb1e(arg, cpu, mmu),
-        0xcb1f => op_cb1f(arg, cpu, mmu),
-        0xcb20 => op_cb20(arg, cpu, mmu),
-        0xcb21 => op_cb21(arg, cpu, mmu),
-        0xcb22 => op_cb22(arg, cpu, mmu),
-        0xcb23 => op_cb23(arg, cpu, mmu),
-        0xcb24 => op_cb24(arg, cpu, mmu),
-        0xcb25 => op_cb25(arg, cpu, mmu),
-        0xcb26 => op_cb26(arg, cpu, mmu),
-        0xcb27 => op_cb27(arg, cpu, mmu),
-        0xcb28 => op_cb28(arg, cpu, mmu),
-        0xcb29 => op_cb29(arg, cpu, mmu),
-        0xcb2a => op_cb2a(arg, cpu, mmu),
-        0xcb2b => op_cb2b(arg, cpu, mmu),
-        0xcb2c => op_cb2c(arg, cpu, mmu),
-        0xcb2d => op_cb2d(arg, cpu, mmu),
-        0xcb2e => op_cb2e(arg, cpu, mmu),
-        0xcb2f => op_cb2f(arg, cpu, mmu),
-        0xcb30 => op_cb30(arg, cpu, mmu),
-        0xcb31 => op_cb31(arg, cpu, mmu),
-        0xcb32 => op_cb32(arg, cpu, mmu),
-        0xcb33 => op_cb33(arg, cpu, mmu),
-        0xcb34 => op_cb34(arg, cpu, mmu),
-        0xcb35 => op_cb35(arg, cpu, mmu),
-        0xcb36 => op_cb36(arg, cpu, mmu),
-        0xcb37 => op_cb37(arg, cpu, mmu),
-        0xcb38 => op_cb38(arg, cpu, mmu),
-        0xcb39 => op_cb39(arg, cpu, mmu),
-        0xcb3a => op_cb3a(arg, cpu, mmu),
-        0xcb3b => op_cb3b(arg, cpu, mmu),
-        0xcb3c => op_cb3c(arg, cpu, mmu),
-        0xcb3d => op_cb3d(arg, cpu, mmu),
-        0xcb3e => op_cb3e(arg, cpu, mmu),
-        0xcb3f => op_cb3f(arg, cpu, mmu),
-        0xcb40 => op_cb40(arg, cpu, mmu),
-        0xcb41 => op_cb41(arg, cpu, mmu),
-        0xcb42 => op_cb42(arg, cpu, mmu),
-        0xcb43 => op_cb43(arg, cpu, mmu),
-        0xcb44 => op_cb44(arg, cpu, mmu),
-        0xcb45 => op_cb45(arg, cpu, mmu),
-        0xcb46 => op_cb46(arg, cpu, mmu),
-        0xcb47 => op_cb47(arg, cpu, mmu),
-        0xcb48 => op_cb48(arg, cpu, mmu),
-        0xcb49 => op_cb49(arg, cpu, mmu),
-        0xcb4a => op_cb4a(arg, cpu, mmu),
-        0xcb4b => op_cb4b(arg, cpu, mmu),
-        0xcb4c => op_cb4c(arg, cpu, mmu),
-        0xcb4d => op_cb4d(arg, cpu, mmu),
-        0xcb4e => op_cb4e(arg, cpu, mmu),
-        0xcb4f => op_cb4f(arg, cpu, mmu),
-        0xcb50 => op_cb50(arg, cpu, mmu),
-        0xcb51 => op_cb51(arg, cpu, mmu),
-        0xcb52 => op_cb52(arg, cpu, mmu),
-        0xcb53 => op_cb53(arg, cpu, mmu),
-        0xcb54 => op_cb54(arg, cpu, mmu),
-        0xcb55 => op_cb55(arg, cpu, mmu),
-        0xcb56 => op_cb56(arg, cpu, mmu),
-        0xcb57 => op_cb57(arg, cpu, mmu),
-        0xcb58 => op_cb58(arg, cpu, mmu),
-        0xcb59 => op_cb59(arg, cpu, mmu),
-        0xcb5a => op_cb5a(arg, cpu, mmu),
-        0xcb5b => op_cb5b(arg, cpu, mmu),
-        0xcb5c => op_cb5c(arg, cpu, mmu),
-        0xcb5d => op_cb5d(arg, cpu, mmu),
-        0xcb5e => op_cb5e(arg, cpu, mmu),
-        0xcb5f => op_cb5f(arg, cpu, mmu),
-        0xcb60 => op_cb60(arg, cpu, mmu),
-        0xcb61 => op_cb61(arg, cpu, mmu),
-        0xcb62 => op_cb62(arg, cpu, mmu),
-        0xcb63 => op_cb63(arg, cpu, mmu),
-        0xcb64 => op_cb64(arg, cpu, mmu),
-        0xcb65 => op_cb65(arg, cpu, mmu),
-        0xcb66 => op_cb66(arg, cpu, mmu),
-        0xcb67 => op_cb67(arg, cpu, mmu),
-        0xcb68 => op_cb68(arg, cpu, mmu),
-        0xcb69 => op_cb69(arg, cpu, mmu),
-        0xcb6a => op_cb6a(arg, cpu, mmu),
-        0xcb6b => op_cb6b(arg, cpu, mmu),
-        0xcb6c => op_cb6c(arg, cpu, mmu),
-        0xcb6d => op_cb6d(arg, cpu, mmu),
-        0xcb6e => op_cb6e(arg, cpu, mmu),
-        0xcb6f => op_cb6f(arg, cpu, mmu),
-        0xcb70 => op_cb70(arg, cpu, mmu),
-        0xcb71 => op_cb71(arg, cpu, mmu),
-        0xcb72 => op_cb72(arg, cpu, mmu),
-        0xcb73 => op_cb73(arg, cpu, mmu),
-        0xcb74 => op_cb74(arg, cpu, mmu),
-        0xcb75 => op_cb75(arg, cpu, mmu),
-        0xcb76 => op_cb76(arg, cpu, mmu),
-        0xcb77 => op_cb77(arg, cpu, mmu),
-        0xcb78 => op_cb78(arg, cpu, mmu),
-        0xcb79 => op_cb79(arg, cpu, mmu),
-        0xcb7a => op_cb7a(arg, cpu, mmu),
-        0xcb7b => op_cb7b(arg, cpu, mmu),
-        0xcb7c => op_cb7c(arg, cpu, mmu),
-        0xcb7d => op_cb7d(arg, cpu, mmu),
-        0xcb7e => op_cb7e(arg, cpu, mmu),
-        0xcb7f => op_cb7f(arg, cpu, mmu),
-        0xcb80 => op_cb80(arg, cpu, mmu),
-        0xcb81 => op_cb81(arg, cpu, mmu),
-        0xcb82 => op_cb82(arg, cpu, mmu),
-        0xcb83 => op_cb83(arg, cpu, mmu),
-        0xcb84 => op_cb84(arg, cpu, mmu),
-        0xcb85 => op_cb85(arg, cpu, mmu),
-        0xcb86 => op_cb86(arg, cpu, mmu),
-        0xcb87 => op_cb87(arg, cpu, mmu),
-        0xcb88 => op_cb88(arg, cpu, mmu),
-        0xcb89 => op_cb89(arg, cpu, mmu),
-        0xcb8a => op_cb8a(arg, cpu, mmu),
-        0xcb8b => op_cb8b(arg, cpu, mmu),
-        0xcb8c => op_cb8c(arg, cpu, mmu),
-        0xcb8d => op_cb8d(arg, cpu, mmu),
-        0xcb8e => op_cb8e(arg, cpu, mmu),
-        0xcb8f => op_cb8f(arg, cpu, mmu),
-        0xcb90 => op_cb90(arg, cpu, mmu),
-        0xcb91 => op_cb91(arg, cpu, mmu),
-        0xcb92 => op_cb92(arg, cpu, mmu),
-        0xcb93 => op_cb93(arg, cpu, mmu),
-        0xcb94 => op_cb94(arg, cpu, mmu),
-        0xcb95 => op_cb95(arg, cpu, mmu),
-        0xcb96 => op_cb96(arg, cpu, mmu),
-        0xcb97 => op_cb97(arg, cpu, mmu),
-        0xcb98 => op_cb98(arg, cpu, mmu),
-        0xcb99 => op_cb99(arg, cpu, mmu),
-        0xcb9a => op_cb9a(arg, cpu, mmu),
-        0xcb9b => op_cb9b(arg, cpu, mmu),
-        0xcb9c => op_cb9c(arg, cpu, mmu),
-        0xcb9d => op_cb9d(arg, cpu, mmu),
-        0xcb9e => op_cb9e(arg, cpu, mmu),
-        0xcb9f => op_cb9f(arg, cpu, mmu),
-        0xcba0 => op_cba0(arg, cpu, mmu),
-        0xcba1 => op_cba1(arg, cpu, mmu),
-        0xcba2 => op_cba2(arg, cpu, mmu),
-        0xcba3 => op_cba3(arg, cpu, mmu),
-        0xcba4 => op_cba4(arg, cpu, mmu),
-        0xcba5 => op_cba5(arg, cpu, mmu),
-        0xcba6 => op_cba6(arg, cpu, mmu),
-        0xcba7 => op_cba7(arg, cpu, mmu),
-        0xcba8 => op_cba8(arg, cpu, mmu),
-        0xcba9 => op_cba9(arg, cpu, mmu),
-        0xcbaa => op_cbaa(arg, cpu, mmu),
-        0xcbab => op_cbab(arg, cpu, mmu),
-        0xcbac => op_cbac(arg, cpu, mmu),
-        0xcbad => op_cbad(arg, cpu, mmu),
-        0xcbae => op_cbae(arg, cpu, mmu),
-        0xcbaf => op_cbaf(arg, cpu, mmu),
-        0xcbb0 => op_cbb0(arg, cpu, mmu),
-        0xcbb1 => op_cbb1(arg, cpu, mmu),
-        0xcbb2 => op_cbb2(arg, cpu, mmu),
-        0xcbb3 => op_cbb3(arg, cpu, mmu),
-        0xcbb4 => op_cbb4(arg, cpu, mmu),
-        0xcbb5 => op_cbb5(arg, cpu, mmu),
-        0xcbb6 => op_cbb6(arg, cpu, mmu),
-        0xcbb7 => op_cbb7(arg, cpu, mmu),
-        0xcbb8 => op_cbb8(arg, cpu, mmu),
-        0xcbb9 => op_cbb9(arg, cpu, mmu),
-        0xcbba => op_cbba(arg, cpu, mmu),
-        0xcbbb => op_cbbb(arg, cpu, mmu),
-        0xcbbc => op_cbbc(arg, cpu, mmu),
-        0xcbbd => op_cbbd(arg, cpu, mmu),
-        0xcbbe => op_cbbe(arg, cpu, mmu),
-        0xcbbf => op_cbbf(arg, cpu, mmu),
-        0xcbc0 => op_cbc0(arg, cpu, mmu),
-        0xcbc1 => op_cbc1(arg, cpu, mmu),
-        0xcbc2 => op_cbc2(arg, cpu, mmu),
-        0xcbc3 => op_cbc3(arg, cpu, mmu),
-        0xcbc4 => op_cbc4(arg, cpu, mmu),
-        0xcbc5 => op_cbc5(arg, cpu, mmu),
-        0xcbc6 => op_cbc6(arg, cpu, mmu),
-        0xcbc7 => op_cbc7(arg, cpu, mmu),
-        0xcbc8 => op_cbc8(arg, cpu, mmu),
-        0xcbc9 => op_cbc9(arg, cpu, mmu),
-        0xcbca => op_cbca(arg, cpu, mmu),
-        0xcbcb => op_cbcb(arg, cpu, mmu),
-        0xcbcc => op_cbcc(arg, cpu, mmu),
-        0xcbcd => op_cbcd(arg, cpu, mmu),
-        0xcbce => op_cbce(arg, cpu, mmu),
-        0xcbcf => op_cbcf(arg, cpu, mmu),
-        0xcbd0 => op_cbd0(arg, cpu, mmu),
-        0xcbd1 => op_cbd1(arg, cpu, mmu),
-        0xcbd2 => op_cbd2(arg, cpu, mmu),
-        0xcbd3 => op_cbd3(arg, cpu, mmu),
-        0xcbd4 => op_cbd4(arg, cpu, mmu),
-        0xcbd5 => op_cbd5(arg, cpu, mmu),
-        0xcbd6 => op_cbd6(arg, cpu, mmu),
-        0xcbd7 => op_cbd7(arg, cpu, mmu),
-        0xcbd8 => op_cbd8(arg, cpu, mmu),
-        0xcbd9 => op_cbd9(arg, cpu, mmu),
-        0xcbda => op_cbda(arg, cpu, mmu),
-        0xcbdb => op_cbdb(arg, cpu, mmu),
-        0xcbdc => op_cbdc(arg, cpu, mmu),
-        0xcbdd => op_cbdd(arg, cpu, mmu),
-        0xcbde => op_cbde(arg, cpu, mmu),
-        0xcbdf => op_cbdf(arg, cpu, mmu),
-        0xcbe0 => op_cbe0(arg, cpu, mmu),
-        0xcbe1 => op_cbe1(arg, cpu, mmu),
-        0xcbe2 => op_cbe2(arg, cpu, mmu),
-        0xcbe3 => op_cbe3(arg, cpu, mmu),
-        0xcbe4 => op_cbe4(arg, cpu, mmu),
-        0xcbe5 => op_cbe5(arg, cpu, mmu),
-        0xcbe6 => op_cbe6(arg, cpu, mmu),
-        0xcbe7 => op_cbe7(arg, cpu, mmu),
-        0xcbe8 => op_cbe8(arg, cpu, mmu),
-        0xcbe9 => op_cbe9(arg, cpu, mmu),
-        0xcbea => op_cbea(arg, cpu, mmu),
-        0xcbeb => op_cbeb(arg, cpu, mmu),
-        0xcbec => op_cbec(arg, cpu, mmu),
-        0xcbed => op_cbed(arg, cpu, mmu),
-        0xcbee => op_cbee(arg, cpu, mmu),
-        0xcbef => op_cbef(arg, cpu, mmu),
-        0xcbf0 => op_cbf0(arg, cpu, mmu),
-        0xcbf1 => op_cbf1(arg, cpu, mmu),
-        0xcbf2 => op_cbf2(arg, cpu, mmu),
-        0xcbf3 => op_cbf3(arg, cpu, mmu),
-        0xcbf4 => op_cbf4(arg, cpu, mmu),
-        0xcbf5 => op_cbf5(arg, cpu, mmu),
-        0xcbf6 => op_cbf6(arg, cpu, mmu),
-        0xcbf7 => op_cbf7(arg, cpu, mmu),
-        0xcbf8 => op_cbf8(arg, cpu, mmu),
-        0xcbf9 => op_cbf9(arg, cpu, mmu),
-        0xcbfa => op_cbfa(arg, cpu, mmu),
-        0xcbfb => op_cbfb(arg, cpu, mmu),
-        0xcbfc => op_cbfc(arg, cpu, mmu),
-        0xcbfd => op_cbfd(arg, cpu, mmu),
-        0xcbfe => op_cbfe(arg, cpu, mmu),
-        0xcbff => op_cbff(arg, cpu, mmu),
-        _ => panic!("Invalid opcode: {:04x}: {:04x}", cpu.get_pc(), code),
+    match lookup(code) {
+        Some(inst) => inst.exec(arg, cpu, mmu),
+        None => {
+            error!("Invalid opcode: {:04x}: {:04x}", cpu.get_pc(), code);
+            cpu.lock();
+            (4, 0)
+        }
     }
 }
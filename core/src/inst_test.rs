@@ -0,0 +1,2702 @@
+//! The ALU instructions of the decoder checked against the reference model in `alu.rs`.
+//!
+//! Generated by `codegen tests` from the same instruction list as the decoder.
+
+#![allow(unused_variables)]
+
+use crate::alu::reference as r;
+use crate::cpu::Cpu;
+use crate::inst::lookup;
+use crate::mmu::Mmu;
+
+/// The address `(hl)` points to.
+const HL: u16 = 0xc000;
+
+/// The address of the instruction, followed by its immediate operand.
+const PC: u16 = 0xd000;
+
+/// The flags the instructions start with: only the carry is an input, and the other flags
+/// must not leak into the result.
+const FLAGS: [u8; 2] = [0x00, 0xf0];
+
+/// Every combination of the flags, for DAA which depends on N and H too.
+const ALL_FLAGS: [u8; 16] = [
+    0x00, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80, 0x90, 0xa0, 0xb0, 0xc0, 0xd0, 0xe0, 0xf0,
+];
+
+type Reference = fn(u8, u8, u8) -> (u8, u8);
+type Set = fn(&mut Cpu, &mut Mmu, u8);
+type Get = fn(&mut Cpu, &mut Mmu) -> u8;
+
+fn setup(a: u8, f: u8) -> Cpu {
+    let mut cpu = Cpu::new();
+    cpu.set_af(((a as u16) << 8) | f as u16);
+    cpu.set_hl(HL);
+    cpu.set_pc(PC);
+    cpu
+}
+
+/// Check an instruction which stores its result in the accumulator, for every value of the
+/// accumulator and of the operand, if it has one.
+fn check_a(code: u16, flags: &[u8], reference: Reference, set: Option<Set>) {
+    let inst = lookup(code).unwrap();
+    let mut mmu = Mmu::new();
+    let values = if set.is_some() { 0..=255 } else { 0..=0 };
+
+    for a in 0..=255 {
+        for v in values.clone() {
+            for f in flags {
+                let mut cpu = setup(a, *f);
+                if let Some(set) = set {
+                    set(&mut cpu, &mut mmu, v);
+                }
+                // The operand may be the accumulator itself.
+                let a = cpu.get_a();
+
+                inst.exec(1, &mut cpu, &mut mmu);
+
+                let (r, rf) = reference(a, v, *f);
+                assert_eq!(
+                    (cpu.get_a(), cpu.get_af() as u8),
+                    (r, rf),
+                    "{}: a={:02x} v={:02x} f={:02x}",
+                    inst.mnemonic,
+                    a,
+                    v,
+                    f
+                );
+            }
+        }
+    }
+}
+
+/// Check an instruction prefixed by `0xcb`, which stores its result in the operand.
+fn check_cb(code: u16, reference: Reference, set: Set, get: Get) {
+    let inst = lookup(code).unwrap();
+    let mut mmu = Mmu::new();
+
+    for v in 0..=255 {
+        for f in ALL_FLAGS.iter() {
+            let mut cpu = setup(0, *f);
+            set(&mut cpu, &mut mmu, v);
+
+            inst.exec(1, &mut cpu, &mut mmu);
+
+            let (r, rf) = reference(0, v, *f);
+            assert_eq!(
+                (get(&mut cpu, &mut mmu), cpu.get_af() as u8),
+                (r, rf),
+                "{}: v={:02x} f={:02x}",
+                inst.mnemonic,
+                v,
+                f
+            );
+        }
+    }
+}
+
+/// daa
+#[test]
+fn op_0027() {
+    check_a(0x0027, &ALL_FLAGS, r::daa, None);
+}
+
+/// add a,b
+#[test]
+fn op_0080() {
+    check_a(
+        0x0080,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// add a,c
+#[test]
+fn op_0081() {
+    check_a(
+        0x0081,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// add a,d
+#[test]
+fn op_0082() {
+    check_a(
+        0x0082,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// add a,e
+#[test]
+fn op_0083() {
+    check_a(
+        0x0083,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// add a,h
+#[test]
+fn op_0084() {
+    check_a(
+        0x0084,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// add a,l
+#[test]
+fn op_0085() {
+    check_a(
+        0x0085,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// add a,(hl)
+#[test]
+fn op_0086() {
+    check_a(
+        0x0086,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// add a,a
+#[test]
+fn op_0087() {
+    check_a(
+        0x0087,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// adc a,b
+#[test]
+fn op_0088() {
+    check_a(
+        0x0088,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// adc a,c
+#[test]
+fn op_0089() {
+    check_a(
+        0x0089,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// adc a,d
+#[test]
+fn op_008a() {
+    check_a(
+        0x008a,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// adc a,e
+#[test]
+fn op_008b() {
+    check_a(
+        0x008b,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// adc a,h
+#[test]
+fn op_008c() {
+    check_a(
+        0x008c,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// adc a,l
+#[test]
+fn op_008d() {
+    check_a(
+        0x008d,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// adc a,(hl)
+#[test]
+fn op_008e() {
+    check_a(
+        0x008e,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// adc a,a
+#[test]
+fn op_008f() {
+    check_a(
+        0x008f,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// sub b
+#[test]
+fn op_0090() {
+    check_a(
+        0x0090,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// sub c
+#[test]
+fn op_0091() {
+    check_a(
+        0x0091,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// sub d
+#[test]
+fn op_0092() {
+    check_a(
+        0x0092,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// sub e
+#[test]
+fn op_0093() {
+    check_a(
+        0x0093,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// sub h
+#[test]
+fn op_0094() {
+    check_a(
+        0x0094,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// sub l
+#[test]
+fn op_0095() {
+    check_a(
+        0x0095,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// sub (hl)
+#[test]
+fn op_0096() {
+    check_a(
+        0x0096,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// sub a
+#[test]
+fn op_0097() {
+    check_a(
+        0x0097,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// sbc a,b
+#[test]
+fn op_0098() {
+    check_a(
+        0x0098,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// sbc a,c
+#[test]
+fn op_0099() {
+    check_a(
+        0x0099,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// sbc a,d
+#[test]
+fn op_009a() {
+    check_a(
+        0x009a,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// sbc a,e
+#[test]
+fn op_009b() {
+    check_a(
+        0x009b,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// sbc a,h
+#[test]
+fn op_009c() {
+    check_a(
+        0x009c,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// sbc a,l
+#[test]
+fn op_009d() {
+    check_a(
+        0x009d,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// sbc a,(hl)
+#[test]
+fn op_009e() {
+    check_a(
+        0x009e,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// sbc a,a
+#[test]
+fn op_009f() {
+    check_a(
+        0x009f,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// and b
+#[test]
+fn op_00a0() {
+    check_a(
+        0x00a0,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// and c
+#[test]
+fn op_00a1() {
+    check_a(
+        0x00a1,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// and d
+#[test]
+fn op_00a2() {
+    check_a(
+        0x00a2,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// and e
+#[test]
+fn op_00a3() {
+    check_a(
+        0x00a3,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// and h
+#[test]
+fn op_00a4() {
+    check_a(
+        0x00a4,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// and l
+#[test]
+fn op_00a5() {
+    check_a(
+        0x00a5,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// and (hl)
+#[test]
+fn op_00a6() {
+    check_a(
+        0x00a6,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// and a
+#[test]
+fn op_00a7() {
+    check_a(
+        0x00a7,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// xor b
+#[test]
+fn op_00a8() {
+    check_a(
+        0x00a8,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// xor c
+#[test]
+fn op_00a9() {
+    check_a(
+        0x00a9,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// xor d
+#[test]
+fn op_00aa() {
+    check_a(
+        0x00aa,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// xor e
+#[test]
+fn op_00ab() {
+    check_a(
+        0x00ab,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// xor h
+#[test]
+fn op_00ac() {
+    check_a(
+        0x00ac,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// xor l
+#[test]
+fn op_00ad() {
+    check_a(
+        0x00ad,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// xor (hl)
+#[test]
+fn op_00ae() {
+    check_a(
+        0x00ae,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// xor a
+#[test]
+fn op_00af() {
+    check_a(
+        0x00af,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// or b
+#[test]
+fn op_00b0() {
+    check_a(
+        0x00b0,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// or c
+#[test]
+fn op_00b1() {
+    check_a(
+        0x00b1,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// or d
+#[test]
+fn op_00b2() {
+    check_a(
+        0x00b2,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// or e
+#[test]
+fn op_00b3() {
+    check_a(
+        0x00b3,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// or h
+#[test]
+fn op_00b4() {
+    check_a(
+        0x00b4,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// or l
+#[test]
+fn op_00b5() {
+    check_a(
+        0x00b5,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// or (hl)
+#[test]
+fn op_00b6() {
+    check_a(
+        0x00b6,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// or a
+#[test]
+fn op_00b7() {
+    check_a(
+        0x00b7,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// cp b
+#[test]
+fn op_00b8() {
+    check_a(
+        0x00b8,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_b(v);
+        }),
+    );
+}
+
+/// cp c
+#[test]
+fn op_00b9() {
+    check_a(
+        0x00b9,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_c(v);
+        }),
+    );
+}
+
+/// cp d
+#[test]
+fn op_00ba() {
+    check_a(
+        0x00ba,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_d(v);
+        }),
+    );
+}
+
+/// cp e
+#[test]
+fn op_00bb() {
+    check_a(
+        0x00bb,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_e(v);
+        }),
+    );
+}
+
+/// cp h
+#[test]
+fn op_00bc() {
+    check_a(
+        0x00bc,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_h(v);
+        }),
+    );
+}
+
+/// cp l
+#[test]
+fn op_00bd() {
+    check_a(
+        0x00bd,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_l(v);
+        }),
+    );
+}
+
+/// cp (hl)
+#[test]
+fn op_00be() {
+    check_a(
+        0x00be,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        }),
+    );
+}
+
+/// cp a
+#[test]
+fn op_00bf() {
+    check_a(
+        0x00bf,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            cpu.set_a(v);
+        }),
+    );
+}
+
+/// add a,d8
+#[test]
+fn op_00c6() {
+    check_a(
+        0x00c6,
+        &FLAGS,
+        r::add,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// adc a,d8
+#[test]
+fn op_00ce() {
+    check_a(
+        0x00ce,
+        &FLAGS,
+        r::adc,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// sub d8
+#[test]
+fn op_00d6() {
+    check_a(
+        0x00d6,
+        &FLAGS,
+        r::sub,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// sbc a,d8
+#[test]
+fn op_00de() {
+    check_a(
+        0x00de,
+        &FLAGS,
+        r::sbc,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// and d8
+#[test]
+fn op_00e6() {
+    check_a(
+        0x00e6,
+        &FLAGS,
+        r::and,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// xor d8
+#[test]
+fn op_00ee() {
+    check_a(
+        0x00ee,
+        &FLAGS,
+        r::xor,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// or d8
+#[test]
+fn op_00f6() {
+    check_a(
+        0x00f6,
+        &FLAGS,
+        r::or,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// cp d8
+#[test]
+fn op_00fe() {
+    check_a(
+        0x00fe,
+        &FLAGS,
+        r::cp,
+        Some(|cpu, mmu, v| {
+            mmu.write8(cpu.get_pc().wrapping_add(1), v);
+        }),
+    );
+}
+
+/// rlc b
+#[test]
+fn op_cb00() {
+    check_cb(
+        0xcb00,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// rlc c
+#[test]
+fn op_cb01() {
+    check_cb(
+        0xcb01,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// rlc d
+#[test]
+fn op_cb02() {
+    check_cb(
+        0xcb02,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// rlc e
+#[test]
+fn op_cb03() {
+    check_cb(
+        0xcb03,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// rlc h
+#[test]
+fn op_cb04() {
+    check_cb(
+        0xcb04,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// rlc l
+#[test]
+fn op_cb05() {
+    check_cb(
+        0xcb05,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// rlc (hl)
+#[test]
+fn op_cb06() {
+    check_cb(
+        0xcb06,
+        r::rlc,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// rlc a
+#[test]
+fn op_cb07() {
+    check_cb(
+        0xcb07,
+        r::rlc,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// rrc b
+#[test]
+fn op_cb08() {
+    check_cb(
+        0xcb08,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// rrc c
+#[test]
+fn op_cb09() {
+    check_cb(
+        0xcb09,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// rrc d
+#[test]
+fn op_cb0a() {
+    check_cb(
+        0xcb0a,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// rrc e
+#[test]
+fn op_cb0b() {
+    check_cb(
+        0xcb0b,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// rrc h
+#[test]
+fn op_cb0c() {
+    check_cb(
+        0xcb0c,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// rrc l
+#[test]
+fn op_cb0d() {
+    check_cb(
+        0xcb0d,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// rrc (hl)
+#[test]
+fn op_cb0e() {
+    check_cb(
+        0xcb0e,
+        r::rrc,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// rrc a
+#[test]
+fn op_cb0f() {
+    check_cb(
+        0xcb0f,
+        r::rrc,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// rl b
+#[test]
+fn op_cb10() {
+    check_cb(
+        0xcb10,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// rl c
+#[test]
+fn op_cb11() {
+    check_cb(
+        0xcb11,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// rl d
+#[test]
+fn op_cb12() {
+    check_cb(
+        0xcb12,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// rl e
+#[test]
+fn op_cb13() {
+    check_cb(
+        0xcb13,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// rl h
+#[test]
+fn op_cb14() {
+    check_cb(
+        0xcb14,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// rl l
+#[test]
+fn op_cb15() {
+    check_cb(
+        0xcb15,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// rl (hl)
+#[test]
+fn op_cb16() {
+    check_cb(
+        0xcb16,
+        r::rl,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// rl a
+#[test]
+fn op_cb17() {
+    check_cb(
+        0xcb17,
+        r::rl,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// rr b
+#[test]
+fn op_cb18() {
+    check_cb(
+        0xcb18,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// rr c
+#[test]
+fn op_cb19() {
+    check_cb(
+        0xcb19,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// rr d
+#[test]
+fn op_cb1a() {
+    check_cb(
+        0xcb1a,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// rr e
+#[test]
+fn op_cb1b() {
+    check_cb(
+        0xcb1b,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// rr h
+#[test]
+fn op_cb1c() {
+    check_cb(
+        0xcb1c,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// rr l
+#[test]
+fn op_cb1d() {
+    check_cb(
+        0xcb1d,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// rr (hl)
+#[test]
+fn op_cb1e() {
+    check_cb(
+        0xcb1e,
+        r::rr,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// rr a
+#[test]
+fn op_cb1f() {
+    check_cb(
+        0xcb1f,
+        r::rr,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// sla b
+#[test]
+fn op_cb20() {
+    check_cb(
+        0xcb20,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// sla c
+#[test]
+fn op_cb21() {
+    check_cb(
+        0xcb21,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// sla d
+#[test]
+fn op_cb22() {
+    check_cb(
+        0xcb22,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// sla e
+#[test]
+fn op_cb23() {
+    check_cb(
+        0xcb23,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// sla h
+#[test]
+fn op_cb24() {
+    check_cb(
+        0xcb24,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// sla l
+#[test]
+fn op_cb25() {
+    check_cb(
+        0xcb25,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// sla (hl)
+#[test]
+fn op_cb26() {
+    check_cb(
+        0xcb26,
+        r::sla,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// sla a
+#[test]
+fn op_cb27() {
+    check_cb(
+        0xcb27,
+        r::sla,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// sra b
+#[test]
+fn op_cb28() {
+    check_cb(
+        0xcb28,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// sra c
+#[test]
+fn op_cb29() {
+    check_cb(
+        0xcb29,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// sra d
+#[test]
+fn op_cb2a() {
+    check_cb(
+        0xcb2a,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// sra e
+#[test]
+fn op_cb2b() {
+    check_cb(
+        0xcb2b,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// sra h
+#[test]
+fn op_cb2c() {
+    check_cb(
+        0xcb2c,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// sra l
+#[test]
+fn op_cb2d() {
+    check_cb(
+        0xcb2d,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// sra (hl)
+#[test]
+fn op_cb2e() {
+    check_cb(
+        0xcb2e,
+        r::sra,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// sra a
+#[test]
+fn op_cb2f() {
+    check_cb(
+        0xcb2f,
+        r::sra,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// swap b
+#[test]
+fn op_cb30() {
+    check_cb(
+        0xcb30,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// swap c
+#[test]
+fn op_cb31() {
+    check_cb(
+        0xcb31,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// swap d
+#[test]
+fn op_cb32() {
+    check_cb(
+        0xcb32,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// swap e
+#[test]
+fn op_cb33() {
+    check_cb(
+        0xcb33,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// swap h
+#[test]
+fn op_cb34() {
+    check_cb(
+        0xcb34,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// swap l
+#[test]
+fn op_cb35() {
+    check_cb(
+        0xcb35,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// swap (hl)
+#[test]
+fn op_cb36() {
+    check_cb(
+        0xcb36,
+        r::swap,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// swap a
+#[test]
+fn op_cb37() {
+    check_cb(
+        0xcb37,
+        r::swap,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// srl b
+#[test]
+fn op_cb38() {
+    check_cb(
+        0xcb38,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// srl c
+#[test]
+fn op_cb39() {
+    check_cb(
+        0xcb39,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// srl d
+#[test]
+fn op_cb3a() {
+    check_cb(
+        0xcb3a,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// srl e
+#[test]
+fn op_cb3b() {
+    check_cb(
+        0xcb3b,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// srl h
+#[test]
+fn op_cb3c() {
+    check_cb(
+        0xcb3c,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// srl l
+#[test]
+fn op_cb3d() {
+    check_cb(
+        0xcb3d,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// srl (hl)
+#[test]
+fn op_cb3e() {
+    check_cb(
+        0xcb3e,
+        r::srl,
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// srl a
+#[test]
+fn op_cb3f() {
+    check_cb(
+        0xcb3f,
+        r::srl,
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 0,b
+#[test]
+fn op_cb40() {
+    check_cb(
+        0xcb40,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 0,c
+#[test]
+fn op_cb41() {
+    check_cb(
+        0xcb41,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 0,d
+#[test]
+fn op_cb42() {
+    check_cb(
+        0xcb42,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 0,e
+#[test]
+fn op_cb43() {
+    check_cb(
+        0xcb43,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 0,h
+#[test]
+fn op_cb44() {
+    check_cb(
+        0xcb44,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 0,l
+#[test]
+fn op_cb45() {
+    check_cb(
+        0xcb45,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 0,(hl)
+#[test]
+fn op_cb46() {
+    check_cb(
+        0xcb46,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 0,a
+#[test]
+fn op_cb47() {
+    check_cb(
+        0xcb47,
+        |_, v, f| r::bit(0, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 1,b
+#[test]
+fn op_cb48() {
+    check_cb(
+        0xcb48,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 1,c
+#[test]
+fn op_cb49() {
+    check_cb(
+        0xcb49,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 1,d
+#[test]
+fn op_cb4a() {
+    check_cb(
+        0xcb4a,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 1,e
+#[test]
+fn op_cb4b() {
+    check_cb(
+        0xcb4b,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 1,h
+#[test]
+fn op_cb4c() {
+    check_cb(
+        0xcb4c,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 1,l
+#[test]
+fn op_cb4d() {
+    check_cb(
+        0xcb4d,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 1,(hl)
+#[test]
+fn op_cb4e() {
+    check_cb(
+        0xcb4e,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 1,a
+#[test]
+fn op_cb4f() {
+    check_cb(
+        0xcb4f,
+        |_, v, f| r::bit(1, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 2,b
+#[test]
+fn op_cb50() {
+    check_cb(
+        0xcb50,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 2,c
+#[test]
+fn op_cb51() {
+    check_cb(
+        0xcb51,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 2,d
+#[test]
+fn op_cb52() {
+    check_cb(
+        0xcb52,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 2,e
+#[test]
+fn op_cb53() {
+    check_cb(
+        0xcb53,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 2,h
+#[test]
+fn op_cb54() {
+    check_cb(
+        0xcb54,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 2,l
+#[test]
+fn op_cb55() {
+    check_cb(
+        0xcb55,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 2,(hl)
+#[test]
+fn op_cb56() {
+    check_cb(
+        0xcb56,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 2,a
+#[test]
+fn op_cb57() {
+    check_cb(
+        0xcb57,
+        |_, v, f| r::bit(2, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 3,b
+#[test]
+fn op_cb58() {
+    check_cb(
+        0xcb58,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 3,c
+#[test]
+fn op_cb59() {
+    check_cb(
+        0xcb59,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 3,d
+#[test]
+fn op_cb5a() {
+    check_cb(
+        0xcb5a,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 3,e
+#[test]
+fn op_cb5b() {
+    check_cb(
+        0xcb5b,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 3,h
+#[test]
+fn op_cb5c() {
+    check_cb(
+        0xcb5c,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 3,l
+#[test]
+fn op_cb5d() {
+    check_cb(
+        0xcb5d,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 3,(hl)
+#[test]
+fn op_cb5e() {
+    check_cb(
+        0xcb5e,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 3,a
+#[test]
+fn op_cb5f() {
+    check_cb(
+        0xcb5f,
+        |_, v, f| r::bit(3, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 4,b
+#[test]
+fn op_cb60() {
+    check_cb(
+        0xcb60,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 4,c
+#[test]
+fn op_cb61() {
+    check_cb(
+        0xcb61,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 4,d
+#[test]
+fn op_cb62() {
+    check_cb(
+        0xcb62,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 4,e
+#[test]
+fn op_cb63() {
+    check_cb(
+        0xcb63,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 4,h
+#[test]
+fn op_cb64() {
+    check_cb(
+        0xcb64,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 4,l
+#[test]
+fn op_cb65() {
+    check_cb(
+        0xcb65,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 4,(hl)
+#[test]
+fn op_cb66() {
+    check_cb(
+        0xcb66,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 4,a
+#[test]
+fn op_cb67() {
+    check_cb(
+        0xcb67,
+        |_, v, f| r::bit(4, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 5,b
+#[test]
+fn op_cb68() {
+    check_cb(
+        0xcb68,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 5,c
+#[test]
+fn op_cb69() {
+    check_cb(
+        0xcb69,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 5,d
+#[test]
+fn op_cb6a() {
+    check_cb(
+        0xcb6a,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 5,e
+#[test]
+fn op_cb6b() {
+    check_cb(
+        0xcb6b,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 5,h
+#[test]
+fn op_cb6c() {
+    check_cb(
+        0xcb6c,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 5,l
+#[test]
+fn op_cb6d() {
+    check_cb(
+        0xcb6d,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 5,(hl)
+#[test]
+fn op_cb6e() {
+    check_cb(
+        0xcb6e,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 5,a
+#[test]
+fn op_cb6f() {
+    check_cb(
+        0xcb6f,
+        |_, v, f| r::bit(5, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 6,b
+#[test]
+fn op_cb70() {
+    check_cb(
+        0xcb70,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 6,c
+#[test]
+fn op_cb71() {
+    check_cb(
+        0xcb71,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 6,d
+#[test]
+fn op_cb72() {
+    check_cb(
+        0xcb72,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 6,e
+#[test]
+fn op_cb73() {
+    check_cb(
+        0xcb73,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 6,h
+#[test]
+fn op_cb74() {
+    check_cb(
+        0xcb74,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 6,l
+#[test]
+fn op_cb75() {
+    check_cb(
+        0xcb75,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 6,(hl)
+#[test]
+fn op_cb76() {
+    check_cb(
+        0xcb76,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 6,a
+#[test]
+fn op_cb77() {
+    check_cb(
+        0xcb77,
+        |_, v, f| r::bit(6, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
+
+/// bit 7,b
+#[test]
+fn op_cb78() {
+    check_cb(
+        0xcb78,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_b(v);
+        },
+        |cpu, mmu| cpu.get_b(),
+    );
+}
+
+/// bit 7,c
+#[test]
+fn op_cb79() {
+    check_cb(
+        0xcb79,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_c(v);
+        },
+        |cpu, mmu| cpu.get_c(),
+    );
+}
+
+/// bit 7,d
+#[test]
+fn op_cb7a() {
+    check_cb(
+        0xcb7a,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_d(v);
+        },
+        |cpu, mmu| cpu.get_d(),
+    );
+}
+
+/// bit 7,e
+#[test]
+fn op_cb7b() {
+    check_cb(
+        0xcb7b,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_e(v);
+        },
+        |cpu, mmu| cpu.get_e(),
+    );
+}
+
+/// bit 7,h
+#[test]
+fn op_cb7c() {
+    check_cb(
+        0xcb7c,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_h(v);
+        },
+        |cpu, mmu| cpu.get_h(),
+    );
+}
+
+/// bit 7,l
+#[test]
+fn op_cb7d() {
+    check_cb(
+        0xcb7d,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_l(v);
+        },
+        |cpu, mmu| cpu.get_l(),
+    );
+}
+
+/// bit 7,(hl)
+#[test]
+fn op_cb7e() {
+    check_cb(
+        0xcb7e,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            mmu.write8(cpu.get_hl(), v);
+        },
+        |cpu, mmu| mmu.read8(cpu.get_hl()),
+    );
+}
+
+/// bit 7,a
+#[test]
+fn op_cb7f() {
+    check_cb(
+        0xcb7f,
+        |_, v, f| r::bit(7, v, f),
+        |cpu, mmu, v| {
+            cpu.set_a(v);
+        },
+        |cpu, mmu| cpu.get_a(),
+    );
+}
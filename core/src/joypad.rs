@@ -2,26 +2,78 @@ use crate::device::IoHandler;
 use crate::hardware::{HardwareHandle, Key};
 use crate::ic::Irq;
 use crate::mmu::{MemRead, MemWrite, Mmu};
-use log::*;
+use crate::sgb::{Sgb, SGB_PACKET_LEN};
+use alloc::vec::Vec;
+
+/// The clock cycles between two polls of the keys, about 1 kHz.
+pub const POLL_CYCLES: usize = 4194;
+
+/// The keys games take as a request to reset.
+const RESET_COMBO: [Key; 4] = [Key::A, Key::B, Key::Start, Key::Select];
 
 pub struct Joypad {
     hw: HardwareHandle,
     irq: Irq,
     select: u8,
     pressed: u8,
+    /// Watch for the reset combination.
+    watch_combo: bool,
+    /// All the keys of the reset combination are held.
+    combo_held: bool,
+    /// The reset combination was pressed since the last call to `take_combo`.
+    combo: bool,
+    /// The receiver of the Super Game Boy command packets.
+    sgb: Option<Sgb>,
 }
 
 impl Joypad {
-    pub fn new(hw: HardwareHandle, irq: Irq) -> Self {
+    pub fn new(hw: HardwareHandle, irq: Irq, watch_combo: bool, sgb: bool) -> Self {
         Self {
             hw,
             irq,
             select: 0xff,
             pressed: 0x0f,
+            watch_combo,
+            combo_held: false,
+            combo: false,
+            sgb: if sgb { Some(Sgb::new()) } else { None },
         }
     }
 
+    /// Restore the power-on state.
+    ///
+    /// The held keys are remembered so that a reset combination held through the reset
+    /// isn't reported again.
+    pub fn reset(&mut self) {
+        let combo_held = self.combo_held;
+        let sgb = self.sgb.is_some();
+        *self = Joypad::new(self.hw.clone(), self.irq.clone(), self.watch_combo, sgb);
+        self.combo_held = combo_held;
+    }
+
+    /// Return whether the reset combination A+B+Start+Select was pressed since the last call, and reset it.
+    pub fn take_combo(&mut self) -> bool {
+        core::mem::replace(&mut self.combo, false)
+    }
+
+    /// Return the command packets received by the Super Game Boy since the last call.
+    pub fn take_sgb_packets(&mut self) -> Vec<[u8; SGB_PACKET_LEN]> {
+        self.sgb
+            .as_mut()
+            .map(|sgb| sgb.take_packets())
+            .unwrap_or_default()
+    }
+
     pub fn poll(&mut self) {
+        if self.watch_combo {
+            let held = {
+                let mut hw = self.hw.get().borrow_mut();
+                RESET_COMBO.iter().all(|key| hw.joypad_pressed(key.clone()))
+            };
+            self.combo |= held && !self.combo_held;
+            self.combo_held = held;
+        }
+
         let pressed = self.check();
 
         for i in 0..4 {
@@ -38,20 +90,26 @@ impl Joypad {
     fn check(&self) -> u8 {
         let p = |key| self.hw.get().borrow_mut().joypad_pressed(key);
 
-        let mut value = 0;
+        let mut value = 0x0f;
 
+        // A pressed key pulls its line low, so with both groups selected
+        // a line reads low if a key of either group is pressed.
         if self.select & 0x10 == 0 {
-            value |= if p(Key::Right) { 0x00 } else { 0x01 };
-            value |= if p(Key::Left) { 0x00 } else { 0x02 };
-            value |= if p(Key::Up) { 0x00 } else { 0x04 };
-            value |= if p(Key::Down) { 0x00 } else { 0x08 };
-        } else if self.select & 0x20 == 0 {
-            value |= if p(Key::A) { 0x00 } else { 0x01 };
-            value |= if p(Key::B) { 0x00 } else { 0x02 };
-            value |= if p(Key::Select) { 0x00 } else { 0x04 };
-            value |= if p(Key::Start) { 0x0 } else { 0x08 };
-        } else {
-            value = 0x0f;
+            let mut dpad = 0;
+            dpad |= if p(Key::Right) { 0x00 } else { 0x01 };
+            dpad |= if p(Key::Left) { 0x00 } else { 0x02 };
+            dpad |= if p(Key::Up) { 0x00 } else { 0x04 };
+            dpad |= if p(Key::Down) { 0x00 } else { 0x08 };
+            value &= dpad;
+        }
+
+        if self.select & 0x20 == 0 {
+            let mut buttons = 0;
+            buttons |= if p(Key::A) { 0x00 } else { 0x01 };
+            buttons |= if p(Key::B) { 0x00 } else { 0x02 };
+            buttons |= if p(Key::Select) { 0x00 } else { 0x04 };
+            buttons |= if p(Key::Start) { 0x00 } else { 0x08 };
+            value &= buttons;
         }
 
         value
@@ -63,7 +121,8 @@ impl IoHandler for Joypad {
         if addr == 0xff00 {
             debug!("Joypad read: dir: {:02x}", self.select);
 
-            MemRead::Replace(self.check())
+            // The upper two bits are unused and read high.
+            MemRead::Replace(0xc0 | self.select & 0x30 | self.check())
         } else {
             MemRead::PassThrough
         }
@@ -72,6 +131,9 @@ impl IoHandler for Joypad {
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
         if addr == 0xff00 {
             self.select = value & 0xf0;
+            if let Some(sgb) = self.sgb.as_mut() {
+                sgb.write(self.select);
+            }
         }
         MemWrite::PassThrough
     }
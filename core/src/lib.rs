@@ -2,7 +2,8 @@
 //! `rgy` is no-std cross-platform Rust GameBoy emulator library.
 //!
 //! The users of this library only needs to implement [`Hardware`][] trait, which abstracts OS-specific function.
-//! Once it's implemented, the emulator works.
+//! Once it's implemented, the emulator works. The optional capabilities, [`SavePersistence`][],
+//! [`SerialPort`][], [`SchedHook`][] and [`EventHook`][], come with default implementations.
 //!
 //! The following code is the example which just implements `Hardware`. The implementation does nothing.
 //! You can replace the body of each function with the actual meaningful logic.
@@ -57,7 +58,9 @@
 //!             .expect("Couldn't get epoch");
 //!         epoch.as_micros() as u64
 //!     }
+//! }
 //!
+//! impl rgy::SerialPort for Hardware {
 //!     // Called when the emulator sends a byte to the serial port.
 //!     fn send_byte(&mut self, _b: u8) {
 //!         // TODO: Send a byte to a serial port.
@@ -68,14 +71,18 @@
 //!         // TODO: Check the status of the serial port and read a byte if any.
 //!         None
 //!     }
+//! }
 //!
+//! impl rgy::SchedHook for Hardware {
 //!     // Called every time the emulator executes an instruction.
 //!     fn sched(&mut self) -> bool {
 //!         // TODO: Do some periodic jobs if any. Return `true` to continue, `false` to stop the emulator.
 //!         println!("It's running!");
 //!         true
 //!     }
+//! }
 //!
+//! impl rgy::SavePersistence for Hardware {
 //!     // Called when the emulator stores the save data to the battery-backed RAM.
 //!     fn load_ram(&mut self, size: usize) -> Vec<u8> {
 //!         // TODO: Return save data.
@@ -88,6 +95,14 @@
 //!     }
 //! }
 //!
+//! impl rgy::EventHook for Hardware {
+//!     // Called when the emulated hardware changes its state, e.g. the cartridge switches banks.
+//!     fn event(&mut self, event: rgy::Event) {
+//!         // TODO: Trace the event if needed.
+//!         println!("Event: {:?}", event);
+//!     }
+//! }
+//!
 //! fn main() {
 //!     // Create the default config.
 //!     let cfg = Config::new();
@@ -107,19 +122,45 @@
 #![warn(missing_docs)]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
+
+#[cfg(not(feature = "log"))]
+#[macro_use]
+mod nolog;
 
 mod alu;
+mod buffer;
 mod cgb;
+mod coverage;
 mod dma;
+#[cfg(any(feature = "wasm", feature = "ffi", feature = "libretro"))]
+mod embed;
+mod error;
 mod fc;
 mod gpu;
 mod ic;
+#[cfg(test)]
+mod inst_test;
 mod joypad;
 mod mbc;
+mod profile;
+mod rng;
+mod sched;
+#[cfg(feature = "serial")]
 mod serial;
+mod sgb;
 mod sound;
+mod stats;
+mod symbols;
 mod system;
 mod timer;
+mod unused;
+mod vector;
 
 /// CPU state.
 pub mod cpu;
@@ -136,8 +177,79 @@ pub mod inst;
 /// Handles memory and I/O port access from the CPU.
 pub mod mmu;
 
+/// Save data compatible with other emulators.
+pub mod save;
+
+/// Capture of the sound and video output.
+#[cfg(feature = "capture")]
+pub mod capture;
+
+/// Workloads to measure the emulator performance.
+#[cfg(feature = "bench")]
+pub mod bench;
+
+/// Entry points for the fuzz targets.
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+
+/// Run loop for desktop frontends.
+#[cfg(feature = "std")]
+pub mod runner;
+
+/// Bindings for JavaScript.
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Bindings for C.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+/// Libretro core.
+#[cfg(feature = "libretro")]
+pub mod libretro;
+
 /// Hardware interface, which abstracts OS-specific functions.
 mod hardware;
 
-pub use crate::hardware::{Hardware, Key, Stream, VRAM_HEIGHT, VRAM_WIDTH};
-pub use crate::system::{run, run_debug, Config, System};
+/// Fails the build if the emulator state doesn't fit in the given number of bytes.
+///
+/// The size checked is [`System::STATE_SIZE`][] for the enabled features.
+///
+/// ```rust
+/// // Make sure the emulator fits in 256KB of RAM.
+/// rgy::assert_state_size!(256 * 1024);
+/// ```
+#[macro_export]
+macro_rules! assert_state_size {
+    ($budget:expr) => {
+        const _: () = assert!(
+            $crate::System::<$crate::debug::NullDebugger>::STATE_SIZE <= $budget,
+            "rgy emulator state exceeds the RAM budget"
+        );
+    };
+}
+
+pub use crate::coverage::Coverage;
+pub use crate::error::Error;
+pub use crate::gpu::{
+    CgbColor, ColorMapper, DmgColor, LineCacheStats, NullObserver, Rgb888, VideoObserver,
+    DRAW_MIN_DOTS, FRAME_DOTS, FRAME_LINES, HBLANK_MAX_DOTS, LINE_DOTS, OAM_SCAN_DOTS,
+};
+pub use crate::hardware::{
+    ChannelSample, Event, EventHook, Hardware, Key, SavePersistence, SchedHook, SerialPort, Stream,
+    VRAM_HEIGHT, VRAM_WIDTH,
+};
+pub use crate::ic::InterruptState;
+pub use crate::mbc::{Header, Mapper, MbcState};
+pub use crate::profile::{OpcodeStat, PageStat, ProfileReport};
+pub use crate::rng::Rng;
+#[cfg(feature = "serial")]
+pub use crate::serial::{QueueFull, SerialDevice, SERIAL_QUEUE_LEN};
+pub use crate::sgb::SGB_PACKET_LEN;
+pub use crate::stats::{FrameStats, Stats};
+pub use crate::symbols::Symbols;
+pub use crate::system::{
+    run, run_debug, Buffers, Config, CycleStepped, Disconnected, InstructionStepped, Model, Policy,
+    PollData, RamFill, ResetCombo, StepOutcome, Stepping, System,
+};
+pub use crate::vector::{Vector, VectorAction, VectorHook};
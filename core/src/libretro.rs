@@ -0,0 +1,432 @@
+//! The libretro API, to run the emulator as a core of RetroArch and other libretro frontends.
+//!
+//! Build the core with `cargo rustc --release --features libretro --crate-type cdylib`.
+//! Savestates aren't supported yet; the battery-backed RAM is exposed as `RETRO_MEMORY_SAVE_RAM`.
+
+use crate::embed::Embedded;
+use crate::{VRAM_HEIGHT, VRAM_WIDTH};
+use alloc::vec::Vec;
+use core::ffi::{c_char, c_void};
+use core::{ptr, slice};
+
+const RETRO_API_VERSION: u32 = 1;
+
+const RETRO_ENVIRONMENT_SET_PIXEL_FORMAT: u32 = 10;
+const RETRO_PIXEL_FORMAT_XRGB8888: u32 = 1;
+
+const RETRO_DEVICE_JOYPAD: u32 = 1;
+
+const RETRO_MEMORY_SAVE_RAM: u32 = 0;
+
+const RETRO_REGION_NTSC: u32 = 0;
+
+/// The output sample rate of the audio.
+const SAMPLE_RATE: u32 = 44100;
+
+/// The libretro joypad buttons in the bit order of [`Embedded::set_buttons`][].
+const BUTTONS: [u32; 8] = [
+    7, // RIGHT
+    6, // LEFT
+    4, // UP
+    5, // DOWN
+    8, // A
+    0, // B
+    2, // SELECT
+    3, // START
+];
+
+/// Information about the core.
+#[repr(C)]
+pub struct RetroSystemInfo {
+    library_name: *const c_char,
+    library_version: *const c_char,
+    valid_extensions: *const c_char,
+    need_fullpath: bool,
+    block_extract: bool,
+}
+
+/// The geometry of the frames.
+#[repr(C)]
+pub struct RetroGameGeometry {
+    base_width: u32,
+    base_height: u32,
+    max_width: u32,
+    max_height: u32,
+    aspect_ratio: f32,
+}
+
+/// The timing of the frames and the audio.
+#[repr(C)]
+pub struct RetroSystemTiming {
+    fps: f64,
+    sample_rate: f64,
+}
+
+/// The audio and video information of the running game.
+#[repr(C)]
+pub struct RetroSystemAvInfo {
+    geometry: RetroGameGeometry,
+    timing: RetroSystemTiming,
+}
+
+/// The game to load.
+#[repr(C)]
+pub struct RetroGameInfo {
+    path: *const c_char,
+    data: *const c_void,
+    size: usize,
+    meta: *const c_char,
+}
+
+type EnvironmentFn = unsafe extern "C" fn(cmd: u32, data: *mut c_void) -> bool;
+type VideoRefreshFn =
+    unsafe extern "C" fn(data: *const c_void, width: u32, height: u32, pitch: usize);
+type AudioSampleFn = unsafe extern "C" fn(left: i16, right: i16);
+type AudioSampleBatchFn = unsafe extern "C" fn(data: *const i16, frames: usize) -> usize;
+type InputPollFn = unsafe extern "C" fn();
+type InputStateFn = unsafe extern "C" fn(port: u32, device: u32, index: u32, id: u32) -> i16;
+
+struct Callbacks {
+    environment: Option<EnvironmentFn>,
+    video_refresh: Option<VideoRefreshFn>,
+    audio_sample_batch: Option<AudioSampleBatchFn>,
+    input_poll: Option<InputPollFn>,
+    input_state: Option<InputStateFn>,
+}
+
+struct Core {
+    emu: Embedded,
+    samples: Vec<f32>,
+    audio: Vec<i16>,
+    /// The fraction of an audio sample carried over to the next frame.
+    audio_frac: f64,
+}
+
+static mut CALLBACKS: Callbacks = Callbacks {
+    environment: None,
+    video_refresh: None,
+    audio_sample_batch: None,
+    input_poll: None,
+    input_state: None,
+};
+
+static mut CORE: Option<Core> = None;
+
+/// Libretro calls the core from a single thread.
+unsafe fn callbacks() -> &'static mut Callbacks {
+    &mut *ptr::addr_of_mut!(CALLBACKS)
+}
+
+unsafe fn core() -> Option<&'static mut Core> {
+    (*ptr::addr_of_mut!(CORE)).as_mut()
+}
+
+/// Return the libretro API version implemented.
+#[no_mangle]
+pub extern "C" fn retro_api_version() -> u32 {
+    RETRO_API_VERSION
+}
+
+/// Initialize the core.
+#[no_mangle]
+pub extern "C" fn retro_init() {}
+
+/// Deinitialize the core.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_deinit() {
+    *ptr::addr_of_mut!(CORE) = None;
+}
+
+/// Set the environment callback.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_set_environment(f: EnvironmentFn) {
+    callbacks().environment = Some(f);
+}
+
+/// Set the video refresh callback.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_set_video_refresh(f: VideoRefreshFn) {
+    callbacks().video_refresh = Some(f);
+}
+
+/// Set the single audio sample callback, which is unused in favor of the batch one.
+#[no_mangle]
+pub extern "C" fn retro_set_audio_sample(_f: AudioSampleFn) {}
+
+/// Set the audio sample batch callback.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_set_audio_sample_batch(f: AudioSampleBatchFn) {
+    callbacks().audio_sample_batch = Some(f);
+}
+
+/// Set the input poll callback.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_set_input_poll(f: InputPollFn) {
+    callbacks().input_poll = Some(f);
+}
+
+/// Set the input state callback.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_set_input_state(f: InputStateFn) {
+    callbacks().input_state = Some(f);
+}
+
+/// Set the device of the controller port. Only the joypad is supported.
+#[no_mangle]
+pub extern "C" fn retro_set_controller_port_device(_port: u32, _device: u32) {}
+
+/// Fill the information about the core.
+///
+/// # Safety
+///
+/// `info` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn retro_get_system_info(info: *mut RetroSystemInfo) {
+    *info = RetroSystemInfo {
+        library_name: b"rgy\0".as_ptr() as *const c_char,
+        library_version: concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char,
+        valid_extensions: b"gb|gbc\0".as_ptr() as *const c_char,
+        need_fullpath: false,
+        block_extract: false,
+    };
+}
+
+/// Fill the audio and video information.
+///
+/// # Safety
+///
+/// `info` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn retro_get_system_av_info(info: *mut RetroSystemAvInfo) {
+    let fps = core().map(|c| c.emu.fps()).unwrap_or(0.0);
+
+    *info = RetroSystemAvInfo {
+        geometry: RetroGameGeometry {
+            base_width: VRAM_WIDTH as u32,
+            base_height: VRAM_HEIGHT as u32,
+            max_width: VRAM_WIDTH as u32,
+            max_height: VRAM_HEIGHT as u32,
+            aspect_ratio: VRAM_WIDTH as f32 / VRAM_HEIGHT as f32,
+        },
+        timing: RetroSystemTiming {
+            fps,
+            sample_rate: SAMPLE_RATE as f64,
+        },
+    };
+}
+
+/// Load the game from the data passed by the frontend.
+///
+/// # Safety
+///
+/// `game` must be null or point to a valid game info whose data is `size` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn retro_load_game(game: *const RetroGameInfo) -> bool {
+    let game = match game.as_ref() {
+        Some(game) if !game.data.is_null() => game,
+        _ => return false,
+    };
+
+    if let Some(env) = callbacks().environment {
+        let mut format = RETRO_PIXEL_FORMAT_XRGB8888;
+        if !env(
+            RETRO_ENVIRONMENT_SET_PIXEL_FORMAT,
+            &mut format as *mut u32 as *mut c_void,
+        ) {
+            return false;
+        }
+    }
+
+    let rom = slice::from_raw_parts(game.data as *const u8, game.size);
+    let emu = match Embedded::try_new(rom) {
+        Ok(emu) => emu,
+        Err(e) => {
+            error!("Invalid ROM: {}", e);
+            return false;
+        }
+    };
+
+    *ptr::addr_of_mut!(CORE) = Some(Core {
+        emu,
+        samples: Vec::new(),
+        audio: Vec::new(),
+        audio_frac: 0.0,
+    });
+
+    true
+}
+
+/// Special game types aren't supported.
+#[no_mangle]
+pub extern "C" fn retro_load_game_special(
+    _game_type: u32,
+    _info: *const RetroGameInfo,
+    _num_info: usize,
+) -> bool {
+    false
+}
+
+/// Unload the game.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_unload_game() {
+    *ptr::addr_of_mut!(CORE) = None;
+}
+
+/// Restart the game, keeping the battery-backed RAM and the clock of the cartridge.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_reset() {
+    if let Some(core) = core() {
+        // The frontend keeps the pointer to the RAM, which the reset keeps in place.
+        core.emu.system().reset(true);
+    }
+}
+
+/// Run a frame.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_run() {
+    let cbs = callbacks();
+    let core = match core() {
+        Some(core) => core,
+        None => return,
+    };
+
+    if let Some(poll) = cbs.input_poll {
+        poll();
+    }
+    if let Some(state) = cbs.input_state {
+        let mut bits = 0;
+        for (i, id) in BUTTONS.iter().enumerate() {
+            if state(0, RETRO_DEVICE_JOYPAD, 0, *id) != 0 {
+                bits |= 1 << i;
+            }
+        }
+        core.emu.set_buttons(bits);
+    }
+
+    core.emu.step_frame();
+
+    if let Some(video) = cbs.video_refresh {
+        let (frame, _) = core.emu.frame();
+        video(
+            frame as *const c_void,
+            VRAM_WIDTH as u32,
+            VRAM_HEIGHT as u32,
+            VRAM_WIDTH * 4,
+        );
+    }
+
+    if let Some(audio) = cbs.audio_sample_batch {
+        core.audio_frac += SAMPLE_RATE as f64 / core.emu.fps();
+        let n = core.audio_frac as usize;
+        core.audio_frac -= n as f64;
+
+        core.samples.resize(n, 0.0);
+        core.emu.audio_pull(&mut core.samples, SAMPLE_RATE);
+
+        // Interleaved stereo from the mono samples, centered around zero.
+        core.audio.clear();
+        for s in core.samples.iter() {
+            let v = ((2.0 * *s - 1.0) * i16::MAX as f32) as i16;
+            core.audio.push(v);
+            core.audio.push(v);
+        }
+
+        audio(core.audio.as_ptr(), n);
+    }
+}
+
+/// Savestates aren't supported yet.
+#[no_mangle]
+pub extern "C" fn retro_serialize_size() -> usize {
+    0
+}
+
+/// Savestates aren't supported yet.
+#[no_mangle]
+pub extern "C" fn retro_serialize(_data: *mut c_void, _size: usize) -> bool {
+    false
+}
+
+/// Savestates aren't supported yet.
+#[no_mangle]
+pub extern "C" fn retro_unserialize(_data: *const c_void, _size: usize) -> bool {
+    false
+}
+
+/// Cheats aren't supported.
+#[no_mangle]
+pub extern "C" fn retro_cheat_reset() {}
+
+/// Cheats aren't supported.
+#[no_mangle]
+pub extern "C" fn retro_cheat_set(_index: u32, _enabled: bool, _code: *const c_char) {}
+
+/// Return the region of the game.
+#[no_mangle]
+pub extern "C" fn retro_get_region() -> u32 {
+    RETRO_REGION_NTSC
+}
+
+/// Return the battery-backed RAM of the cartridge for the frontend to persist.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_get_memory_data(id: u32) -> *mut c_void {
+    match core() {
+        Some(core) if id == RETRO_MEMORY_SAVE_RAM => {
+            core.emu.system().cartridge_ram_mut().as_mut_ptr() as *mut c_void
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Return the size of the battery-backed RAM of the cartridge.
+///
+/// # Safety
+///
+/// Called by the libretro frontend.
+#[no_mangle]
+pub unsafe extern "C" fn retro_get_memory_size(id: u32) -> usize {
+    match core() {
+        Some(core) if id == RETRO_MEMORY_SAVE_RAM => core.emu.system().cartridge_ram().len(),
+        _ => 0,
+    }
+}
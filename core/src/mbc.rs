@@ -1,13 +1,16 @@
 use crate::device::IoHandler;
-use crate::hardware::HardwareHandle;
+use crate::error::Error;
+use crate::hardware::{Event, HardwareHandle};
 use crate::mmu::{MemRead, MemWrite, Mmu};
+use crate::save::{self, RtcSave, SaveError};
+use crate::system::Model;
 use alloc::{
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
-use log::*;
 
-const BOOT_ROM: &[u8] = {
+pub(crate) const BOOT_ROM: &[u8] = {
     #[cfg(feature = "color")]
     {
         include_bytes!("cgb.bin")
@@ -18,6 +21,133 @@ const BOOT_ROM: &[u8] = {
     }
 };
 
+/// The size of the boot ROM of this build, including the cartridge header gap on the CGB.
+const BOOT_ROM_SIZE: usize = if cfg!(feature = "color") {
+    0x900
+} else {
+    0x100
+};
+
+/// The boot ROM of the model, if it's available in this build.
+///
+/// The MGB and the AGB boot ROMs are derived from the DMG and the CGB ones. They only differ
+/// in the registers handed over to the cartridge, which games check to detect the model.
+pub(crate) fn boot_rom(model: Model) -> Option<Vec<u8>> {
+    let mut rom = BOOT_ROM.to_vec();
+
+    match model {
+        Model::Dmg if !cfg!(feature = "color") => {}
+        Model::Cgb if cfg!(feature = "color") => {}
+        Model::Mgb if !cfg!(feature = "color") => {
+            // The DMG boot ROM ends with `ld a, $01` at $fc and `ldh ($50), a` at $fe.
+            // The MGB one hands over with `a` = $ff instead, so patch the operand at $fd.
+            rom[0xfd] = 0xff;
+        }
+        Model::Agb if cfg!(feature = "color") => {
+            // The CGB boot ROM ends at $f2 with `jr $f6` over two `nop`s, then `call $05d0`,
+            // `xor a`, `ldh ($70), a`, `ld a, $11` and `ldh ($50), a`. The AGB one also sets
+            // bit 0 of `b`, so move the call over the `jr` to make room for an `inc b` after
+            // `xor a`, and pad the bytes left with `nop`s to keep the hand-over at $fe.
+            rom[0xf2..0x100].copy_from_slice(&[
+                0xcd, 0xd0, 0x05, // call $05d0
+                0xaf, // xor a
+                0xe0, 0x70, // ldh ($70), a
+                0x04, // inc b
+                0x3e, 0x11, // ld a, $11
+                0x00, 0x00, 0x00, // nop
+                0xe0, 0x50, // ldh ($50), a
+            ]);
+        }
+        _ => return None,
+    }
+
+    Some(rom)
+}
+
+/// Load the battery-backed RAM, making sure it's large enough for the controller.
+///
+/// Only the `len` bytes declared in the cartridge header are requested from the hardware;
+/// the rest of the `size` bytes the controller can address are zeroed.
+fn load_ram(hw: &HardwareHandle, size: usize, len: usize) -> Vec<u8> {
+    let mut ram = hw.get().borrow_mut().load_ram(len);
+    if ram.len() < len {
+        warn!("Save data is smaller than RAM: {} < {}", ram.len(), len);
+    }
+    ram.resize(size, 0);
+    ram
+}
+
+/// Hand the battery-backed RAM to the hardware, unless the cartridge has none.
+fn save_ram(hw: &HardwareHandle, ram: &[u8]) {
+    if !ram.is_empty() {
+        hw.get().borrow_mut().save_ram(ram);
+    }
+}
+
+/// The size of the battery-backed RAM declared in the header of the ROM, at most `size`.
+fn header_ram_len(rom: &[u8], size: usize) -> usize {
+    rom.get(0x149).map_or(0, |code| ram_size(*code)).min(size)
+}
+
+/// The size of the ROM declared by the cartridge header.
+fn rom_size(code: u8) -> usize {
+    match code {
+        0x00..=0x08 => 0x8000 << code,
+        0x52 => 72 * 0x4000,
+        0x53 => 80 * 0x4000,
+        0x54 => 96 * 0x4000,
+        _ => 0x8000,
+    }
+}
+
+/// The end of the cartridge header.
+const HEADER_END: usize = 0x150;
+
+/// Check if the ROM holds the whole cartridge header, and its length matches the ROM size
+/// in the header.
+pub fn validate_rom(rom: &[u8]) -> Result<(), Error> {
+    if rom.len() < HEADER_END {
+        return Err(Error::RomTooShort { actual: rom.len() });
+    }
+
+    let expected = rom_size(rom[0x148]);
+
+    if rom.len() == expected {
+        Ok(())
+    } else {
+        Err(Error::RomSizeMismatch {
+            expected,
+            actual: rom.len(),
+        })
+    }
+}
+
+/// Compute the offset in the ROM for the address in the switchable bank area.
+///
+/// The bank number is masked by the number of banks, as the unused upper bits
+/// of the bank register aren't connected on the cartridge.
+fn rom_offset(rom: &[u8], bank: usize, addr: u16) -> usize {
+    rom_base(rom, bank) + (addr as usize - 0x4000)
+}
+
+/// The offset in the ROM image of the first byte of the bank.
+fn rom_base(rom: &[u8], bank: usize) -> usize {
+    (bank & (rom.len() / 0x4000 - 1)) * 0x4000
+}
+
+/// The size of the external RAM declared by the cartridge header.
+fn ram_size(code: u8) -> usize {
+    match code {
+        0x00 => 0,
+        0x01 => 0x800,
+        0x02 => 0x2000,
+        0x03 => 0x8000,
+        0x04 => 0x20000,
+        0x05 => 0x10000,
+        _ => 0,
+    }
+}
+
 struct MbcNone {
     rom: Vec<u8>,
 }
@@ -50,51 +180,55 @@ struct Mbc1 {
     hw: HardwareHandle,
     rom: Vec<u8>,
     ram: Vec<u8>,
+    /// The size of the RAM declared in the cartridge header, the part kept as save data.
+    ram_len: usize,
     rom_bank: usize,
     ram_bank: usize,
     ram_enable: bool,
     ram_select: bool,
+    /// The offsets of the mapped banks, updated on the bank switches rather than on each access.
+    rom_base: usize,
+    ram_base: usize,
 }
 
 impl Mbc1 {
     fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
-        let ram = hw.get().borrow_mut().load_ram(0x8000);
+        let ram_len = header_ram_len(&rom, 0x8000);
+        let ram = load_ram(&hw, 0x8000, ram_len);
 
-        Self {
+        let mut mbc = Self {
             hw,
             rom,
             ram,
+            ram_len,
             rom_bank: 0,
             ram_bank: 0,
             ram_enable: false,
             ram_select: false,
-        }
+            rom_base: 0,
+            ram_base: 0,
+        };
+        mbc.switch();
+        mbc
+    }
+
+    /// Restore the power-on state of the registers, keeping the ROM and the RAM.
+    fn reset(&mut self) {
+        self.rom_bank = 0;
+        self.ram_bank = 0;
+        self.ram_enable = false;
+        self.ram_select = false;
+        self.switch();
     }
 
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
         if addr <= 0x3fff {
             MemRead::Replace(self.rom[addr as usize])
         } else if addr >= 0x4000 && addr <= 0x7fff {
-            let rom_bank = self.rom_bank.max(1);
-
-            // ROM bank 0x20, 0x40, 0x60 are somehow not available
-            let rom_bank = if rom_bank == 0x20 || rom_bank == 0x40 || rom_bank == 0x60 {
-                warn!("Odd ROM bank selection: {:02x}", rom_bank);
-                rom_bank + 1
-            } else {
-                rom_bank
-            };
-
-            let base = rom_bank * 0x4000;
-            let offset = addr as usize - 0x4000;
-            let addr = (base + offset) & (self.rom.len() - 1);
-            MemRead::Replace(self.rom[addr])
+            MemRead::Replace(self.rom[self.rom_base + (addr as usize - 0x4000)])
         } else if addr >= 0xa000 && addr <= 0xbfff {
             if self.ram_enable {
-                let base = self.ram_bank as usize * 0x2000;
-                let offset = addr as usize - 0xa000;
-                let addr = (base + offset) & (self.rom.len() - 1);
-                MemRead::Replace(self.ram[addr])
+                MemRead::Replace(self.ram[self.ram_addr(addr)])
             } else {
                 warn!("Read from disabled external RAM: {:04x}", addr);
                 MemRead::Replace(0)
@@ -104,6 +238,32 @@ impl Mbc1 {
         }
     }
 
+    /// The ROM bank mapped to the switchable bank area.
+    fn rom_bank(&self) -> usize {
+        let rom_bank = self.rom_bank.max(1);
+
+        // ROM bank 0x20, 0x40, 0x60 are somehow not available
+        if rom_bank == 0x20 || rom_bank == 0x40 || rom_bank == 0x60 {
+            rom_bank + 1
+        } else {
+            rom_bank
+        }
+    }
+
+    /// Follow a write to the bank registers.
+    fn switch(&mut self) {
+        let rom_bank = self.rom_bank();
+        if rom_bank != self.rom_bank.max(1) {
+            warn!("Odd ROM bank selection: {:02x}", self.rom_bank);
+        }
+        debug!("Switch ROM bank to {:02x}", rom_bank);
+        self.rom_base = rom_base(&self.rom, rom_bank);
+
+        // RAM banking is only effective in the RAM banking mode.
+        let ram_bank = if self.ram_select { self.ram_bank } else { 0 };
+        self.ram_base = (ram_bank * 0x2000) & (self.ram.len() - 1);
+    }
+
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
         if addr <= 0x1fff {
             if value & 0xf == 0x0a {
@@ -112,12 +272,12 @@ impl Mbc1 {
             } else {
                 info!("External RAM disabled");
                 self.ram_enable = false;
-                self.hw.get().borrow_mut().save_ram(&self.ram);
+                save_ram(&self.hw, &self.ram[..self.ram_len]);
             }
             MemWrite::Block
         } else if addr >= 0x2000 && addr <= 0x3fff {
             self.rom_bank = (self.rom_bank & !0x1f) | (value as usize & 0x1f);
-            debug!("Switch ROM bank to {:02x}", self.rom_bank);
+            self.switch();
             MemWrite::Block
         } else if addr >= 0x4000 && addr <= 0x5fff {
             if self.ram_select {
@@ -125,21 +285,16 @@ impl Mbc1 {
             } else {
                 self.rom_bank = (self.rom_bank & !0x60) | ((value as usize & 0x3) << 5);
             }
+            self.switch();
             MemWrite::Block
         } else if addr >= 0x6000 && addr <= 0x7fff {
-            if value == 0x00 {
-                self.ram_select = false;
-            } else if value == 0x01 {
-                self.ram_select = true;
-            } else {
-                unimplemented!("Invalid ROM/RAM select mode");
-            }
+            self.ram_select = value & 0x01 != 0;
+            self.switch();
             MemWrite::Block
         } else if addr >= 0xa000 && addr <= 0xbfff {
             if self.ram_enable {
-                let base = self.ram_bank as usize * 0x2000;
-                let offset = addr as usize - 0xa000;
-                self.ram[base + offset] = value;
+                let addr = self.ram_addr(addr);
+                self.ram[addr] = value;
                 MemWrite::Block
             } else {
                 warn!("Write to disabled external RAM: {:04x} {:02x}", addr, value);
@@ -149,6 +304,10 @@ impl Mbc1 {
             unimplemented!("write to rom {:04x} {:02x}", addr, value)
         }
     }
+
+    fn ram_addr(&self, addr: u16) -> usize {
+        self.ram_base + (addr as usize - 0xa000)
+    }
 }
 
 struct Mbc2 {
@@ -161,7 +320,7 @@ struct Mbc2 {
 
 impl Mbc2 {
     fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
-        let ram = hw.get().borrow_mut().load_ram(0x200);
+        let ram = load_ram(&hw, 0x200, 0x200);
 
         Self {
             hw,
@@ -172,13 +331,18 @@ impl Mbc2 {
         }
     }
 
+    /// Restore the power-on state of the registers, keeping the ROM and the RAM.
+    fn reset(&mut self) {
+        self.rom_bank = 1;
+        self.ram_enable = false;
+    }
+
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
         if addr <= 0x3fff {
             MemRead::Replace(self.rom[addr as usize])
         } else if addr >= 0x4000 && addr <= 0x7fff {
-            let base = self.rom_bank.max(1) * 0x4000;
-            let offset = addr as usize - 0x4000;
-            MemRead::Replace(self.rom[base + offset])
+            let rom_bank = self.rom_bank.max(1);
+            MemRead::Replace(self.rom[rom_offset(&self.rom, rom_bank, addr)])
         } else if addr >= 0xa000 && addr <= 0xa1ff {
             if self.ram_enable {
                 MemRead::Replace(self.ram[addr as usize - 0xa000] & 0xf)
@@ -205,7 +369,7 @@ impl Mbc2 {
                     value
                 );
                 if !self.ram_enable {
-                    self.hw.get().borrow_mut().save_ram(&self.ram);
+                    save_ram(&self.hw, &self.ram);
                 }
             }
             MemWrite::Block
@@ -233,19 +397,205 @@ impl Mbc2 {
     }
 }
 
+#[cfg(feature = "rtc")]
+struct Rtc {
+    hw: HardwareHandle,
+    secs: u8,
+    mins: u8,
+    hours: u8,
+    day_low: u8,
+    day_high: u8,
+    epoch: u64,
+}
+
+#[cfg(feature = "rtc")]
+impl Rtc {
+    fn new(hw: HardwareHandle) -> Self {
+        let mut s = Self {
+            hw,
+            secs: 0,
+            mins: 0,
+            hours: 0,
+            day_low: 0,
+            day_high: 0,
+            epoch: 0,
+        };
+        s.update_epoch();
+        s
+    }
+
+    fn epoch(&self) -> u64 {
+        self.hw.get().borrow_mut().clock() / 1000_000
+    }
+
+    fn read(&self, select: u8) -> u8 {
+        match select {
+            0x08 => self.secs,
+            0x09 => self.mins,
+            0x0a => self.hours,
+            0x0b => self.day_low,
+            0x0c => self.day_high,
+            s => {
+                warn!("Read from unknown RTC register: {:02x}", s);
+                0xff
+            }
+        }
+    }
+
+    fn write(&mut self, select: u8, value: u8) {
+        match select {
+            0x08 => self.secs = value,
+            0x09 => self.mins = value,
+            0x0a => self.hours = value,
+            0x0b => self.day_low = value,
+            0x0c => self.day_high = value,
+            s => {
+                warn!("Write to unknown RTC register: {:02x} {:02x}", s, value);
+                return;
+            }
+        }
+        self.update_epoch();
+    }
+
+    fn update_epoch(&mut self) {
+        self.epoch = self.epoch();
+    }
+
+    fn regs(&self) -> [u8; 5] {
+        [
+            self.secs,
+            self.mins,
+            self.hours,
+            self.day_low,
+            self.day_high,
+        ]
+    }
+
+    fn set_regs(&mut self, regs: [u8; 5]) {
+        self.secs = regs[0];
+        self.mins = regs[1];
+        self.hours = regs[2];
+        self.day_low = regs[3];
+        self.day_high = regs[4];
+    }
+
+    fn save(&self) -> Option<RtcSave> {
+        let latched = self.regs();
+
+        // The registers hold the values at `epoch`; advance a copy to get the current time.
+        let mut now = Rtc {
+            hw: self.hw.clone(),
+            ..*self
+        };
+        now.latch();
+
+        Some(RtcSave {
+            regs: now.regs(),
+            latched,
+            timestamp: now.epoch,
+        })
+    }
+
+    fn load(&mut self, rtc: &RtcSave) {
+        // The time elapsed since the save is added on the next latch. A clock behind the
+        // timestamp, e.g. the emulated clock starting at zero, adds no time.
+        self.set_regs(rtc.regs);
+        self.epoch = rtc.timestamp.min(self.epoch());
+    }
+
+    fn day(&self) -> u64 {
+        ((self.day_high as u64 & 1) << 8) | self.day_low as u64
+    }
+
+    fn dhms_to_secs(&self) -> u64 {
+        let d = self.day();
+        let s = self.secs as u64;
+        let m = self.mins as u64;
+        let h = self.hours as u64;
+        (d * 24 + h) * 3600 + m * 60 + s
+    }
+
+    fn secs_to_dhms(&mut self, secs: u64) {
+        let s = secs % 60;
+        let m = (secs / 60) % 60;
+        let h = (secs / 3600) % 24;
+        let d = secs / (3600 * 24);
+        self.secs = s as u8;
+        self.mins = m as u8;
+        self.hours = h as u8;
+        self.day_low = d as u8 & 0xff;
+        self.day_high = (self.day_high & !1) | ((d >> 8) & 1) as u8;
+    }
+
+    fn latch(&mut self) {
+        let new_epoch = if self.day_high & 0x40 == 0 {
+            self.epoch()
+        } else {
+            // Halt
+            self.epoch
+        };
+        let elapsed = new_epoch.saturating_sub(self.epoch);
+
+        let last_day = self.day();
+        let last_secs = self.dhms_to_secs();
+        self.secs_to_dhms(last_secs + elapsed);
+        let new_day = self.day();
+
+        // Overflow
+        if new_day < last_day {
+            self.day_high |= 0x80;
+        }
+
+        debug!(
+            "Latching RTC: {:04}/{:02}:{:02}:{:02}",
+            self.day(),
+            self.hours,
+            self.mins,
+            self.secs
+        );
+
+        self.epoch = new_epoch;
+    }
+}
+
+/// RTC stub for builds without the `rtc` feature.
+///
+/// The registers read as 0xff and ignore writes, as on cartridges without the clock chip.
+#[cfg(not(feature = "rtc"))]
+struct Rtc;
+
+#[cfg(not(feature = "rtc"))]
+impl Rtc {
+    fn new(_hw: HardwareHandle) -> Self {
+        Rtc
+    }
+
+    fn read(&self, _select: u8) -> u8 {
+        0xff
+    }
+
+    fn write(&mut self, _select: u8, _value: u8) {}
+
+    fn latch(&mut self) {}
+
+    fn save(&self) -> Option<RtcSave> {
+        None
+    }
+
+    fn load(&mut self, _rtc: &RtcSave) {}
+}
+
 struct Mbc3 {
     hw: HardwareHandle,
     rom: Vec<u8>,
     ram: Vec<u8>,
+    /// The size of the RAM declared in the cartridge header, the part kept as save data.
+    ram_len: usize,
     rom_bank: usize,
     enable: bool,
     select: u8,
-    rtc_secs: u8,
-    rtc_mins: u8,
-    rtc_hours: u8,
-    rtc_day_low: u8,
-    rtc_day_high: u8,
-    epoch: u64,
+    rtc: Rtc,
+    has_rtc: bool,
     prelatch: bool,
 }
 
@@ -256,34 +606,35 @@ impl Drop for Mbc3 {
 }
 
 impl Mbc3 {
-    fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
-        let ram = hw.get().borrow_mut().load_ram(0x8000);
+    fn new(hw: HardwareHandle, rom: Vec<u8>, has_rtc: bool) -> Self {
+        let ram_len = header_ram_len(&rom, 0x8000);
+        let ram = load_ram(&hw, 0x8000, ram_len);
+        let rtc = Rtc::new(hw.clone());
 
-        let mut s = Self {
+        Self {
             hw,
             rom,
             ram,
+            ram_len,
             rom_bank: 0,
             enable: false,
             select: 0,
-            rtc_secs: 0,
-            rtc_mins: 0,
-            rtc_hours: 0,
-            rtc_day_low: 0,
-            rtc_day_high: 0,
-            epoch: 0,
+            rtc,
+            has_rtc,
             prelatch: false,
-        };
-        s.update_epoch();
-        s
+        }
     }
 
-    fn save(&mut self) {
-        self.hw.get().borrow_mut().save_ram(&self.ram);
+    /// Restore the power-on state of the registers, keeping the ROM, the RAM and the clock.
+    fn reset(&mut self) {
+        self.rom_bank = 0;
+        self.enable = false;
+        self.select = 0;
+        self.prelatch = false;
     }
 
-    fn epoch(&self) -> u64 {
-        self.hw.get().borrow_mut().clock() / 1000_000
+    fn save(&mut self) {
+        save_ram(&self.hw, &self.ram[..self.ram_len]);
     }
 
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
@@ -291,22 +642,19 @@ impl Mbc3 {
             MemRead::Replace(self.rom[addr as usize])
         } else if addr >= 0x4000 && addr <= 0x7fff {
             let rom_bank = self.rom_bank.max(1);
-            let base = rom_bank * 0x4000;
-            let offset = addr as usize - 0x4000;
-            MemRead::Replace(self.rom[base + offset])
+            MemRead::Replace(self.rom[rom_offset(&self.rom, rom_bank, addr)])
         } else if addr >= 0xa000 && addr <= 0xbfff {
+            if !self.enable {
+                warn!("Read from disabled external RAM/RTC: {:04x}", addr);
+                return MemRead::Replace(0xff);
+            }
             match self.select {
                 x if x == 0x00 || x == 0x01 || x == 0x02 || x == 0x03 => {
                     let base = x as usize * 0x2000;
                     let offset = addr as usize - 0xa000;
                     MemRead::Replace(self.ram[base + offset])
                 }
-                0x08 => MemRead::Replace(self.rtc_secs),
-                0x09 => MemRead::Replace(self.rtc_mins),
-                0x0a => MemRead::Replace(self.rtc_hours),
-                0x0b => MemRead::Replace(self.rtc_day_low),
-                0x0c => MemRead::Replace(self.rtc_day_high),
-                s => unimplemented!("Unknown selector: {:02x}", s),
+                s => MemRead::Replace(self.rtc.read(s)),
             }
         } else {
             unreachable!("Invalid read from ROM: {:02x}", addr);
@@ -335,7 +683,7 @@ impl Mbc3 {
         } else if addr >= 0x6000 && addr <= 0x7fff {
             if self.prelatch {
                 if value == 0x01 {
-                    self.latch();
+                    self.rtc.latch();
                 }
                 self.prelatch = false;
             } else {
@@ -345,6 +693,13 @@ impl Mbc3 {
             }
             MemWrite::Block
         } else if addr >= 0xa000 && addr <= 0xbfff {
+            if !self.enable {
+                warn!(
+                    "Write to disabled external RAM/RTC: {:04x} {:02x}",
+                    addr, value
+                );
+                return MemWrite::Block;
+            }
             match self.select {
                 x if x == 0x00 || x == 0x01 || x == 0x02 || x == 0x03 => {
                     let base = x as usize * 0x2000;
@@ -352,132 +707,77 @@ impl Mbc3 {
                     self.ram[base + offset] = value;
                     MemWrite::Block
                 }
-                0x08 => {
-                    self.rtc_secs = value;
-                    self.update_epoch();
-                    MemWrite::Block
-                }
-                0x09 => {
-                    self.rtc_mins = value;
-                    self.update_epoch();
+                s => {
+                    self.rtc.write(s, value);
                     MemWrite::Block
                 }
-                0x0a => {
-                    self.rtc_hours = value;
-                    self.update_epoch();
-                    MemWrite::Block
-                }
-                0x0b => {
-                    self.rtc_day_low = value;
-                    self.update_epoch();
-                    MemWrite::Block
-                }
-                0x0c => {
-                    self.rtc_day_high = value;
-                    self.update_epoch();
-                    MemWrite::Block
-                }
-                s => unimplemented!("Unknown selector: {:02x}", s),
             }
         } else {
             unimplemented!("write to rom {:04x} {:02x}", addr, value)
         }
     }
-
-    fn update_epoch(&mut self) {
-        self.epoch = self.epoch();
-    }
-
-    fn day(&self) -> u64 {
-        ((self.rtc_day_high as u64 & 1) << 8) & self.rtc_day_low as u64
-    }
-
-    fn dhms_to_secs(&self) -> u64 {
-        let d = self.day();
-        let s = self.rtc_secs as u64;
-        let m = self.rtc_mins as u64;
-        let h = self.rtc_hours as u64;
-        (d * 24 + h) * 3600 + m * 60 + s
-    }
-
-    fn secs_to_dhms(&mut self, secs: u64) {
-        let s = secs % 60;
-        let m = (secs / 60) % 60;
-        let h = (secs / 3600) % 24;
-        let d = secs / (3600 * 24);
-        self.rtc_secs = s as u8;
-        self.rtc_mins = m as u8;
-        self.rtc_hours = h as u8;
-        self.rtc_day_low = d as u8 & 0xff;
-        self.rtc_day_high = (self.rtc_day_high & !1) | ((d >> 8) & 1) as u8;
-    }
-
-    fn latch(&mut self) {
-        let new_epoch = if self.rtc_day_high & 0x40 == 0 {
-            self.epoch()
-        } else {
-            // Halt
-            self.epoch
-        };
-        let elapsed = new_epoch - self.epoch;
-
-        let last_day = self.day();
-        let last_secs = self.dhms_to_secs();
-        self.secs_to_dhms(last_secs + elapsed);
-        let new_day = self.day();
-
-        // Overflow
-        if new_day < last_day {
-            self.rtc_day_high |= 0x80;
-        }
-
-        debug!(
-            "Latching RTC: {:04}/{:02}:{:02}:{:02}",
-            self.day(),
-            self.rtc_hours,
-            self.rtc_mins,
-            self.rtc_secs
-        );
-
-        self.epoch = new_epoch;
-    }
 }
 
 struct Mbc5 {
     hw: HardwareHandle,
     rom: Vec<u8>,
     ram: Vec<u8>,
+    /// The size of the RAM declared in the cartridge header, the part kept as save data.
+    ram_len: usize,
     rom_bank: usize,
     ram_bank: usize,
     ram_enable: bool,
+    /// The offsets of the mapped banks, updated on the bank switches rather than on each access.
+    rom_base: usize,
+    ram_base: usize,
 }
 
 impl Mbc5 {
     fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
-        let ram = hw.get().borrow_mut().load_ram(0x20000);
+        let ram_len = header_ram_len(&rom, 0x20000);
+        let ram = load_ram(&hw, 0x20000, ram_len);
 
         Self {
             hw,
             rom,
             ram,
+            ram_len,
             rom_bank: 0,
             ram_bank: 0,
             ram_enable: false,
+            rom_base: 0,
+            ram_base: 0,
         }
     }
 
+    /// Restore the power-on state of the registers, keeping the ROM and the RAM.
+    fn reset(&mut self) {
+        self.rom_bank = 0;
+        self.ram_bank = 0;
+        self.ram_enable = false;
+        self.rom_base = 0;
+        self.ram_base = 0;
+    }
+
+    /// Follow a write to the bank registers.
+    fn switch(&mut self) {
+        debug!("Switch ROM bank to {:02x}", self.rom_bank);
+        self.rom_base = rom_base(&self.rom, self.rom_bank);
+        self.ram_base = (self.ram_bank * 0x2000) & (self.ram.len() - 1);
+    }
+
+    fn ram_addr(&self, addr: u16) -> usize {
+        self.ram_base + (addr as usize - 0xa000)
+    }
+
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
         if addr <= 0x3fff {
             MemRead::Replace(self.rom[addr as usize])
         } else if addr >= 0x4000 && addr <= 0x7fff {
-            let base = self.rom_bank * 0x4000;
-            let offset = addr as usize - 0x4000;
-            MemRead::Replace(self.rom[base + offset])
+            MemRead::Replace(self.rom[self.rom_base + (addr as usize - 0x4000)])
         } else if addr >= 0xa000 && addr <= 0xbfff {
             if self.ram_enable {
-                let base = self.ram_bank * 0x2000;
-                let offset = addr as usize - 0xa000;
-                MemRead::Replace(self.ram[base + offset])
+                MemRead::Replace(self.ram[self.ram_addr(addr)])
             } else {
                 warn!("Read from disabled external RAM: {:04x}", addr);
                 MemRead::Replace(0)
@@ -495,25 +795,28 @@ impl Mbc5 {
             } else {
                 info!("External RAM disabled");
                 self.ram_enable = false;
-                self.hw.get().borrow_mut().save_ram(&self.ram);
+                save_ram(&self.hw, &self.ram[..self.ram_len]);
             }
             MemWrite::Block
         } else if addr >= 0x2000 && addr <= 0x2fff {
             self.rom_bank = (self.rom_bank & !0xff) | value as usize;
-            debug!("Switch ROM bank to {:02x}", self.rom_bank);
+            self.switch();
             MemWrite::Block
         } else if addr >= 0x3000 && addr <= 0x3fff {
             self.rom_bank = (self.rom_bank & !0x100) | (value as usize & 1) << 8;
-            debug!("Switch ROM bank to {:02x}", self.rom_bank);
+            self.switch();
             MemWrite::Block
         } else if addr >= 0x4000 && addr <= 0x5fff {
             self.ram_bank = value as usize & 0xf;
+            self.switch();
+            MemWrite::Block
+        } else if addr >= 0x6000 && addr <= 0x7fff {
+            warn!("Writing to read-only range: {:04x} {:02x}", addr, value);
             MemWrite::Block
         } else if addr >= 0xa000 && addr <= 0xbfff {
             if self.ram_enable {
-                let base = self.ram_bank * 0x2000;
-                let offset = addr as usize - 0xa000;
-                self.ram[base + offset] = value;
+                let addr = self.ram_addr(addr);
+                self.ram[addr] = value;
                 MemWrite::Block
             } else {
                 warn!("Write to disabled external RAM: {:04x} {:02x}", addr, value);
@@ -525,50 +828,170 @@ impl Mbc5 {
     }
 }
 
-#[allow(unused)]
-struct HuC1 {
+/// Read the ROM area from the 32KB bank which replaces both the fixed and the switchable banks.
+fn read_32k(rom: &[u8], bank: usize, addr: u16) -> MemRead {
+    if addr <= 0x7fff {
+        MemRead::Replace(rom[(bank * 0x8000 + addr as usize) & (rom.len() - 1)])
+    } else {
+        MemRead::PassThrough
+    }
+}
+
+/// Whether the ROM is one of the unlicensed Wisdom Tree games.
+///
+/// These declare no memory bank controller, but are larger than 32KB and carry the publisher name.
+fn is_wisdom_tree(rom: &[u8]) -> bool {
+    if rom.len() <= 0x8000 {
+        return false;
+    }
+    let contains = |name: &[u8]| rom[..0x8000].windows(name.len()).any(|w| w == name);
+    contains(b"WISDOM TREE") || contains(b"WISDOM\x00TREE")
+}
+
+/// Whether the ROM is the Mani 4 in 1 (Tetris Set) multicart using the M161 controller.
+///
+/// It declares an MBC3, so the cartridge is recognized by its title.
+fn is_m161(rom: &[u8]) -> bool {
+    &rom[0x134..0x13e] == b"TETRIS SET"
+}
+
+/// The Wisdom Tree controller, switching 32KB banks by the lower byte of the address written to.
+struct MbcWisdomTree {
     rom: Vec<u8>,
+    bank: usize,
 }
 
-impl HuC1 {
+impl MbcWisdomTree {
     fn new(rom: Vec<u8>) -> Self {
-        Self { rom }
+        Self { rom, bank: 0 }
     }
 
-    fn on_read(&mut self, _mmu: &Mmu, _addr: u16) -> MemRead {
-        unimplemented!()
+    fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
+        read_32k(&self.rom, self.bank, addr)
     }
 
-    fn on_write(&mut self, _mmu: &Mmu, _addr: u16, _value: u8) -> MemWrite {
-        unimplemented!()
+    fn on_write(&mut self, _mmu: &Mmu, addr: u16, _value: u8) -> MemWrite {
+        if addr <= 0x3fff {
+            self.bank = addr as usize & 0xff;
+            debug!("Switch ROM bank to {:02x}", self.bank);
+            MemWrite::Block
+        } else if addr <= 0x7fff {
+            MemWrite::Block
+        } else {
+            MemWrite::PassThrough
+        }
     }
 }
 
-enum MbcType {
-    None(MbcNone),
-    Mbc1(Mbc1),
-    Mbc2(Mbc2),
-    Mbc3(Mbc3),
-    Mbc5(Mbc5),
-    HuC1(HuC1),
+/// The M161 controller, switching 32KB banks once until the console is reset.
+struct MbcM161 {
+    rom: Vec<u8>,
+    bank: usize,
+    latched: bool,
 }
 
-impl MbcType {
-    fn new(hw: HardwareHandle, code: u8, rom: Vec<u8>) -> Self {
-        match code {
-            0x00 => MbcType::None(MbcNone::new(rom)),
-            0x01 | 0x02 | 0x03 => MbcType::Mbc1(Mbc1::new(hw, rom)),
-            0x05 | 0x06 => MbcType::Mbc2(Mbc2::new(hw, rom)),
-            0x08 | 0x09 => unimplemented!("ROM+RAM: {:02x}", code),
-            0x0b | 0x0c | 0x0d => unimplemented!("MMM01: {:02x}", code),
-            0x0f | 0x10 | 0x11 | 0x12 | 0x13 => MbcType::Mbc3(Mbc3::new(hw, rom)),
-            0x15 | 0x16 | 0x17 => unimplemented!("Mbc4: {:02x}", code),
+impl MbcM161 {
+    fn new(rom: Vec<u8>) -> Self {
+        Self {
+            rom,
+            bank: 0,
+            latched: false,
+        }
+    }
+
+    fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
+        read_32k(&self.rom, self.bank, addr)
+    }
+
+    fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
+        if addr >= 0x4000 && addr <= 0x5fff {
+            if self.latched {
+                warn!("ROM bank already selected: {:02x}", value);
+            } else {
+                self.bank = value as usize & 0x7;
+                self.latched = true;
+                debug!("Switch ROM bank to {:02x}", self.bank);
+            }
+            MemWrite::Block
+        } else if addr <= 0x7fff {
+            MemWrite::Block
+        } else {
+            MemWrite::PassThrough
+        }
+    }
+}
+
+/// A memory bank controller supplied by the user, e.g. for flash carts, homebrew or test mappers.
+///
+/// The mapper handles the accesses to the ROM area (0x0000-0x7fff) and the external RAM area
+/// (0xa000-0xbfff). It's passed to [`System::new_with_mapper`][crate::System::new_with_mapper].
+pub trait Mapper {
+    /// Read a byte of the ROM area or the external RAM area.
+    fn read(&mut self, addr: u16) -> u8;
+
+    /// Write a byte to the ROM area, usually a bank register, or to the external RAM area.
+    fn write(&mut self, addr: u16, value: u8);
+
+    /// The whole ROM image, which also holds the cartridge header.
+    fn rom(&self) -> &[u8];
+
+    /// The ROM bank mapped to 0x4000-0x7fff.
+    fn rom_bank(&self) -> usize {
+        1
+    }
+
+    /// The external RAM bank mapped to 0xa000-0xbfff.
+    fn ram_bank(&self) -> usize {
+        0
+    }
+
+    /// Whether the external RAM is enabled.
+    fn ram_enabled(&self) -> bool {
+        false
+    }
+
+    /// The battery-backed external RAM, exported as save data.
+    fn ram(&self) -> &[u8] {
+        &[]
+    }
+
+    /// Mutable reference to the battery-backed external RAM.
+    fn ram_mut(&mut self) -> &mut [u8] {
+        &mut []
+    }
+
+    /// Restore the power-on state of the registers, called by [`System::reset`][crate::System::reset].
+    fn reset(&mut self) {}
+}
+
+enum MbcType {
+    None(MbcNone),
+    Mbc1(Mbc1),
+    Mbc2(Mbc2),
+    Mbc3(Mbc3),
+    Mbc5(Mbc5),
+    WisdomTree(MbcWisdomTree),
+    M161(MbcM161),
+    Custom(Box<dyn Mapper>),
+}
+
+impl MbcType {
+    fn new(hw: HardwareHandle, code: u8, rom: Vec<u8>) -> Self {
+        match code {
+            0x00 if is_wisdom_tree(&rom) => MbcType::WisdomTree(MbcWisdomTree::new(rom)),
+            0x10 if is_m161(&rom) => MbcType::M161(MbcM161::new(rom)),
+            0x00 => MbcType::None(MbcNone::new(rom)),
+            0x01 | 0x02 | 0x03 => MbcType::Mbc1(Mbc1::new(hw, rom)),
+            0x05 | 0x06 => MbcType::Mbc2(Mbc2::new(hw, rom)),
+            0x0f | 0x10 => MbcType::Mbc3(Mbc3::new(hw, rom, true)),
+            0x11 | 0x12 | 0x13 => MbcType::Mbc3(Mbc3::new(hw, rom, false)),
             0x19 | 0x1a | 0x1b | 0x1c | 0x1d | 0x1e => MbcType::Mbc5(Mbc5::new(hw, rom)),
-            0xfc => unimplemented!("POCKET CAMERA"),
-            0xfd => unimplemented!("BANDAI TAMAS"),
-            0xfe => unimplemented!("HuC3"),
-            0xff => MbcType::HuC1(HuC1::new(rom)),
-            _ => unreachable!("Invalid cartridge type: {:02x}", code),
+            // HuC1 switches the banks the same way as MBC1.
+            0xff => MbcType::Mbc1(Mbc1::new(hw, rom)),
+            _ => {
+                warn!("Unsupported cartridge type: {:02x}", code);
+                MbcType::None(MbcNone::new(rom))
+            }
         }
     }
 
@@ -579,7 +1002,9 @@ impl MbcType {
             MbcType::Mbc2(c) => c.on_read(mmu, addr),
             MbcType::Mbc3(c) => c.on_read(mmu, addr),
             MbcType::Mbc5(c) => c.on_read(mmu, addr),
-            MbcType::HuC1(c) => c.on_read(mmu, addr),
+            MbcType::WisdomTree(c) => c.on_read(mmu, addr),
+            MbcType::M161(c) => c.on_read(mmu, addr),
+            MbcType::Custom(c) => MemRead::Replace(c.read(addr)),
         }
     }
 
@@ -590,7 +1015,154 @@ impl MbcType {
             MbcType::Mbc2(c) => c.on_write(mmu, addr, value),
             MbcType::Mbc3(c) => c.on_write(mmu, addr, value),
             MbcType::Mbc5(c) => c.on_write(mmu, addr, value),
-            MbcType::HuC1(c) => c.on_write(mmu, addr, value),
+            MbcType::WisdomTree(c) => c.on_write(mmu, addr, value),
+            MbcType::M161(c) => c.on_write(mmu, addr, value),
+            MbcType::Custom(c) => {
+                c.write(addr, value);
+                MemWrite::Block
+            }
+        }
+    }
+}
+
+impl MbcType {
+    /// Restore the power-on state of the registers, keeping the ROM and the RAM in place.
+    ///
+    /// The clock of MBC3 keeps running on the battery if `keep_rtc` is set.
+    fn reset(&mut self, keep_rtc: bool) {
+        match self {
+            MbcType::None(_) => {}
+            MbcType::Mbc1(c) => c.reset(),
+            MbcType::Mbc2(c) => c.reset(),
+            MbcType::Mbc3(c) => {
+                c.reset();
+                if !keep_rtc {
+                    c.rtc = Rtc::new(c.hw.clone());
+                }
+            }
+            MbcType::Mbc5(c) => c.reset(),
+            MbcType::WisdomTree(c) => c.bank = 0,
+            MbcType::M161(c) => {
+                c.bank = 0;
+                c.latched = false;
+            }
+            MbcType::Custom(c) => c.reset(),
+        }
+    }
+
+    fn rom(&self) -> &[u8] {
+        match self {
+            MbcType::None(c) => &c.rom,
+            MbcType::Mbc1(c) => &c.rom,
+            MbcType::Mbc2(c) => &c.rom,
+            MbcType::Mbc3(c) => &c.rom,
+            MbcType::Mbc5(c) => &c.rom,
+            MbcType::WisdomTree(c) => &c.rom,
+            MbcType::M161(c) => &c.rom,
+            MbcType::Custom(c) => c.rom(),
+        }
+    }
+
+    /// The ROM bank mapped to the switchable bank area, masked by the number of banks.
+    fn rom_bank(&self) -> usize {
+        let bank = match self {
+            MbcType::Mbc1(c) => c.rom_bank(),
+            MbcType::Mbc2(c) => c.rom_bank.max(1),
+            MbcType::Mbc3(c) => c.rom_bank.max(1),
+            MbcType::Mbc5(c) => c.rom_bank,
+            MbcType::None(_) => 1,
+            // The upper half of the 32KB bank is mapped there.
+            MbcType::WisdomTree(c) => c.bank * 2 + 1,
+            MbcType::M161(c) => c.bank * 2 + 1,
+            // The mapper masks the bank itself, and its ROM isn't padded.
+            MbcType::Custom(c) => return c.rom_bank(),
+        };
+
+        rom_offset(self.rom(), bank, 0x4000) / 0x4000
+    }
+
+    /// The ROM bank mapped to the fixed bank area, masked by the number of banks.
+    fn fixed_bank(&self) -> usize {
+        let bank = match self {
+            // The lower half of the 32KB bank is mapped there.
+            MbcType::WisdomTree(c) => c.bank * 2,
+            MbcType::M161(c) => c.bank * 2,
+            _ => return 0,
+        };
+
+        rom_base(self.rom(), bank) / 0x4000
+    }
+
+    /// The external RAM bank mapped to 0xa000-0xbfff.
+    fn ram_bank(&self) -> usize {
+        match self {
+            MbcType::Mbc1(c) if c.ram_select => c.ram_bank,
+            MbcType::Mbc3(c) if c.select <= 0x03 => c.select as usize,
+            MbcType::Mbc5(c) => c.ram_bank,
+            MbcType::Custom(c) => c.ram_bank(),
+            _ => 0,
+        }
+    }
+
+    fn ram_enabled(&self) -> bool {
+        match self {
+            MbcType::Mbc1(c) => c.ram_enable,
+            MbcType::Mbc2(c) => c.ram_enable,
+            MbcType::Mbc3(c) => c.enable,
+            MbcType::Mbc5(c) => c.ram_enable,
+            MbcType::None(_) | MbcType::WisdomTree(_) | MbcType::M161(_) => false,
+            MbcType::Custom(c) => c.ram_enabled(),
+        }
+    }
+
+    /// The offset in the external RAM of the address in the mapped bank, whether or not the RAM is enabled.
+    fn ram_offset(&self, addr: u16) -> Option<usize> {
+        let offset = addr as usize - 0xa000;
+
+        match self {
+            MbcType::Mbc1(c) => Some(c.ram_addr(addr)),
+            MbcType::Mbc2(c) if offset < c.ram.len() => Some(offset),
+            MbcType::Mbc3(c) if c.select <= 0x03 => Some(c.select as usize * 0x2000 + offset),
+            MbcType::Mbc5(c) => Some(c.ram_addr(addr)),
+            MbcType::Custom(c) => {
+                Some(c.ram_bank() * 0x2000 + offset).filter(|&o| o < c.ram().len())
+            }
+            _ => None,
+        }
+    }
+
+    fn ram(&self) -> Option<&[u8]> {
+        match self {
+            MbcType::Mbc1(c) => Some(&c.ram),
+            MbcType::Mbc2(c) => Some(&c.ram),
+            MbcType::Mbc3(c) => Some(&c.ram),
+            MbcType::Mbc5(c) => Some(&c.ram),
+            MbcType::None(_) | MbcType::WisdomTree(_) | MbcType::M161(_) => None,
+            MbcType::Custom(c) => Some(c.ram()),
+        }
+    }
+
+    fn rtc(&self) -> Option<RtcSave> {
+        match self {
+            MbcType::Mbc3(c) if c.has_rtc => c.rtc.save(),
+            _ => None,
+        }
+    }
+
+    fn load_rtc(&mut self, rtc: &RtcSave) {
+        if let MbcType::Mbc3(c) = self {
+            c.rtc.load(rtc);
+        }
+    }
+
+    fn ram_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            MbcType::Mbc1(c) => Some(&mut c.ram),
+            MbcType::Mbc2(c) => Some(&mut c.ram),
+            MbcType::Mbc3(c) => Some(&mut c.ram),
+            MbcType::Mbc5(c) => Some(&mut c.ram),
+            MbcType::None(_) | MbcType::WisdomTree(_) | MbcType::M161(_) => None,
+            MbcType::Custom(c) => Some(c.ram_mut()),
         }
     }
 }
@@ -603,7 +1175,9 @@ impl alloc::fmt::Display for MbcType {
             MbcType::Mbc2(_) => "Mbc2",
             MbcType::Mbc3(_) => "Mbc3",
             MbcType::Mbc5(_) => "Mbc5",
-            MbcType::HuC1(_) => "HuC1",
+            MbcType::WisdomTree(_) => "WisdomTree",
+            MbcType::M161(_) => "M161",
+            MbcType::Custom(_) => "Custom",
         };
 
         write!(f, "{}", name)
@@ -619,18 +1193,66 @@ fn parse_str(b: &[u8]) -> String {
     String::from_utf8_lossy(&b).to_string()
 }
 
+/// The cartridge header at 0x0100-0x014f of the ROM.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Header {
+    /// The title, with the trailing padding.
+    pub title: String,
+    /// The CGB flag at 0x0143.
+    pub cgb_flag: u8,
+    /// The new licensee code.
+    pub licensee: String,
+    /// The old licensee code, 0x33 if the new one is used instead.
+    pub old_licensee: u8,
+    /// Whether the game supports the SGB functions.
+    pub sgb: bool,
+    /// The type of the memory bank controller and the hardware on the cartridge.
+    pub cartridge_type: u8,
+    /// The ROM size code.
+    pub rom_size: u8,
+    /// The RAM size code.
+    pub ram_size: u8,
+    /// The destination code, 0x00 for Japan.
+    pub destination: u8,
+    /// The version number of the game.
+    pub version: u8,
+}
+
+impl Header {
+    /// Parse the header of the ROM image. A truncated header reads as zeros.
+    pub fn parse(rom: &[u8]) -> Self {
+        let mut rom = rom[..rom.len().min(HEADER_END)].to_vec();
+        rom.resize(HEADER_END, 0);
+
+        Self {
+            title: parse_str(&rom[0x134..0x144]),
+            cgb_flag: rom[0x143],
+            licensee: parse_str(&rom[0x144..0x146]),
+            old_licensee: rom[0x14b],
+            sgb: rom[0x146] == 0x03,
+            cartridge_type: rom[0x147],
+            rom_size: rom[0x148],
+            ram_size: rom[0x149],
+            destination: rom[0x14a],
+            version: rom[0x14c],
+        }
+    }
+
+    /// Whether the game uses the CGB functions when run on a CGB.
+    pub fn supports_cgb(&self) -> bool {
+        self.cgb_flag & 0x80 != 0
+    }
+
+    /// Whether the game only runs on a CGB. Such games show a message asking for a CGB on the DMG.
+    pub fn requires_cgb(&self) -> bool {
+        self.cgb_flag == 0xc0
+    }
+}
+
 struct Cartridge {
-    title: String,
-    cgb: bool,
-    cgb_only: bool,
-    license_new: String,
-    license_old: u8,
-    sgb: bool,
+    header: Header,
     mbc: MbcType,
-    rom_size: u8,
-    ram_size: u8,
-    dstcode: u8,
-    rom_version: u8,
 }
 
 fn verify(rom: &[u8], checksum: u16) {
@@ -654,33 +1276,45 @@ fn verify(rom: &[u8], checksum: u16) {
 }
 
 impl Cartridge {
-    fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
+    fn new(hw: HardwareHandle, mut rom: Vec<u8>) -> Self {
+        if let Err(e) = validate_rom(&rom) {
+            warn!("{}", e);
+        }
+
+        // A truncated header reads as zeros, i.e. a ROM-only cartridge.
+        if rom.len() < HEADER_END {
+            rom.resize(HEADER_END, 0);
+        }
+
         let checksum = (rom[0x14e] as u16) << 8 | (rom[0x14f] as u16);
 
         verify(&rom, checksum);
 
-        Self {
-            title: parse_str(&rom[0x134..0x144]),
-            cgb: rom[0x143] & 0x80 != 0,
-            cgb_only: rom[0x143] == 0xc0,
-            license_new: parse_str(&rom[0x144..0x146]),
-            license_old: rom[0x14b],
-            sgb: rom[0x146] == 0x03,
-            mbc: MbcType::new(hw, rom[0x147], rom.clone()),
-            rom_size: rom[0x148],
-            ram_size: rom[0x149],
-            dstcode: rom[0x14a],
-            rom_version: rom[0x14c],
-        }
+        // Pad the ROM to a power of two so that bank numbers can be masked safely.
+        let size = rom_size(rom[0x148]).max(rom.len()).next_power_of_two();
+        rom.resize(size, 0xff);
+
+        let code = rom[0x147];
+        Self::with_header(Header::parse(&rom), MbcType::new(hw, code, rom))
+    }
+
+    /// Create the cartridge handled by the mapper, reading the header from its ROM.
+    fn with_mapper(mapper: Box<dyn Mapper>) -> Self {
+        Self::with_header(Header::parse(mapper.rom()), MbcType::Custom(mapper))
+    }
+
+    fn with_header(header: Header, mbc: MbcType) -> Self {
+        Self { header, mbc }
     }
 
     fn show_info(&self) {
-        info!("Title: {}", self.title);
+        let header = &self.header;
+        info!("Title: {}", header.title);
         info!(
             "License: {} ({:02x}), Version: {}",
-            self.license_new, self.license_old, self.rom_version,
+            header.licensee, header.old_licensee, header.version,
         );
-        let dstcode = match self.dstcode {
+        let dstcode = match header.destination {
             0x00 => "Japanese",
             0x01 => "Non-Japanese",
             _ => "Unknown",
@@ -690,10 +1324,12 @@ impl Cartridge {
         info!("Mbc: {}", self.mbc);
         info!(
             "Color: {} (Compat: {}), Super: {}",
-            self.cgb, !self.cgb_only, self.sgb,
+            header.supports_cgb(),
+            !header.requires_cgb(),
+            header.sgb,
         );
 
-        let rom_size = match self.rom_size {
+        let rom_size = match header.rom_size {
             0x00 => "32KByte (no ROM banking)",
             0x01 => "64KByte (4 banks)",
             0x02 => "128KByte (8 banks)",
@@ -707,7 +1343,7 @@ impl Cartridge {
             0x54 => "1.5MByte (96 banks)",
             _ => "Unknown",
         };
-        let ram_size = match self.ram_size {
+        let ram_size = match header.ram_size {
             0x00 => "None",
             0x01 => "2 KBytes",
             0x02 => "8 Kbytes",
@@ -718,6 +1354,35 @@ impl Cartridge {
         info!("RAM size: {}", ram_size);
     }
 
+    /// The size of the external RAM the game actually uses.
+    fn ram_len(&self) -> usize {
+        match self.mbc {
+            // MBC2 has built-in RAM and the header declares no RAM.
+            MbcType::Mbc2(_) => 0x200,
+            MbcType::Custom(ref c) => c.ram().len(),
+            _ => ram_size(self.header.ram_size),
+        }
+    }
+
+    fn ram(&self) -> &[u8] {
+        let len = self.ram_len();
+        self.mbc
+            .ram()
+            .map(|r| &r[..len.min(r.len())])
+            .unwrap_or(&[])
+    }
+
+    fn ram_mut(&mut self) -> &mut [u8] {
+        let len = self.ram_len();
+        match self.mbc.ram_mut() {
+            Some(r) => {
+                let len = len.min(r.len());
+                &mut r[..len]
+            }
+            None => &mut [],
+        }
+    }
+
     fn on_read(&mut self, mmu: &Mmu, addr: u16) -> MemRead {
         self.mbc.on_read(mmu, addr)
     }
@@ -727,31 +1392,233 @@ impl Cartridge {
     }
 }
 
+/// Snapshot of the memory bank controller state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MbcState {
+    /// The ROM bank mapped to 0x0000-0x3fff, zero unless the controller switches 32KB banks.
+    pub fixed_bank: usize,
+    /// The ROM bank mapped to 0x4000-0x7fff.
+    pub rom_bank: usize,
+    /// The external RAM bank mapped to 0xa000-0xbfff.
+    ///
+    /// While an MBC3 maps a clock register there instead, this is zero.
+    pub ram_bank: usize,
+    /// Whether the external RAM is enabled.
+    pub ram_enabled: bool,
+}
+
 pub struct Mbc {
+    hw: HardwareHandle,
     cartridge: Cartridge,
+    boot_rom: Vec<u8>,
     use_boot_rom: bool,
 }
 
 impl Mbc {
+    #[cfg(any(test, feature = "fuzz"))]
     pub fn new(hw: HardwareHandle, rom: Vec<u8>) -> Self {
-        let cartridge = Cartridge::new(hw, rom);
+        Self::with_boot_rom(hw, rom, Some(BOOT_ROM.to_vec()))
+    }
+
+    /// Create the controller mapping the boot ROM, if any, until it's unmapped by a write to 0xff50.
+    pub fn with_boot_rom(hw: HardwareHandle, rom: Vec<u8>, boot_rom: Option<Vec<u8>>) -> Self {
+        let cartridge = Cartridge::new(hw.clone(), rom);
+        Self::with_cartridge(hw, cartridge, boot_rom)
+    }
+
+    /// Create the controller delegating the cartridge accesses to the mapper.
+    pub fn with_mapper(
+        hw: HardwareHandle,
+        mapper: Box<dyn Mapper>,
+        boot_rom: Option<Vec<u8>>,
+    ) -> Self {
+        let mut mapper = mapper;
+        let ram = mapper.ram_mut();
+        if !ram.is_empty() {
+            let saved = load_ram(&hw, ram.len(), ram.len());
+            ram.copy_from_slice(&saved[..ram.len()]);
+        }
+
+        Self::with_cartridge(hw, Cartridge::with_mapper(mapper), boot_rom)
+    }
 
+    /// The header of the cartridge.
+    pub fn header(&self) -> &Header {
+        &self.cartridge.header
+    }
+
+    fn with_cartridge(hw: HardwareHandle, cartridge: Cartridge, boot_rom: Option<Vec<u8>>) -> Self {
         cartridge.show_info();
 
-        Self {
+        let mut mbc = Self {
+            hw,
             cartridge,
-            use_boot_rom: true,
+            use_boot_rom: false,
+            boot_rom: Vec::new(),
+        };
+        mbc.set_boot_rom(boot_rom);
+        mbc
+    }
+
+    /// Map the boot ROM, if any, checking its size once rather than on each read.
+    fn set_boot_rom(&mut self, boot_rom: Option<Vec<u8>>) {
+        if let Some(boot_rom) = &boot_rom {
+            assert_eq!(boot_rom.len(), BOOT_ROM_SIZE, "Invalid boot ROM size");
+        }
+        self.use_boot_rom = boot_rom.is_some();
+        self.boot_rom = boot_rom.unwrap_or_default();
+    }
+
+    /// Restore the power-on state with the boot ROM, if any.
+    ///
+    /// The external RAM is kept if `keep_ram` is set, otherwise it's loaded again from the hardware.
+    pub fn reset(&mut self, boot_rom: Option<Vec<u8>>, keep_ram: bool) {
+        // The clock keeps running on the battery of the cartridge too.
+        self.cartridge.mbc.reset(keep_ram);
+
+        // The RAM is loaded into the same buffer, as the frontends may hold a pointer to it.
+        let len = self.cartridge.ram_len();
+        if let Some(ram) = self.cartridge.mbc.ram_mut() {
+            if !keep_ram && !ram.is_empty() {
+                let saved = load_ram(&self.hw, ram.len(), len.min(ram.len()));
+                ram.copy_from_slice(&saved);
+            }
+        }
+
+        self.set_boot_rom(boot_rom);
+    }
+
+    /// Replace the cartridge by the ROM, mapping the boot ROM if any.
+    ///
+    /// The external RAM of the previous cartridge is saved through the hardware first,
+    /// then the external RAM of the new cartridge is loaded from it.
+    pub fn swap_rom(&mut self, rom: Vec<u8>, boot_rom: Option<Vec<u8>>) {
+        save_ram(&self.hw, self.cartridge_ram());
+
+        *self = Mbc::with_boot_rom(self.hw.clone(), rom, boot_rom);
+    }
+
+    /// The external RAM of the cartridge, limited to the size in the cartridge header.
+    pub fn cartridge_ram(&self) -> &[u8] {
+        self.cartridge.ram()
+    }
+
+    /// Mutable reference to the external RAM of the cartridge.
+    pub fn cartridge_ram_mut(&mut self) -> &mut [u8] {
+        self.cartridge.ram_mut()
+    }
+
+    /// The ROM bank of the address, or zero if the address isn't in the ROM area.
+    pub fn rom_bank(&self, addr: u16) -> usize {
+        if addr <= 0x3fff {
+            self.cartridge.mbc.fixed_bank()
+        } else if addr <= 0x7fff {
+            self.cartridge.mbc.rom_bank()
+        } else {
+            0
+        }
+    }
+
+    /// Read the external RAM at the address in the mapped bank, even while the RAM is disabled.
+    ///
+    /// Returns `None` if no RAM is mapped there, e.g. an MBC3 clock register is selected.
+    pub fn peek_ram(&self, addr: u16) -> Option<u8> {
+        let mbc = &self.cartridge.mbc;
+        Some(mbc.ram()?[mbc.ram_offset(addr)?])
+    }
+
+    /// Write the external RAM at the address in the mapped bank, even while the RAM is disabled.
+    ///
+    /// Returns `false` if no RAM is mapped there.
+    pub fn poke_ram(&mut self, addr: u16, value: u8) -> bool {
+        let mbc = &mut self.cartridge.mbc;
+        let value = match mbc {
+            // MBC2 RAM is made of 4-bit cells.
+            MbcType::Mbc2(_) => value & 0xf,
+            _ => value,
+        };
+
+        match (mbc.ram_offset(addr), mbc.ram_mut()) {
+            (Some(offset), Some(ram)) => {
+                ram[offset] = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Snapshot of the banks currently mapped by the controller.
+    pub fn state(&self) -> MbcState {
+        MbcState {
+            fixed_bank: self.cartridge.mbc.fixed_bank(),
+            rom_bank: self.cartridge.mbc.rom_bank(),
+            ram_bank: self.cartridge.mbc.ram_bank(),
+            ram_enabled: self.cartridge.mbc.ram_enabled(),
+        }
+    }
+
+    /// The size of the ROM image, padded to a power of two.
+    pub fn rom_len(&self) -> usize {
+        self.cartridge.mbc.rom().len()
+    }
+
+    /// The offset in the ROM image of the address, if the cartridge ROM is mapped there.
+    pub fn rom_offset_of(&self, addr: u16) -> Option<usize> {
+        if self.use_boot_rom && self.in_boot_rom(addr) {
+            None
+        } else if addr <= 0x7fff {
+            Some(self.rom_bank(addr) * 0x4000 + (addr as usize & 0x3fff))
+        } else {
+            None
+        }
+    }
+
+    /// The state of the real-time clock, if the cartridge has one.
+    pub fn rtc(&self) -> Option<RtcSave> {
+        self.cartridge.mbc.rtc()
+    }
+
+    /// Export the save data, appending the RTC state if the cartridge has a clock.
+    pub fn export_save(&self) -> Vec<u8> {
+        let mut data = self.cartridge.ram().to_vec();
+        if let Some(rtc) = self.cartridge.mbc.rtc() {
+            data.extend_from_slice(&rtc.to_bytes());
+        }
+        data
+    }
+
+    /// Import save data exported by this or other emulators.
+    pub fn import_save(&mut self, data: &[u8]) -> Result<(), SaveError> {
+        let ram = self.cartridge.ram_mut();
+        let (data, rtc) = save::split(data, ram.len())?;
+        ram.copy_from_slice(data);
+        if let Some(rtc) = rtc {
+            self.cartridge.mbc.load_rtc(&rtc);
+        }
+        Ok(())
+    }
+
+    /// Raise the events for the bank switches done by a write to the MBC registers.
+    fn raise_events(&self, old: MbcState) {
+        let new = self.state();
+        let mut hw = self.hw.get().borrow_mut();
+
+        if new.rom_bank != old.rom_bank {
+            hw.event(Event::RomBank(new.rom_bank));
+        }
+        if new.ram_bank != old.ram_bank {
+            hw.event(Event::RamBank(new.ram_bank));
+        }
+        if new.ram_enabled != old.ram_enabled {
+            hw.event(Event::CartRam(new.ram_enabled));
         }
     }
 
     fn in_boot_rom(&self, addr: u16) -> bool {
         if cfg!(feature = "color") {
-            assert_eq!(0x900, BOOT_ROM.len());
-
             (addr < 0x100 || (addr >= 0x200 && addr < 0x900))
         } else {
-            assert_eq!(0x100, BOOT_ROM.len());
-
             addr < 0x100
         }
     }
@@ -760,21 +1627,437 @@ impl Mbc {
 impl IoHandler for Mbc {
     fn on_read(&mut self, mmu: &Mmu, addr: u16) -> MemRead {
         if self.use_boot_rom && self.in_boot_rom(addr) {
-            MemRead::Replace(BOOT_ROM[addr as usize])
+            MemRead::Replace(self.boot_rom[addr as usize])
         } else {
             self.cartridge.on_read(mmu, addr)
         }
     }
 
     fn on_write(&mut self, mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
-        if self.use_boot_rom && addr < 0x100 {
-            unreachable!("Writing to boot ROM")
-        } else if addr == 0xff50 {
+        // The boot ROM is read-only, so the writes reach the cartridge as usual.
+        if addr == 0xff50 {
             info!("Disable boot ROM");
+            if self.use_boot_rom {
+                self.hw.get().borrow_mut().event(Event::BootRomDisabled);
+            }
             self.use_boot_rom = false;
             MemWrite::Block
+        } else if addr <= 0x7fff {
+            let old = self.state();
+            let res = self.cartridge.on_write(mmu, addr, value);
+            self.raise_events(old);
+            res
         } else {
             self.cartridge.on_write(mmu, addr, value)
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn rom_bank_mask() {
+        let rom = vec![0; 0x8000 << 2];
+
+        assert_eq!(rom_offset(&rom, 1, 0x4000), 0x4000);
+        assert_eq!(rom_offset(&rom, 7, 0x7fff), 0x1ffff);
+        // Bank 9 wraps around to bank 1 on a 8-bank cartridge.
+        assert_eq!(rom_offset(&rom, 9, 0x4000), 0x4000);
+    }
+
+    #[test]
+    fn rom_size_validation() {
+        let mut rom = vec![0; 0x10000];
+        rom[0x148] = 0x01;
+        assert_eq!(validate_rom(&rom), Ok(()));
+
+        rom[0x148] = 0x02;
+        assert_eq!(
+            validate_rom(&rom),
+            Err(Error::RomSizeMismatch {
+                expected: 0x20000,
+                actual: 0x10000
+            })
+        );
+
+        let rom = vec![0; 0x14f];
+        assert_eq!(
+            validate_rom(&rom),
+            Err(Error::RomTooShort { actual: 0x14f })
+        );
+    }
+
+    #[test]
+    fn bank_events() {
+        use crate::hardware::{
+            EventHook, Hardware, Key, SavePersistence, SchedHook, SerialPort, Stream,
+        };
+        use alloc::boxed::Box;
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+
+        struct Recorder(Rc<RefCell<Vec<Event>>>);
+
+        impl Hardware for Recorder {
+            fn vram_update(&mut self, _line: usize, _buffer: &[u32]) {}
+
+            fn joypad_pressed(&mut self, _key: Key) -> bool {
+                false
+            }
+
+            fn sound_play(&mut self, _stream: Box<dyn Stream>) {}
+
+            fn clock(&mut self) -> u64 {
+                0
+            }
+        }
+
+        impl SavePersistence for Recorder {}
+
+        impl SerialPort for Recorder {}
+
+        impl SchedHook for Recorder {}
+
+        impl EventHook for Recorder {
+            fn event(&mut self, event: Event) {
+                self.0.borrow_mut().push(event);
+            }
+        }
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut rom = vec![0; 0x20000];
+        rom[0x147] = 0x03;
+        rom[0x148] = 0x02;
+        rom[0x149] = 0x03;
+
+        let hw = HardwareHandle::new(Recorder(events.clone()));
+        let mut mbc = Mbc::new(hw, rom);
+        let mmu = Mmu::new();
+
+        mbc.on_write(&mmu, 0xff50, 1);
+        mbc.on_write(&mmu, 0xff50, 1);
+        mbc.on_write(&mmu, 0x0000, 0x0a);
+        mbc.on_write(&mmu, 0x2000, 0x05);
+        // The same bank again doesn't raise an event.
+        mbc.on_write(&mmu, 0x2000, 0x05);
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                Event::BootRomDisabled,
+                Event::CartRam(true),
+                Event::RomBank(5)
+            ]
+        );
+    }
+
+    #[cfg(feature = "rtc")]
+    #[test]
+    fn rtc_day() {
+        use crate::hardware::NullHardware;
+
+        let mut rtc = Rtc::new(HardwareHandle::new(NullHardware));
+        rtc.write(0x0b, 0x34);
+        rtc.write(0x0c, 0x01);
+        rtc.latch();
+        assert_eq!(rtc.day(), 0x134);
+        assert_eq!((rtc.read(0x0b), rtc.read(0x0c)), (0x34, 0x01));
+
+        let save = rtc.save().unwrap();
+        assert_eq!(save.regs[3..], [0x34, 0x01]);
+
+        // A save from the future, with the clock of the hardware starting at zero.
+        let mut rtc = Rtc::new(HardwareHandle::new(NullHardware));
+        rtc.load(&RtcSave {
+            timestamp: 1_000_000,
+            ..save
+        });
+        rtc.latch();
+        assert_eq!(rtc.day(), 0x134);
+    }
+
+    #[test]
+    fn save_ram_size() {
+        use crate::hardware::{
+            EventHook, Hardware, Key, SavePersistence, SchedHook, SerialPort, Stream,
+        };
+        use alloc::boxed::Box;
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+
+        /// The sizes of the RAM loaded and saved.
+        struct Recorder(Rc<RefCell<Vec<usize>>>);
+
+        impl Hardware for Recorder {
+            fn vram_update(&mut self, _line: usize, _buffer: &[u32]) {}
+
+            fn joypad_pressed(&mut self, _key: Key) -> bool {
+                false
+            }
+
+            fn sound_play(&mut self, _stream: Box<dyn Stream>) {}
+
+            fn clock(&mut self) -> u64 {
+                0
+            }
+        }
+
+        impl SavePersistence for Recorder {
+            fn load_ram(&mut self, size: usize) -> Vec<u8> {
+                self.0.borrow_mut().push(size);
+                vec![0x55; size]
+            }
+
+            fn save_ram(&mut self, ram: &[u8]) {
+                self.0.borrow_mut().push(ram.len());
+            }
+        }
+
+        impl SerialPort for Recorder {}
+
+        impl SchedHook for Recorder {}
+
+        impl EventHook for Recorder {}
+
+        let mmu = Mmu::new();
+        let sizes = Rc::new(RefCell::new(Vec::new()));
+
+        // MBC5 with 8 KB of RAM, though the controller can address 128 KB.
+        let mut rom = vec![0; 0x8000];
+        rom[0x147] = 0x1b;
+        rom[0x149] = 0x02;
+        let hw = HardwareHandle::new(Recorder(sizes.clone()));
+        let mut mbc = Mbc::new(hw, rom);
+        mbc.on_write(&mmu, 0x0000, 0x0a);
+        mbc.on_write(&mmu, 0x0000, 0x00);
+        assert_eq!(*sizes.borrow(), [0x2000, 0x2000]);
+        assert_eq!(mbc.cartridge_ram(), &[0x55; 0x2000][..]);
+
+        // MBC1 without RAM has nothing to save.
+        sizes.borrow_mut().clear();
+        let mut rom = vec![0; 0x8000];
+        rom[0x147] = 0x03;
+        let hw = HardwareHandle::new(Recorder(sizes.clone()));
+        let mut mbc = Mbc::new(hw, rom);
+        mbc.on_write(&mmu, 0x0000, 0x0a);
+        mbc.on_write(&mmu, 0x0000, 0x00);
+        assert_eq!(*sizes.borrow(), [0]);
+        assert!(mbc.cartridge_ram().is_empty());
+    }
+
+    #[test]
+    fn bank_switch() {
+        let mmu = Mmu::new();
+        let read = |mbc: &mut Mbc, addr| match mbc.on_read(&mmu, addr) {
+            MemRead::Replace(value) => value,
+            MemRead::PassThrough => unreachable!(),
+        };
+        let cart = |kind, size, banks: usize| {
+            let mut rom = vec![0; banks * 0x4000];
+            for bank in 0..banks {
+                rom[bank * 0x4000 + 0x1000] = bank as u8;
+                rom[bank * 0x4000 + 0x1001] = (bank >> 8) as u8;
+            }
+            rom[0x147] = kind;
+            rom[0x148] = size;
+            let hw = HardwareHandle::new(crate::hardware::NullHardware);
+            let mut mbc = Mbc::new(hw, rom);
+            mbc.on_write(&mmu, 0xff50, 1);
+            mbc
+        };
+
+        // MBC1 with 64 banks.
+        let mut mbc = cart(0x03, 0x05, 64);
+        assert_eq!(read(&mut mbc, 0x5000), 1);
+        mbc.on_write(&mmu, 0x2000, 0x05);
+        assert_eq!(read(&mut mbc, 0x5000), 5);
+        // Bank 0x20 maps bank 0x21.
+        mbc.on_write(&mmu, 0x2000, 0x00);
+        mbc.on_write(&mmu, 0x4000, 0x01);
+        assert_eq!(read(&mut mbc, 0x5000), 0x21);
+
+        // The RAM bank only applies in the RAM banking mode.
+        mbc.on_write(&mmu, 0x0000, 0x0a);
+        mbc.on_write(&mmu, 0x6000, 0x01);
+        mbc.on_write(&mmu, 0x4000, 0x02);
+        mbc.on_write(&mmu, 0xa000, 0x42);
+        mbc.on_write(&mmu, 0x6000, 0x00);
+        assert_eq!(read(&mut mbc, 0xa000), 0x00);
+        mbc.on_write(&mmu, 0x6000, 0x01);
+        assert_eq!(read(&mut mbc, 0xa000), 0x42);
+
+        // MBC5 with 512 banks, selected with 9 bits.
+        let mut mbc = cart(0x1b, 0x08, 512);
+        assert_eq!(read(&mut mbc, 0x5000), 0);
+        mbc.on_write(&mmu, 0x2000, 0x34);
+        mbc.on_write(&mmu, 0x3000, 0x01);
+        assert_eq!(read(&mut mbc, 0x5000), 0x34);
+        assert_eq!(read(&mut mbc, 0x5001), 0x01);
+
+        mbc.on_write(&mmu, 0x0000, 0x0a);
+        mbc.on_write(&mmu, 0x4000, 0x03);
+        mbc.on_write(&mmu, 0xbfff, 0x24);
+        mbc.on_write(&mmu, 0x4000, 0x00);
+        assert_eq!(read(&mut mbc, 0xbfff), 0x00);
+        mbc.on_write(&mmu, 0x4000, 0x03);
+        assert_eq!(read(&mut mbc, 0xbfff), 0x24);
+    }
+
+    #[test]
+    fn truncated_rom() {
+        let hw = HardwareHandle::new(crate::hardware::NullHardware);
+        let mut mbc = Mbc::new(hw, vec![0x3c; 0x10]);
+
+        mbc.on_write(&Mmu::new(), 0xff50, 1);
+        assert!(matches!(
+            mbc.on_read(&Mmu::new(), 0x0000),
+            MemRead::Replace(0x3c)
+        ));
+        assert!(matches!(
+            mbc.on_read(&Mmu::new(), 0x7fff),
+            MemRead::Replace(0xff)
+        ));
+    }
+
+    #[test]
+    fn derived_boot_rom() {
+        let model = if cfg!(feature = "color") {
+            Model::Agb
+        } else {
+            Model::Mgb
+        };
+        let rom = boot_rom(model).unwrap();
+        assert_eq!(rom.len(), BOOT_ROM_SIZE);
+
+        // Only the code before the hand-over is patched, which still unmaps the boot ROM at $fe.
+        assert_eq!(rom[..0xf2], BOOT_ROM[..0xf2]);
+        assert_eq!(rom[0xfe..], BOOT_ROM[0xfe..]);
+        assert_eq!(rom[0xfe..0x100], [0xe0, 0x50]);
+    }
+
+    #[test]
+    fn custom_mapper() {
+        struct Banked {
+            rom: Vec<u8>,
+            bank: usize,
+            ram: Vec<u8>,
+        }
+
+        impl Mapper for Banked {
+            fn read(&mut self, addr: u16) -> u8 {
+                match addr {
+                    0x0000..=0x3fff => self.rom[addr as usize],
+                    0x4000..=0x7fff => self.rom[self.bank * 0x4000 + (addr as usize - 0x4000)],
+                    _ => self.ram[addr as usize - 0xa000],
+                }
+            }
+
+            fn write(&mut self, addr: u16, value: u8) {
+                match addr {
+                    0x0000..=0x7fff => self.bank = (value as usize & 3).max(1),
+                    _ => self.ram[addr as usize - 0xa000] = value,
+                }
+            }
+
+            fn rom(&self) -> &[u8] {
+                &self.rom
+            }
+
+            fn rom_bank(&self) -> usize {
+                self.bank
+            }
+
+            fn ram(&self) -> &[u8] {
+                &self.ram
+            }
+
+            fn ram_mut(&mut self) -> &mut [u8] {
+                &mut self.ram
+            }
+
+            fn reset(&mut self) {
+                self.bank = 1;
+            }
+        }
+
+        let mut rom = vec![0; 0x10000];
+        rom[0x134..0x138].copy_from_slice(b"TEST");
+        for bank in 0..4 {
+            rom[bank * 0x4000 + 0x100] = bank as u8;
+        }
+        let mapper = Banked {
+            rom,
+            bank: 1,
+            ram: vec![0; 0x2000],
+        };
+
+        let hw = HardwareHandle::new(crate::hardware::NullHardware);
+        let mut mbc = Mbc::with_mapper(hw, Box::new(mapper), None);
+        let mmu = Mmu::new();
+
+        assert_eq!(mbc.header().title.trim(), "TEST");
+        assert!(matches!(mbc.on_read(&mmu, 0x4100), MemRead::Replace(1)));
+
+        mbc.on_write(&mmu, 0x2000, 3);
+        mbc.on_write(&mmu, 0xa010, 0x42);
+        assert_eq!(mbc.rom_bank(0x4000), 3);
+        assert!(matches!(mbc.on_read(&mmu, 0x4100), MemRead::Replace(3)));
+        assert_eq!(mbc.export_save()[0x10], 0x42);
+        assert_eq!(mbc.peek_ram(0xa010), Some(0x42));
+
+        mbc.reset(None, true);
+        assert_eq!(mbc.rom_bank(0x4000), 1);
+        assert_eq!(mbc.export_save()[0x10], 0x42);
+    }
+
+    #[test]
+    fn unlicensed_mappers() {
+        let hw = HardwareHandle::new(crate::hardware::NullHardware);
+        let mmu = Mmu::new();
+
+        let mut rom = vec![0; 0x20000];
+        for bank in 0..4 {
+            rom[bank * 0x8000 + 0x4000] = bank as u8;
+        }
+        rom[0x200..0x20b].copy_from_slice(b"WISDOM TREE");
+
+        let mut mbc = Mbc::new(hw.clone(), rom.clone());
+        mbc.on_write(&mmu, 0xff50, 1);
+        assert_eq!(mbc.cartridge.mbc.to_string(), "WisdomTree");
+        // The bank is selected by the address rather than the value.
+        mbc.on_write(&mmu, 0x0002, 0);
+        assert!(matches!(mbc.on_read(&mmu, 0x4000), MemRead::Replace(2)));
+        assert!(matches!(mbc.on_read(&mmu, 0x0200), MemRead::Replace(0)));
+        assert_eq!(mbc.rom_bank(0x4000), 5);
+        // The whole 32KB bank is mapped, including the fixed bank area.
+        assert_eq!(mbc.rom_bank(0x0200), 4);
+        assert_eq!(mbc.rom_offset_of(0x0200), Some(0x10200));
+        assert_eq!(mbc.rom_offset_of(0x4000), Some(0x14000));
+
+        // Some of the games separate the words of the publisher name with a NUL.
+        let mut nul = rom.clone();
+        nul[0x200..0x20b].copy_from_slice(b"WISDOM\x00TREE");
+        assert!(is_wisdom_tree(&nul));
+        nul[0x206] = b'-';
+        assert!(!is_wisdom_tree(&nul));
+
+        rom[0x147] = 0x10;
+        rom[0x148] = 0x02;
+        rom[0x134..0x13e].copy_from_slice(b"TETRIS SET");
+
+        let mut mbc = Mbc::new(hw, rom);
+        mbc.on_write(&mmu, 0xff50, 1);
+        assert_eq!(mbc.cartridge.mbc.to_string(), "M161");
+        mbc.on_write(&mmu, 0x4000, 3);
+        // Only the first selection is taken until the console is reset.
+        mbc.on_write(&mmu, 0x4000, 1);
+        assert!(matches!(mbc.on_read(&mmu, 0x4000), MemRead::Replace(3)));
+
+        mbc.reset(None, true);
+        mbc.on_write(&mmu, 0x4000, 1);
+        assert!(matches!(mbc.on_read(&mmu, 0x4000), MemRead::Replace(1)));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::buffer::Buffer;
 use alloc::rc::Rc;
 use alloc::{vec, vec::Vec};
 use hashbrown::HashMap;
@@ -29,6 +30,15 @@ pub trait MemHandler {
     fn on_write(&self, mmu: &Mmu, addr: u16, value: u8) -> MemWrite;
 }
 
+/// The peripherals advanced by the MMU on each machine cycle the CPU spends on memory access.
+pub trait Clock {
+    /// The function is called once per machine cycle, before the access of that cycle.
+    fn tick(&self, mmu: &mut Mmu);
+}
+
+/// The size of the memory byte array backing the whole address space.
+pub(crate) const RAM_SIZE: usize = 0x10000;
+
 /// The handle of a memory handler.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Handle(u64);
@@ -38,22 +48,61 @@ pub struct Handle(u64);
 /// This unit holds a memory byte array which represents address space of the memory.
 /// It provides the logic to intercept access from the CPU to the memory byte array,
 /// and to modify the memory access behaviour.
-pub struct Mmu {
-    ram: Vec<u8>,
+pub struct Mmu<'a> {
+    ram: Buffer<'a>,
     handles: HashMap<Handle, (u16, u16)>,
-    handlers: HashMap<u16, Vec<(Handle, Rc<dyn MemHandler>)>>,
+    handlers: HashMap<u16, Vec<(Handle, Rc<dyn MemHandler + 'a>)>>,
     hdgen: u64,
+    clock: Option<Rc<dyn Clock + 'a>>,
+    cycles: usize,
 }
 
-impl Mmu {
+impl<'a> Mmu<'a> {
     /// Create a new MMU instance.
-    pub fn new() -> Mmu {
+    pub fn new() -> Self {
+        Mmu::with_ram(Buffer::new(None, RAM_SIZE))
+    }
+
+    pub(crate) fn with_ram(ram: Buffer<'a>) -> Self {
+        assert_eq!(ram.len(), RAM_SIZE);
+
         Mmu {
-            ram: vec![0u8; 0x10000],
+            ram,
             handles: HashMap::new(),
             handlers: HashMap::new(),
             hdgen: 0,
+            clock: None,
+            cycles: 0,
+        }
+    }
+
+    /// Clear the memory, keeping the handlers and the clock.
+    pub(crate) fn reset(&mut self) {
+        for b in self.ram.iter_mut() {
+            *b = 0;
         }
+        self.cycles = 0;
+    }
+
+    /// Set the peripherals to advance on each machine cycle of the CPU memory access.
+    pub fn set_clock<T>(&mut self, clock: T)
+    where
+        T: Clock + 'a,
+    {
+        self.clock = Some(Rc::new(clock));
+    }
+
+    /// Advance the clocked peripherals by one machine cycle, if any.
+    pub fn tick(&mut self) {
+        if let Some(clock) = self.clock.clone() {
+            clock.tick(self);
+            self.cycles += 1;
+        }
+    }
+
+    /// Return the number of machine cycles ticked since the last call, and reset it.
+    pub fn take_cycles(&mut self) -> usize {
+        core::mem::replace(&mut self.cycles, 0)
     }
 
     fn next_handle(&mut self) -> Handle {
@@ -67,7 +116,7 @@ impl Mmu {
     /// Add a new memory handler.
     pub fn add_handler<T>(&mut self, range: (u16, u16), handler: T) -> Handle
     where
-        T: MemHandler + 'static,
+        T: MemHandler + 'a,
     {
         let handle = self.next_handle();
         let handler = Rc::new(handler);
@@ -108,10 +157,20 @@ impl Mmu {
         }
     }
 
+    /// The object attribute memory, read without calling the handlers.
+    pub(crate) fn oam(&self) -> &[u8] {
+        &self.ram[0xfe00..0xfea0]
+    }
+
     /// Reads one byte from the given address in the memory.
     pub fn get8(&self, addr: u16) -> u8 {
+        self.get8_skip(addr, None)
+    }
+
+    /// Reads one byte from the given address in the memory, without calling the handler of the handle.
+    pub(crate) fn get8_skip(&self, addr: u16, skip: Option<&Handle>) -> u8 {
         if let Some(handlers) = self.handlers.get(&addr) {
-            for (_, handler) in handlers {
+            for (_, handler) in handlers.iter().filter(|(h, _)| Some(h) != skip) {
                 match handler.on_read(self, addr) {
                     MemRead::Replace(alt) => return alt,
                     MemRead::PassThrough => {}
@@ -129,8 +188,13 @@ impl Mmu {
 
     /// Writes one byte at the given address in the memory.
     pub fn set8(&mut self, addr: u16, v: u8) {
+        self.set8_skip(addr, v, None)
+    }
+
+    /// Writes one byte at the given address in the memory, without calling the handler of the handle.
+    pub(crate) fn set8_skip(&mut self, addr: u16, v: u8, skip: Option<&Handle>) {
         if let Some(handlers) = self.handlers.get(&addr) {
-            for (_, handler) in handlers {
+            for (_, handler) in handlers.iter().filter(|(h, _)| Some(h) != skip) {
                 match handler.on_write(self, addr, v) {
                     MemWrite::Replace(alt) => {
                         self.ram[addr as usize] = alt;
@@ -153,13 +217,38 @@ impl Mmu {
     /// Reads two bytes from the given addresss in the memory.
     pub fn get16(&self, addr: u16) -> u16 {
         let l = self.get8(addr);
-        let h = self.get8(addr + 1);
+        let h = self.get8(addr.wrapping_add(1));
         (h as u16) << 8 | l as u16
     }
 
     /// Writes two bytes at the given address in the memory.
     pub fn set16(&mut self, addr: u16, v: u16) {
         self.set8(addr, v as u8);
-        self.set8(addr + 1, (v >> 8) as u8);
+        self.set8(addr.wrapping_add(1), (v >> 8) as u8);
+    }
+
+    /// Reads one byte from the given address as the CPU, taking one machine cycle.
+    pub fn read8(&mut self, addr: u16) -> u8 {
+        self.tick();
+        self.get8(addr)
+    }
+
+    /// Writes one byte at the given address as the CPU, taking one machine cycle.
+    pub fn write8(&mut self, addr: u16, v: u8) {
+        self.tick();
+        self.set8(addr, v);
+    }
+
+    /// Reads two bytes from the given address as the CPU, taking two machine cycles.
+    pub fn read16(&mut self, addr: u16) -> u16 {
+        let l = self.read8(addr);
+        let h = self.read8(addr.wrapping_add(1));
+        (h as u16) << 8 | l as u16
+    }
+
+    /// Writes two bytes at the given address as the CPU, taking two machine cycles.
+    pub fn write16(&mut self, addr: u16, v: u16) {
+        self.write8(addr, v as u8);
+        self.write8(addr.wrapping_add(1), (v >> 8) as u8);
     }
 }
@@ -0,0 +1,48 @@
+//! Stand-ins for the macros of the `log` crate, used when the `log` feature is disabled.
+//!
+//! The arguments are still type-checked, but never formatted, so the hot paths pay
+//! nothing for their messages.
+
+/// Swallow the arguments of a log message.
+#[inline(always)]
+pub(crate) fn discard(_args: core::fmt::Arguments) {}
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if false {
+            $crate::nolog::discard(format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if false {
+            $crate::nolog::discard(format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if false {
+            $crate::nolog::discard(format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        if false {
+            $crate::nolog::discard(format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => {
+        if false {
+            $crate::nolog::discard(format_args!($($arg)*));
+        }
+    };
+}
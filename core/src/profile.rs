@@ -0,0 +1,172 @@
+use crate::inst;
+use alloc::{vec, vec::Vec};
+use hashbrown::HashMap;
+
+/// The clock cycles spent in the instructions of an opcode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OpcodeStat {
+    /// The opcode, prefixed by `0xcb` for the extended instructions.
+    pub code: u16,
+    /// The mnemonic string of the opcode.
+    pub mnemonic: &'static str,
+    /// The number of times the opcode was executed.
+    pub count: u64,
+    /// The clock cycles spent in the opcode.
+    pub cycles: u64,
+}
+
+/// The clock cycles spent in the instructions of a 256-byte page of the address space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PageStat {
+    /// The ROM bank of the page, or zero outside the switchable bank area.
+    pub bank: usize,
+    /// The first address of the page.
+    pub addr: u16,
+    /// The clock cycles spent in the page.
+    pub cycles: u64,
+}
+
+/// The cycle counts accumulated by the profiler, hottest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProfileReport {
+    /// The cycles spent per opcode.
+    pub opcodes: Vec<OpcodeStat>,
+    /// The cycles spent per page and ROM bank.
+    pub pages: Vec<PageStat>,
+    /// The cycles spent halting.
+    pub halted: u64,
+}
+
+/// Accumulates the clock cycles of the executed instructions.
+pub struct Profiler {
+    opcodes: Vec<(u64, u64)>,
+    pages: HashMap<(usize, u16), u64>,
+    halted: u64,
+}
+
+fn index(code: u16) -> usize {
+    if code & 0xff00 == 0xcb00 {
+        0x100 + (code & 0xff) as usize
+    } else {
+        code as usize
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            opcodes: vec![(0, 0); 0x200],
+            pages: HashMap::new(),
+            halted: 0,
+        }
+    }
+
+    /// Record the instruction at `pc` in the ROM `bank` which took `time` clock cycles.
+    pub fn record(&mut self, bank: usize, pc: u16, code: u16, time: usize) {
+        let (count, cycles) = &mut self.opcodes[index(code)];
+        *count += 1;
+        *cycles += time as u64;
+
+        *self.pages.entry((bank, pc & 0xff00)).or_insert(0) += time as u64;
+    }
+
+    /// Record the clock cycles spent halting.
+    pub fn record_halt(&mut self, time: usize) {
+        self.halted += time as u64;
+    }
+
+    /// Clear the accumulated cycle counts.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Summarize the accumulated cycle counts.
+    pub fn report(&self) -> ProfileReport {
+        let mut opcodes: Vec<_> = self
+            .opcodes
+            .iter()
+            .enumerate()
+            .filter(|(_, (count, _))| *count > 0)
+            .map(|(i, (count, cycles))| {
+                let code = if i >= 0x100 { 0xcb00 | (i & 0xff) } else { i } as u16;
+                OpcodeStat {
+                    code,
+                    mnemonic: inst::mnem(code),
+                    count: *count,
+                    cycles: *cycles,
+                }
+            })
+            .collect();
+        opcodes.sort_by(|a, b| b.cycles.cmp(&a.cycles).then(a.code.cmp(&b.code)));
+
+        let mut pages: Vec<_> = self
+            .pages
+            .iter()
+            .map(|(&(bank, addr), &cycles)| PageStat { bank, addr, cycles })
+            .collect();
+        pages.sort_by(|a, b| {
+            b.cycles
+                .cmp(&a.cycles)
+                .then((a.bank, a.addr).cmp(&(b.bank, b.addr)))
+        });
+
+        ProfileReport {
+            opcodes,
+            pages,
+            halted: self.halted,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let mut p = Profiler::new();
+
+        p.record(0, 0x0150, 0x00, 4);
+        p.record(3, 0x4010, 0xcb37, 8);
+        p.record(3, 0x40f0, 0xcb37, 8);
+        p.record_halt(16);
+
+        let r = p.report();
+        assert_eq!(r.halted, 16);
+        assert_eq!(
+            r.opcodes,
+            vec![
+                OpcodeStat {
+                    code: 0xcb37,
+                    mnemonic: "swap a",
+                    count: 2,
+                    cycles: 16
+                },
+                OpcodeStat {
+                    code: 0x0000,
+                    mnemonic: "nop ",
+                    count: 1,
+                    cycles: 4
+                },
+            ]
+        );
+        assert_eq!(
+            r.pages,
+            vec![
+                PageStat {
+                    bank: 3,
+                    addr: 0x4000,
+                    cycles: 16
+                },
+                PageStat {
+                    bank: 0,
+                    addr: 0x0100,
+                    cycles: 4
+                },
+            ]
+        );
+    }
+}
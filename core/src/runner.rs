@@ -0,0 +1,119 @@
+//! A ready-made run loop for frontends on `std` targets.
+//!
+//! Instead of implementing [`Hardware`][] line by line, a frontend implements [`Frontend`][],
+//! which is called once per frame. The loop paces the frames to the configured CPU frequency.
+
+use crate::debug::NullDebugger;
+use crate::hardware::{EventHook, Hardware, Key, SavePersistence, SchedHook, SerialPort, Stream};
+use crate::system::{Config, System, FRAME_CYCLES};
+use crate::{VRAM_HEIGHT, VRAM_WIDTH};
+use alloc::boxed::Box;
+use alloc::{vec, vec::Vec};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The callbacks a desktop frontend provides to [`run`][].
+pub trait Frontend {
+    /// Called with the whole frame, `VRAM_WIDTH * VRAM_HEIGHT` pixels in rows, once it's drawn.
+    fn frame(&mut self, frame: &[u32]);
+
+    /// Called when the emulator checks if the key is pressed.
+    fn pressed(&mut self, key: Key) -> bool;
+
+    /// Called with the stream to pull the audio samples from, each time a sound starts.
+    fn audio(&mut self, _stream: Box<dyn Stream>) {}
+
+    /// Called once per frame. Returning `false` stops the emulator.
+    fn running(&mut self) -> bool {
+        true
+    }
+
+    /// Called to load the cartridge battery-backed RAM.
+    fn load_ram(&mut self, size: usize) -> Vec<u8> {
+        vec![0; size]
+    }
+
+    /// Called when the cartridge battery-backed RAM is written.
+    fn save_ram(&mut self, _ram: &[u8]) {}
+}
+
+/// Collects the lines into a frame for the frontend.
+struct Adapter<F> {
+    frontend: F,
+    frame: Vec<u32>,
+}
+
+impl<F: Frontend> Hardware for Adapter<F> {
+    fn vram_update(&mut self, line: usize, buffer: &[u32]) {
+        let off = line * VRAM_WIDTH;
+        self.frame[off..off + VRAM_WIDTH].copy_from_slice(buffer);
+
+        if line == VRAM_HEIGHT - 1 {
+            self.frontend.frame(&self.frame);
+        }
+    }
+
+    fn joypad_pressed(&mut self, key: Key) -> bool {
+        self.frontend.pressed(key)
+    }
+
+    fn sound_play(&mut self, stream: Box<dyn Stream>) {
+        self.frontend.audio(stream)
+    }
+
+    fn clock(&mut self) -> u64 {
+        let epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Couldn't get epoch");
+        epoch.as_micros() as u64
+    }
+}
+
+impl<F: Frontend> SavePersistence for Adapter<F> {
+    fn load_ram(&mut self, size: usize) -> Vec<u8> {
+        self.frontend.load_ram(size)
+    }
+
+    fn save_ram(&mut self, ram: &[u8]) {
+        self.frontend.save_ram(ram)
+    }
+}
+
+impl<F> SerialPort for Adapter<F> {}
+
+impl<F: Frontend> SchedHook for Adapter<F> {
+    fn sched(&mut self) -> bool {
+        self.frontend.running()
+    }
+}
+
+impl<F> EventHook for Adapter<F> {}
+
+/// Run the emulator until [`Frontend::running`][] returns `false`.
+///
+/// Frames are paced with the CPU frequency set by [`Config::freq`][], so
+/// the per-instruction frequency control is turned off.
+pub fn run<F: Frontend + 'static>(cfg: Config, rom: &[u8], frontend: F) {
+    let frame_time = Duration::from_micros(FRAME_CYCLES * 1_000_000 / cfg.clock());
+    let cfg = cfg.native_speed(true);
+
+    let hw = Adapter {
+        frontend,
+        frame: vec![0; VRAM_WIDTH * VRAM_HEIGHT],
+    };
+    let mut sys = System::new(cfg, rom, hw, NullDebugger);
+
+    let mut next = Instant::now();
+
+    while sys.run_until_vblank().running {
+        next += frame_time;
+
+        let now = Instant::now();
+        if next > now {
+            thread::sleep(next - now);
+        } else {
+            // Running behind; don't try to catch up with a burst of frames.
+            next = now;
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Save data in the format used by other emulators such as BGB and VBA.
+//!
+//! A save file is the raw external RAM of the cartridge, sized as declared in the cartridge header.
+//! For cartridges with a real-time clock, the RAM is followed by an RTC appendix.
+
+use core::cell::Ref;
+use core::fmt;
+
+/// The error returned when save data can't be imported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SaveError {
+    /// The length of the save data matches neither the RAM size nor the RAM size with an RTC appendix.
+    InvalidSize {
+        /// The RAM size declared in the cartridge header.
+        ram: usize,
+        /// The length of the given save data.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::InvalidSize { ram, actual } => {
+                write!(f, "Invalid save data size: {} (RAM size: {})", actual, ram)
+            }
+        }
+    }
+}
+
+/// The state of the MBC3 real-time clock appended to the save data.
+///
+/// The registers are in the order of seconds, minutes, hours, lower 8 bits of days
+/// and upper bit of days with the halt/carry flags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RtcSave {
+    /// The current values of the clock registers.
+    pub regs: [u8; 5],
+    /// The latched values of the clock registers.
+    pub latched: [u8; 5],
+    /// UNIX time in seconds when the registers were saved.
+    pub timestamp: u64,
+}
+
+impl RtcSave {
+    /// The size of the RTC appendix.
+    pub const SIZE: usize = 48;
+
+    /// The size of the RTC appendix written by older emulators with a 32-bit timestamp.
+    pub const SIZE_32: usize = 44;
+
+    /// Serialize the RTC state; each register takes 4 bytes, followed by a 64-bit timestamp.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut b = [0; Self::SIZE];
+
+        for (i, r) in self.regs.iter().chain(self.latched.iter()).enumerate() {
+            b[i * 4] = *r;
+        }
+        b[40..].copy_from_slice(&self.timestamp.to_le_bytes());
+
+        b
+    }
+
+    /// Deserialize the RTC state from either the 48-byte or the 44-byte format.
+    pub fn from_bytes(b: &[u8]) -> Option<Self> {
+        let timestamp = match b.len() {
+            Self::SIZE => {
+                let mut t = [0; 8];
+                t.copy_from_slice(&b[40..48]);
+                u64::from_le_bytes(t)
+            }
+            Self::SIZE_32 => {
+                let mut t = [0; 4];
+                t.copy_from_slice(&b[40..44]);
+                u32::from_le_bytes(t) as u64
+            }
+            _ => return None,
+        };
+
+        let mut regs = [0; 5];
+        let mut latched = [0; 5];
+        for i in 0..5 {
+            regs[i] = b[i * 4];
+            latched[i] = b[(i + 5) * 4];
+        }
+
+        Some(Self {
+            regs,
+            latched,
+            timestamp,
+        })
+    }
+}
+
+/// The save data of the cartridge, borrowed from the emulator without copying the RAM.
+///
+/// Returned by [`System::save_data`][crate::System::save_data]. The emulator can't run
+/// while it's held.
+pub struct SaveData<'a> {
+    /// The battery-backed RAM, limited to the size declared in the cartridge header.
+    pub ram: Ref<'a, [u8]>,
+    /// The state of the real-time clock, for cartridges with a clock.
+    pub rtc: Option<RtcSave>,
+}
+
+impl SaveData<'_> {
+    /// The save data has nothing to persist, i.e. the cartridge has neither RAM nor clock.
+    pub fn is_empty(&self) -> bool {
+        self.ram.is_empty() && self.rtc.is_none()
+    }
+}
+
+/// Split save data into the RAM contents and the RTC appendix if any.
+pub fn split(data: &[u8], ram: usize) -> Result<(&[u8], Option<RtcSave>), SaveError> {
+    if data.len() == ram {
+        return Ok((data, None));
+    }
+
+    match data.get(ram..).and_then(RtcSave::from_bytes) {
+        Some(rtc) => Ok((&data[..ram], Some(rtc))),
+        None => Err(SaveError::InvalidSize {
+            ram,
+            actual: data.len(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn rtc_roundtrip() {
+        let rtc = RtcSave {
+            regs: [1, 2, 3, 4, 0x41],
+            latched: [5, 6, 7, 8, 0x01],
+            timestamp: 0x1_2345_6789,
+        };
+
+        let b = rtc.to_bytes();
+        assert_eq!(b[16], 0x41);
+        assert_eq!(b[20], 5);
+        // The timestamp is little-endian whatever the endianness of the target.
+        assert_eq!(b[40..], [0x89, 0x67, 0x45, 0x23, 0x01, 0, 0, 0]);
+        assert_eq!(RtcSave::from_bytes(&b), Some(rtc));
+    }
+
+    #[test]
+    fn split_save() {
+        let mut data = vec![0xaa; 0x2000];
+        assert_eq!(split(&data, 0x2000).unwrap().1, None);
+
+        data.extend_from_slice(&[0; RtcSave::SIZE_32]);
+        let (ram, rtc) = split(&data, 0x2000).unwrap();
+        assert_eq!(ram.len(), 0x2000);
+        assert_eq!(rtc.unwrap().timestamp, 0);
+
+        assert_eq!(
+            split(&data[..0x100], 0x2000),
+            Err(SaveError::InvalidSize {
+                ram: 0x2000,
+                actual: 0x100
+            })
+        );
+    }
+}
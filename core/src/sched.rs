@@ -0,0 +1,100 @@
+//! Deferred stepping of the peripherals.
+//!
+//! A deferred peripheral isn't stepped on every instruction. It's only brought up to date
+//! when its next event is due, e.g. an interrupt, or when its registers are accessed.
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+
+/// The clock cycles elapsed in a clock domain, shared by the system and the deferred peripherals.
+#[derive(Clone, Default)]
+pub struct Timeline(Rc<Cell<u64>>);
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The clock cycles elapsed so far.
+    pub fn now(&self) -> u64 {
+        self.0.get()
+    }
+
+    /// Advance the time by the clock cycles.
+    pub fn advance(&self, time: usize) {
+        self.0.set(self.0.get() + time as u64);
+    }
+}
+
+/// The time at which a deferred peripheral has to be stepped, checked by the system.
+#[derive(Clone)]
+pub struct Deadline {
+    timeline: Timeline,
+    at: Rc<Cell<u64>>,
+}
+
+impl Deadline {
+    /// Whether the next event of the peripheral is due.
+    pub fn due(&self) -> bool {
+        self.timeline.now() >= self.at.get()
+    }
+
+    /// The clock cycles until the next event of the peripheral, if it has one.
+    pub fn remaining(&self) -> Option<u64> {
+        match self.at.get() {
+            u64::MAX => None,
+            at => Some(at.saturating_sub(self.timeline.now())),
+        }
+    }
+}
+
+/// The bookkeeping of a deferred peripheral.
+pub struct Lazy {
+    timeline: Timeline,
+    synced: u64,
+    at: Rc<Cell<u64>>,
+}
+
+impl Lazy {
+    /// Create the bookkeeping of a peripheral following the timeline, which has no event yet.
+    pub fn new(timeline: Timeline) -> Self {
+        Self {
+            synced: timeline.now(),
+            timeline,
+            at: Rc::new(Cell::new(u64::MAX)),
+        }
+    }
+
+    /// Start over from the current time, keeping the deadline checked by the system.
+    pub fn restart(&self) -> Self {
+        self.at.set(u64::MAX);
+
+        Self {
+            timeline: self.timeline.clone(),
+            synced: self.timeline.now(),
+            at: self.at.clone(),
+        }
+    }
+
+    /// The deadline checked by the system.
+    pub fn deadline(&self) -> Deadline {
+        Deadline {
+            timeline: self.timeline.clone(),
+            at: self.at.clone(),
+        }
+    }
+
+    /// Return the clock cycles elapsed since the last call, which the peripheral has to catch up on.
+    pub fn elapsed(&mut self) -> usize {
+        let now = self.timeline.now();
+        let time = now - self.synced;
+        self.synced = now;
+        time as usize
+    }
+
+    /// Set the clock cycles from now until the next event of the peripheral, if any.
+    pub fn schedule(&self, next: Option<usize>) {
+        self.at
+            .set(next.map_or(u64::MAX, |next| self.synced + next as u64));
+    }
+}
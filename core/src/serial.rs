@@ -2,7 +2,44 @@ use crate::device::IoHandler;
 use crate::hardware::HardwareHandle;
 use crate::ic::Irq;
 use crate::mmu::{MemRead, MemWrite, Mmu};
-use log::*;
+use crate::sched::Lazy;
+use crate::system::Disconnected;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use core::fmt;
+
+/// The number of bytes held by each of the serial queues of the system.
+pub const SERIAL_QUEUE_LEN: usize = 64;
+
+/// The error returned when a byte is pushed to a full serial queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QueueFull;
+
+impl fmt::Display for QueueFull {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "serial queue full")
+    }
+}
+
+/// An accessory plugged into the serial port in place of the link cable,
+/// e.g. a printer or a barcode reader.
+pub trait SerialDevice {
+    /// Exchange a byte in a transfer clocked by the GameBoy.
+    ///
+    /// The function is called when the transfer completes with the byte sent by the GameBoy,
+    /// and returns the byte sent back by the device.
+    fn exchange(&mut self, sent: u8) -> u8;
+
+    /// Poll the device while the GameBoy waits for a transfer clocked by the device.
+    ///
+    /// Returns the byte sent by the device once it has clocked the transfer, or `None` to keep waiting.
+    fn external(&mut self, _sent: u8) -> Option<u8> {
+        None
+    }
+
+    /// Advance the device by the given clock cycles, e.g. to model the printing time.
+    fn step(&mut self, _time: usize) {}
+}
 
 pub struct Serial {
     hw: HardwareHandle,
@@ -11,10 +48,28 @@ pub struct Serial {
     recv: u8,
     ctrl: u8,
     clock: usize,
+    device: Option<Box<dyn SerialDevice>>,
+    transfers: u64,
+    incoming: VecDeque<u8>,
+    outgoing: VecDeque<u8>,
+    /// Whether the sent bytes are kept in the outgoing queue.
+    queue: bool,
+    /// True once a byte was dropped from the full outgoing queue, until `take_overflow`.
+    overflow: bool,
+    sched: Lazy,
+    disconnected: Disconnected,
+    /// The clock cycles the transfer clocked by the other side has waited for a byte.
+    waited: usize,
 }
 
 impl Serial {
-    pub fn new(hw: HardwareHandle, irq: Irq) -> Self {
+    pub fn new(
+        hw: HardwareHandle,
+        irq: Irq,
+        sched: Lazy,
+        disconnected: Disconnected,
+        queue: bool,
+    ) -> Self {
         Self {
             hw,
             irq,
@@ -22,10 +77,125 @@ impl Serial {
             recv: 0,
             ctrl: 0,
             clock: 0,
+            device: None,
+            transfers: 0,
+            incoming: VecDeque::with_capacity(SERIAL_QUEUE_LEN),
+            outgoing: VecDeque::new(),
+            queue,
+            overflow: false,
+            sched,
+            disconnected,
+            waited: 0,
+        }
+    }
+
+    /// Restore the power-on state, keeping the device plugged into the serial port.
+    pub fn reset(&mut self) {
+        let device = self.device.take();
+        *self = Serial::new(
+            self.hw.clone(),
+            self.irq.clone(),
+            self.sched.restart(),
+            self.disconnected,
+            self.queue,
+        );
+        self.device = device;
+        self.sched.schedule(self.next_event());
+    }
+
+    /// Plug the device into the serial port instead of the link cable of the hardware.
+    pub fn set_device(&mut self, device: Option<Box<dyn SerialDevice>>) {
+        self.sync();
+        self.device = device;
+        self.sched.schedule(self.next_event());
+    }
+
+    /// Catch up on the time elapsed since the last sync, and schedule the next step.
+    pub fn sync(&mut self) {
+        let time = self.sched.elapsed();
+        if time > 0 {
+            self.step(time);
+        }
+        self.sched.schedule(self.next_event());
+    }
+
+    /// The clock cycles until the serial port has to be stepped.
+    fn next_event(&self) -> Option<usize> {
+        if self.device.is_some() {
+            // The device is advanced all the time.
+            Some(0)
+        } else if self.ctrl & 0x80 == 0 {
+            None
+        } else if self.ctrl & 0x01 != 0 {
+            // The transfer completes once the step is longer than the clocks left.
+            Some(self.clock + 1)
+        } else {
+            // The link cable is polled until the other side clocks the transfer.
+            Some(0)
+        }
+    }
+
+    /// Queue a byte to be received over the link cable, ahead of the bytes of the hardware.
+    pub fn push_in(&mut self, byte: u8) -> Result<(), QueueFull> {
+        if self.incoming.len() == SERIAL_QUEUE_LEN {
+            return Err(QueueFull);
+        }
+        self.incoming.push_back(byte);
+        Ok(())
+    }
+
+    /// Take the bytes sent over the link cable since the last call.
+    pub fn drain_out(&mut self) -> impl Iterator<Item = u8> + '_ {
+        self.outgoing.drain(..)
+    }
+
+    /// Receive a byte over the link cable, from the incoming queue first, then from the hardware.
+    fn link_recv(&mut self) -> Option<u8> {
+        self.incoming
+            .pop_front()
+            .or_else(|| self.hw.get().borrow_mut().recv_byte())
+    }
+
+    /// Send a byte over the link cable, to the outgoing queue if enabled and to the hardware.
+    fn link_send(&mut self, data: u8) {
+        if self.queue {
+            if self.outgoing.len() == SERIAL_QUEUE_LEN {
+                debug!(
+                    "Serial outgoing queue full, dropping: {:02x}",
+                    self.outgoing[0]
+                );
+                self.outgoing.pop_front();
+                self.overflow = true;
+            }
+            self.outgoing.push_back(data);
         }
+        self.hw.get().borrow_mut().send_byte(data);
+    }
+
+    /// Return the number of bytes transferred since the last call, and reset it.
+    pub fn take_transfers(&mut self) -> u64 {
+        core::mem::replace(&mut self.transfers, 0)
+    }
+
+    /// Return whether the outgoing queue dropped bytes since the last call, and reset it.
+    pub fn take_overflow(&mut self) -> bool {
+        core::mem::replace(&mut self.overflow, false)
+    }
+
+    fn complete(&mut self, data: u8) {
+        self.data = data;
+        self.transfers += 1;
+
+        // End of transfer
+        self.ctrl &= !0x80;
+        self.irq.serial(true);
     }
 
     pub fn step(&mut self, time: usize) {
+        if let Some(device) = self.device.as_mut() {
+            device.step(time);
+        }
+
         if self.ctrl & 0x80 == 0 {
             // No transfer
             return;
@@ -34,22 +204,26 @@ impl Serial {
         if self.ctrl & 0x01 != 0 {
             if self.clock < time {
                 debug!("Serial transfer completed");
-                self.data = self.recv;
-
-                // End of transfer
-                self.ctrl &= !0x80;
-                self.irq.serial(true);
+                let data = match self.device.as_mut() {
+                    Some(device) => device.exchange(self.data),
+                    None => self.recv,
+                };
+                self.complete(data);
             } else {
                 self.clock -= time;
             }
-        } else {
-            if let Some(data) = self.hw.get().borrow_mut().recv_byte() {
-                self.hw.get().borrow_mut().send_byte(self.data);
-                self.data = data;
-
-                // End of transfer
-                self.ctrl &= !0x80;
-                self.irq.serial(true);
+        } else if let Some(device) = self.device.as_mut() {
+            if let Some(data) = device.external(self.data) {
+                self.complete(data);
+            }
+        } else if let Some(data) = self.link_recv() {
+            self.link_send(self.data);
+            self.complete(data);
+        } else if let Disconnected::Idle(timeout) = self.disconnected {
+            self.waited += time;
+            if self.waited > timeout {
+                debug!("Serial transfer timed out");
+                self.complete(0xff);
             }
         }
     }
@@ -57,6 +231,8 @@ impl Serial {
 
 impl IoHandler for Serial {
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
+        self.sync();
+
         if addr == 0xff01 {
             MemRead::Replace(self.data)
         } else if addr == 0xff02 {
@@ -67,6 +243,8 @@ impl IoHandler for Serial {
     }
 
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
+        self.sync();
+
         if addr == 0xff01 {
             self.data = value;
             MemWrite::Block
@@ -80,16 +258,70 @@ impl IoHandler for Serial {
                     // Internal clock is 8192 Hz = 512 cpu clocks
                     self.clock = 512 * 8;
 
-                    // Do transfer one byte at once
-                    self.hw.get().borrow_mut().send_byte(self.data);
-                    self.recv = self.hw.get().borrow_mut().recv_byte().unwrap_or(0xff);
+                    // Do transfer one byte at once, unless a device exchanges it on completion
+                    if self.device.is_none() {
+                        self.link_send(self.data);
+                        self.recv = self.link_recv().unwrap_or(0xff);
+                    }
                 } else {
                     debug!("Serial transfer (External): {:02x}", self.data);
+                    self.waited = 0;
                 }
             }
+            self.sched.schedule(self.next_event());
             MemWrite::Block
         } else {
             unreachable!("Write to serial: {:04x} {:02x}", addr, value)
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::NullHardware;
+    use crate::ic::Ic;
+    use crate::sched::Timeline;
+
+    fn unplugged(disconnected: Disconnected) -> (Serial, Ic) {
+        let ic = Ic::new();
+        let serial = Serial::new(
+            HardwareHandle::new(NullHardware),
+            ic.irq(),
+            Lazy::new(Timeline::new()),
+            disconnected,
+            true,
+        );
+        (serial, ic)
+    }
+
+    #[test]
+    fn disconnected() {
+        let mmu = Mmu::new();
+
+        // The hardware waits forever for the other side.
+        let (mut serial, ic) = unplugged(Disconnected::Wait);
+        serial.on_write(&mmu, 0xff01, 0x42);
+        serial.on_write(&mmu, 0xff02, 0x80);
+        serial.step(1_000_000);
+        assert_eq!(serial.ctrl & 0x80, 0x80);
+        assert_eq!(ic.state(false).request & 0x08, 0);
+
+        let (mut serial, ic) = unplugged(Disconnected::Idle(1000));
+        serial.on_write(&mmu, 0xff01, 0x42);
+        serial.on_write(&mmu, 0xff02, 0x80);
+        serial.step(1000);
+        assert_eq!(serial.ctrl & 0x80, 0x80);
+        serial.step(1);
+        assert_eq!(serial.ctrl & 0x80, 0);
+        assert_eq!(serial.data, 0xff);
+        assert_eq!(ic.state(false).request & 0x08, 0x08);
+
+        // A byte from the other side still completes the transfer first.
+        serial.on_write(&mmu, 0xff02, 0x80);
+        serial.push_in(0x12).unwrap();
+        serial.step(1);
+        assert_eq!(serial.data, 0x12);
+        assert_eq!(serial.drain_out().collect::<alloc::vec::Vec<_>>(), [0xff]);
+    }
+}
@@ -0,0 +1,125 @@
+use alloc::vec::Vec;
+
+/// The bytes of a command packet sent to the Super Game Boy.
+pub const SGB_PACKET_LEN: usize = 16;
+
+/// The packets kept until they are taken, so a frontend that ignores them doesn't grow the queue forever.
+const QUEUE_LEN: usize = 64;
+
+/// Receive the command packets the games send to the Super Game Boy through the joypad register.
+///
+/// A packet starts with a reset pulse, both P14 and P15 low, followed by 128 bits sent
+/// LSB first, each a pulse of P14 (0) or P15 (1) low, and a stop bit 0.
+pub struct Sgb {
+    /// The packet being received, or `None` while waiting for a reset pulse.
+    packet: Option<[u8; SGB_PACKET_LEN]>,
+    /// The bits of the packet received so far.
+    bits: usize,
+    /// Both lines were released since the last pulse.
+    released: bool,
+    packets: Vec<[u8; SGB_PACKET_LEN]>,
+}
+
+impl Sgb {
+    pub fn new() -> Self {
+        Self {
+            packet: None,
+            bits: 0,
+            released: false,
+            packets: Vec::new(),
+        }
+    }
+
+    /// Follow a write of the P14/P15 lines to the joypad register.
+    pub fn write(&mut self, select: u8) {
+        match select & 0x30 {
+            0x00 => {
+                self.packet = Some([0; SGB_PACKET_LEN]);
+                self.bits = 0;
+                self.released = false;
+            }
+            0x30 => self.released = true,
+            lines => {
+                if !self.released {
+                    return;
+                }
+                self.released = false;
+
+                let bit = lines == 0x10;
+                self.receive(bit);
+            }
+        }
+    }
+
+    fn receive(&mut self, bit: bool) {
+        let packet = match self.packet.as_mut() {
+            Some(packet) => packet,
+            None => return,
+        };
+
+        if self.bits < SGB_PACKET_LEN * 8 {
+            if bit {
+                packet[self.bits / 8] |= 1 << (self.bits % 8);
+            }
+            self.bits += 1;
+            return;
+        }
+
+        // The stop bit must be 0, otherwise the packet is dropped.
+        if !bit {
+            if self.packets.len() == QUEUE_LEN {
+                self.packets.remove(0);
+            }
+            self.packets.push(*packet);
+        } else {
+            warn!("SGB packet without a stop bit");
+        }
+        self.packet = None;
+    }
+
+    /// Return the packets received since the last call, the oldest first.
+    pub fn take_packets(&mut self) -> Vec<[u8; SGB_PACKET_LEN]> {
+        core::mem::take(&mut self.packets)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn send(sgb: &mut Sgb, packet: &[u8; SGB_PACKET_LEN], stop: bool) {
+        sgb.write(0x00);
+        sgb.write(0x30);
+        for i in 0..SGB_PACKET_LEN * 8 {
+            let bit = packet[i / 8] & (1 << (i % 8)) != 0;
+            sgb.write(if bit { 0x10 } else { 0x20 });
+            sgb.write(0x30);
+        }
+        sgb.write(if stop { 0x20 } else { 0x10 });
+        sgb.write(0x30);
+    }
+
+    #[test]
+    fn packets() {
+        let mut sgb = Sgb::new();
+        let mut packet = [0; SGB_PACKET_LEN];
+        for (i, b) in packet.iter_mut().enumerate() {
+            *b = (i as u8).wrapping_mul(0x37) ^ 0xa5;
+        }
+
+        send(&mut sgb, &packet, true);
+        assert_eq!(sgb.take_packets(), vec![packet]);
+        assert!(sgb.take_packets().is_empty());
+
+        // A held line counts once, and the packet needs the stop bit.
+        sgb.write(0x10);
+        send(&mut sgb, &packet, false);
+        assert!(sgb.take_packets().is_empty());
+
+        // Bits without a reset pulse are ignored.
+        sgb.write(0x10);
+        sgb.write(0x30);
+        assert!(sgb.take_packets().is_empty());
+    }
+}
@@ -1,12 +1,14 @@
 use alloc::boxed::Box;
 use alloc::sync::Arc;
-use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use log::*;
-use spin::Mutex;
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
 
 use crate::device::IoHandler;
-use crate::hardware::{HardwareHandle, Stream};
+use crate::hardware::{ChannelSample, HardwareHandle, Stream};
 use crate::mmu::{MemRead, MemWrite, Mmu};
+use crate::sched::Lazy;
+use crate::system::{Config, Policy};
 
 trait AtomicHelper {
     type Item;
@@ -132,37 +134,62 @@ impl Envelop {
     }
 }
 
-struct Counter {
+/// The length counter, clocked by the frame sequencer at 256 Hz.
+#[derive(Debug, Clone)]
+struct Length {
     enable: bool,
     count: usize,
-    base: usize,
-    clock: usize,
+    max: usize,
 }
 
-impl Counter {
-    fn new(enable: bool, count: usize, base: usize) -> Self {
+impl Length {
+    fn new(max: usize) -> Self {
         Self {
-            enable,
-            count,
-            base,
-            clock: 0,
+            enable: false,
+            count: 0,
+            max,
         }
     }
 
-    fn stop(&mut self, rate: usize) -> bool {
-        if !self.enable {
-            return false;
+    fn load(&mut self, len: usize) {
+        self.count = self.max - len;
+    }
+
+    /// Update the length on a write to NRx4.
+    ///
+    /// `clocking` tells if the next step of the frame sequencer clocks the length.
+    fn write(&mut self, enable: bool, trigger: bool, clocking: bool) {
+        let enabled = !self.enable && enable;
+        self.enable = enable;
+
+        // Enabling the length in the first half of the period clocks it an extra time.
+        if enabled && !clocking && self.count > 0 {
+            self.count -= 1;
         }
 
-        let deadline = rate * (self.base - self.count) / 256;
+        // Triggering with zero length reloads it to the maximum, clocking it again if needed.
+        if trigger && self.count == 0 {
+            self.count = if enable && !clocking {
+                self.max - 1
+            } else {
+                self.max
+            };
+        }
+    }
 
-        if self.clock >= deadline {
-            true
+    /// Clock the length, returning `true` if it expires.
+    fn clock(&mut self) -> bool {
+        if self.enable && self.count > 0 {
+            self.count -= 1;
+            self.count == 0
         } else {
-            self.clock += 1;
             false
         }
     }
+
+    fn expired(&self) -> bool {
+        self.enable && self.count == 0
+    }
 }
 
 struct WaveIndex {
@@ -187,37 +214,28 @@ impl WaveIndex {
     }
 }
 
+/// The 15-bit linear feedback shift register of the noise channel.
 struct LFSR {
     value: u16,
-    short: bool,
 }
 
 impl LFSR {
-    fn new(short: bool) -> Self {
-        Self {
-            value: 0xdead,
-            short,
-        }
+    fn new() -> Self {
+        Self { value: 0x7fff }
     }
 
     fn high(&self) -> bool {
         self.value & 1 == 0
     }
 
-    fn update(&mut self) {
-        if self.short {
-            self.value &= 0xff;
-            let bit = (self.value & 0x0001)
-                ^ ((self.value & 0x0004) >> 2)
-                ^ ((self.value & 0x0008) >> 3)
-                ^ ((self.value & 0x0010) >> 5);
-            self.value = (self.value >> 1) | (bit << 7);
-        } else {
-            let bit = (self.value & 0x0001)
-                ^ ((self.value & 0x0004) >> 2)
-                ^ ((self.value & 0x0008) >> 3)
-                ^ ((self.value & 0x0020) >> 5);
-            self.value = (self.value >> 1) | (bit << 15);
+    /// Shift the register, feeding back the XOR of the two lowest bits.
+    /// In the 7-bit width mode, the bit is also fed back to bit 6.
+    fn update(&mut self, short: bool) {
+        let bit = (self.value ^ (self.value >> 1)) & 1;
+
+        self.value = (self.value >> 1) | (bit << 14);
+        if short {
+            self.value = (self.value & !0x40) | (bit << 6);
         }
     }
 }
@@ -228,19 +246,19 @@ struct RandomWave {
 }
 
 impl RandomWave {
-    fn new(short: bool) -> Self {
+    fn new() -> Self {
         Self {
-            lfsr: LFSR::new(short),
+            lfsr: LFSR::new(),
             clock: 0,
         }
     }
 
-    fn high(&mut self, rate: usize, freq: usize) -> bool {
+    fn high(&mut self, rate: usize, freq: usize, short: bool) -> bool {
         self.clock += freq;
 
-        if self.clock >= rate {
+        while self.clock >= rate {
             self.clock -= rate;
-            self.lfsr.update()
+            self.lfsr.update(short);
         }
 
         self.lfsr.high()
@@ -252,12 +270,11 @@ struct Tone {
     sweep_time: usize,
     sweep_sub: bool,
     sweep_shift: usize,
-    sound_len: usize,
-    wave_duty: usize,
+    length: Length,
+    wave_duty: Arc<AtomicUsize>,
     env_init: usize,
     env_inc: bool,
     env_count: usize,
-    counter: bool,
     freq: usize,
 }
 
@@ -267,32 +284,24 @@ impl Tone {
             sweep_time: 0,
             sweep_sub: false,
             sweep_shift: 0,
-            sound_len: 0,
-            wave_duty: 0,
+            length: Length::new(64),
+            wave_duty: Arc::new(AtomicUsize::new(0)),
             env_init: 0,
             env_inc: false,
             env_count: 0,
-            counter: false,
             freq: 0,
         }
     }
 
-    fn on_read(&mut self, base: u16, addr: u16) -> MemRead {
-        if addr == base + 3 {
-            MemRead::Replace(0xff)
-        } else {
-            MemRead::PassThrough
-        }
-    }
-
-    fn on_write(&mut self, base: u16, addr: u16, value: u8) -> bool {
+    fn on_write(&mut self, base: u16, addr: u16, value: u8, clocking: bool) -> bool {
         if addr == base + 0 {
             self.sweep_time = ((value >> 4) & 0x7) as usize;
             self.sweep_sub = value & 0x08 != 0;
             self.sweep_shift = (value & 0x07) as usize;
         } else if addr == base + 1 {
-            self.wave_duty = (value >> 6).into();
-            self.sound_len = (value & 0x1f) as usize;
+            // The duty takes effect immediately, keeping the position in the duty cycle.
+            self.wave_duty.set((value >> 6).into());
+            self.length.load((value & 0x3f) as usize);
         } else if addr == base + 2 {
             self.env_init = (value >> 4) as usize;
             self.env_inc = value & 0x08 != 0;
@@ -300,9 +309,10 @@ impl Tone {
         } else if addr == base + 3 {
             self.freq = (self.freq & !0xff) | value as usize;
         } else if addr == base + 4 {
-            self.counter = value & 0x40 != 0;
+            let trigger = value & 0x80 != 0;
+            self.length.write(value & 0x40 != 0, trigger, clocking);
             self.freq = (self.freq & !0x700) | (((value & 0x7) as usize) << 8);
-            return value & 0x80 != 0;
+            return trigger;
         } else {
             unreachable!()
         }
@@ -311,11 +321,32 @@ impl Tone {
     }
 }
 
+/// The bits of the registers FF10-FF26 which read as 1, being unused or write-only.
+const READ_MASK: [u8; 0x17] = [
+    0x80, 0x3f, 0x00, 0xff, 0xbf, // NR10-NR14
+    0xff, 0x3f, 0x00, 0xff, 0xbf, // NR20-NR24
+    0x7f, 0xff, 0x9f, 0xff, 0xbf, // NR30-NR34
+    0xff, 0xff, 0x00, 0x00, 0xbf, // NR40-NR44
+    0x00, 0x00, 0x70, // NR50-NR52
+];
+
+/// The speed multiplier of 1 in the fixed point format shared with the audio stream.
+const SPEED_ONE: usize = 256;
+
+/// A channel synthesized by the audio stream.
+trait Channel {
+    /// Generate the next sample at the sample rate.
+    ///
+    /// The envelope and the sweep run at `effect_rate` instead, which differs from the sample rate
+    /// when the emulation is sped up or slowed down, so that they follow the emulated time
+    /// while the pitch is kept.
+    fn next(&mut self, rate: usize, effect_rate: usize) -> u16;
+}
+
 struct ToneStream {
     tone: Tone,
     sweep: Sweep,
     env: Envelop,
-    counter: Counter,
     index: WaveIndex,
 }
 
@@ -330,51 +361,39 @@ impl ToneStream {
             tone.sweep_shift,
         );
         let env = Envelop::new(tone.env_init, tone.env_count, tone.env_inc);
-        let counter = Counter::new(tone.counter, tone.sound_len, 64);
 
         Self {
             tone,
             sweep,
             env,
-            counter,
             index: WaveIndex::new(),
         }
     }
 }
 
-impl Stream for ToneStream {
-    fn max(&self) -> u16 {
-        unreachable!()
-    }
-
-    fn next(&mut self, rate: u32) -> u16 {
-        let rate = rate as usize;
-
-        // Stop counter
-        if self.counter.stop(rate) {
-            return 0;
-        }
-
+impl Channel for ToneStream {
+    fn next(&mut self, rate: usize, effect_rate: usize) -> u16 {
         // Envelop
-        let amp = self.env.amp(rate);
+        let amp = self.env.amp(effect_rate);
 
         // Sweep
-        let freq = self.sweep.freq(rate);
-
-        // Square wave generation
-        let duty = match self.tone.wave_duty {
-            0 => 0,
-            1 => 1,
-            2 => 3,
-            3 => 5,
+        let freq = self.sweep.freq(effect_rate);
+
+        // Square wave generation: 12.5%, 25%, 50% and 75% duty cycles.
+        // The position restarts from zero on trigger, as a new stream is created.
+        let duty = match self.tone.wave_duty.get() {
+            0 => 0b0000_0001,
+            1 => 0b1000_0001,
+            2 => 0b1000_0111,
+            3 => 0b0111_1110,
             _ => unreachable!(),
         };
 
         let index = self.index.index(rate, freq * 8, 8);
-        if index <= duty {
-            0
-        } else {
+        if duty & (0x80 >> index) != 0 {
             amp as u16
+        } else {
+            0
         }
     }
 }
@@ -382,41 +401,35 @@ impl Stream for ToneStream {
 #[derive(Debug, Clone)]
 struct Wave {
     enable: bool,
-    sound_len: usize,
+    length: Length,
     amp_shift: Arc<AtomicUsize>,
-    counter: bool,
     freq: Arc<AtomicUsize>,
     wavebuf: [u8; 16],
 }
 
 impl Wave {
-    fn new() -> Self {
+    fn new(wavebuf: [u8; 16]) -> Self {
         Self {
             enable: false,
-            sound_len: 0,
+            length: Length::new(256),
             amp_shift: Arc::new(AtomicUsize::new(0)),
-            counter: false,
             freq: Arc::new(AtomicUsize::new(0)),
-            wavebuf: [0; 16],
+            wavebuf,
         }
     }
 
-    fn on_read(&mut self, addr: u16) -> MemRead {
-        if addr == 0xff1d {
-            MemRead::Replace(0xff)
-        } else {
-            MemRead::PassThrough
-        }
+    fn read_ram(&self, addr: u16) -> u8 {
+        self.wavebuf[(addr - 0xff30) as usize]
     }
 
-    fn on_write(&mut self, addr: u16, value: u8) -> bool {
+    fn on_write(&mut self, addr: u16, value: u8, clocking: bool) -> bool {
         if addr == 0xff1a {
             debug!("Wave enable: {:02x}", value);
             self.enable = value & 0x80 != 0;
             return true;
         } else if addr == 0xff1b {
             debug!("Wave len: {:02x}", value);
-            self.sound_len = value as usize;
+            self.length.load(value as usize);
         } else if addr == 0xff1c {
             debug!("Wave amp shift: {:02x}", value);
             self.amp_shift.set((value as usize >> 5) & 0x3);
@@ -425,10 +438,11 @@ impl Wave {
             self.freq.set((self.freq.get() & !0xff) | value as usize);
         } else if addr == 0xff1e {
             debug!("Wave freq2: {:02x}", value);
-            self.counter = value & 0x40 != 0;
+            let trigger = value & 0x80 != 0;
+            self.length.write(value & 0x40 != 0, trigger, clocking);
             self.freq
                 .set((self.freq.get() & !0x700) | (((value & 0x7) as usize) << 8));
-            return value & 0x80 != 0;
+            return trigger;
         } else if addr >= 0xff30 && addr <= 0xff3f {
             self.wavebuf[(addr - 0xff30) as usize] = value;
         } else {
@@ -441,39 +455,24 @@ impl Wave {
 
 struct WaveStream {
     wave: Wave,
-    counter: Counter,
     index: WaveIndex,
 }
 
 impl WaveStream {
     fn new(wave: Wave) -> Self {
-        let counter = Counter::new(wave.counter, wave.sound_len, 256);
-
         Self {
             wave,
-            counter,
             index: WaveIndex::new(),
         }
     }
 }
 
-impl Stream for WaveStream {
-    fn max(&self) -> u16 {
-        unreachable!()
-    }
-
-    fn next(&mut self, rate: u32) -> u16 {
+impl Channel for WaveStream {
+    fn next(&mut self, rate: usize, _effect_rate: usize) -> u16 {
         if !self.wave.enable {
             return 0;
         }
 
-        let rate = rate as usize;
-
-        // Stop counter
-        if self.counter.stop(rate) {
-            return 0;
-        }
-
         let samples = self.wave.wavebuf.len() * 2;
         let freq = 65536 / (2048 - self.wave.freq.get());
         let index_freq = freq * samples;
@@ -499,56 +498,42 @@ impl Stream for WaveStream {
 
 #[derive(Debug, Clone)]
 struct Noise {
-    sound_len: usize,
+    length: Length,
 
     env_init: usize,
     env_inc: bool,
     env_count: usize,
 
-    shift_freq: usize,
-    step: bool,
-    div_freq: usize,
-
-    counter: bool,
-    freq: usize,
+    poly: Arc<AtomicUsize>,
 }
 
 impl Noise {
     fn new() -> Self {
         Self {
-            sound_len: 0,
+            length: Length::new(64),
 
             env_init: 0,
             env_inc: false,
             env_count: 0,
 
-            shift_freq: 0,
-            step: false,
-            div_freq: 0,
-
-            counter: false,
-            freq: 0,
+            poly: Arc::new(AtomicUsize::new(0)),
         }
     }
 
-    fn on_read(&mut self, _addr: u16) -> MemRead {
-        MemRead::PassThrough
-    }
-
-    fn on_write(&mut self, addr: u16, value: u8) -> bool {
+    fn on_write(&mut self, addr: u16, value: u8, clocking: bool) -> bool {
         if addr == 0xff20 {
-            self.sound_len = (value & 0x1f) as usize;
+            self.length.load((value & 0x3f) as usize);
         } else if addr == 0xff21 {
             self.env_init = (value >> 4) as usize;
             self.env_inc = value & 0x08 != 0;
             self.env_count = (value & 0x7) as usize;
         } else if addr == 0xff22 {
-            self.shift_freq = (value >> 4) as usize;
-            self.step = value & 0x08 != 0;
-            self.div_freq = (value & 0x7) as usize;
+            // The clock and the width take effect immediately, without restarting the channel.
+            self.poly.set(value as usize);
         } else if addr == 0xff23 {
-            self.counter = value & 0x40 != 0;
-            return value & 0x80 != 0;
+            let trigger = value & 0x80 != 0;
+            self.length.write(value & 0x40 != 0, trigger, clocking);
+            return trigger;
         } else {
             unreachable!()
         }
@@ -560,52 +545,33 @@ impl Noise {
 struct NoiseStream {
     noise: Noise,
     env: Envelop,
-    counter: Counter,
     wave: RandomWave,
 }
 
 impl NoiseStream {
     fn new(noise: Noise) -> Self {
         let env = Envelop::new(noise.env_init, noise.env_count, noise.env_inc);
-        let counter = Counter::new(noise.counter, noise.sound_len, 64);
-        let wave = RandomWave::new(noise.step);
+        let wave = RandomWave::new();
 
-        Self {
-            noise,
-            env,
-            counter,
-            wave,
-        }
+        Self { noise, env, wave }
     }
 }
 
-impl Stream for NoiseStream {
-    fn max(&self) -> u16 {
-        unreachable!()
-    }
-
-    fn next(&mut self, rate: u32) -> u16 {
-        let rate = rate as usize;
-
-        // Stop counter
-        if self.counter.stop(rate) {
-            return 0;
-        }
-
+impl Channel for NoiseStream {
+    fn next(&mut self, rate: usize, effect_rate: usize) -> u16 {
         // Envelop
-        let amp = self.env.amp(rate);
-
-        // Noise: 524288 Hz / r / 2 ^ (s+1)
-        let r = self.noise.div_freq;
-        let s = self.noise.shift_freq as u32;
-        let freq = if r == 0 {
-            // For r = 0, assume r = 0.5 instead
-            524288 * 5 / 10 / 2usize.pow(s + 1)
-        } else {
-            524288 / self.noise.div_freq / 2usize.pow(s + 1)
+        let amp = self.env.amp(effect_rate);
+
+        // Noise: 4194304 Hz / (divisor << s)
+        let poly = self.noise.poly.get();
+        let divisor = match poly & 0x7 {
+            0 => 8,
+            r => r * 16,
         };
+        let freq = 4194304 / (divisor << (poly >> 4));
+        let short = poly & 0x08 != 0;
 
-        if self.wave.high(rate, freq) {
+        if self.wave.high(rate, freq, short) {
             amp as u16
         } else {
             0
@@ -622,16 +588,22 @@ struct Mixer {
 }
 
 impl Mixer {
-    fn new() -> Self {
+    fn new(policy: Policy) -> Self {
         Self {
             so1_volume: 0,
             so2_volume: 0,
             so_mask: 0,
             enable: false,
-            stream: MixerStream::new(),
+            stream: MixerStream::new(policy),
         }
     }
 
+    /// Let the audio stream follow the speed multiplier of the emulation.
+    fn set_speed(&self, multiplier: f32) {
+        let speed = (multiplier * SPEED_ONE as f32) as usize;
+        self.stream.speed.set(speed.max(1));
+    }
+
     fn setup_stream(&self, hw: &HardwareHandle) {
         hw.get()
             .borrow_mut()
@@ -640,12 +612,13 @@ impl Mixer {
 
     fn on_read(&mut self, addr: u16) -> MemRead {
         if addr == 0xff26 {
-            let mut v = 0;
+            // The unused bits read high.
+            let mut v = 0x70;
             v |= if self.enable { 0x80 } else { 0x00 };
-            v |= if self.stream.tone1.on() { 0x08 } else { 0x00 };
-            v |= if self.stream.tone2.on() { 0x04 } else { 0x00 };
-            v |= if self.stream.wave.on() { 0x02 } else { 0x00 };
-            v |= if self.stream.noise.on() { 0x01 } else { 0x00 };
+            v |= if self.stream.tone1.on() { 0x01 } else { 0x00 };
+            v |= if self.stream.tone2.on() { 0x02 } else { 0x00 };
+            v |= if self.stream.wave.on() { 0x04 } else { 0x00 };
+            v |= if self.stream.noise.on() { 0x08 } else { 0x00 };
             MemRead::Replace(v)
         } else {
             MemRead::PassThrough
@@ -654,8 +627,9 @@ impl Mixer {
 
     fn on_write(&mut self, addr: u16, value: u8) {
         if addr == 0xff24 {
-            self.so1_volume = (value as usize & 0x70) >> 4;
-            self.so2_volume = value as usize & 0x07;
+            // SO1 is the right output, SO2 the left one.
+            self.so1_volume = value as usize & 0x07;
+            self.so2_volume = (value as usize & 0x70) >> 4;
             self.update_volume();
         } else if addr == 0xff25 {
             self.so_mask = value as usize;
@@ -673,20 +647,20 @@ impl Mixer {
         }
     }
 
-    fn restart_tone1(&self, t: Tone) {
-        self.stream.tone1.update(Some(ToneStream::new(t, true)));
+    fn restart_tone1(&mut self, t: Tone) {
+        self.stream.tone1.update(ToneStream::new(t, true));
     }
 
-    fn restart_tone2(&self, t: Tone) {
-        self.stream.tone2.update(Some(ToneStream::new(t, false)));
+    fn restart_tone2(&mut self, t: Tone) {
+        self.stream.tone2.update(ToneStream::new(t, false));
     }
 
-    fn restart_wave(&self, w: Wave) {
-        self.stream.wave.update(Some(WaveStream::new(w)));
+    fn restart_wave(&mut self, w: Wave) {
+        self.stream.wave.update(WaveStream::new(w));
     }
 
-    fn restart_noise(&self, n: Noise) {
-        self.stream.noise.update(Some(NoiseStream::new(n)));
+    fn restart_noise(&mut self, n: Noise) {
+        self.stream.noise.update(NoiseStream::new(n));
     }
 
     fn update_volume(&self) {
@@ -697,32 +671,83 @@ impl Mixer {
         self.stream.noise.volume.set(self.get_volume(3));
     }
 
+    /// The volumes of the channel on the left and right outputs, packed as `left << 8 | right`.
     fn get_volume(&self, id: u8) -> usize {
         let mask = 1 << id;
-        let v1 = if self.so_mask & mask != 0 {
+        let right = if self.so_mask & mask != 0 {
             self.so1_volume
         } else {
             0
         };
-        let v2 = if self.so_mask & (mask << 4) != 0 {
+        let left = if self.so_mask & (mask << 4) != 0 {
             self.so2_volume
         } else {
             0
         };
-        v1 + v2
+        left << 8 | right
+    }
+}
+
+/// Single-slot mailbox to hand over a value to the audio thread without locking.
+///
+/// The emulator publishes a new value by swapping the pointer, and the audio thread
+/// takes it by swapping in null, so each value is owned by exactly one side at a time.
+struct Slot<T> {
+    ptr: AtomicPtr<T>,
+    _marker: PhantomData<Box<T>>,
+}
+
+impl<T> Slot<T> {
+    fn new() -> Self {
+        Self {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            _marker: PhantomData,
+        }
+    }
+
+    fn publish(&self, v: T) {
+        let new = Box::into_raw(Box::new(v));
+        let old = self.ptr.swap(new, Ordering::AcqRel);
+        if !old.is_null() {
+            // The audio thread hasn't taken the previous value; discard it.
+            drop(unsafe { Box::from_raw(old) });
+        }
+    }
+
+    fn take(&self) -> Option<T> {
+        let p = self.ptr.swap(ptr::null_mut(), Ordering::AcqRel);
+        if p.is_null() {
+            None
+        } else {
+            Some(*unsafe { Box::from_raw(p) })
+        }
+    }
+}
+
+impl<T> Drop for Slot<T> {
+    fn drop(&mut self) {
+        let _ = self.take();
     }
 }
 
 struct Unit<T> {
-    stream: Arc<Mutex<Option<T>>>,
+    pending: Arc<Slot<T>>,
+    stream: Option<T>,
+    on: Arc<AtomicBool>,
     volume: Arc<AtomicUsize>,
+    /// The last amplitude output by the channel, read back through PCM12/PCM34.
+    amp: Arc<AtomicUsize>,
 }
 
 impl<T> Clone for Unit<T> {
     fn clone(&self) -> Self {
+        // The stream being played is local to each side.
         Self {
-            stream: self.stream.clone(),
+            pending: self.pending.clone(),
+            stream: None,
+            on: self.on.clone(),
             volume: self.volume.clone(),
+            amp: self.amp.clone(),
         }
     }
 }
@@ -730,30 +755,59 @@ impl<T> Clone for Unit<T> {
 impl<T> Unit<T> {
     fn new() -> Self {
         Self {
-            stream: Arc::new(Mutex::new(None)),
+            pending: Arc::new(Slot::new()),
+            stream: None,
+            on: Arc::new(AtomicBool::new(false)),
             volume: Arc::new(AtomicUsize::new(0)),
+            amp: Arc::new(AtomicUsize::new(0)),
         }
     }
 }
 
-impl<T: Stream> Unit<T> {
+impl<T: Channel> Unit<T> {
     fn on(&self) -> bool {
-        self.stream.lock().is_some()
+        self.on.get()
     }
 
-    fn update(&self, s: Option<T>) {
-        *self.stream.lock() = s;
+    /// The last amplitude output by the channel, from 0 to 15.
+    ///
+    /// As the channels are run by the audio stream, this lags behind the emulation
+    /// by the latency of the audio output.
+    fn amp(&self) -> u8 {
+        self.amp.get() as u8
     }
 
-    fn next(&self, rate: u32) -> (u16, u16) {
-        (
+    fn update(&mut self, s: T) {
+        self.pending.publish(s);
+        self.on.set(true);
+    }
+
+    /// Silence the channel, e.g. when the length expires.
+    fn stop(&mut self) {
+        self.on.set(false);
+    }
+
+    fn next(&mut self, rate: usize, effect_rate: usize) -> ChannelSample {
+        if let Some(s) = self.pending.take() {
+            self.stream = Some(s);
+        }
+
+        let amp = if self.on.get() {
             self.stream
-                .lock()
                 .as_mut()
-                .map(|s| s.next(rate))
-                .unwrap_or(0),
-            self.volume.get() as u16,
-        )
+                .map(|s| s.next(rate, effect_rate))
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        self.amp.set(amp as usize);
+
+        let volume = self.volume.get();
+        ChannelSample {
+            amp,
+            left: (volume >> 8) as u16,
+            right: (volume & 0xff) as u16,
+        }
     }
 }
 
@@ -764,21 +818,29 @@ struct MixerStream {
     wave: Unit<WaveStream>,
     noise: Unit<NoiseStream>,
     enable: Arc<AtomicBool>,
+    /// The speed multiplier of the emulation, in units of `SPEED_ONE`.
+    speed: Arc<AtomicUsize>,
+    /// The samples generated since the last call to `Sound::take_samples`.
+    samples: Arc<AtomicUsize>,
+    policy: Policy,
 }
 
 impl MixerStream {
-    fn new() -> Self {
+    fn new(policy: Policy) -> Self {
         Self {
             tone1: Unit::new(),
             tone2: Unit::new(),
             wave: Unit::new(),
             noise: Unit::new(),
             enable: Arc::new(AtomicBool::new(false)),
+            speed: Arc::new(AtomicUsize::new(SPEED_ONE)),
+            samples: Arc::new(AtomicUsize::new(0)),
+            policy,
         }
     }
 
-    fn volume(&self, amp: u16, vol: u16) -> u16 {
-        amp * vol
+    fn volume(&self, sample: ChannelSample) -> u16 {
+        sample.amp * (sample.left + sample.right)
     }
 }
 
@@ -793,22 +855,40 @@ impl Stream for MixerStream {
 
     fn next(&mut self, rate: u32) -> u16 {
         if self.enable.get() {
+            let [tone1, tone2, wave, noise] = self.next_channels(rate);
+
             let mut vol = 0;
+            vol += self.volume(tone1);
+            vol += self.volume(tone2);
+            vol += self.volume(wave);
+            vol += self.volume(noise) / 2; // Soften the noise
+
+            if vol > 840 {
+                self.policy
+                    .violation(format_args!("Mixer volume out of range: {}", vol));
+            }
 
-            let (t, v) = self.tone1.next(rate);
-            vol += self.volume(t, v);
-            let (t, v) = self.tone2.next(rate);
-            vol += self.volume(t, v);
-            let (t, v) = self.wave.next(rate);
-            vol += self.volume(t, v);
-            let (t, v) = self.noise.next(rate);
-            vol += self.volume(t, v) / 2; // Soften the noise
+            vol.min(840)
+        } else {
+            self.samples.fetch_add(1, Ordering::Relaxed);
+            0
+        }
+    }
 
-            assert!(vol <= 840, "vol = {}", vol);
+    fn next_channels(&mut self, rate: u32) -> [ChannelSample; 4] {
+        self.samples.fetch_add(1, Ordering::Relaxed);
 
-            vol
+        if self.enable.get() {
+            let rate = rate as usize;
+            let effect_rate = rate * SPEED_ONE / self.speed.get().max(1);
+            [
+                self.tone1.next(rate, effect_rate),
+                self.tone2.next(rate, effect_rate),
+                self.wave.next(rate, effect_rate),
+                self.noise.next(rate, effect_rate),
+            ]
         } else {
-            0
+            [ChannelSample::default(); 4]
         }
     }
 }
@@ -819,62 +899,239 @@ pub struct Sound {
     wave: Wave,
     noise: Noise,
     mixer: Mixer,
+    fs_clock: usize,
+    fs_step: usize,
+    sched: Lazy,
+    /// The values written to the registers FF10-FF26.
+    regs: [u8; 0x17],
+    /// Running in CGB mode, where the length counters are cleared with the power.
+    cgb: bool,
 }
 
 impl Sound {
-    pub fn new(hw: HardwareHandle) -> Self {
-        let mixer = Mixer::new();
+    pub fn new(hw: HardwareHandle, cfg: &Config, sched: Lazy) -> Self {
+        let mixer = Mixer::new(cfg.policy);
 
         mixer.setup_stream(&hw);
+        mixer.set_speed(cfg.speed);
+        sched.schedule(Some(8192));
 
         Self {
             tone1: Tone::new(),
             tone2: Tone::new(),
-            wave: Wave::new(),
+            wave: Wave::new(cfg.initial_wave_ram()),
             noise: Noise::new(),
             mixer,
+            fs_clock: 0,
+            fs_step: 0,
+            sched,
+            regs: [0; 0x17],
+            cgb: cfg.model.cgb_mode(),
         }
     }
+
+    /// Restore the power-on state, handing a new stream to the hardware.
+    pub fn reset(&mut self, hw: HardwareHandle, cfg: &Config) {
+        *self = Sound::new(hw, cfg, self.sched.restart());
+    }
+
+    /// Set the speed multiplier of the emulation, which the envelopes and the sweep follow.
+    pub fn set_speed(&mut self, multiplier: f32) {
+        self.mixer.set_speed(multiplier);
+    }
+
+    /// Return the samples generated by the audio stream since the last call, and reset the count.
+    pub fn take_samples(&mut self) -> u64 {
+        self.mixer.stream.samples.swap(0, Ordering::Relaxed) as u64
+    }
+
+    /// Catch up on the time elapsed since the last sync, and schedule the next frame sequencer step.
+    pub fn sync(&mut self) {
+        let time = self.sched.elapsed();
+        if time > 0 {
+            self.step(time);
+        }
+        self.sched.schedule(Some(8192 - self.fs_clock));
+    }
+
+    /// Advance the frame sequencer, which clocks the length counters.
+    pub fn step(&mut self, time: usize) {
+        self.fs_clock += time;
+
+        // The frame sequencer runs at 512 Hz = 8192 cpu clocks
+        while self.fs_clock >= 8192 {
+            self.fs_clock -= 8192;
+            self.clock_sequencer();
+        }
+    }
+
+    fn clock_sequencer(&mut self) {
+        if self.clocking() {
+            if self.tone1.length.clock() {
+                self.mixer.stream.tone1.stop();
+            }
+            if self.tone2.length.clock() {
+                self.mixer.stream.tone2.stop();
+            }
+            if self.wave.length.clock() {
+                self.mixer.stream.wave.stop();
+            }
+            if self.noise.length.clock() {
+                self.mixer.stream.noise.stop();
+            }
+        }
+
+        self.fs_step = (self.fs_step + 1) % 8;
+    }
+
+    /// Follow a write to DIV, which restarts the period of the frame sequencer.
+    ///
+    /// The frame sequencer is clocked on the falling edge of DIV bit 4, which is set
+    /// in the second half of the period, so resetting DIV then clocks it early.
+    fn reset_div(&mut self) {
+        if self.fs_clock >= 4096 {
+            self.clock_sequencer();
+        }
+        self.fs_clock = 0;
+    }
+
+    /// Follow a write to NR52, which powers the APU on or off.
+    ///
+    /// Powering off clears the registers and stops the channels. On DMG, the length counters
+    /// are kept, and they can still be loaded while the power is off.
+    fn power(&mut self, on: bool) {
+        if on && !self.mixer.enable {
+            self.fs_step = 0;
+        } else if !on && self.mixer.enable {
+            let mut tone1 = Tone::new();
+            let mut tone2 = Tone::new();
+            let mut wave = Wave::new(self.wave.wavebuf);
+            let mut noise = Noise::new();
+            if !self.cgb {
+                tone1.length.count = self.tone1.length.count;
+                tone2.length.count = self.tone2.length.count;
+                wave.length.count = self.wave.length.count;
+                noise.length.count = self.noise.length.count;
+            }
+            self.tone1 = tone1;
+            self.tone2 = tone2;
+            self.wave = wave;
+            self.noise = noise;
+
+            self.mixer.so1_volume = 0;
+            self.mixer.so2_volume = 0;
+            self.mixer.so_mask = 0;
+            self.mixer.stream.tone1.stop();
+            self.mixer.stream.tone2.stop();
+            self.mixer.stream.wave.stop();
+            self.mixer.stream.noise.stop();
+            self.regs = [0; 0x17];
+        }
+    }
+
+    /// Check if a register can be written with the power off, which on DMG is only the case
+    /// of the length counters.
+    fn writable_off(&self, addr: u16) -> bool {
+        !self.cgb && matches!(addr, 0xff11 | 0xff16 | 0xff1b | 0xff20)
+    }
+
+    /// Check if the next step of the frame sequencer clocks the length counters.
+    fn clocking(&self) -> bool {
+        self.fs_step % 2 == 0
+    }
 }
 
 impl IoHandler for Sound {
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
-        if addr >= 0xff10 && addr <= 0xff14 {
-            self.tone1.on_read(0xff10, addr)
-        } else if addr >= 0xff15 && addr <= 0xff19 {
-            self.tone2.on_read(0xff15, addr)
-        } else if addr >= 0xff1a && addr <= 0xff1e {
-            self.wave.on_read(addr)
-        } else if addr >= 0xff20 && addr <= 0xff23 {
-            self.noise.on_read(addr)
-        } else if addr >= 0xff24 && addr <= 0xff26 {
+        self.sync();
+
+        if addr == 0xff26 {
             self.mixer.on_read(addr)
+        } else if addr >= 0xff10 && addr <= 0xff25 {
+            let i = (addr - 0xff10) as usize;
+            MemRead::Replace(self.regs[i] | READ_MASK[i])
+        } else if addr >= 0xff27 && addr <= 0xff2f {
+            MemRead::Replace(0xff)
+        } else if addr >= 0xff30 && addr <= 0xff3f {
+            MemRead::Replace(self.wave.read_ram(addr))
+        } else if cfg!(feature = "color") && addr == 0xff76 {
+            let s = &self.mixer.stream;
+            MemRead::Replace(s.tone2.amp() << 4 | s.tone1.amp())
+        } else if cfg!(feature = "color") && addr == 0xff77 {
+            let s = &self.mixer.stream;
+            MemRead::Replace(s.noise.amp() << 4 | s.wave.amp())
         } else {
             MemRead::PassThrough
         }
     }
 
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
+        self.sync();
+        let clocking = self.clocking();
+
+        if addr == 0xff76 || addr == 0xff77 {
+            // PCM12/PCM34 are read-only.
+            return MemWrite::Block;
+        }
+
+        let value = if addr == 0xff26 {
+            self.power(value & 0x80 != 0);
+            value
+        } else if addr >= 0xff10 && addr <= 0xff25 {
+            let value = if self.mixer.enable {
+                value
+            } else if self.writable_off(addr) {
+                // Only the length is loaded, the duty stays cleared.
+                if addr == 0xff1b {
+                    value
+                } else {
+                    value & 0x3f
+                }
+            } else {
+                return MemWrite::Block;
+            };
+            self.regs[(addr - 0xff10) as usize] = value;
+            value
+        } else {
+            value
+        };
+
         if addr >= 0xff10 && addr <= 0xff14 {
-            if self.tone1.on_write(0xff10, addr, value) {
+            if self.tone1.on_write(0xff10, addr, value, clocking) {
                 self.mixer.restart_tone1(self.tone1.clone());
             }
+            if self.tone1.length.expired() {
+                self.mixer.stream.tone1.stop();
+            }
         } else if addr >= 0xff15 && addr <= 0xff19 {
-            if self.tone2.on_write(0xff15, addr, value) {
+            if self.tone2.on_write(0xff15, addr, value, clocking) {
                 self.mixer.restart_tone2(self.tone2.clone());
             }
+            if self.tone2.length.expired() {
+                self.mixer.stream.tone2.stop();
+            }
         } else if addr >= 0xff1a && addr <= 0xff1e {
-            if self.wave.on_write(addr, value) {
+            if self.wave.on_write(addr, value, clocking) {
                 self.mixer.restart_wave(self.wave.clone());
             }
+            if self.wave.length.expired() {
+                self.mixer.stream.wave.stop();
+            }
         } else if addr >= 0xff30 && addr <= 0xff3f {
-            let _ = self.wave.on_write(addr, value);
+            let _ = self.wave.on_write(addr, value, clocking);
         } else if addr >= 0xff20 && addr <= 0xff23 {
-            if self.noise.on_write(addr, value) {
+            if self.noise.on_write(addr, value, clocking) {
                 self.mixer.restart_noise(self.noise.clone());
             }
+            if self.noise.length.expired() {
+                self.mixer.stream.noise.stop();
+            }
         } else if addr >= 0xff24 && addr <= 0xff26 {
             self.mixer.on_write(addr, value);
+        } else if addr == 0xff04 {
+            // DIV itself belongs to the timer.
+            self.reset_div();
+            self.sched.schedule(Some(8192));
         } else {
             info!("Write sound: {:04x} {:02x}", addr, value);
         }
@@ -882,3 +1139,325 @@ impl IoHandler for Sound {
         MemWrite::PassThrough
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hardware::NullHardware;
+    use crate::sched::Timeline;
+    use crate::system::Model;
+    use alloc::vec::Vec;
+
+    fn tone(duty: usize) -> Tone {
+        let mut tone = Tone::new();
+        tone.wave_duty.set(duty);
+        tone.env_init = 15;
+        // 131072 / (2048 - 1024) = 128 Hz, so one duty step per sample at 1024 Hz.
+        tone.freq = 1024;
+        tone
+    }
+
+    fn samples(s: &mut ToneStream, n: usize) -> Vec<u16> {
+        (0..n).map(|_| s.next(1024, 1024)).collect()
+    }
+
+    #[test]
+    fn duty_cycle() {
+        let tone = tone(2);
+        let mut s = ToneStream::new(tone.clone(), false);
+
+        assert_eq!(samples(&mut s, 8), [0, 0, 0, 0, 15, 15, 15, 15]);
+
+        // Changing the duty keeps the position.
+        tone.wave_duty.set(0);
+        assert_eq!(samples(&mut s, 4), [0, 0, 0, 0]);
+        assert_eq!(samples(&mut s, 4), [0, 0, 15, 0]);
+    }
+
+    #[test]
+    fn pcm_amplitude() {
+        let mut unit = Unit::new();
+        let reader = unit.clone();
+        assert_eq!(reader.amp(), 0);
+
+        unit.update(ToneStream::new(tone(2), false));
+        for _ in 0..5 {
+            unit.next(1024, 1024);
+        }
+        assert_eq!(reader.amp(), 15);
+
+        unit.stop();
+        unit.next(1024, 1024);
+        assert_eq!(reader.amp(), 0);
+    }
+
+    #[test]
+    fn channel_outputs() {
+        let mut mixer = Mixer::new(Policy::Strict);
+        let mut stream = mixer.stream.clone();
+
+        mixer.on_write(0xff26, 0x80);
+        // Left volume 5, right volume 2.
+        mixer.on_write(0xff24, 0x52);
+        // Tone 1 on both outputs, tone 2 on the left one only.
+        mixer.on_write(0xff25, 0x31);
+        mixer.restart_tone1(tone(2));
+        mixer.restart_tone2(tone(2));
+
+        let mut channels = [ChannelSample::default(); 4];
+        for _ in 0..5 {
+            channels = stream.next_channels(1024);
+        }
+
+        let out = |amp, left, right| ChannelSample { amp, left, right };
+        assert_eq!(
+            channels,
+            [out(15, 5, 2), out(15, 5, 0), out(0, 0, 0), out(0, 0, 0)]
+        );
+
+        // The mixed output adds up the same channels.
+        assert_eq!(stream.next(1024), 15 * 7 + 15 * 5);
+    }
+
+    #[test]
+    fn speed_multiplier() {
+        let envelop = |speed| {
+            let mut mixer = Mixer::new(Policy::Strict);
+            let mut stream = mixer.stream.clone();
+            mixer.set_speed(speed);
+            mixer.on_write(0xff26, 0x80);
+            mixer.on_write(0xff24, 0x11);
+            mixer.on_write(0xff25, 0x01);
+
+            // Decrease the volume every 1/64 second, i.e. every 16 samples at 1024 Hz.
+            let mut tone = tone(2);
+            tone.env_count = 1;
+            mixer.restart_tone1(tone);
+            (0..16)
+                .map(|_| stream.next_channels(1024)[0].amp)
+                .last()
+                .unwrap()
+        };
+
+        assert_eq!(envelop(1.0), 14);
+        // The envelope follows the emulated time at twice the speed.
+        assert_eq!(envelop(2.0), 13);
+        assert_eq!(envelop(0.5), 15);
+    }
+
+    #[test]
+    fn div_reset() {
+        let mut sound = Sound::new(
+            HardwareHandle::new(NullHardware),
+            &Config::new(),
+            Lazy::new(Timeline::new()),
+        );
+
+        // DIV bit 4 is clear in the first half of the period.
+        sound.step(4000);
+        sound.reset_div();
+        assert_eq!(sound.fs_step, 0);
+
+        // Resetting DIV with the bit set clocks the frame sequencer early.
+        sound.step(5000);
+        sound.reset_div();
+        assert_eq!(sound.fs_step, 1);
+
+        // The next period starts from the reset.
+        sound.step(8191);
+        assert_eq!(sound.fs_step, 1);
+        sound.step(1);
+        assert_eq!(sound.fs_step, 2);
+    }
+
+    fn sound(model: Model) -> Sound {
+        Sound::new(
+            HardwareHandle::new(NullHardware),
+            &Config::new().model(model),
+            Lazy::new(Timeline::new()),
+        )
+    }
+
+    fn read(sound: &mut Sound, mmu: &Mmu, addr: u16) -> u8 {
+        match sound.on_read(mmu, addr) {
+            MemRead::Replace(value) => value,
+            MemRead::PassThrough => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn read_masks() {
+        let mmu = Mmu::new();
+        let mut sound = sound(Model::Dmg);
+        sound.on_write(&mmu, 0xff26, 0x80);
+
+        for addr in 0xff10..=0xff25 {
+            sound.on_write(&mmu, addr, 0x00);
+            let mask = READ_MASK[(addr - 0xff10) as usize];
+            assert_eq!(read(&mut sound, &mmu, addr), mask, "{:04x}", addr);
+        }
+        for addr in 0xff27..=0xff2f {
+            assert_eq!(read(&mut sound, &mmu, addr), 0xff);
+        }
+
+        sound.on_write(&mmu, 0xff24, 0x77);
+        assert_eq!(read(&mut sound, &mmu, 0xff24), 0x77);
+        sound.on_write(&mmu, 0xff11, 0x80);
+        assert_eq!(read(&mut sound, &mmu, 0xff11), 0xbf);
+    }
+
+    #[test]
+    fn power_gating() {
+        let mmu = Mmu::new();
+        let mut sound = sound(Model::Dmg);
+        sound.on_write(&mmu, 0xff26, 0x80);
+        sound.on_write(&mmu, 0xff24, 0x77);
+        sound.on_write(&mmu, 0xff12, 0xf0);
+        sound.on_write(&mmu, 0xff14, 0x80);
+        assert_eq!(read(&mut sound, &mmu, 0xff26), 0xf1);
+
+        // Powering off clears the registers and stops the channels.
+        sound.on_write(&mmu, 0xff26, 0x00);
+        assert_eq!(read(&mut sound, &mmu, 0xff26), 0x70);
+        assert_eq!(read(&mut sound, &mmu, 0xff24), 0x00);
+        assert_eq!(read(&mut sound, &mmu, 0xff12), 0x00);
+
+        // The registers are read-only while off, but not the wave RAM.
+        for addr in 0xff10..=0xff25 {
+            if !matches!(addr, 0xff11 | 0xff16 | 0xff1b | 0xff20) {
+                assert!(matches!(sound.on_write(&mmu, addr, 0xff), MemWrite::Block));
+                let mask = READ_MASK[(addr - 0xff10) as usize];
+                assert_eq!(read(&mut sound, &mmu, addr), mask, "{:04x}", addr);
+            }
+        }
+        sound.on_write(&mmu, 0xff30, 0x5a);
+        assert_eq!(read(&mut sound, &mmu, 0xff30), 0x5a);
+
+        // Powering on again doesn't restore anything.
+        sound.on_write(&mmu, 0xff26, 0x80);
+        assert_eq!(read(&mut sound, &mmu, 0xff26), 0xf0);
+        assert_eq!(read(&mut sound, &mmu, 0xff24), 0x00);
+    }
+
+    #[test]
+    fn dmg_length_power_off() {
+        let mmu = Mmu::new();
+        let mut sound = sound(Model::Dmg);
+        sound.on_write(&mmu, 0xff26, 0x80);
+        sound.on_write(&mmu, 0xff1b, 0x10);
+
+        // The length counters survive the power off, and are loaded without the duty.
+        sound.on_write(&mmu, 0xff26, 0x00);
+        assert_eq!(sound.wave.length.count, 0x100 - 0x10);
+        sound.on_write(&mmu, 0xff11, 0xc1);
+        assert_eq!(sound.tone1.length.count, 0x40 - 0x01);
+        assert_eq!(read(&mut sound, &mmu, 0xff11), 0x3f);
+        sound.on_write(&mmu, 0xff20, 0x02);
+        assert_eq!(sound.noise.length.count, 0x40 - 0x02);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn cgb_length_power_off() {
+        let mmu = Mmu::new();
+        let mut sound = sound(Model::Cgb);
+        sound.on_write(&mmu, 0xff26, 0x80);
+        sound.on_write(&mmu, 0xff1b, 0x10);
+
+        // The CGB clears the length counters and gates them too.
+        sound.on_write(&mmu, 0xff26, 0x00);
+        assert_eq!(sound.wave.length.count, 0);
+        assert!(matches!(
+            sound.on_write(&mmu, 0xff11, 0x01),
+            MemWrite::Block
+        ));
+        assert_eq!(sound.tone1.length.count, 0);
+    }
+
+    #[test]
+    fn wave_ram_power_on() {
+        let wave_ram = |cfg: Config| {
+            let sound = Sound::new(
+                HardwareHandle::new(NullHardware),
+                &cfg,
+                Lazy::new(Timeline::new()),
+            );
+            [0xff30, 0xff31, 0xff3f].map(|addr| sound.wave.read_ram(addr))
+        };
+
+        assert_eq!(
+            wave_ram(Config::new().model(Model::Dmg)),
+            [0x84, 0x40, 0xda]
+        );
+        assert_eq!(
+            wave_ram(Config::new().model(Model::Cgb)),
+            [0x00, 0xff, 0xff]
+        );
+
+        let mut custom = [0; 16];
+        custom[1] = 0x12;
+        custom[15] = 0x34;
+        let cfg = Config::new().model(Model::Cgb).wave_ram(custom);
+        assert_eq!(wave_ram(cfg), [0x00, 0x12, 0x34]);
+    }
+
+    #[test]
+    fn length_extra_clock() {
+        let mut len = Length::new(64);
+        len.load(62);
+
+        // Enabling when the next step doesn't clock the length clocks it once.
+        len.write(true, false, false);
+        assert_eq!(len.count, 1);
+        assert!(len.clock());
+        assert!(len.expired());
+
+        // No extra clock when already enabled.
+        len.load(60);
+        len.write(true, false, false);
+        assert_eq!(len.count, 4);
+    }
+
+    #[test]
+    fn length_trigger_reload() {
+        let mut len = Length::new(256);
+
+        len.write(false, true, true);
+        assert_eq!(len.count, 256);
+
+        len.count = 0;
+        len.write(true, true, true);
+        assert_eq!(len.count, 256);
+
+        len.enable = false;
+        len.count = 0;
+        len.write(true, true, false);
+        assert_eq!(len.count, 255);
+    }
+
+    fn lfsr_period(short: bool) -> usize {
+        let mut lfsr = LFSR::new();
+        // Skip the bits shifted in from the seed.
+        for _ in 0..16 {
+            lfsr.update(short);
+        }
+
+        let start = lfsr.value;
+        (1..)
+            .find(|_| {
+                lfsr.update(short);
+                lfsr.value == start
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn lfsr() {
+        let mut lfsr = LFSR::new();
+        lfsr.update(false);
+        assert_eq!(lfsr.value, 0x3fff);
+
+        assert_eq!(lfsr_period(false), 32767);
+        assert_eq!(lfsr_period(true), 127);
+    }
+}
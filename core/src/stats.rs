@@ -0,0 +1,81 @@
+/// The work done by each subsystem, to find what costs the most on the host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameStats {
+    /// The CPU clock cycles emulated.
+    pub cycles: u64,
+    /// The instructions executed, including the halted steps.
+    pub instructions: u64,
+    /// The lines drawn by the PPU.
+    pub lines: u64,
+    /// The OAM DMA transfers started.
+    pub dma_transfers: u64,
+    /// The 16-byte blocks copied to the video RAM by the CGB HDMA.
+    pub hdma_blocks: u64,
+    /// The times the deferred timer, sound and serial port caught up with the CPU.
+    pub syncs: u64,
+    /// The samples generated by the audio stream.
+    ///
+    /// The audio stream is pulled by the hardware, so these are counted when the emulation
+    /// sees them rather than exactly within the frame.
+    pub apu_samples: u64,
+}
+
+impl FrameStats {
+    fn add(&mut self, other: &FrameStats) {
+        self.cycles += other.cycles;
+        self.instructions += other.instructions;
+        self.lines += other.lines;
+        self.dma_transfers += other.dma_transfers;
+        self.hdma_blocks += other.hdma_blocks;
+        self.syncs += other.syncs;
+        self.apu_samples += other.apu_samples;
+    }
+}
+
+/// The per-frame statistics retrieved by [`System::stats`][crate::System::stats].
+///
+/// A frame ends when the display enters VBlank, so nothing is counted as a frame while
+/// the LCD is off.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+    /// The number of frames completed.
+    pub frames: u64,
+    /// The work done in the last complete frame.
+    pub last_frame: FrameStats,
+    /// The work done so far in the frame in progress.
+    pub current: FrameStats,
+}
+
+/// Accumulate the work done on each step into frames.
+pub struct Collector {
+    stats: Stats,
+}
+
+impl Collector {
+    pub fn new() -> Self {
+        Self {
+            stats: Stats::default(),
+        }
+    }
+
+    /// Record the work of a step, which ends the frame if the display entered VBlank.
+    pub fn record(&mut self, step: &FrameStats, frame_complete: bool) {
+        self.stats.current.add(step);
+
+        if frame_complete {
+            self.stats.frames += 1;
+            self.stats.last_frame = core::mem::take(&mut self.stats.current);
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Clear the statistics.
+    pub fn reset(&mut self) {
+        self.stats = Stats::default();
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+/// The table of symbols to annotate the addresses, e.g. loaded from RGBDS `.sym` files.
+///
+/// Symbols are keyed by the bank and the address. Addresses in the fixed ROM bank area
+/// `0x0000-0x3fff` always use bank zero.
+#[derive(Clone, Debug, Default)]
+pub struct Symbols {
+    map: BTreeMap<(usize, u16), String>,
+}
+
+fn key(bank: usize, addr: u16) -> (usize, u16) {
+    if addr <= 0x3fff {
+        (0, addr)
+    } else {
+        (bank, addr)
+    }
+}
+
+impl Symbols {
+    /// Create an empty symbol table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a symbol at the address of the bank, replacing the existing one if any.
+    pub fn insert(&mut self, bank: usize, addr: u16, name: &str) {
+        self.map.insert(key(bank, addr), name.into());
+    }
+
+    /// Return the symbol exactly at the address of the bank.
+    pub fn get(&self, bank: usize, addr: u16) -> Option<&str> {
+        self.map.get(&key(bank, addr)).map(|s| s.as_str())
+    }
+
+    /// Return the closest symbol at or before the address in the same bank,
+    /// with the offset of the address from the symbol.
+    pub fn locate(&self, bank: usize, addr: u16) -> Option<(&str, u16)> {
+        let (bank, addr) = key(bank, addr);
+
+        self.map
+            .range((bank, 0)..=(bank, addr))
+            .next_back()
+            .map(|(&(_, base), name)| (name.as_str(), addr - base))
+    }
+
+    /// The number of symbols.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check if the table has no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locate() {
+        let mut s = Symbols::new();
+
+        s.insert(0, 0x0150, "Main");
+        s.insert(3, 0x4000, "Bank3Func");
+        s.insert(4, 0x4000, "Bank4Func");
+        s.insert(7, 0x0200, "Other");
+
+        assert_eq!(s.locate(2, 0x0152), Some(("Main", 2)));
+        assert_eq!(s.get(9, 0x0200), Some("Other"));
+        assert_eq!(s.locate(3, 0x4010), Some(("Bank3Func", 0x10)));
+        assert_eq!(s.locate(4, 0x4010), Some(("Bank4Func", 0x10)));
+        assert_eq!(s.locate(5, 0x4010), None);
+        assert_eq!(s.locate(0, 0x0100), None);
+    }
+}
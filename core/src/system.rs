@@ -1,19 +1,38 @@
-use crate::cgb::Cgb;
+use crate::buffer::Buffer;
+use crate::cgb::{self, Cgb, SPEED_SWITCH_CYCLES};
+use crate::coverage::Coverage;
 use crate::cpu::Cpu;
 use crate::debug::Debugger;
 use crate::device::Device;
 use crate::dma::Dma;
+use crate::error::Error;
 use crate::fc::FreqControl;
-use crate::gpu::Gpu;
+use crate::gpu::{
+    self, ColorMapper, Gpu, LineCacheStats, NullObserver, Rgb888, VideoObserver, FRAME_DOTS,
+};
 use crate::hardware::{Hardware, HardwareHandle};
-use crate::ic::Ic;
-use crate::joypad::Joypad;
-use crate::mbc::Mbc;
-use crate::mmu::Mmu;
-use crate::serial::Serial;
+use crate::ic::{Ic, InterruptState};
+use crate::joypad::{self, Joypad};
+use crate::mbc::{self, Header, Mapper, Mbc, MbcState};
+use crate::mmu::{self, Clock, Handle, Mmu};
+use crate::profile::{ProfileReport, Profiler};
+use crate::rng::Rng;
+use crate::save::{SaveData, SaveError};
+use crate::sched::{Deadline, Lazy, Timeline};
+#[cfg(feature = "serial")]
+use crate::serial::{QueueFull, Serial, SerialDevice};
+use crate::sgb::SGB_PACKET_LEN;
 use crate::sound::Sound;
+use crate::stats::{Collector, Stats};
+use crate::symbols::Symbols;
 use crate::timer::Timer;
-use log::*;
+use crate::unused::{self, Unused};
+use crate::vector::{self, Vector, VectorAction, VectorHook};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::{Ref, RefMut};
+use core::marker::PhantomData;
+use core::mem::size_of;
 
 /// Configuration of the emulator.
 pub struct Config {
@@ -25,6 +44,241 @@ pub struct Config {
     pub(crate) delay_unit: u64,
     /// Don't adjust CPU frequency.
     pub(crate) native_speed: bool,
+    /// The multiplier of the CPU frequency the controller paces to.
+    pub(crate) speed: f32,
+    /// Reuse the rendered background of lines whose inputs didn't change.
+    pub(crate) line_cache: bool,
+    /// Accumulate the clock cycles per opcode and per page.
+    pub(crate) profile: bool,
+    /// Record the ROM bytes executed as an opcode.
+    pub(crate) coverage: bool,
+    /// Count the work done by each subsystem per frame.
+    pub(crate) stats: bool,
+    /// What to do when the emulation goes beyond what the emulator handles.
+    pub(crate) policy: Policy,
+    /// Seed of the random number generator.
+    pub(crate) seed: u64,
+    /// The contents of the work RAM and the video RAM at power-on.
+    pub(crate) ram_fill: RamFill,
+    /// The model to emulate.
+    pub(crate) model: Model,
+    /// Start the cartridge without running the boot ROM.
+    pub(crate) skip_boot: bool,
+    /// What to do when A+B+Start+Select are pressed together.
+    pub(crate) reset_combo: ResetCombo,
+    /// Refuse the CGB-only games in DMG mode rather than letting them show their lockout message.
+    pub(crate) reject_cgb_only: bool,
+    /// The contents of the wave RAM at power-on, overriding the pattern of the model.
+    pub(crate) wave_ram: Option<[u8; 16]>,
+    /// What a transfer clocked by the other side does without a link partner.
+    pub(crate) disconnected: Disconnected,
+    /// Keep the bytes sent over the link cable for the caller to drain.
+    pub(crate) serial_queue: bool,
+}
+
+/// What to do when A+B+Start+Select are pressed together, which many games take as a request
+/// to reset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResetCombo {
+    /// Leave the keys to the game.
+    Ignore,
+    /// Report the keys in [`PollData::reset_combo`][] and let the frontend decide.
+    Report,
+    /// Report the keys and reset the system with [`System::reset`][], keeping the cartridge RAM.
+    Reset,
+}
+
+/// What a serial transfer clocked by the other side does when no link partner clocks it.
+///
+/// The hardware waits forever. Some games time out on their own, while others hang unless
+/// the transfer completes with the byte of the idle line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Disconnected {
+    /// Wait for a byte from the hardware or the incoming queue, as the hardware does.
+    Wait,
+    /// Complete the transfer with 0xff after the given clock cycles without a byte.
+    Idle(usize),
+}
+
+/// The Game Boy model to emulate, which picks the boot ROM and the registers it leaves to the cartridge.
+///
+/// Some games tell the models apart from these registers, e.g. `a` is 0xff on the MGB and the SGB2,
+/// and `b` is 0x01 on the AGB. Only the DMG and the MGB boot ROMs are available without the `color`
+/// feature, and only the CGB and the AGB boot ROMs with it; the other models start the cartridge
+/// as if [`Config::skip_boot`][] was set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Model {
+    /// The first revision of the original Game Boy.
+    Dmg0,
+    /// The original Game Boy.
+    Dmg,
+    /// The Game Boy Pocket.
+    Mgb,
+    /// The Super Game Boy.
+    ///
+    /// Its clock is derived from the SNES one, so it runs about 2.4% faster than the handhelds.
+    Sgb,
+    /// The Super Game Boy 2.
+    Sgb2,
+    /// The Game Boy Color.
+    Cgb,
+    /// The Game Boy Advance.
+    Agb,
+}
+
+impl Model {
+    /// Whether the model is a CGB, with its hardware differences even without the `color` feature.
+    pub(crate) fn cgb(self) -> bool {
+        matches!(self, Model::Cgb | Model::Agb)
+    }
+
+    /// Whether the games run in CGB mode, which needs the `color` feature.
+    pub(crate) fn cgb_mode(self) -> bool {
+        cfg!(feature = "color") && self.cgb()
+    }
+
+    /// The contents of the wave RAM at power-on.
+    ///
+    /// The DMG pattern varies between units, so this is the one of a typical unit.
+    pub(crate) fn wave_ram(self) -> [u8; 16] {
+        match self {
+            Model::Cgb | Model::Agb => [
+                0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff, 0x00, 0xff,
+                0x00, 0xff,
+            ],
+            _ => [
+                0x84, 0x40, 0x43, 0xaa, 0x2d, 0x78, 0x92, 0x3c, 0x60, 0x59, 0x59, 0xb0, 0x34, 0xb8,
+                0x2e, 0xda,
+            ],
+        }
+    }
+
+    /// Whether the games can send command packets to the SNES side.
+    pub(crate) fn sgb(self) -> bool {
+        matches!(self, Model::Sgb | Model::Sgb2)
+    }
+
+    /// The CPU frequency of the model, given the one of the handhelds.
+    pub(crate) fn clock(self, freq: u64) -> u64 {
+        match self {
+            Model::Sgb => freq * SGB_CLOCK / DMG_CLOCK,
+            _ => freq,
+        }
+    }
+}
+
+/// The CPU clock of the handhelds in Hz.
+const DMG_CLOCK: u64 = 4194304;
+
+/// The CPU clock of the Super Game Boy in Hz, the SNES master clock divided by 5.
+const SGB_CLOCK: u64 = 4295454;
+
+impl Default for Model {
+    /// The DMG, or the CGB with the `color` feature.
+    fn default() -> Self {
+        if cfg!(feature = "color") {
+            Model::Cgb
+        } else {
+            Model::Dmg
+        }
+    }
+}
+
+/// The contents of the work RAM and the video RAM at power-on.
+///
+/// The RAM of the hardware isn't cleared, and a few games and test ROMs depend on what's left in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RamFill {
+    /// All the bytes are 0x00.
+    Zeros,
+    /// All the bytes are 0xff.
+    Ones,
+    /// Random bytes from the generator seeded by [`Config::seed`][].
+    Random,
+    /// The bytes alternate between 0x0f and 0xf0.
+    Nibbles,
+}
+
+impl RamFill {
+    pub(crate) fn fill(self, buf: &mut [u8], rng: &mut Rng) {
+        match self {
+            RamFill::Zeros => buf.iter_mut().for_each(|b| *b = 0x00),
+            RamFill::Ones => buf.iter_mut().for_each(|b| *b = 0xff),
+            RamFill::Random => rng.fill(buf),
+            RamFill::Nibbles => {
+                for (i, b) in buf.iter_mut().enumerate() {
+                    *b = if i % 2 == 0 { 0x0f } else { 0xf0 };
+                }
+            }
+        }
+    }
+}
+
+/// The cartridge to build the system with.
+enum Cart<'a> {
+    /// A ROM image with the memory bank controller declared in its header.
+    Rom(&'a [u8]),
+    /// A mapper supplied by the user.
+    Mapper(Box<dyn Mapper>),
+}
+
+/// The boot ROM of the configured model, unless it's skipped.
+fn boot_rom(cfg: &Config) -> Option<Vec<u8>> {
+    if cfg.skip_boot {
+        None
+    } else {
+        mbc::boot_rom(cfg.model)
+    }
+}
+
+/// The CPU state on power-on, or after the boot ROM if it's skipped.
+fn power_on_cpu(cfg: &Config, skip_boot: bool) -> Cpu {
+    if skip_boot {
+        Cpu::after_boot(cfg.model)
+    } else {
+        Cpu::new()
+    }
+}
+
+/// Set the I/O registers the boot ROM would have set.
+fn skip_boot_io(mmu: &mut Mmu) {
+    for (addr, value) in BOOT_IO {
+        mmu.set8(*addr, *value);
+    }
+}
+
+/// The I/O registers the DMG boot ROM leaves set, in the order to write them.
+const BOOT_IO: &[(u16, u8)] = &[
+    (0xff26, 0xf1),
+    (0xff24, 0x77),
+    (0xff25, 0xf3),
+    (0xff40, 0x91),
+    (0xff47, 0xfc),
+    (0xff50, 0x01),
+];
+
+/// What to do when a game drives the hardware in a way the emulator doesn't handle,
+/// e.g. the mixer output going out of range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Log the condition and keep going with the closest valid state.
+    Resilient,
+    /// Panic, to catch the condition in tests.
+    Strict,
+}
+
+impl Policy {
+    /// Handle a violated expectation of the emulator.
+    pub(crate) fn violation(self, args: core::fmt::Arguments) {
+        match self {
+            Policy::Resilient => warn!("{}", args),
+            Policy::Strict => panic!("{}", args),
+        }
+    }
 }
 
 impl Config {
@@ -36,15 +290,37 @@ impl Config {
             sample: freq / 1000,
             delay_unit: 10,
             native_speed: false,
+            speed: 1.0,
+            line_cache: false,
+            profile: false,
+            coverage: false,
+            stats: false,
+            policy: Policy::Resilient,
+            seed: 0,
+            ram_fill: RamFill::Zeros,
+            model: Model::default(),
+            skip_boot: false,
+            reset_combo: ResetCombo::Ignore,
+            reject_cgb_only: false,
+            wave_ram: None,
+            disconnected: Disconnected::Wait,
+            serial_queue: false,
         }
     }
 
     /// Set the CPU frequency.
+    ///
+    /// This is the frequency of the handhelds, which is scaled for the Super Game Boy.
     pub fn freq(mut self, freq: u64) -> Self {
         self.freq = freq;
         self
     }
 
+    /// The CPU frequency of the configured model.
+    pub(crate) fn clock(&self) -> u64 {
+        self.model.clock(self.freq)
+    }
+
     /// Set the sampling count of the CPU frequency controller.
     pub fn sample(mut self, sample: u64) -> Self {
         self.sample = sample;
@@ -62,30 +338,532 @@ impl Config {
         self.native_speed = native;
         self
     }
+
+    /// Set the speed multiplier, e.g. 2.0 to fast-forward or 0.5 for slow motion.
+    ///
+    /// The frequency controller paces the CPU to the frequency times the multiplier, and
+    /// the audio keeps its pitch. See [`System::set_speed_multiplier`][].
+    pub fn speed_multiplier(mut self, multiplier: f32) -> Self {
+        self.speed = multiplier;
+        self
+    }
+
+    /// Set the flag to cache the background of each line.
+    ///
+    /// Lines whose tile data, tile map and scroll/palette registers are unchanged
+    /// since the last frame skip background and window rendering.
+    pub fn line_cache(mut self, enable: bool) -> Self {
+        self.line_cache = enable;
+        self
+    }
+
+    /// Set the flag to profile the executed instructions.
+    ///
+    /// The cycle counts are retrieved by [`System::profile_report`][].
+    pub fn profile(mut self, enable: bool) -> Self {
+        self.profile = enable;
+        self
+    }
+
+    /// Set the flag to record the ROM bytes executed as an opcode.
+    ///
+    /// The bitmap is retrieved by [`System::coverage`][].
+    pub fn coverage(mut self, enable: bool) -> Self {
+        self.coverage = enable;
+        self
+    }
+
+    /// Set the flag to count the work done by each subsystem per frame.
+    ///
+    /// The counts are retrieved by [`System::stats`][].
+    pub fn stats(mut self, enable: bool) -> Self {
+        self.stats = enable;
+        self
+    }
+
+    /// Set the policy for the conditions the emulator doesn't handle.
+    ///
+    /// The default is [`Policy::Resilient`][].
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the seed of the random number generator, for reproducible runs.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the contents of the work RAM and the video RAM at power-on.
+    ///
+    /// The default is [`RamFill::Zeros`][].
+    pub fn ram_fill(mut self, fill: RamFill) -> Self {
+        self.ram_fill = fill;
+        self
+    }
+
+    /// Set the model to emulate.
+    ///
+    /// The default is [`Model::Dmg`][], or [`Model::Cgb`][] with the `color` feature.
+    pub fn model(mut self, model: Model) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Set the flag to skip the boot ROM and start the cartridge with the registers the boot ROM
+    /// of the model leaves.
+    ///
+    /// The display, palette and sound registers are set as the DMG boot ROM leaves them,
+    /// but the CGB palettes aren't initialized.
+    pub fn skip_boot(mut self, skip: bool) -> Self {
+        self.skip_boot = skip;
+        self
+    }
+
+    /// Set what to do when A+B+Start+Select are pressed together.
+    ///
+    /// The default is [`ResetCombo::Ignore`][].
+    pub fn reset_combo(mut self, combo: ResetCombo) -> Self {
+        self.reset_combo = combo;
+        self
+    }
+
+    /// Set what a serial transfer clocked by the other side does without a link partner.
+    ///
+    /// The default is [`Disconnected::Wait`][]. This doesn't apply to a [`SerialDevice`][crate::SerialDevice],
+    /// which clocks the transfers itself.
+    pub fn disconnected(mut self, disconnected: Disconnected) -> Self {
+        self.disconnected = disconnected;
+        self
+    }
+
+    /// Set the flag to keep the bytes sent over the link cable for [`System::drain_serial_out`][].
+    ///
+    /// By default, the bytes are only passed to [`SerialPort::send_byte`][crate::SerialPort::send_byte].
+    pub fn serial_queue(mut self, enable: bool) -> Self {
+        self.serial_queue = enable;
+        self
+    }
+
+    /// Set the flag to refuse the games which require a CGB when not emulating one.
+    ///
+    /// By default, such games run as on the hardware, detecting the DMG and showing a message asking
+    /// for a CGB. With the flag set, [`System::try_new`][] fails with [`Error::RequiresCgb`][] instead.
+    /// [`Header::requires_cgb`][crate::Header::requires_cgb] lets frontends warn beforehand.
+    pub fn reject_cgb_only(mut self, reject: bool) -> Self {
+        self.reject_cgb_only = reject;
+        self
+    }
+
+    /// Set the contents of the wave RAM at power-on.
+    ///
+    /// By default, the wave RAM holds the pattern of the model, which a few games play
+    /// before writing their own waveform.
+    pub fn wave_ram(mut self, wave_ram: [u8; 16]) -> Self {
+        self.wave_ram = Some(wave_ram);
+        self
+    }
+
+    /// The contents of the wave RAM at power-on.
+    pub(crate) fn initial_wave_ram(&self) -> [u8; 16] {
+        self.wave_ram.unwrap_or_else(|| self.model.wave_ram())
+    }
+
+    /// Create the random number generator from the seed.
+    pub(crate) fn rng(&self) -> Rng {
+        Rng::new(self.seed)
+    }
+}
+
+/// Memory provided by the user to place the large emulator buffers.
+///
+/// Each buffer that isn't provided is allocated by the emulator.
+/// This allows targets to put the buffers in specific RAM sections.
+/// The emulator borrows the buffers for as long as it lives.
+pub struct Buffers<'a> {
+    ram: Option<&'a mut [u8]>,
+    wram: Option<&'a mut [u8]>,
+    vram: Option<&'a mut [u8]>,
+    frame: Option<&'a mut [u32]>,
+    back_frame: Option<&'a mut [u32]>,
+}
+
+impl<'a> Buffers<'a> {
+    /// The size of the buffer for the memory map, which includes OAM and high RAM.
+    pub const RAM_SIZE: usize = mmu::RAM_SIZE;
+
+    /// The size of the buffer for the work RAM banks.
+    pub const WRAM_SIZE: usize = cgb::WRAM_SIZE;
+
+    /// The size of the buffer for the video RAM banks.
+    pub const VRAM_SIZE: usize = gpu::VRAM_SIZE;
+
+    /// The number of pixels in the frame buffer, `VRAM_WIDTH * VRAM_HEIGHT`.
+    pub const FRAME_SIZE: usize = gpu::FRAME_SIZE;
+
+    /// Let the emulator allocate all the buffers.
+    pub fn new() -> Self {
+        Self {
+            ram: None,
+            wram: None,
+            vram: None,
+            frame: None,
+            back_frame: None,
+        }
+    }
+
+    /// Provide the buffer for the memory map. The length must be [`Buffers::RAM_SIZE`][].
+    pub fn ram(mut self, buf: &'a mut [u8]) -> Self {
+        self.ram = Some(buf);
+        self
+    }
+
+    /// Provide the buffer for the work RAM. The length must be [`Buffers::WRAM_SIZE`][].
+    pub fn wram(mut self, buf: &'a mut [u8]) -> Self {
+        self.wram = Some(buf);
+        self
+    }
+
+    /// Provide the buffer for the video RAM. The length must be [`Buffers::VRAM_SIZE`][].
+    pub fn vram(mut self, buf: &'a mut [u8]) -> Self {
+        self.vram = Some(buf);
+        self
+    }
+
+    /// Provide the frame buffer the display is drawn into. The length must be [`Buffers::FRAME_SIZE`][].
+    ///
+    /// Each line is written at `line * VRAM_WIDTH` instead of being passed to [`Hardware::vram_update`][].
+    /// [`PollData::frame_complete`][] tells when the buffer holds a whole frame.
+    pub fn frame(mut self, buf: &'a mut [u32]) -> Self {
+        self.frame = Some(buf);
+        self
+    }
+
+    /// Provide a second frame buffer to double-buffer the display. The length must be [`Buffers::FRAME_SIZE`][].
+    ///
+    /// The display is drawn into one buffer while the other holds the last complete frame,
+    /// and both are swapped on VBlank. [`System::frame`][] then never returns a torn frame,
+    /// so the frame can be read at a different pace than the emulation is polled.
+    /// Requires [`Buffers::frame`][].
+    pub fn back_frame(mut self, buf: &'a mut [u32]) -> Self {
+        self.back_frame = Some(buf);
+        self
+    }
+}
+
+impl Default for Buffers<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The granularity at which the peripherals are advanced alongside the CPU.
+pub trait Stepping {
+    /// Advance the peripherals on each machine cycle instead of after each instruction.
+    const CYCLE_STEPPED: bool;
+}
+
+/// Advance the peripherals once per instruction by the clock cycles it took.
+///
+/// This is the fastest mode, and the default one.
+pub struct InstructionStepped;
+
+impl Stepping for InstructionStepped {
+    const CYCLE_STEPPED: bool = false;
+}
+
+/// Advance the peripherals on each machine cycle, interleaved with the memory access of the CPU.
+///
+/// This allows e.g. the timer to increment and the OAM DMA to progress in the middle of an instruction.
+pub struct CycleStepped;
+
+impl Stepping for CycleStepped {
+    const CYCLE_STEPPED: bool = true;
+}
+
+/// The clock cycles of a whole frame, including VBlank.
+pub(crate) const FRAME_CYCLES: u64 = FRAME_DOTS as u64;
+
+/// The aggregated result of running a batch of instructions with [`System::run_cycles`][]
+/// or [`System::run_until_vblank`][].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PollData {
+    /// False once the hardware stopped the emulation; the system shouldn't be run again.
+    pub running: bool,
+    /// The clock cycles emulated.
+    pub cycles: u64,
+    /// The number of lines drawn on the display.
+    pub lines: u64,
+    /// The number of bytes transferred over the serial port.
+    pub serial_bytes: u64,
+    /// True if the oldest bytes sent over the serial port were dropped, because more than
+    /// [`SERIAL_QUEUE_LEN`][crate::SERIAL_QUEUE_LEN] bytes were waiting for
+    /// [`System::drain_serial_out`][]. Always false without [`Config::serial_queue`][].
+    pub serial_overflow: bool,
+    /// True if the display entered VBlank, i.e. a whole frame was drawn.
+    pub frame_complete: bool,
+    /// True if A+B+Start+Select were pressed together, with [`Config::reset_combo`][] enabled.
+    ///
+    /// With [`ResetCombo::Reset`][], the system was reset at the end of the batch.
+    pub reset_combo: bool,
+}
+
+/// The result of [`System::step_for`][], which runs within a budget rather than blocking.
+///
+/// An async executor polls the system like a future: [`StepOutcome::is_ready`][] once a frame
+/// is ready to present, pending otherwise. In either case the executor can sleep for
+/// [`StepOutcome::micros`][], the real time the emulated cycles take, and wake up earlier if
+/// it has to react to the next event, e.g. to present the frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StepOutcome {
+    /// The aggregated result of the instructions run.
+    pub poll: PollData,
+    /// The real time the emulated clock cycles take at the configured clock and speed, in microseconds.
+    pub micros: u64,
+    /// The CPU clock cycles from the end of the step until the next scheduled event:
+    /// VBlank, an interrupt of the timer or the serial port, a sound update or a joypad poll.
+    pub next_event: u64,
+    /// The real time until the next scheduled event, in microseconds.
+    pub next_event_micros: u64,
+}
+
+impl StepOutcome {
+    /// A frame was completed, i.e. the step stopped at VBlank.
+    pub fn is_ready(&self) -> bool {
+        self.poll.frame_complete
+    }
+
+    /// The budget was spent without completing a frame.
+    pub fn is_pending(&self) -> bool {
+        self.poll.running && !self.poll.frame_complete
+    }
+}
+
+/// The peripherals advanced by the MMU on each machine cycle in the cycle-stepped mode.
+struct Peripherals<'a, O, C> {
+    cgb: Device<Cgb<'a>>,
+    gpu: Device<Gpu<'a, O, C>>,
+    sound: Device<Sound>,
+    timer: Device<Timer>,
+    #[cfg(feature = "serial")]
+    serial: Device<Serial>,
+    dma: Device<Dma>,
+}
+
+impl<O: VideoObserver, C: ColorMapper> Clock for Peripherals<'_, O, C> {
+    fn tick(&self, mmu: &mut Mmu) {
+        let domain = self.cgb.borrow().clock_domain();
+        self.dma.borrow_mut().tick(mmu);
+        self.gpu.borrow_mut().step(domain.normal(4), mmu);
+        self.sound.borrow_mut().step(domain.normal(4));
+        self.timer.borrow_mut().step(4);
+        #[cfg(feature = "serial")]
+        self.serial.borrow_mut().step(4);
+    }
+}
+
+/// The peripherals stepped only when their next event is due, in the instruction-stepped mode.
+///
+/// They catch up on the elapsed time by themselves when their registers are accessed.
+struct Deferred {
+    cpu_time: Timeline,
+    normal_time: Timeline,
+    timer: Deadline,
+    sound: Deadline,
+    #[cfg(feature = "serial")]
+    serial: Deadline,
+    /// The clock cycles until the keys are polled again, in either stepping.
+    joypad: usize,
 }
 
 /// Represents the entire emulator context.
-pub struct System<D> {
+///
+/// The stepping parameter `S` selects how finely the peripherals are interleaved with the CPU,
+/// `O` observes the writes to the video memory and `C` maps the colors to pixels.
+pub struct System<'a, D, S = InstructionStepped, O = NullObserver, C = Rgb888> {
     cfg: Config,
     hw: HardwareHandle,
     fc: FreqControl,
     cpu: Cpu,
-    mmu: Option<Mmu>,
+    mmu: Option<Mmu<'a>>,
     dbg: Device<D>,
+    dbg_handle: Handle,
     ic: Device<Ic>,
-    gpu: Device<Gpu>,
+    gpu: Device<Gpu<'a, O, C>>,
+    sound: Device<Sound>,
     joypad: Device<Joypad>,
     timer: Device<Timer>,
+    #[cfg(feature = "serial")]
     serial: Device<Serial>,
     dma: Device<Dma>,
+    mbc: Device<Mbc>,
+    cgb: Device<Cgb<'a>>,
+    deferred: Deferred,
+    profiler: Option<Profiler>,
+    coverage: Option<Coverage>,
+    stats: Option<Collector>,
+    symbols: Option<Symbols>,
+    vector_hook: Option<Box<dyn VectorHook>>,
+    _stepping: PhantomData<S>,
+}
+
+impl<D, S, O, C> System<'_, D, S, O, C> {
+    /// An estimate of the bytes of RAM the emulator state occupies.
+    ///
+    /// This covers the CPU, the memory map, the devices and the work/video RAM of the build
+    /// configuration, but not the ROM image, the cartridge RAM, the line cache, the debugger
+    /// or the other heap allocations, e.g. the handlers of the memory map and the audio buffers.
+    /// Use [`assert_state_size`][] to check it against a RAM budget at build time.
+    pub const STATE_SIZE: usize = size_of::<Self>()
+        + device_size::<Gpu<'static, O, C>>()
+        + device_size::<Cgb<'static>>()
+        + device_size::<Sound>()
+        + device_size::<Ic>()
+        + device_size::<Joypad>()
+        + device_size::<Timer>()
+        + SERIAL_SIZE
+        + device_size::<Dma>()
+        + device_size::<Mbc>()
+        + mmu::RAM_SIZE
+        + cgb::WRAM_SIZE
+        + gpu::VRAM_SIZE;
 }
 
-impl<D> System<D>
+/// The size of the heap allocation of a device: the reference counts and the device itself.
+const fn device_size<T>() -> usize {
+    2 * size_of::<usize>() + size_of::<core::cell::RefCell<T>>()
+}
+
+#[cfg(feature = "serial")]
+const SERIAL_SIZE: usize = device_size::<Serial>();
+#[cfg(not(feature = "serial"))]
+const SERIAL_SIZE: usize = 0;
+
+impl<'a, D> System<'a, D>
 where
     D: Debugger + 'static,
 {
     /// Create a new emulator context.
     pub fn new<T>(cfg: Config, rom: &[u8], hw: T, dbg: D) -> Self
+    where
+        T: Hardware + 'static,
+    {
+        Self::with_buffers(cfg, rom, hw, dbg, Buffers::new())
+    }
+
+    /// Create a new emulator context, failing if the ROM is shorter than the cartridge header
+    /// or its size doesn't match the header.
+    ///
+    /// [`System::new`][] accepts such ROMs, padding or masking the ROM banks as needed.
+    /// With [`Config::reject_cgb_only`][], this also fails for the games requiring a CGB
+    /// unless one is emulated.
+    pub fn try_new<T>(cfg: Config, rom: &[u8], hw: T, dbg: D) -> Result<Self, Error>
+    where
+        T: Hardware + 'static,
+    {
+        mbc::validate_rom(rom)?;
+
+        if cfg.reject_cgb_only && !cfg.model.cgb_mode() && Header::parse(rom).requires_cgb() {
+            return Err(Error::RequiresCgb);
+        }
+
+        Ok(Self::new(cfg, rom, hw, dbg))
+    }
+
+    /// Create a new emulator context placing the large buffers in the given memory.
+    pub fn with_buffers<T>(cfg: Config, rom: &[u8], hw: T, dbg: D, bufs: Buffers<'a>) -> Self
+    where
+        T: Hardware + 'static,
+    {
+        Self::build(cfg, Cart::Rom(rom), hw, dbg, bufs, NullObserver, Rgb888)
+    }
+
+    /// Create a new emulator context with a cartridge handled by the mapper rather than
+    /// the built-in memory bank controllers.
+    pub fn new_with_mapper<T, M>(cfg: Config, mapper: M, hw: T, dbg: D) -> Self
+    where
+        T: Hardware + 'static,
+        M: Mapper + 'static,
+    {
+        let cart = Cart::Mapper(Box::new(mapper));
+        Self::build(cfg, cart, hw, dbg, Buffers::new(), NullObserver, Rgb888)
+    }
+}
+
+impl<'a, D> System<'a, D, CycleStepped>
+where
+    D: Debugger + 'static,
+{
+    /// Create a new emulator context which advances the peripherals on each machine cycle.
+    ///
+    /// This is slower than [`System::with_buffers`][], but more accurate for the software
+    /// which depends on the timing of the peripherals within an instruction.
+    pub fn cycle_stepped<T>(cfg: Config, rom: &[u8], hw: T, dbg: D, bufs: Buffers<'a>) -> Self
+    where
+        T: Hardware + 'static,
+    {
+        Self::build(cfg, Cart::Rom(rom), hw, dbg, bufs, NullObserver, Rgb888)
+    }
+}
+
+impl<'a, D, S, O> System<'a, D, S, O>
+where
+    D: Debugger + 'static,
+    S: Stepping,
+    O: VideoObserver,
+{
+    /// Create a new emulator context which reports the writes to the video memory to the observer.
+    ///
+    /// The stepping is picked by the type, e.g. `System<D, CycleStepped, O>`.
+    pub fn with_observer<T>(
+        cfg: Config,
+        rom: &[u8],
+        hw: T,
+        dbg: D,
+        bufs: Buffers<'a>,
+        observer: O,
+    ) -> Self
+    where
+        T: Hardware + 'static,
+    {
+        Self::build(cfg, Cart::Rom(rom), hw, dbg, bufs, observer, Rgb888)
+    }
+}
+
+impl<'a, D, S, O, C> System<'a, D, S, O, C>
+where
+    D: Debugger + 'static,
+    S: Stepping,
+    O: VideoObserver,
+    C: ColorMapper,
+{
+    /// Create a new emulator context with the video memory observer and the mapping of
+    /// the colors to the pixels of the frontend.
+    pub fn with_video<T>(
+        cfg: Config,
+        rom: &[u8],
+        hw: T,
+        dbg: D,
+        bufs: Buffers<'a>,
+        observer: O,
+        colors: C,
+    ) -> Self
+    where
+        T: Hardware + 'static,
+    {
+        Self::build(cfg, Cart::Rom(rom), hw, dbg, bufs, observer, colors)
+    }
+
+    fn build<T>(
+        cfg: Config,
+        cart: Cart<'_>,
+        hw: T,
+        dbg: D,
+        bufs: Buffers<'a>,
+        observer: O,
+        colors: C,
+    ) -> Self
     where
         T: Hardware + 'static,
     {
@@ -95,44 +873,142 @@ where
 
         let mut fc = FreqControl::new(hw.clone(), &cfg);
 
+        let mut rng = cfg.rng();
+        let mut wram = Buffer::new(bufs.wram, cgb::WRAM_SIZE);
+        let mut vram = Buffer::new(bufs.vram, gpu::VRAM_SIZE);
+        cfg.ram_fill.fill(&mut wram, &mut rng);
+        cfg.ram_fill.fill(&mut vram, &mut rng);
+
         let dbg = Device::mediate(dbg);
-        let cpu = Cpu::new();
-        let mut mmu = Mmu::new();
-        let sound = Device::new(Sound::new(hw.clone()));
+        let boot_rom = boot_rom(&cfg);
+        let skip_boot = boot_rom.is_none();
+        let cpu = power_on_cpu(&cfg, skip_boot);
+        let mut mmu = Mmu::with_ram(Buffer::new(bufs.ram, mmu::RAM_SIZE));
+        let cpu_time = Timeline::new();
+        let normal_time = Timeline::new();
+        let sound_sched = Lazy::new(normal_time.clone());
+        let timer_sched = Lazy::new(cpu_time.clone());
+        #[cfg(feature = "serial")]
+        let serial_sched = Lazy::new(cpu_time.clone());
+        let deferred = Deferred {
+            timer: timer_sched.deadline(),
+            sound: sound_sched.deadline(),
+            #[cfg(feature = "serial")]
+            serial: serial_sched.deadline(),
+            cpu_time,
+            normal_time,
+            joypad: 0,
+        };
+
+        let sound = Device::new(Sound::new(hw.clone(), &cfg, sound_sched));
         let ic = Device::new(Ic::new());
         let irq = ic.borrow().irq().clone();
-        let gpu = Device::new(Gpu::new(hw.clone(), irq.clone()));
-        let joypad = Device::new(Joypad::new(hw.clone(), irq.clone()));
-        let timer = Device::new(Timer::new(irq.clone()));
-        let serial = Device::new(Serial::new(hw.clone(), irq.clone()));
-        let mbc = Device::new(Mbc::new(hw.clone(), rom.to_vec()));
-        let cgb = Device::new(Cgb::new());
-        let dma = Device::new(Dma::new());
+        let gpu = Device::new(Gpu::new(
+            hw.clone(),
+            irq.clone(),
+            &cfg,
+            vram,
+            bufs.frame,
+            bufs.back_frame,
+            observer,
+            colors,
+        ));
+        let joypad = Device::new(Joypad::new(
+            hw.clone(),
+            irq.clone(),
+            cfg.reset_combo != ResetCombo::Ignore,
+            cfg.model.sgb(),
+        ));
+        let timer = Device::new(Timer::new(irq.clone(), timer_sched));
+        #[cfg(feature = "serial")]
+        let serial = Device::new(Serial::new(
+            hw.clone(),
+            irq.clone(),
+            serial_sched,
+            cfg.disconnected,
+            cfg.serial_queue,
+        ));
+        let mbc = Device::new(match cart {
+            Cart::Rom(rom) => Mbc::with_boot_rom(hw.clone(), rom.to_vec(), boot_rom),
+            Cart::Mapper(mapper) => Mbc::with_mapper(hw.clone(), mapper, boot_rom),
+        });
+        if mbc.borrow().header().requires_cgb() && !cfg.model.cgb_mode() {
+            warn!("The game requires a CGB, and detects the DMG to show its lockout message");
+        }
+        let cgb = Device::new(Cgb::new(wram));
+        let profiler = if cfg.profile {
+            Some(Profiler::new())
+        } else {
+            None
+        };
 
-        mmu.add_handler((0x0000, 0xffff), dbg.handler());
+        let dbg_handle = mmu.add_handler((0x0000, 0xffff), dbg.handler());
+        let dma = Device::new(Dma::new(dbg_handle.clone()));
+
+        // The registers which don't exist take precedence over the devices handling them on the CGB.
+        let unused = Device::new(Unused);
+        for range in unused::ranges(cfg.model.cgb_mode()) {
+            mmu.add_handler(range, unused.handler());
+        }
 
         mmu.add_handler((0xc000, 0xdfff), cgb.handler());
-        mmu.add_handler((0xff4d, 0xff4d), cgb.handler());
+        mmu.add_handler((0xff4c, 0xff4d), cgb.handler());
+        mmu.add_handler((0xff4f, 0xff4f), cgb.handler());
+        mmu.add_handler((0xff50, 0xff50), cgb.handler());
         mmu.add_handler((0xff56, 0xff56), cgb.handler());
+        mmu.add_handler((0xff68, 0xff6c), cgb.handler());
         mmu.add_handler((0xff70, 0xff70), cgb.handler());
 
         mmu.add_handler((0x0000, 0x7fff), mbc.handler());
         mmu.add_handler((0xff50, 0xff50), mbc.handler());
         mmu.add_handler((0xa000, 0xbfff), mbc.handler());
         mmu.add_handler((0xff10, 0xff3f), sound.handler());
+        mmu.add_handler((0xff76, 0xff77), sound.handler());
+        mmu.add_handler((0xff04, 0xff04), sound.handler());
 
         mmu.add_handler((0xff46, 0xff46), dma.handler());
 
         mmu.add_handler((0x8000, 0x9fff), gpu.handler());
         mmu.add_handler((0xff40, 0xff55), gpu.handler());
-        mmu.add_handler((0xff68, 0xff6b), gpu.handler());
+        mmu.add_handler((0xff68, 0xff6c), gpu.handler());
+        if O::ENABLED {
+            mmu.add_handler(gpu::OAM, gpu.handler());
+        }
 
         mmu.add_handler((0xff0f, 0xff0f), ic.handler());
         mmu.add_handler((0xffff, 0xffff), ic.handler());
         mmu.add_handler((0xff00, 0xff00), joypad.handler());
         mmu.add_handler((0xff04, 0xff07), timer.handler());
+        #[cfg(feature = "serial")]
         mmu.add_handler((0xff01, 0xff02), serial.handler());
 
+        if S::CYCLE_STEPPED {
+            mmu.set_clock(Peripherals {
+                cgb: cgb.clone(),
+                gpu: gpu.clone(),
+                sound: sound.clone(),
+                timer: timer.clone(),
+                #[cfg(feature = "serial")]
+                serial: serial.clone(),
+                dma: dma.clone(),
+            });
+        }
+
+        if skip_boot {
+            skip_boot_io(&mut mmu);
+        }
+
+        let stats = if cfg.stats {
+            Some(Collector::new())
+        } else {
+            None
+        };
+        let coverage = if cfg.coverage {
+            Some(Coverage::new(mbc.borrow().rom_len()))
+        } else {
+            None
+        };
+
         dbg.borrow_mut().init(&mmu);
 
         info!("Starting...");
@@ -148,57 +1024,673 @@ where
             cpu,
             mmu,
             dbg,
+            dbg_handle,
             ic,
             gpu,
+            sound,
             joypad,
             timer,
+            #[cfg(feature = "serial")]
             serial,
             dma,
+            mbc,
+            cgb,
+            deferred,
+            profiler,
+            coverage,
+            stats,
+            symbols: None,
+            vector_hook: None,
+            _stepping: PhantomData,
         }
     }
 
-    fn step(&mut self, mut mmu: Mmu) -> Mmu {
+    /// Restore the power-on state, as pressing the power switch off and on again.
+    ///
+    /// The ROM, the buffers, the serial device, the symbols and the statistics are kept.
+    /// If `keep_ram` is set the cartridge RAM is kept as is, otherwise it's loaded again through
+    /// [`SavePersistence::load_ram`][crate::SavePersistence::load_ram] as when the system was created.
+    pub fn reset(&mut self, keep_ram: bool) {
+        info!("Resetting...");
+        self.power_on(None, keep_ram);
+    }
+
+    /// Replace the cartridge by the ROM and restore the power-on state, as swapping the cartridge
+    /// with the power switched off.
+    ///
+    /// This allows frontends to switch games, e.g. from a menu, without creating a new system.
+    /// The cartridge RAM of the previous game is saved through
+    /// [`SavePersistence::save_ram`][crate::SavePersistence::save_ram] first, then the RAM of the
+    /// new game is loaded through [`SavePersistence::load_ram`][crate::SavePersistence::load_ram].
+    /// The symbols of the previous game are dropped and the coverage is cleared.
+    pub fn swap_rom(&mut self, rom: &[u8]) {
+        info!("Swapping the cartridge...");
+        self.power_on(Some(rom), false);
+
+        self.symbols = None;
+        if self.coverage.is_some() {
+            self.coverage = Some(Coverage::new(self.mbc.borrow().rom_len()));
+        }
+    }
+
+    /// Restore the power-on state with the new ROM if any, otherwise with the current cartridge.
+    fn power_on(&mut self, rom: Option<&[u8]>, keep_ram: bool) {
+        let mut mmu = self.mmu.take().unwrap();
+        let mut rng = self.cfg.rng();
+        let boot_rom = boot_rom(&self.cfg);
+        let skip_boot = boot_rom.is_none();
+
+        mmu.reset();
+        // Same order as on power-on so the random fill is the same.
+        self.cgb.borrow_mut().reset(self.cfg.ram_fill, &mut rng);
+        self.gpu.borrow_mut().reset(&self.cfg, &mut rng);
+        self.sound.borrow_mut().reset(self.hw.clone(), &self.cfg);
+        self.ic.borrow_mut().reset();
+        self.joypad.borrow_mut().reset();
+        self.deferred.joypad = 0;
+        self.timer.borrow_mut().reset();
+        #[cfg(feature = "serial")]
+        self.serial.borrow_mut().reset();
+        self.dma.borrow_mut().reset();
+        match rom {
+            Some(rom) => self.mbc.borrow_mut().swap_rom(rom.to_vec(), boot_rom),
+            None => self.mbc.borrow_mut().reset(boot_rom, keep_ram),
+        }
+
+        self.cpu = power_on_cpu(&self.cfg, skip_boot);
+        if skip_boot {
+            skip_boot_io(&mut mmu);
+        }
+
+        self.fc.reset();
+        self.mmu = Some(mmu);
+    }
+
+    fn step(&mut self, mut mmu: Mmu<'a>) -> (Mmu<'a>, usize) {
         {
             let mut dbg = self.dbg.borrow_mut();
             dbg.check_signal();
             dbg.take_cpu_snapshot(self.cpu.clone());
+            dbg.take_mbc_state(self.mbc.borrow().state());
             dbg.on_decode(&mmu);
         }
 
-        let mut time = self.cpu.execute(&mut mmu);
+        #[cfg(feature = "log")]
+        if let Some(symbols) = self.symbols.as_ref() {
+            if log_enabled!(log::Level::Trace) && !self.cpu.halted() {
+                let pc = self.cpu.get_pc();
+                let bank = self.mbc.borrow().rom_bank(pc);
+                if let Some((name, offset)) = symbols.locate(bank, pc) {
+                    trace!("{:02x}:{:04x}: {}+{:x}", bank, pc, name, offset);
+                }
+            }
+        }
 
-        time += self.cpu.check_interrupt(&mut mmu, &self.ic);
+        if let Some(coverage) = self.coverage.as_mut() {
+            if !self.cpu.halted() {
+                if let Some(offset) = self.mbc.borrow().rom_offset_of(self.cpu.get_pc()) {
+                    coverage.mark(offset);
+                }
+            }
+        }
 
-        self.dma.borrow_mut().step(&mut mmu);
-        self.gpu.borrow_mut().step(time, &mut mmu);
-        self.timer.borrow_mut().step(time);
-        self.serial.borrow_mut().step(time);
-        self.joypad.borrow_mut().poll();
+        // The opcode executed, taken from the decoder so the debugger doesn't see another read.
+        let pc = self.cpu.get_pc();
+        let (op, mut time) = self.cpu.execute_op(&mut mmu);
 
-        if !self.cfg.native_speed {
-            self.fc.adjust(time);
+        // rst 0x00 to rst 0x38
+        if let Some(code @ 0xc7..=0xff) = op {
+            if code & 0xc7 == 0xc7 {
+                self.call_vector_hook(Vector::Rst(code & 0x38), &mut mmu);
+            }
         }
 
-        mmu
-    }
+        if let Some(profiler) = self.profiler.as_mut() {
+            match op {
+                Some(code) => {
+                    let bank = self.mbc.borrow().rom_bank(pc);
+                    profiler.record(bank, pc, code, time)
+                }
+                None => profiler.record_halt(time),
+            }
+        }
 
-    /// Run a single step of emulation.
-    /// This function needs to be called repeatedly until it returns `false`.
-    /// Returning `false` indicates the end of emulation, and the functions shouldn't be called again.
-    pub fn poll(&mut self) -> bool {
-        if !self.hw.get().borrow_mut().sched() {
-            return false;
+        let dispatch = self.cpu.check_interrupt(&mut mmu, &self.ic);
+        if dispatch > 0 {
+            self.call_vector_hook(Vector::Interrupt(self.cpu.get_pc()), &mut mmu);
         }
+        time += dispatch;
 
-        let mmu = self.mmu.take().unwrap();
-        self.mmu = Some(self.step(mmu));
+        if self.cpu.take_stop() && self.cgb.borrow_mut().try_switch_speed() {
+            time += SPEED_SWITCH_CYCLES;
+        }
+        let (domain, compat) = {
+            let cgb = self.cgb.borrow();
+            (cgb.clock_domain(), cgb.compat_mode())
+        };
+        let stall = {
+            let mut gpu = self.gpu.borrow_mut();
+            gpu.set_compat_mode(compat);
+            gpu.take_hdma_stall()
+        };
 
-        true
-    }
-}
+        // The CPU is halted while HDMA copies data, but the other peripherals keep running.
+        time += domain.cpu(stall);
 
-/// Run the emulator with the given configuration.
-pub fn run<T: Hardware + 'static>(cfg: Config, rom: &[u8], hw: T) {
+        let mut syncs = 0;
+        if S::CYCLE_STEPPED {
+            // The memory access already advanced the peripherals; tick the internal cycles left.
+            let ticked = mmu.take_cycles();
+            for _ in ticked..time / 4 {
+                mmu.tick();
+            }
+            mmu.take_cycles();
+        } else {
+            self.dma.borrow_mut().step(&mut mmu);
+            self.gpu.borrow_mut().step(domain.normal(time), &mut mmu);
+
+            let deferred = &self.deferred;
+            deferred.cpu_time.advance(time);
+            deferred.normal_time.advance(domain.normal(time));
+            if deferred.sound.due() {
+                self.sound.borrow_mut().sync();
+                syncs += 1;
+            }
+            if deferred.timer.due() {
+                self.timer.borrow_mut().sync();
+                syncs += 1;
+            }
+            #[cfg(feature = "serial")]
+            if deferred.serial.due() {
+                self.serial.borrow_mut().sync();
+                syncs += 1;
+            }
+        }
+
+        // The keys are polled at about 1 kHz rather than on every instruction.
+        let elapsed = domain.normal(time);
+        if self.deferred.joypad <= elapsed {
+            self.poll_joypad();
+        } else {
+            self.deferred.joypad -= elapsed;
+        }
+
+        if let Some(stats) = self.stats.as_mut() {
+            let (mut counts, frame_complete) = self.gpu.borrow_mut().take_counts();
+            counts.cycles = time as u64;
+            counts.instructions = 1;
+            counts.syncs = syncs;
+            counts.dma_transfers = self.dma.borrow_mut().take_transfers();
+            counts.apu_samples = self.sound.borrow_mut().take_samples();
+            stats.record(&counts, frame_complete);
+        }
+
+        if !self.cfg.native_speed {
+            self.fc.adjust(domain.normal(time));
+        }
+
+        (mmu, time)
+    }
+
+    fn call_vector_hook(&mut self, vector: Vector, mmu: &mut Mmu) {
+        if let Some(hook) = self.vector_hook.as_mut() {
+            if hook.on_vector(vector, &mut self.cpu, mmu) == VectorAction::Return {
+                vector::skip(vector, &mut self.cpu, mmu);
+            }
+        }
+    }
+
+    /// Intercept the calls to the `rst` targets and the interrupt vectors with the hook,
+    /// e.g. to stub out routines in a test harness.
+    pub fn set_vector_hook<T>(&mut self, hook: T)
+    where
+        T: VectorHook + 'static,
+    {
+        self.vector_hook = Some(Box::new(hook));
+    }
+
+    /// Remove the hook set by [`System::set_vector_hook`][].
+    pub fn remove_vector_hook(&mut self) {
+        self.vector_hook = None;
+    }
+
+    /// Return whether the LCD is on. No frame is drawn while it's off.
+    pub fn lcd_on(&self) -> bool {
+        self.gpu.borrow().enabled()
+    }
+
+    /// Return the dot within the current line, from 0 to [`LINE_DOTS`][crate::LINE_DOTS] - 1,
+    /// for debuggers and tests reasoning about the display timing. This is 0 while the LCD is off.
+    pub fn line_dot(&self) -> usize {
+        self.gpu.borrow().line_dot()
+    }
+
+    /// The CPU clock cycles a frame takes, for frontends pacing the frames themselves.
+    ///
+    /// This is 70224 cycles, about 59.7 frames per second, or twice as many in CGB double
+    /// speed mode as the display keeps the normal speed.
+    pub fn frame_duration_cycles(&self) -> u64 {
+        self.cgb.borrow().clock_domain().cpu(FRAME_CYCLES as usize) as u64
+    }
+
+    /// Switch between running at native speed and pacing the CPU to the configured frequency.
+    ///
+    /// This overrides [`Config::native_speed`][] at runtime, e.g. for a turbo button.
+    pub fn set_native_speed(&mut self, native: bool) {
+        if self.cfg.native_speed && !native {
+            // Don't count the time spent unpaced against the next sample.
+            self.fc.reset();
+        }
+        self.cfg.native_speed = native;
+    }
+
+    /// Return true if the emulator runs at native speed without pacing.
+    pub fn native_speed(&self) -> bool {
+        self.cfg.native_speed
+    }
+
+    /// Change the speed multiplier at runtime, e.g. 2.0 to fast-forward or 0.5 for slow motion.
+    ///
+    /// The frequency controller paces the CPU to the configured frequency times the multiplier.
+    /// The audio is still played at the hardware sample rate, so it keeps its pitch while the
+    /// envelopes and the sweep follow the emulated time. This has no effect on the pacing
+    /// when running at native speed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multiplier isn't positive.
+    pub fn set_speed_multiplier(&mut self, multiplier: f32) {
+        assert!(multiplier > 0.0, "invalid speed multiplier: {}", multiplier);
+
+        self.cfg.speed = multiplier;
+        self.fc.set_target(&self.cfg);
+        // Don't count the time spent at the former speed against the next sample.
+        self.fc.reset();
+        self.sound.borrow_mut().set_speed(multiplier);
+    }
+
+    /// Return the speed multiplier.
+    pub fn speed_multiplier(&self) -> f32 {
+        self.cfg.speed
+    }
+
+    /// Return which interrupts are enabled and requested.
+    pub fn interrupt_state(&self) -> InterruptState {
+        self.ic.borrow().state(self.cpu.get_ime())
+    }
+
+    /// Return the header of the cartridge, e.g. to check [`Header::requires_cgb`][].
+    pub fn header(&self) -> Header {
+        self.mbc.borrow().header().clone()
+    }
+
+    /// Return the banks currently mapped by the memory bank controller of the cartridge.
+    pub fn mbc_state(&self) -> MbcState {
+        self.mbc.borrow().state()
+    }
+
+    /// Read a byte of the memory as the CPU sees it, for memory viewers.
+    ///
+    /// The debugger isn't notified, so read watches don't trigger, and the emulation doesn't advance.
+    /// The cartridge RAM is read from the mapped bank even while it's disabled.
+    pub fn debug_read(&self, addr: u16) -> u8 {
+        if (0xa000..=0xbfff).contains(&addr) {
+            let value = self.mbc.borrow().peek_ram(addr);
+            if let Some(value) = value {
+                return value;
+            }
+        }
+
+        self.mmu
+            .as_ref()
+            .unwrap()
+            .get8_skip(addr, Some(&self.dbg_handle))
+    }
+
+    /// Write a byte to the memory, for memory editors, e.g. to freeze a value while testing a game.
+    ///
+    /// The debugger isn't notified, so write watches don't trigger.
+    /// Writes to the ROM area `0x0000-0x7fff` are discarded instead of being taken as bank switching
+    /// commands, and the cartridge RAM is written in the mapped bank even while it's disabled.
+    /// Writes to the I/O registers `0xff00-0xff7f` and `0xffff` have the same side effects as those
+    /// of the CPU, e.g. writing to FF46 starts an OAM DMA transfer.
+    pub fn debug_write(&mut self, addr: u16, value: u8) {
+        match addr {
+            0x0000..=0x7fff => {}
+            0xa000..=0xbfff => {
+                self.mbc.borrow_mut().poke_ram(addr, value);
+            }
+            _ => self
+                .mmu
+                .as_mut()
+                .unwrap()
+                .set8_skip(addr, value, Some(&self.dbg_handle)),
+        }
+    }
+
+    /// Return the frame buffer provided by [`Buffers::frame`][].
+    ///
+    /// If the display is double-buffered by [`Buffers::back_frame`][], this is the last complete
+    /// frame, otherwise the frame being drawn. Returns `None` without a frame buffer.
+    pub fn frame(&self) -> Option<Ref<'_, [u32]>> {
+        let gpu = self.gpu.borrow();
+        gpu.frame()?;
+        Some(Ref::map(gpu, |gpu| gpu.frame().unwrap()))
+    }
+
+    /// The observer of the writes to the video memory.
+    pub fn observer(&self) -> Ref<'_, O> {
+        Ref::map(self.gpu.borrow(), |gpu| gpu.observer())
+    }
+
+    /// The observer of the writes to the video memory.
+    pub fn observer_mut(&mut self) -> RefMut<'_, O> {
+        RefMut::map(self.gpu.borrow_mut(), |gpu| gpu.observer_mut())
+    }
+
+    /// Return the external RAM of the cartridge.
+    ///
+    /// The slice is limited to the RAM size declared in the cartridge header,
+    /// so that frontends can store it as save data as is.
+    pub fn cartridge_ram(&self) -> Ref<'_, [u8]> {
+        Ref::map(self.mbc.borrow(), |mbc| mbc.cartridge_ram())
+    }
+
+    /// Return the mutable external RAM of the cartridge, e.g. to restore save data.
+    pub fn cartridge_ram_mut(&mut self) -> RefMut<'_, [u8]> {
+        RefMut::map(self.mbc.borrow_mut(), |mbc| mbc.cartridge_ram_mut())
+    }
+
+    /// The size of the battery-backed RAM declared in the cartridge header.
+    pub fn cartridge_ram_size(&self) -> usize {
+        self.mbc.borrow().cartridge_ram().len()
+    }
+
+    /// Return the battery-backed RAM and the state of the real-time clock, if any.
+    ///
+    /// Unlike [`System::export_save`][], the RAM is borrowed rather than copied, so frontends
+    /// can write it to the save file as is, followed by
+    /// [`RtcSave::to_bytes`][crate::save::RtcSave::to_bytes] for the `.sav` format.
+    pub fn save_data(&self) -> SaveData<'_> {
+        let mbc = self.mbc.borrow();
+        let rtc = mbc.rtc();
+        SaveData {
+            ram: Ref::map(mbc, |mbc| mbc.cartridge_ram()),
+            rtc,
+        }
+    }
+
+    /// Export the save data in the `.sav` format used by other emulators.
+    ///
+    /// The data is the cartridge RAM followed by the 48-byte RTC appendix for cartridges with a clock.
+    pub fn export_save(&self) -> Vec<u8> {
+        self.mbc.borrow().export_save()
+    }
+
+    /// Import save data in the `.sav` format, with or without the RTC appendix.
+    pub fn import_save(&mut self, data: &[u8]) -> Result<(), SaveError> {
+        self.mbc.borrow_mut().import_save(data)
+    }
+
+    /// Return the clock cycles spent per opcode and per page, hottest first.
+    ///
+    /// Returns `None` unless profiling is enabled by [`Config::profile`][].
+    pub fn profile_report(&self) -> Option<ProfileReport> {
+        self.profiler.as_ref().map(|p| p.report())
+    }
+
+    /// Clear the cycle counts accumulated by the profiler.
+    pub fn reset_profile(&mut self) {
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.reset();
+        }
+    }
+
+    /// Return the bitmap of the ROM bytes executed as an opcode.
+    ///
+    /// Returns `None` unless the coverage is enabled by [`Config::coverage`][].
+    pub fn coverage(&self) -> Option<&Coverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Clear the bitmap of the executed ROM bytes.
+    pub fn reset_coverage(&mut self) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.clear();
+        }
+    }
+
+    /// Return the work done by each subsystem in the last frame and in the frame in progress.
+    ///
+    /// Returns `None` unless the statistics are enabled by [`Config::stats`][].
+    pub fn stats(&self) -> Option<Stats> {
+        self.stats.as_ref().map(|s| s.stats())
+    }
+
+    /// Clear the statistics.
+    pub fn reset_stats(&mut self) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.reset();
+        }
+    }
+
+    /// Register the symbols to annotate the trace log of the executed instructions.
+    pub fn set_symbols(&mut self, symbols: Symbols) {
+        self.symbols = Some(symbols);
+    }
+
+    /// Return the closest symbol at or before the address in the currently mapped ROM bank,
+    /// with the offset of the address from the symbol.
+    pub fn symbol_at(&self, addr: u16) -> Option<(&str, u16)> {
+        let bank = self.mbc.borrow().rom_bank(addr);
+        self.symbols.as_ref()?.locate(bank, addr)
+    }
+
+    /// Poll the keys right away, e.g. when the frontend knows they changed.
+    ///
+    /// The keys are otherwise polled at about 1 kHz, which may delay the joypad interrupt
+    /// and the reset combination by up to a millisecond.
+    pub fn poll_joypad(&mut self) {
+        self.joypad.borrow_mut().poll();
+        self.deferred.joypad = joypad::POLL_CYCLES;
+    }
+
+    /// Plug an accessory into the serial port in place of the link cable of the hardware.
+    #[cfg(feature = "serial")]
+    pub fn set_serial_device<T>(&mut self, device: T)
+    where
+        T: SerialDevice + 'static,
+    {
+        self.serial.borrow_mut().set_device(Some(Box::new(device)));
+    }
+
+    /// Unplug the accessory from the serial port, going back to the link cable of the hardware.
+    #[cfg(feature = "serial")]
+    pub fn remove_serial_device(&mut self) {
+        self.serial.borrow_mut().set_device(None);
+    }
+
+    /// Queue a byte to be received over the link cable.
+    ///
+    /// The queued bytes are received before the ones of
+    /// [`SerialPort::recv_byte`][crate::SerialPort::recv_byte], so the caller doesn't have to
+    /// poll whether the byte was taken. Fails if [`SERIAL_QUEUE_LEN`][crate::SERIAL_QUEUE_LEN]
+    /// bytes are already waiting.
+    #[cfg(feature = "serial")]
+    pub fn push_serial_in(&mut self, byte: u8) -> Result<(), QueueFull> {
+        self.serial.borrow_mut().push_in(byte)
+    }
+
+    /// Take the bytes sent over the link cable since the last call.
+    ///
+    /// The bytes are only kept with [`Config::serial_queue`][], and are also passed to
+    /// [`SerialPort::send_byte`][crate::SerialPort::send_byte]. Only the last [`SERIAL_QUEUE_LEN`][crate::SERIAL_QUEUE_LEN] bytes are kept, the older
+    /// ones are reported by [`PollData::serial_overflow`][].
+    #[cfg(feature = "serial")]
+    pub fn drain_serial_out(&mut self) -> Vec<u8> {
+        self.serial.borrow_mut().drain_out().collect()
+    }
+
+    /// Return the hit/miss counters of the background line cache.
+    ///
+    /// The counters stay zero unless the cache is enabled by [`Config::line_cache`][].
+    pub fn line_cache_stats(&self) -> LineCacheStats {
+        self.gpu.borrow().line_cache_stats()
+    }
+
+    /// Run a single step of emulation.
+    /// This function needs to be called repeatedly until it returns `false`.
+    /// Returning `false` indicates the end of emulation, and the functions shouldn't be called again.
+    pub fn poll(&mut self) -> bool {
+        if !self.hw.get().borrow_mut().sched() {
+            return false;
+        }
+
+        let mmu = self.mmu.take().unwrap();
+        self.mmu = Some(self.step(mmu).0);
+        self.check_reset_combo();
+
+        true
+    }
+
+    /// Run at least the given clock cycles of emulation at once.
+    ///
+    /// Unlike [`System::poll`][], [`Hardware::sched`][] is called once per batch rather than once per instruction.
+    pub fn run_cycles(&mut self, cycles: u64) -> PollData {
+        self.run_while(|data, _| data.cycles < cycles)
+    }
+
+    /// Run emulation until the display enters VBlank, i.e. a frame has been drawn.
+    ///
+    /// Stops after a frame worth of clock cycles if the display is off.
+    /// [`Hardware::sched`][] is called once per batch rather than once per instruction.
+    pub fn run_until_vblank(&mut self) -> PollData {
+        // A frame takes slightly more than `FRAME_CYCLES` as the instructions overrun the modes,
+        // so only stop short of VBlank while the display is off.
+        let gpu = self.gpu.clone();
+        let frame = self.frame_duration_cycles();
+        self.run_while(move |data, vblank| {
+            !vblank && (data.cycles < frame || gpu.borrow().enabled())
+        })
+    }
+
+    /// Run emulation for at most the given clock cycles, stopping early at VBlank.
+    ///
+    /// This never waits to pace the CPU, whatever [`Config::native_speed`][], so it suits async
+    /// executors which sleep on their own timers instead, e.g. for
+    /// [`StepOutcome::next_event_micros`][]. The budget may be overrun by the last instruction.
+    /// [`Hardware::sched`][] is called once per step.
+    pub fn step_for(&mut self, budget_cycles: u64) -> StepOutcome {
+        let native = self.native_speed();
+        self.set_native_speed(true);
+        let poll = self.run_while(|data, vblank| !vblank && data.cycles < budget_cycles);
+        self.set_native_speed(native);
+
+        let next_event = self.next_event();
+        StepOutcome {
+            poll,
+            micros: self.micros(poll.cycles),
+            next_event,
+            next_event_micros: self.micros(next_event),
+        }
+    }
+
+    /// The CPU clock cycles until the next scheduled event.
+    fn next_event(&self) -> u64 {
+        let domain = self.cgb.borrow().clock_domain();
+        let cpu = |normal: u64| domain.cpu(normal as usize) as u64;
+
+        let mut next = cpu(self.deferred.joypad as u64);
+        if let Some(vblank) = self.gpu.borrow().vblank_in() {
+            next = next.min(cpu(vblank as u64));
+        }
+        // The deadlines are only followed in the instruction-stepped mode.
+        if !S::CYCLE_STEPPED {
+            let deferred = &self.deferred;
+            let deadlines = [
+                deferred.timer.remaining(),
+                deferred.sound.remaining().map(cpu),
+                #[cfg(feature = "serial")]
+                deferred.serial.remaining(),
+            ];
+            next = deadlines
+                .iter()
+                .flatten()
+                .fold(next, |next, at| next.min(*at));
+        }
+        next
+    }
+
+    /// The real time the CPU clock cycles take at the configured clock and speed, in microseconds.
+    fn micros(&self, cycles: u64) -> u64 {
+        let domain = self.cgb.borrow().clock_domain();
+        let normal = domain.normal(cycles as usize) as f64;
+        (normal * 1_000_000.0 / (self.cfg.clock() as f64 * self.cfg.speed as f64)) as u64
+    }
+
+    fn run_while<F>(&mut self, mut cond: F) -> PollData
+    where
+        F: FnMut(&PollData, bool) -> bool,
+    {
+        let mut data = PollData::default();
+
+        if !self.hw.get().borrow_mut().sched() {
+            return data;
+        }
+        data.running = true;
+
+        // Drop what the previous calls to `poll` accumulated.
+        self.gpu.borrow_mut().take_lines();
+        self.gpu.borrow_mut().take_vblank();
+        #[cfg(feature = "serial")]
+        {
+            self.serial.borrow_mut().take_transfers();
+            self.serial.borrow_mut().take_overflow();
+        }
+
+        let mut mmu = self.mmu.take().unwrap();
+        let mut vblank = false;
+
+        while cond(&data, vblank) {
+            let (next, time) = self.step(mmu);
+            mmu = next;
+            data.cycles += time as u64;
+            vblank = self.gpu.borrow_mut().take_vblank();
+            data.frame_complete |= vblank;
+        }
+
+        self.mmu = Some(mmu);
+
+        data.lines = self.gpu.borrow_mut().take_lines();
+        #[cfg(feature = "serial")]
+        {
+            data.serial_bytes = self.serial.borrow_mut().take_transfers();
+            data.serial_overflow = self.serial.borrow_mut().take_overflow();
+        }
+        data.reset_combo = self.check_reset_combo();
+
+        data
+    }
+
+    /// Return the command packets the game sent to the Super Game Boy since the last call.
+    ///
+    /// Packets are only received with the [`Model::Sgb`][] and [`Model::Sgb2`][] models.
+    pub fn take_sgb_packets(&mut self) -> Vec<[u8; SGB_PACKET_LEN]> {
+        self.joypad.borrow_mut().take_sgb_packets()
+    }
+
+    /// Return whether the reset combination was pressed, and reset the system if configured so.
+    fn check_reset_combo(&mut self) -> bool {
+        let pressed = self.joypad.borrow_mut().take_combo();
+        if pressed && self.cfg.reset_combo == ResetCombo::Reset {
+            self.reset(true);
+        }
+        pressed
+    }
+}
+
+/// Run the emulator with the given configuration.
+pub fn run<T: Hardware + 'static>(cfg: Config, rom: &[u8], hw: T) {
     run_inner(cfg, rom, hw, Debugger::empty())
 }
 
@@ -216,3 +1708,742 @@ fn run_inner<T: Hardware + 'static, D: Debugger + 'static>(cfg: Config, rom: &[u
     let mut sys = System::new(cfg, rom, hw, dbg);
     while sys.poll() {}
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::debug::NullDebugger;
+    use crate::device::IoHandler;
+    use crate::hardware::NullHardware;
+    use crate::mmu::{MemRead, MemWrite};
+    use alloc::rc::Rc;
+    use alloc::vec;
+    use core::cell::Cell;
+    use core::ops::RangeInclusive;
+
+    /// A debugger counting the reads in the range.
+    struct Watch {
+        range: RangeInclusive<u16>,
+        reads: Rc<Cell<usize>>,
+    }
+
+    impl Watch {
+        fn new(range: RangeInclusive<u16>) -> (Self, Rc<Cell<usize>>) {
+            let reads = Rc::new(Cell::new(0));
+            let watch = Self {
+                range,
+                reads: reads.clone(),
+            };
+            (watch, reads)
+        }
+    }
+
+    impl Debugger for Watch {
+        fn init(&mut self, _: &Mmu) {}
+
+        fn take_cpu_snapshot(&mut self, _: Cpu) {}
+
+        fn on_decode(&mut self, _: &Mmu) {}
+
+        fn check_signal(&mut self) {}
+    }
+
+    impl IoHandler for Watch {
+        fn on_read(&mut self, _: &Mmu, addr: u16) -> MemRead {
+            if self.range.contains(&addr) {
+                self.reads.set(self.reads.get() + 1);
+            }
+            MemRead::PassThrough
+        }
+
+        fn on_write(&mut self, _: &Mmu, _: u16, _: u8) -> MemWrite {
+            MemWrite::PassThrough
+        }
+    }
+
+    #[test]
+    fn skip_boot() {
+        let mut rom = vec![0; 0x8000];
+        rom[0] = 0xaa;
+
+        let cfg = Config::new().ram_fill(RamFill::Nibbles).skip_boot(true);
+        let sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        // The cartridge is mapped in place of the boot ROM.
+        assert_eq!(sys.debug_read(0x0000), 0xaa);
+        assert_eq!(sys.debug_read(0xff40), 0x91);
+        assert!(sys.lcd_on());
+        assert_eq!(sys.debug_read(0xff47), 0xfc);
+        assert_eq!(sys.debug_read(0xc000), 0x0f);
+        assert_eq!(sys.debug_read(0xc001), 0xf0);
+    }
+
+    #[test]
+    fn frame_stats() {
+        let mut rom = vec![0; 0x8000];
+        // ld a, 0xc0; ldh (0x46), a; jr -2
+        rom[0x100..0x106].copy_from_slice(&[0x3e, 0xc0, 0xe0, 0x46, 0x18, 0xfe]);
+
+        let sys = System::new(
+            Config::new().skip_boot(true),
+            &rom,
+            NullHardware,
+            NullDebugger,
+        );
+        assert_eq!(sys.stats(), None);
+
+        let cfg = Config::new().skip_boot(true).stats(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        sys.run_until_vblank();
+        let stats = sys.stats().unwrap();
+        assert_eq!(stats.frames, 1);
+        assert_eq!(stats.last_frame.dma_transfers, 1);
+
+        sys.run_until_vblank();
+        let stats = sys.stats().unwrap();
+        assert_eq!(stats.frames, 2);
+        let frame = stats.last_frame;
+        assert_eq!(frame.lines, 144);
+        assert_eq!(frame.dma_transfers, 0);
+        // The instructions overrun the modes, so a frame takes slightly more than `FRAME_CYCLES`.
+        assert!(frame.cycles >= FRAME_CYCLES);
+        // Only `jr` is executed, which takes 12 cycles.
+        assert_eq!(frame.instructions, frame.cycles / 12);
+        // The sound catches up on each frame sequencer step at least.
+        assert!(frame.syncs >= FRAME_CYCLES / 8192);
+
+        sys.reset_stats();
+        assert_eq!(sys.stats(), Some(Stats::default()));
+    }
+
+    /// A cartridge storing `a` and `b` at 0xc000 and 0xc001, with a header passing the boot ROM checks.
+    fn identity_rom() -> Vec<u8> {
+        let mut rom = vec![0; 0x8000];
+
+        rom[0x100..0x104].copy_from_slice(&[0x00, 0xc3, 0x50, 0x01]);
+        let logo = mbc::BOOT_ROM
+            .windows(4)
+            .position(|w| w == [0xce, 0xed, 0x66, 0x66])
+            .unwrap();
+        rom[0x104..0x134].copy_from_slice(&mbc::BOOT_ROM[logo..logo + 0x30]);
+        rom[0x14d] = rom[0x134..0x14d]
+            .iter()
+            .fold(0u8, |x, b| x.wrapping_sub(*b).wrapping_sub(1));
+
+        // ld (0xc000), a; ld a, b; ld (0xc001), a; jr -2
+        rom[0x150..0x159].copy_from_slice(&[0xea, 0x00, 0xc0, 0x78, 0xea, 0x01, 0xc0, 0x18, 0xfe]);
+
+        rom
+    }
+
+    /// Run the identity cartridge past the boot ROM, if any.
+    fn boot(model: Model, skip_boot: bool) -> System<'static, NullDebugger> {
+        let cfg = Config::new().model(model).skip_boot(skip_boot);
+        let mut sys = System::new(cfg, &identity_rom(), NullHardware, NullDebugger);
+
+        // Run until the boot ROM is unmapped, which reveals the zeros at 0x0000 of the cartridge.
+        for _ in 0..400 {
+            sys.run_until_vblank();
+            if sys.debug_read(0x0000) == 0x00 {
+                break;
+            }
+        }
+        sys.run_until_vblank();
+
+        sys
+    }
+
+    fn identity(model: Model, skip_boot: bool) -> (u8, u8) {
+        let sys = boot(model, skip_boot);
+        (sys.debug_read(0xc000), sys.debug_read(0xc001))
+    }
+
+    #[test]
+    fn cgb_only() {
+        let mut rom = identity_rom();
+        rom[0x143] = 0xc0;
+        let header = Header::parse(&rom);
+        assert!(header.supports_cgb());
+        assert!(header.requires_cgb());
+
+        // The game runs by default, and sees the DMG value of `a` to show its lockout message.
+        let cfg = Config::new().model(Model::Dmg).skip_boot(true);
+        let mut sys = System::try_new(cfg, &rom, NullHardware, NullDebugger)
+            .ok()
+            .unwrap();
+        assert!(sys.header().requires_cgb());
+        sys.run_until_vblank();
+        assert_eq!(sys.debug_read(0xc000), 0x01);
+
+        let cfg = || Config::new().reject_cgb_only(true);
+        let dmg = System::try_new(cfg().model(Model::Dmg), &rom, NullHardware, NullDebugger);
+        assert_eq!(dmg.err(), Some(Error::RequiresCgb));
+
+        let cgb = System::try_new(cfg().model(Model::Cgb), &rom, NullHardware, NullDebugger);
+        assert_eq!(cgb.is_ok(), cfg!(feature = "color"));
+    }
+
+    #[test]
+    fn model_identity() {
+        assert_eq!(identity(Model::Sgb2, false), (0xff, 0x00));
+        assert_eq!(identity(Model::Agb, true), (0x11, 0x01));
+    }
+
+    #[test]
+    fn boot_registers() {
+        // The derived boot ROMs hand over with the registers of the real ones.
+        for model in [Model::Dmg, Model::Mgb, Model::Cgb, Model::Agb] {
+            if mbc::boot_rom(model).is_none() {
+                continue;
+            }
+
+            // The cartridge only overwrites `a`, after storing it at 0xc000.
+            let sys = boot(model, false);
+            let af = (sys.debug_read(0xc000) as u16) << 8 | sys.cpu.get_af() & 0xff;
+            let expected = Cpu::after_boot(model);
+            assert_eq!(af, expected.get_af(), "{:?}", model);
+            assert_eq!(sys.cpu.get_bc(), expected.get_bc(), "{:?}", model);
+
+            // On the CGB, DE and HL depend on the cartridge header.
+            if !model.cgb() {
+                assert_eq!(sys.cpu.get_de(), expected.get_de(), "{:?}", model);
+                assert_eq!(sys.cpu.get_hl(), expected.get_hl(), "{:?}", model);
+            }
+        }
+    }
+
+    #[test]
+    fn reset() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x147] = 0x03; // MBC1 with battery-backed RAM
+        rom[0x149] = 0x02; // 8KB
+
+        let cfg = Config::new().ram_fill(RamFill::Nibbles).skip_boot(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        sys.debug_write(0xc000, 0x12);
+        sys.debug_write(0xff47, 0x00);
+        sys.cartridge_ram_mut()[0] = 0x34;
+        sys.run_cycles(FRAME_CYCLES);
+        let ram = sys.cartridge_ram_mut().as_mut_ptr();
+
+        sys.reset(true);
+        assert_eq!(sys.debug_read(0xc000), 0x0f);
+        assert_eq!(sys.debug_read(0xff47), 0xfc);
+        assert_eq!(sys.cartridge_ram()[0], 0x34);
+        // The frontends may hold a pointer to the RAM, e.g. for libretro.
+        assert_eq!(sys.cartridge_ram_mut().as_mut_ptr(), ram);
+
+        // The RAM is loaded again from the hardware, into the same buffer.
+        sys.reset(false);
+        assert_eq!(sys.cartridge_ram()[0], 0x00);
+        assert_eq!(sys.cartridge_ram_mut().as_mut_ptr(), ram);
+
+        // The clock isn't reset with the RAM kept.
+        #[cfg(feature = "rtc")]
+        {
+            use crate::save::RtcSave;
+
+            rom[0x147] = 0x10; // MBC3 with a clock and battery-backed RAM
+            let mut sys = System::new(Config::new(), &rom, NullHardware, NullDebugger);
+            let rtc = RtcSave {
+                regs: [1, 2, 3, 0x34, 0x01],
+                latched: [0; 5],
+                timestamp: 0,
+            };
+            let mut save = vec![0; 0x2000];
+            save.extend_from_slice(&rtc.to_bytes());
+            sys.import_save(&save).unwrap();
+
+            sys.reset(true);
+            assert_eq!(sys.mbc.borrow().rtc().unwrap().regs, rtc.regs);
+        }
+    }
+
+    #[test]
+    fn opcode_reads() {
+        struct Continue;
+
+        impl VectorHook for Continue {
+            fn on_vector(&mut self, _: Vector, _: &mut Cpu, _: &mut Mmu) -> VectorAction {
+                VectorAction::Continue
+            }
+        }
+
+        let mut rom = vec![0; 0x8000];
+        // jr -2
+        rom[0x100..0x102].copy_from_slice(&[0x18, 0xfe]);
+
+        // The profiler and the vector hook don't read the opcode again.
+        let reads = [false, true].map(|profile| {
+            let (watch, reads) = Watch::new(0x100..=0x101);
+            let cfg = Config::new().skip_boot(true).profile(profile);
+            let mut sys = System::new(cfg, &rom, NullHardware, watch);
+            sys.set_vector_hook(Continue);
+            sys.run_cycles(FRAME_CYCLES);
+            reads.get()
+        });
+        assert!(reads[0] > 0);
+        assert_eq!(reads[0], reads[1]);
+    }
+
+    #[test]
+    fn dma_sources() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x147] = 0x03; // MBC1 with battery-backed RAM
+        rom[0x149] = 0x02; // 8KB
+
+        // ld a, 0x0a; ld (0x0000), a; jr -2
+        rom[0x100..0x107].copy_from_slice(&[0x3e, 0x0a, 0xea, 0x00, 0x00, 0x18, 0xfe]);
+
+        let (watch, reads) = Watch::new(0xa000..=0xdfff);
+        let cfg = Config::new().skip_boot(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, watch);
+        sys.run_cycles(FRAME_CYCLES);
+
+        for i in 0..0xa0 {
+            sys.cartridge_ram_mut()[0x100 + i] = i as u8;
+            sys.debug_write(0xd000 + i as u16, !i as u8);
+        }
+
+        let oam = |sys: &System<Watch>| {
+            (0..0xa0)
+                .map(|i| sys.debug_read(0xfe00 + i))
+                .collect::<Vec<_>>()
+        };
+
+        // The cartridge RAM, through the bank switching of the cartridge.
+        sys.debug_write(0xff46, 0xa1);
+        sys.run_cycles(FRAME_CYCLES);
+        assert_eq!(oam(&sys), (0..0xa0).collect::<Vec<u8>>());
+
+        // The work RAM, and its echo.
+        for src in [0xd0, 0xf0] {
+            sys.debug_write(0xff46, 0x80);
+            sys.run_cycles(FRAME_CYCLES);
+            sys.debug_write(0xff46, src);
+            sys.run_cycles(FRAME_CYCLES);
+            assert_eq!(
+                oam(&sys),
+                (0..0xa0).map(|i: u8| !i).collect::<Vec<_>>(),
+                "{:02x}",
+                src
+            );
+        }
+
+        // The debugger doesn't see the reads of the DMA.
+        assert_eq!(reads.get(), 0);
+    }
+
+    #[test]
+    fn swap_rom() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x147] = 0x03; // MBC1 with battery-backed RAM
+        rom[0x149] = 0x02; // 8KB
+
+        let cfg = Config::new().skip_boot(true).coverage(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+        sys.debug_write(0xc000, 0x12);
+        sys.cartridge_ram_mut()[0] = 0x34;
+        sys.run_cycles(FRAME_CYCLES);
+
+        let mut other = vec![0; 0x10000];
+        other[0x148] = 0x01;
+        other[0x4000] = 0x56;
+        sys.swap_rom(&other);
+
+        assert_eq!(sys.debug_read(0xc000), 0x00);
+        assert_eq!(sys.debug_read(0x4000), 0x56);
+        assert!(sys.cartridge_ram().is_empty());
+        assert_eq!(sys.cpu.get_pc(), 0x100);
+        assert_eq!(sys.coverage().unwrap().as_bytes().len(), 0x10000 / 8);
+    }
+
+    #[test]
+    fn save_data() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x147] = 0x10; // MBC3 with a clock and battery-backed RAM
+        rom[0x149] = 0x02; // 8KB
+
+        let sys = System::new(Config::new(), &rom, NullHardware, NullDebugger);
+        assert_eq!(sys.cartridge_ram_size(), 0x2000);
+        {
+            let save = sys.save_data();
+            assert_eq!(save.ram.len(), 0x2000);
+            // The clock is only saved if it's emulated.
+            assert_eq!(save.rtc.is_some(), cfg!(feature = "rtc"));
+        }
+
+        rom[0x147] = 0x00;
+        rom[0x149] = 0x00;
+        let sys = System::new(Config::new(), &rom, NullHardware, NullDebugger);
+        assert_eq!(sys.cartridge_ram_size(), 0);
+        assert!(sys.save_data().is_empty());
+    }
+
+    #[test]
+    fn step_for() {
+        let mut rom = vec![0; 0x8000];
+        // jr -2
+        rom[0x100..0x102].copy_from_slice(&[0x18, 0xfe]);
+
+        let cfg = Config::new().skip_boot(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        let outcome = sys.step_for(1000);
+        assert!(outcome.is_pending());
+        assert!(outcome.poll.cycles >= 1000 && outcome.poll.cycles < 1012);
+        // 1000 cycles at 4 MHz take about 240 us.
+        assert_eq!(outcome.micros, outcome.poll.cycles * 1_000_000 / 4194304);
+        assert!(outcome.next_event > 0 && outcome.next_event <= joypad::POLL_CYCLES as u64);
+
+        // The step stops at VBlank, then the next event is at most the next VBlank.
+        let outcome = sys.step_for(FRAME_CYCLES * 2);
+        assert!(outcome.is_ready());
+        assert!(outcome.poll.cycles < FRAME_CYCLES);
+        assert!(sys.debug_read(0xff44) >= 144);
+        assert!(outcome.next_event_micros <= outcome.next_event * 1_000_000 / 4194304 + 1);
+    }
+
+    #[test]
+    fn run_batches() {
+        let mut rom = vec![0; 0x8000];
+        // jr -2
+        rom[0x100..0x102].copy_from_slice(&[0x18, 0xfe]);
+
+        let cfg = Config::new().skip_boot(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        // The budget is overrun by the last instruction at most.
+        let data = sys.run_cycles(1000);
+        assert!(data.running);
+        assert!(data.cycles >= 1000 && data.cycles < 1012);
+        assert!(!data.frame_complete);
+
+        let data = sys.run_until_vblank();
+        assert!(data.frame_complete);
+        assert!(data.cycles < FRAME_CYCLES);
+        assert_eq!(sys.debug_read(0xff44), 144);
+
+        // A budget spanning VBlank doesn't stop there.
+        let data = sys.run_cycles(FRAME_CYCLES * 2);
+        assert!(data.cycles >= FRAME_CYCLES * 2);
+        assert!(data.frame_complete);
+    }
+
+    #[test]
+    fn double_buffer() {
+        let mut rom = vec![0; 0x8000];
+        // jr -2
+        rom[0x100..0x102].copy_from_slice(&[0x18, 0xfe]);
+
+        // The pixels which were never drawn keep the value the buffers are filled with.
+        let undrawn = 0xdead_beef;
+        let mut frame = vec![undrawn; Buffers::FRAME_SIZE];
+        let mut back = vec![undrawn; Buffers::FRAME_SIZE];
+        let bufs = Buffers::new().frame(&mut frame).back_frame(&mut back);
+        let cfg = Config::new().skip_boot(true);
+        let mut sys = System::with_buffers(cfg, &rom, NullHardware, NullDebugger, bufs);
+
+        // The lines being drawn don't show before the frame is complete.
+        sys.run_cycles(1000);
+        assert!(sys.frame().unwrap().iter().all(|&p| p == undrawn));
+
+        sys.run_until_vblank();
+        assert!(sys.frame().unwrap().iter().any(|&p| p != undrawn));
+
+        // The first frame started past the top line, the second one is whole.
+        sys.run_until_vblank();
+        assert!(!sys.frame().unwrap().contains(&undrawn));
+
+        // The next frame is drawn into the other buffer.
+        sys.run_cycles(1000);
+        assert!(!sys.frame().unwrap().contains(&undrawn));
+    }
+
+    #[test]
+    fn vector_hook() {
+        use crate::cpu::Cpu;
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+
+        struct Stub(Rc<RefCell<Vec<Vector>>>);
+
+        impl VectorHook for Stub {
+            fn on_vector(&mut self, vector: Vector, cpu: &mut Cpu, _: &mut Mmu) -> VectorAction {
+                self.0.borrow_mut().push(vector);
+                cpu.set_a(0x33);
+                VectorAction::Return
+            }
+        }
+
+        let mut rom = vec![0; 0x8000];
+        // ld a, 0x22; ret
+        rom[0x28..0x2b].copy_from_slice(&[0x3e, 0x22, 0xc9]);
+        // ld a, 0x44; ld (0xc001), a; reti
+        rom[0x40..0x46].copy_from_slice(&[0x3e, 0x44, 0xea, 0x01, 0xc0, 0xd9]);
+        // ld a, 0x01; ldh (0xff), a; ei; ld a, 0x11; rst 0x28; ld (0xc000), a; jr -2
+        rom[0x100..0x10d].copy_from_slice(&[
+            0x3e, 0x01, 0xe0, 0xff, 0xfb, 0x3e, 0x11, 0xef, 0xea, 0x00, 0xc0, 0x18, 0xfe,
+        ]);
+
+        let cfg = || Config::new().skip_boot(true);
+        let mut sys = System::new(cfg(), &rom, NullHardware, NullDebugger);
+        sys.run_cycles(FRAME_CYCLES * 2);
+        assert_eq!(sys.debug_read(0xc000), 0x22);
+        assert_eq!(sys.debug_read(0xc001), 0x44);
+
+        let vectors = Rc::new(RefCell::new(Vec::new()));
+        let mut sys = System::new(cfg(), &rom, NullHardware, NullDebugger);
+        sys.set_vector_hook(Stub(vectors.clone()));
+        sys.run_cycles(FRAME_CYCLES * 2);
+        // The routines were skipped, and the interrupts are enabled again.
+        assert_eq!(sys.debug_read(0xc000), 0x33);
+        assert_eq!(sys.debug_read(0xc001), 0x00);
+        assert_eq!(
+            vectors.borrow()[..3],
+            [
+                Vector::Rst(0x28),
+                Vector::Interrupt(0x40),
+                Vector::Interrupt(0x40)
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_io() {
+        let rom = vec![0; 0x8000];
+        let mut sys = System::new(
+            Config::new().model(Model::Dmg),
+            &rom,
+            NullHardware,
+            NullDebugger,
+        );
+
+        for addr in [0xff03, 0xff08, 0xff0e, 0xff4d, 0xff4f, 0xff70, 0xff7f] {
+            sys.debug_write(addr, 0x12);
+            assert_eq!(sys.debug_read(addr), 0xff, "{:04x}", addr);
+        }
+        // The speed switch can't be armed on the DMG.
+        assert!(!sys.cgb.borrow().double_speed());
+    }
+
+    #[test]
+    fn deferred_timer() {
+        let mut rom = vec![0; 0x8000];
+        // ld hl, 0xc000; inc (hl); reti
+        rom[0x50..0x55].copy_from_slice(&[0x21, 0x00, 0xc0, 0x34, 0xd9]);
+        // ld a, 0x05; ldh (0x07), a; ld a, 0x04; ldh (0xff), a; ei; halt; jr -3
+        rom[0x100..0x10c].copy_from_slice(&[
+            0x3e, 0x05, 0xe0, 0x07, 0x3e, 0x04, 0xe0, 0xff, 0xfb, 0x76, 0x18, 0xfd,
+        ]);
+
+        let cfg = || Config::new().skip_boot(true);
+        let mut sys = System::new(cfg(), &rom, NullHardware, NullDebugger);
+        let data = sys.run_cycles(FRAME_CYCLES);
+
+        // TIMA overflows every 256 * 16 clocks, as when stepped on every machine cycle.
+        let mut exact =
+            System::cycle_stepped(cfg(), &rom, NullHardware, NullDebugger, Buffers::new());
+        exact.run_cycles(FRAME_CYCLES);
+        assert_eq!(sys.debug_read(0xc000), exact.debug_read(0xc000));
+        assert_eq!(sys.debug_read(0xc000), 17);
+
+        // DIV catches up on access.
+        let div = ((data.cycles - 1) / 256 + 1) as u8;
+        assert_eq!(sys.debug_read(0xff04), div);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn double_speed() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x143] = 0x80;
+        // ld a, 0x01; ldh (0x4d), a; stop; jr -2
+        rom[0x100..0x108].copy_from_slice(&[0x3e, 0x01, 0xe0, 0x4d, 0x10, 0x00, 0x18, 0xfe]);
+
+        let cfg = Config::new().skip_boot(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        sys.run_cycles(SPEED_SWITCH_CYCLES as u64);
+        assert_eq!(sys.debug_read(0xff4d) & 0x81, 0x80);
+        assert_eq!(sys.frame_duration_cycles(), FRAME_CYCLES * 2);
+
+        // The GPU keeps the normal speed, so a frame takes twice the CPU clock cycles.
+        let data = sys.run_cycles(FRAME_CYCLES);
+        assert!((76..=78).contains(&data.lines), "{}", data.lines);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn hdma_stall() {
+        use crate::gpu::HDMA_BLOCK_CYCLES;
+
+        // A general-purpose HDMA of 4 blocks from 0xc000 to 0x8000.
+        let hdma = [
+            0x3e, 0xc0, 0xe0, 0x51, // ld a, 0xc0; ldh (0x51), a
+            0xaf, 0xe0, 0x52, 0xe0, 0x54, // xor a; ldh (0x52), a; ldh (0x54), a
+            0x3e, 0x80, 0xe0, 0x53, // ld a, 0x80; ldh (0x53), a
+            0x3e, 0x03, 0xe0, 0x55, // ld a, 0x03; ldh (0x55), a
+            0x18, 0xfe, // jr -2
+        ];
+        // ld a, 0x01; ldh (0x4d), a; stop
+        let speed_switch = [0x3e, 0x01, 0xe0, 0x4d, 0x10, 0x00];
+
+        for double in [false, true] {
+            let mut rom = vec![0; 0x8000];
+            rom[0x143] = 0x80;
+            let start = if double { speed_switch.len() } else { 0 };
+            rom[0x100..0x100 + start].copy_from_slice(&speed_switch[..start]);
+            rom[0x100 + start..0x100 + start + hdma.len()].copy_from_slice(&hdma);
+            let trigger = 0x100 + start as u16 + 15;
+
+            let cfg = Config::new().skip_boot(true);
+            let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+            while sys.cpu.get_pc() != trigger {
+                sys.run_cycles(1);
+            }
+
+            // The CPU is halted for as long as the blocks take in normal speed.
+            let stall = 4 * HDMA_BLOCK_CYCLES as u64 * if double { 2 } else { 1 };
+            assert_eq!(sys.run_cycles(1).cycles, 12 + stall, "{}", double);
+            assert_eq!(sys.debug_read(0xff55), 0xff);
+        }
+    }
+
+    #[cfg(feature = "serial")]
+    #[test]
+    fn serial_queues() {
+        let mut rom = vec![0; 0x8000];
+        // ld a, 0x42; ldh (0x01), a; ld a, 0x81; ldh (0x02), a; jr -2
+        rom[0x100..0x10a]
+            .copy_from_slice(&[0x3e, 0x42, 0xe0, 0x01, 0x3e, 0x81, 0xe0, 0x02, 0x18, 0xfe]);
+
+        let cfg = Config::new().skip_boot(true).serial_queue(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        for i in 0..crate::serial::SERIAL_QUEUE_LEN {
+            sys.push_serial_in(i as u8).unwrap();
+        }
+        assert_eq!(sys.push_serial_in(0xff), Err(QueueFull));
+
+        sys.run_cycles(FRAME_CYCLES);
+        assert_eq!(sys.drain_serial_out(), [0x42]);
+        assert!(sys.drain_serial_out().is_empty());
+        assert_eq!(sys.debug_read(0xff01), 0x00);
+
+        // The taken byte frees a slot.
+        sys.push_serial_in(0xff).unwrap();
+    }
+
+    #[cfg(feature = "serial")]
+    #[test]
+    fn serial_back_to_back() {
+        let mut rom = vec![0; 0x8000];
+        // inc b; ld a, b; ldh (0x01), a; ld a, 0x81; ldh (0x02), a
+        // wait: ldh a, (0x02); add a, a; jr c, wait; jr -15
+        rom[0x100..0x10f].copy_from_slice(&[
+            0x04, 0x78, 0xe0, 0x01, 0x3e, 0x81, 0xe0, 0x02, 0xf0, 0x02, 0x87, 0x38, 0xfb, 0x18,
+            0xf1,
+        ]);
+
+        // Without the queue, the bytes are only passed to the hardware.
+        let mut sys = System::new(
+            Config::new().skip_boot(true),
+            &rom,
+            NullHardware,
+            NullDebugger,
+        );
+        let data = sys.run_cycles(FRAME_CYCLES * 5);
+        assert!(data.serial_bytes > crate::serial::SERIAL_QUEUE_LEN as u64);
+        assert!(!data.serial_overflow);
+        assert!(sys.drain_serial_out().is_empty());
+
+        let cfg = Config::new().skip_boot(true).serial_queue(true);
+        let mut sys = System::new(cfg, &rom, NullHardware, NullDebugger);
+
+        let consecutive = |bytes: &[u8]| bytes.windows(2).all(|w| w[1] == w[0].wrapping_add(1));
+
+        // A transfer takes 4096 cycles, so a frame sends 17 bytes.
+        let data = sys.run_cycles(FRAME_CYCLES);
+        assert!(
+            (16..=18).contains(&data.serial_bytes),
+            "{}",
+            data.serial_bytes
+        );
+        assert!(!data.serial_overflow);
+        let out = sys.drain_serial_out();
+        // The byte of the transfer in progress is sent when it starts.
+        assert!((0..=1).contains(&(out.len() as u64 - data.serial_bytes)));
+        assert!(consecutive(&out));
+
+        // Without draining, the oldest bytes are dropped.
+        let data = sys.run_cycles(FRAME_CYCLES * 5);
+        assert!(data.serial_bytes > crate::serial::SERIAL_QUEUE_LEN as u64);
+        assert!(data.serial_overflow);
+        let last = out[out.len() - 1];
+        let out = sys.drain_serial_out();
+        assert_eq!(out.len(), crate::serial::SERIAL_QUEUE_LEN);
+        assert!(consecutive(&out));
+        assert!(out[0] != last.wrapping_add(1));
+
+        // The overflow is only reported by the poll it happened in.
+        assert!(!sys.run_cycles(FRAME_CYCLES).serial_overflow);
+    }
+
+    #[test]
+    fn reset_combo() {
+        use crate::hardware::{EventHook, Key, SavePersistence, SchedHook, SerialPort, Stream};
+        use alloc::{boxed::Box, rc::Rc};
+        use core::cell::Cell;
+
+        struct Keys(Rc<Cell<bool>>);
+
+        impl Hardware for Keys {
+            fn vram_update(&mut self, _line: usize, _buffer: &[u32]) {}
+
+            fn joypad_pressed(&mut self, key: Key) -> bool {
+                self.0.get() && matches!(key, Key::A | Key::B | Key::Start | Key::Select)
+            }
+
+            fn sound_play(&mut self, _stream: Box<dyn Stream>) {}
+
+            fn clock(&mut self) -> u64 {
+                0
+            }
+        }
+
+        impl SavePersistence for Keys {}
+        impl SerialPort for Keys {}
+        impl SchedHook for Keys {}
+        impl EventHook for Keys {}
+
+        let held = Rc::new(Cell::new(false));
+        let rom = vec![0; 0x8000];
+        let cfg = Config::new().skip_boot(true).reset_combo(ResetCombo::Reset);
+        let mut sys = System::new(cfg, &rom, Keys(held.clone()), NullDebugger);
+
+        // The keys are polled once within the period.
+        let period = joypad::POLL_CYCLES as u64;
+
+        sys.debug_write(0xc000, 0x12);
+        assert!(!sys.run_cycles(period).reset_combo);
+
+        held.set(true);
+        assert!(sys.run_cycles(period).reset_combo);
+        assert_eq!(sys.debug_read(0xc000), 0x00);
+
+        // Holding the keys doesn't reset again.
+        sys.debug_write(0xc000, 0x12);
+        assert!(!sys.run_cycles(period).reset_combo);
+        assert_eq!(sys.debug_read(0xc000), 0x12);
+
+        // Polling right away doesn't wait for the period.
+        held.set(false);
+        sys.poll_joypad();
+        held.set(true);
+        sys.poll_joypad();
+        assert!(sys.run_cycles(4).reset_combo);
+    }
+}
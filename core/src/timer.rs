@@ -1,10 +1,11 @@
 use crate::device::IoHandler;
 use crate::ic::Irq;
 use crate::mmu::{MemRead, MemWrite, Mmu};
-use log::*;
+use crate::sched::Lazy;
 
 pub struct Timer {
     irq: Irq,
+    sched: Lazy,
     div: u8,
     div_clocks: usize,
     tim: u8,
@@ -14,9 +15,10 @@ pub struct Timer {
 }
 
 impl Timer {
-    pub fn new(irq: Irq) -> Self {
+    pub fn new(irq: Irq, sched: Lazy) -> Self {
         Self {
             irq,
+            sched,
             div: 0,
             div_clocks: 0,
             tim: 0,
@@ -26,14 +28,43 @@ impl Timer {
         }
     }
 
+    /// Restore the power-on state.
+    pub fn reset(&mut self) {
+        *self = Timer::new(self.irq.clone(), self.sched.restart());
+    }
+
+    /// Catch up on the time elapsed since the last sync, and schedule the next overflow.
+    pub fn sync(&mut self) {
+        let time = self.sched.elapsed();
+        if time > 0 {
+            self.step(time);
+        }
+        self.sched.schedule(self.next_event());
+    }
+
+    /// The clock cycles until TIMA overflows and requests the interrupt.
+    fn next_event(&self) -> Option<usize> {
+        if self.ctrl & 0x04 == 0 {
+            return None;
+        }
+
+        // TIMA is incremented once the step is longer than the clocks left.
+        Some(self.tim_clocks + (0xff - self.tim as usize) * self.tim_period() + 1)
+    }
+
     fn tim_clock_reset(&mut self) {
-        self.tim_clocks = match self.ctrl & 0x3 {
+        self.tim_clocks = self.tim_period();
+    }
+
+    /// The CPU clocks between two increments of TIMA.
+    fn tim_period(&self) -> usize {
+        match self.ctrl & 0x3 {
             0x0 => 1024, // 4096Hz = 1024 cpu clocks
             0x1 => 16,   // 262144Hz = 16 cpu clocks
             0x2 => 64,   // 65536Hz = 64 cpu clocks
             0x3 => 256,  // 16384Hz = 256 cpu clocks
             _ => unreachable!(),
-        };
+        }
     }
 
     fn div_clock_reset(&mut self) {
@@ -41,11 +72,14 @@ impl Timer {
     }
 
     pub fn step(&mut self, time: usize) {
+        // A step can be longer than the DIV period, e.g. when HDMA stalls the CPU
+        // or when the timer is synced only on access.
         if self.div_clocks < time {
-            self.div = self.div.wrapping_add(1);
-            let rem = time - self.div_clocks;
+            let over = time - self.div_clocks;
+            let ticks = (over - 1) / 256 + 1;
+            self.div = self.div.wrapping_add(ticks as u8);
             self.div_clock_reset();
-            self.div_clocks -= rem;
+            self.div_clocks -= over - (ticks - 1) * 256;
         } else {
             self.div_clocks -= time;
         }
@@ -80,6 +114,8 @@ impl Timer {
 impl IoHandler for Timer {
     fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
         info!("Timer read: {:04x}", addr);
+        self.sync();
+
         match addr {
             0xff04 => MemRead::Replace(self.div),
             0xff05 => MemRead::Replace(self.tim),
@@ -91,6 +127,7 @@ impl IoHandler for Timer {
 
     fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
         info!("Timer write: {:04x} {:02x}", addr, value);
+        self.sync();
         match addr {
             0xff04 => self.div = 0,
             0xff05 => self.tim = value,
@@ -106,6 +143,7 @@ impl IoHandler for Timer {
             }
             _ => {}
         }
+        self.sched.schedule(self.next_event());
         MemWrite::PassThrough
     }
 }
@@ -0,0 +1,46 @@
+use crate::device::IoHandler;
+use crate::mmu::{MemRead, MemWrite, Mmu};
+
+/// The I/O registers which don't exist on any model.
+///
+/// The unused registers of the sound controller, e.g. FF15 and FF27-FF2F, are masked by it.
+const UNUSED: &[(u16, u16)] = &[
+    (0xff03, 0xff03),
+    (0xff08, 0xff0e),
+    (0xff4e, 0xff4e),
+    (0xff57, 0xff67),
+    (0xff6d, 0xff6f),
+    (0xff71, 0xff71),
+    (0xff78, 0xff7f),
+];
+
+/// The I/O registers which only exist on the CGB.
+const CGB_ONLY: &[(u16, u16)] = &[
+    (0xff4c, 0xff4d),
+    (0xff4f, 0xff4f),
+    (0xff51, 0xff56),
+    (0xff68, 0xff6c),
+    (0xff70, 0xff70),
+    (0xff72, 0xff77),
+];
+
+/// The ranges of the I/O registers which don't exist on the emulated model.
+pub fn ranges(cgb: bool) -> impl Iterator<Item = (u16, u16)> {
+    let cgb_only: &[_] = if cgb { &[] } else { CGB_ONLY };
+    UNUSED.iter().chain(cgb_only).copied()
+}
+
+/// The I/O registers which don't exist: the reads return 0xff, and the writes are ignored.
+pub struct Unused;
+
+impl IoHandler for Unused {
+    fn on_read(&mut self, _mmu: &Mmu, addr: u16) -> MemRead {
+        debug!("Read from unused I/O register: {:04x}", addr);
+        MemRead::Replace(0xff)
+    }
+
+    fn on_write(&mut self, _mmu: &Mmu, addr: u16, value: u8) -> MemWrite {
+        debug!("Write to unused I/O register: {:04x} {:02x}", addr, value);
+        MemWrite::Block
+    }
+}
@@ -0,0 +1,49 @@
+use crate::cpu::Cpu;
+use crate::mmu::Mmu;
+
+/// The target of a call the CPU makes by itself, reported to a [`VectorHook`][].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Vector {
+    /// A `rst` instruction calling the address, from 0x00 to 0x38.
+    Rst(u16),
+    /// An interrupt dispatched to its vector, from 0x40 (VBlank) to 0x60 (joypad).
+    Interrupt(u16),
+}
+
+impl Vector {
+    /// The address the CPU jumped to.
+    pub fn addr(self) -> u16 {
+        match self {
+            Vector::Rst(addr) | Vector::Interrupt(addr) => addr,
+        }
+    }
+}
+
+/// What the CPU does after a [`VectorHook`][] is called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorAction {
+    /// Run the code at the vector as usual.
+    Continue,
+    /// Return to the caller right away, as if the code at the vector executed `ret`,
+    /// or `reti` for an interrupt. The hook has emulated the routine itself.
+    Return,
+}
+
+/// Intercept the calls to the `rst` targets and the interrupt vectors.
+///
+/// Test harnesses and high-level emulation shims use it to stub out routines, e.g. the
+/// link cable routines of a firmware, without running them. Set by [`System::set_vector_hook`][crate::System::set_vector_hook].
+pub trait VectorHook {
+    /// Called once the CPU pushed the return address and jumped to the vector, before the first
+    /// instruction there. The hook may change the registers and the memory.
+    fn on_vector(&mut self, vector: Vector, cpu: &mut Cpu, mmu: &mut Mmu) -> VectorAction;
+}
+
+/// Return to the caller of the vector on behalf of the hook.
+pub(crate) fn skip(vector: Vector, cpu: &mut Cpu, mmu: &mut Mmu) {
+    let pc = cpu.pop(mmu);
+    cpu.set_pc(pc);
+    if let Vector::Interrupt(_) = vector {
+        cpu.enable_interrupt();
+    }
+}
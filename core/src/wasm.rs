@@ -0,0 +1,47 @@
+//! A JavaScript-friendly facade to embed the emulator in web pages.
+//!
+//! The emulator runs a frame per [`Emulator::step_frame`][] call, typically from `requestAnimationFrame`.
+//! The frame buffer can be read directly from the WebAssembly memory.
+
+use crate::embed::Embedded;
+use wasm_bindgen::prelude::*;
+
+/// The emulator driven from JavaScript.
+#[wasm_bindgen]
+pub struct Emulator(Embedded);
+
+#[wasm_bindgen]
+impl Emulator {
+    /// Create the emulator running the given ROM.
+    #[wasm_bindgen(constructor)]
+    pub fn new(rom: &[u8]) -> Emulator {
+        Emulator(Embedded::new(rom))
+    }
+
+    /// Run emulation until a frame is drawn. Returns `false` once the emulator stopped.
+    pub fn step_frame(&mut self) -> bool {
+        self.0.step_frame()
+    }
+
+    /// The address of the frame buffer in the WebAssembly memory.
+    ///
+    /// The buffer holds `framebuffer_len()` pixels in rows of 160, each of which is `0x00RRGGBB`.
+    pub fn framebuffer_ptr(&self) -> *const u32 {
+        self.0.frame().0
+    }
+
+    /// The number of pixels in the frame buffer.
+    pub fn framebuffer_len(&self) -> usize {
+        self.0.frame().1
+    }
+
+    /// Set the pressed buttons. From the lowest bit: right, left, up, down, A, B, select and start.
+    pub fn set_buttons(&mut self, bits: u8) {
+        self.0.set_buttons(bits)
+    }
+
+    /// Fill the buffer with mono samples in `[0, 1]` at the given sample rate.
+    pub fn audio_pull(&mut self, out: &mut [f32], rate: u32) {
+        self.0.audio_pull(out, rate)
+    }
+}
@@ -0,0 +1,140 @@
+use std::ffi::OsStr;
+use std::fs::{read_dir, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use rgy::Header;
+
+use crate::Loader;
+
+/// The bytes read to parse the cartridge header.
+const HEADER_LEN: u64 = 0x150;
+
+/// A ROM file found in a directory.
+pub struct RomFile {
+    /// The file name without the extension.
+    pub name: String,
+    pub path: PathBuf,
+    /// The cartridge header, read without loading the whole file.
+    pub header: Header,
+}
+
+impl RomFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        let mut head = Vec::new();
+        File::open(&path)?.take(HEADER_LEN).read_to_end(&mut head)?;
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Ok(Self {
+            name,
+            header: Header::parse(&head),
+            path,
+        })
+    }
+
+    /// Read the whole ROM image.
+    pub fn load(&self) -> io::Result<Vec<u8>> {
+        std::fs::read(&self.path)
+    }
+}
+
+/// The ROM files in a directory, i.e. the `.gb`, `.gbc` and `.rom` files, sorted by name.
+pub struct RomDir {
+    files: Vec<RomFile>,
+}
+
+impl RomDir {
+    /// List the ROM files in the directory. The files which can't be read are skipped.
+    pub fn scan<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let mut files: Vec<_> = read_dir(dir)?
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if !path.is_file() || !is_rom(path.extension()?) {
+                    return None;
+                }
+                RomFile::open(path).ok()
+            })
+            .collect();
+
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self { files })
+    }
+
+    pub fn files(&self) -> &[RomFile] {
+        &self.files
+    }
+
+    /// Load the file the callback picks by its index, if any.
+    pub fn pick<F>(&self, pick: F) -> io::Result<Option<Vec<u8>>>
+    where
+        F: FnOnce(&[RomFile]) -> Option<usize>,
+    {
+        match pick(&self.files).and_then(|i| self.files.get(i)) {
+            Some(file) => file.load().map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+fn is_rom(ext: &OsStr) -> bool {
+    let ext = ext.to_string_lossy().to_ascii_lowercase();
+    ext == "gb" || ext == "gbc" || ext == "rom"
+}
+
+impl Loader for RomDir {
+    fn roms(&mut self) -> Vec<String> {
+        self.files.iter().map(|file| file.name.clone()).collect()
+    }
+
+    fn load(&mut self, rom: &str) -> Vec<u8> {
+        let file = self
+            .files
+            .iter()
+            .find(|file| file.name == rom)
+            .expect("Unknown ROM");
+        file.load().expect("Couldn't read ROM")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{format, vec};
+
+    #[test]
+    fn scan_and_pick() {
+        let dir = std::env::temp_dir().join(format!("rgy-romdir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut rom = vec![0; 0x8000];
+        rom[0x134..0x138].copy_from_slice(b"TEST");
+        rom[0x143] = 0xc0;
+        std::fs::write(dir.join("b.GBC"), &rom).unwrap();
+        std::fs::write(dir.join("a.gb"), &rom[..0x100]).unwrap();
+        std::fs::write(dir.join("notes.txt"), b"not a rom").unwrap();
+
+        let roms = RomDir::scan(&dir).unwrap();
+        let names: Vec<_> = roms.files().iter().map(|file| file.name.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+
+        // A truncated header reads as zeros.
+        assert_eq!(roms.files()[0].header.title.trim_end(), "");
+        assert_eq!(roms.files()[1].header.title.trim_end(), "TEST");
+        assert!(roms.files()[1].header.requires_cgb());
+
+        let picked = roms
+            .pick(|files| files.iter().position(|file| file.header.requires_cgb()))
+            .unwrap();
+        assert_eq!(picked, Some(rom));
+        assert_eq!(roms.pick(|_| None).unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
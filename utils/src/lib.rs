@@ -1,6 +1,14 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
+mod dir;
+
+#[cfg(feature = "std")]
+pub use crate::dir::{RomDir, RomFile};
 
 use alloc::format;
 use alloc::string::String;